@@ -0,0 +1,90 @@
+use chrono::NaiveDate;
+use reqwest::{Client, RequestBuilder, Response};
+use shared::availability::{AvailabilityRequest, AvailabilitySlot};
+use uuid::Uuid;
+
+use super::supabase::SupabaseAuth;
+
+const API_BASE_URL: &str = "http://localhost:3070/api/v1";
+
+fn authorized(request: RequestBuilder) -> RequestBuilder {
+    match SupabaseAuth::get_token() {
+        Some(token) => request.bearer_auth(token),
+        None => request,
+    }
+}
+
+async fn error_text(response: Response, action: &str) -> String {
+    let error_text = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "Unknown error".to_string());
+    format!("Failed to {}: {}", action, error_text)
+}
+
+/// Fetch the current user's availability between `from` and `to`, with
+/// recurring slots expanded to one entry per date
+pub async fn fetch_availability(from: NaiveDate, to: NaiveDate) -> Result<Vec<AvailabilitySlot>, String> {
+    let response = authorized(Client::new().get(format!("{}/availability", API_BASE_URL)))
+        .query(&[("from", from.to_string()), ("to", to.to_string())])
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+
+    if response.status().is_success() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("JSON parsing error: {}", e))
+    } else {
+        Err(error_text(response, "load availability").await)
+    }
+}
+
+pub async fn create_availability(request: &AvailabilityRequest) -> Result<AvailabilitySlot, String> {
+    let response = authorized(Client::new().post(format!("{}/availability", API_BASE_URL)))
+        .json(request)
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+
+    if response.status().is_success() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("JSON parsing error: {}", e))
+    } else {
+        Err(error_text(response, "save availability").await)
+    }
+}
+
+/// Replace a slot; for a recurring slot this changes every occurrence
+pub async fn update_availability(slot_id: Uuid, request: &AvailabilityRequest) -> Result<AvailabilitySlot, String> {
+    let response = authorized(Client::new().put(format!("{}/availability/{}", API_BASE_URL, slot_id)))
+        .json(request)
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+
+    if response.status().is_success() {
+        response
+            .json()
+            .await
+            .map_err(|e| format!("JSON parsing error: {}", e))
+    } else {
+        Err(error_text(response, "update availability").await)
+    }
+}
+
+pub async fn delete_availability(slot_id: Uuid) -> Result<(), String> {
+    let response = authorized(Client::new().delete(format!("{}/availability/{}", API_BASE_URL, slot_id)))
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(error_text(response, "delete availability").await)
+    }
+}
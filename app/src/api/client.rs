@@ -1,5 +1,6 @@
 use leptos::*;
 use leptos::prelude::*;
+use shared::errors::ProblemDetails;
 use shared::types::ApiError;
 use web_sys::window;
 
@@ -42,7 +43,7 @@ impl ApiClient {
                 .await
                 .map_err(|e| ApiError::Parse(e.to_string()))
         } else {
-            Err(ApiError::Http(response.status()))
+            Err(error_from_response(response).await)
         }
     }
 
@@ -69,11 +70,20 @@ impl ApiClient {
                 .await
                 .map_err(|e| ApiError::Parse(e.to_string()))
         } else {
-            Err(ApiError::Http(response.status()))
+            Err(error_from_response(response).await)
         }
     }
 }
 
+/// The API's problem+json error body, or just the status when there isn't one
+async fn error_from_response(response: gloo_net::http::Response) -> ApiError {
+    let status = response.status();
+    match response.json::<ProblemDetails>().await {
+        Ok(problem) => ApiError::Problem(Box::new(problem)),
+        Err(_) => ApiError::Http(status),
+    }
+}
+
 // Provide API client as context
 pub fn provide_api_client() {
     provide_context(ApiClient::new());
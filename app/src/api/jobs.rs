@@ -1,6 +1,32 @@
+use serde::Deserialize;
+use shared::job_templates::{CreateJobTemplateRequest, RepostDates};
+use shared::pay::PayEstimate;
 use shared::types::{SimpleJob, SimpleJobType, CreateJobRequest, Job};
 use super::client::use_api_client;
 
+#[derive(Deserialize)]
+struct JobPayResponse {
+    estimate: PayEstimate,
+}
+
+/// A saved posting as listed in the job wizard
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct JobTemplateSummary {
+    pub id: String,
+    pub name: String,
+    pub use_count: i32,
+}
+
+#[derive(Deserialize)]
+struct JobTemplateList {
+    templates: Vec<JobTemplateSummary>,
+}
+
+#[derive(Deserialize)]
+struct PrefilledJob {
+    job: CreateJobRequest,
+}
+
 pub async fn fetch_jobs() -> Result<Vec<SimpleJob>, String> {
     let client = use_api_client();
     
@@ -79,4 +105,46 @@ pub async fn create_job(request: CreateJobRequest) -> Result<Job, String> {
         Ok(job) => Ok(job),
         Err(e) => Err(format!("Failed to create job: {:?}", e))
     }
-}
\ No newline at end of file
+}
+/// Itemised award pay for a job's shifts at the advertised rate
+pub async fn fetch_job_pay(id: &str) -> Result<PayEstimate, String> {
+    let client = use_api_client();
+
+    client
+        .get::<JobPayResponse>(&format!("/api/v1/jobs/{}/pay", id))
+        .await
+        .map(|response| response.estimate)
+        .map_err(|e| format!("Failed to load pay estimate: {:?}", e))
+}
+
+/// Templates the current user can post from
+pub async fn fetch_job_templates() -> Result<Vec<JobTemplateSummary>, String> {
+    let client = use_api_client();
+
+    client
+        .get::<JobTemplateList>("/api/v1/job-templates")
+        .await
+        .map(|list| list.templates)
+        .map_err(|e| format!("Failed to load templates: {:?}", e))
+}
+
+pub async fn save_job_template(name: String, job: CreateJobRequest) -> Result<JobTemplateSummary, String> {
+    let client = use_api_client();
+    let request = CreateJobTemplateRequest { name, tenant_id: None, job };
+
+    client
+        .post::<JobTemplateSummary, CreateJobTemplateRequest>("/api/v1/job-templates", &request)
+        .await
+        .map_err(|e| format!("Failed to save template: {:?}", e))
+}
+
+/// A new posting from a template, moved to the template's weekday in the coming week
+pub async fn post_from_template(id: &str) -> Result<CreateJobRequest, String> {
+    let client = use_api_client();
+
+    client
+        .post::<PrefilledJob, RepostDates>(&format!("/api/v1/job-templates/{}/post", id), &RepostDates::default())
+        .await
+        .map(|prefilled| prefilled.job)
+        .map_err(|e| format!("Failed to load template: {:?}", e))
+}
@@ -3,6 +3,7 @@ pub mod auth;
 pub mod client;
 pub mod supabase;
 pub mod applications;
+pub mod availability;
 
 pub use client::ApiClient;
 pub use supabase::{AuthProvider, use_auth, SupabaseAuth, LoginRequest};
\ No newline at end of file
@@ -1,8 +1,12 @@
 use leptos::*;
 use leptos::prelude::*;
 use serde::{Deserialize, Serialize};
-use chrono::{NaiveDate, Weekday, Duration as ChronoDuration, Utc, Datelike};
+use chrono::{NaiveDate, Duration as ChronoDuration, Utc, Datelike};
 use std::collections::HashMap;
+use uuid::Uuid;
+use wasm_bindgen_futures::spawn_local;
+use shared::availability::AvailabilityRequest;
+use crate::api::availability::{create_availability, delete_availability, fetch_availability};
 use crate::components::ui::{Button, ButtonVariant, Alert, AlertVariant, LoadingSpinner, SpinnerSize};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -13,16 +17,15 @@ pub enum CalendarView {
     Year,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub enum AvailabilityType {
-    Available,
-    Busy,
-    Preferred,
-    Unavailable,
+pub use shared::availability::{AvailabilitySlot, AvailabilityType, RecurringPattern, RecurringType};
+
+/// Calendar colours for each availability type
+pub trait AvailabilityTypeStyle {
+    fn color_class(&self) -> &'static str;
 }
 
-impl AvailabilityType {
-    pub fn color_class(&self) -> &'static str {
+impl AvailabilityTypeStyle for AvailabilityType {
+    fn color_class(&self) -> &'static str {
         match self {
             AvailabilityType::Available => "bg-green-100 border-green-300 text-green-800",
             AvailabilityType::Busy => "bg-red-100 border-red-300 text-red-800",
@@ -30,45 +33,30 @@ impl AvailabilityType {
             AvailabilityType::Unavailable => "bg-gray-100 border-gray-300 text-gray-800",
         }
     }
+}
 
-    pub fn display_name(&self) -> &'static str {
-        match self {
-            AvailabilityType::Available => "Available",
-            AvailabilityType::Busy => "Busy",
-            AvailabilityType::Preferred => "Preferred",
-            AvailabilityType::Unavailable => "Unavailable",
+/// Dates to load for the current view; month-based views pad a week either side
+/// so partial weeks at the edges are filled in
+fn visible_range(view: &CalendarView, date: NaiveDate) -> (NaiveDate, NaiveDate) {
+    match view {
+        CalendarView::Year => (
+            NaiveDate::from_ymd_opt(date.year(), 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(date.year(), 12, 31).unwrap(),
+        ),
+        _ => {
+            let month_start = date.with_day(1).unwrap();
+            let month_end = month_start.checked_add_months(chrono::Months::new(1)).unwrap() - ChronoDuration::days(1);
+            (month_start - ChronoDuration::weeks(1), month_end + ChronoDuration::weeks(1))
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AvailabilitySlot {
-    pub id: uuid::Uuid,
-    pub date: NaiveDate,
-    pub start_time: String, // "09:00"
-    pub end_time: String,   // "17:00"
-    pub availability_type: AvailabilityType,
-    pub notes: Option<String>,
-    pub job_type_preference: Option<String>,
-    pub hourly_rate: Option<f64>,
-    pub is_recurring: bool,
-    pub recurring_pattern: Option<RecurringPattern>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RecurringPattern {
-    pub pattern_type: RecurringType,
-    pub end_date: Option<NaiveDate>,
-    pub days_of_week: Vec<Weekday>,
-    pub interval: u32, // Every N weeks/months
-}
-
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub enum RecurringType {
-    Daily,
-    Weekly,
-    Monthly,
-    Custom,
+fn group_by_date(slots: Vec<AvailabilitySlot>) -> HashMap<NaiveDate, Vec<AvailabilitySlot>> {
+    let mut grouped: HashMap<NaiveDate, Vec<AvailabilitySlot>> = HashMap::new();
+    for slot in slots {
+        grouped.entry(slot.date).or_default().push(slot);
+    }
+    grouped
 }
 
 #[component]
@@ -85,52 +73,23 @@ pub fn CalendarSystem(
     let (drag_end_date, set_drag_end_date) = create_signal(None::<NaiveDate>);
     let (show_recurring_modal, set_show_recurring_modal) = create_signal(false);
     let (is_loading, set_is_loading) = create_signal(false);
+    let (load_error, set_load_error) = create_signal(None::<String>);
 
-    // Initialize with sample data
+    // Load the slots for whatever the calendar is showing; recurring slots
+    // arrive already expanded to one entry per date
     create_effect(move |_| {
-        let mut slots = HashMap::new();
-        
-        // Add some sample availability slots
-        let today = Utc::now().date_naive();
-        for i in 0..30 {
-            let date = today + ChronoDuration::days(i);
-            let mut date_slots = Vec::new();
-            
-            // Add random availability
-            if i % 3 == 0 {
-                date_slots.push(AvailabilitySlot {
-                    id: uuid::Uuid::new_v4(),
-                    date,
-                    start_time: "09:00".to_string(),
-                    end_time: "17:00".to_string(),
-                    availability_type: AvailabilityType::Available,
-                    notes: Some("Available for any pharmacy work".to_string()),
-                    job_type_preference: None,
-                    hourly_rate: Some(45.0),
-                    is_recurring: false,
-                    recurring_pattern: None,
-                });
-            } else if i % 4 == 0 {
-                date_slots.push(AvailabilitySlot {
-                    id: uuid::Uuid::new_v4(),
-                    date,
-                    start_time: "10:00".to_string(),
-                    end_time: "14:00".to_string(),
-                    availability_type: AvailabilityType::Preferred,
-                    notes: Some("Prefer morning shifts".to_string()),
-                    job_type_preference: Some("Weekend Relief".to_string()),
-                    hourly_rate: Some(50.0),
-                    is_recurring: false,
-                    recurring_pattern: None,
-                });
-            }
-            
-            if !date_slots.is_empty() {
-                slots.insert(date, date_slots);
+        let (from, to) = visible_range(&current_view.get(), current_date.get());
+        set_is_loading.set(true);
+        spawn_local(async move {
+            match fetch_availability(from, to).await {
+                Ok(slots) => {
+                    set_availability_slots.set(group_by_date(slots));
+                    set_load_error.set(None);
+                }
+                Err(error) => set_load_error.set(Some(error)),
             }
-        }
-        
-        set_availability_slots.set(slots);
+            set_is_loading.set(false);
+        });
     });
 
     // Navigation functions
@@ -188,10 +147,10 @@ pub fn CalendarSystem(
         set_current_date.set(Utc::now().date_naive());
     };
 
-    // Create availability slot
+    // Create availability slot; shown straight away and rolled back if saving fails
     let create_availability_slot = move |date: NaiveDate, availability_type: AvailabilityType| {
         let new_slot = AvailabilitySlot {
-            id: uuid::Uuid::new_v4(),
+            id: Uuid::new_v4(),
             date,
             start_time: "09:00".to_string(),
             end_time: "17:00".to_string(),
@@ -202,11 +161,31 @@ pub fn CalendarSystem(
             is_recurring: false,
             recurring_pattern: None,
         };
+        let pending_id = new_slot.id;
+        let request = AvailabilityRequest::from(&new_slot);
 
         set_availability_slots.update(|slots| {
             slots.entry(date).or_insert_with(Vec::new).push(new_slot.clone());
         });
 
+        spawn_local(async move {
+            match create_availability(&request).await {
+                Ok(saved) => set_availability_slots.update(|slots| {
+                    if let Some(slot) = slots.get_mut(&date).and_then(|day| day.iter_mut().find(|s| s.id == pending_id)) {
+                        *slot = saved;
+                    }
+                }),
+                Err(error) => {
+                    set_availability_slots.update(|slots| {
+                        if let Some(day) = slots.get_mut(&date) {
+                            day.retain(|s| s.id != pending_id);
+                        }
+                    });
+                    set_load_error.set(Some(error));
+                }
+            }
+        });
+
         if let Some(ref callback) = on_availability_change {
             let all_slots: Vec<AvailabilitySlot> = availability_slots.get()
                 .values()
@@ -217,6 +196,20 @@ pub fn CalendarSystem(
         }
     };
 
+    // Delete a slot; recurring slots go as a whole series
+    let delete_availability_slot = move |slot_id: Uuid| {
+        spawn_local(async move {
+            match delete_availability(slot_id).await {
+                Ok(()) => set_availability_slots.update(|slots| {
+                    for day in slots.values_mut() {
+                        day.retain(|s| s.id != slot_id);
+                    }
+                }),
+                Err(error) => set_load_error.set(Some(error)),
+            }
+        });
+    };
+
     // Handle date click
     let handle_date_click = move |date: NaiveDate| {
         move |_| {
@@ -348,6 +341,16 @@ pub fn CalendarSystem(
                 </div>
             </div>
 
+            {move || load_error.get().map(|error| {
+                view! {
+                    <div class="px-6 pt-4">
+                        <Alert variant=AlertVariant::Error>
+                            {error}
+                        </Alert>
+                    </div>
+                }
+            })}
+
             // Calendar content
             <div class="p-6">
                 {move || {
@@ -402,6 +405,7 @@ pub fn CalendarSystem(
                             date=date
                             slots=slots
                             on_close=move || set_selected_date.set(None)
+                            on_delete=delete_availability_slot
                         />
                     }.into_view()
                 } else {
@@ -663,6 +667,7 @@ fn AvailabilityDetailsPanel(
     date: NaiveDate,
     slots: Vec<AvailabilitySlot>,
     on_close: impl Fn() + 'static,
+    on_delete: impl Fn(Uuid) + Copy + 'static,
 ) -> impl IntoView {
     view! {
         <div class="border-t bg-gray-50 p-6">
@@ -694,6 +699,8 @@ fn AvailabilityDetailsPanel(
                     <div class="space-y-4">
                         {slots.into_iter()
                             .map(|slot| {
+                                let slot_id = slot.id;
+                                let is_recurring = slot.is_recurring;
                                 view! {
                                     <div class={format!(
                                         "p-4 rounded-lg border {}",
@@ -738,8 +745,11 @@ fn AvailabilityDetailsPanel(
                                             <button class="text-sm text-blue-600 hover:text-blue-800">
                                                 "Edit"
                                             </button>
-                                            <button class="text-sm text-red-600 hover:text-red-800">
-                                                "Delete"
+                                            <button
+                                                class="text-sm text-red-600 hover:text-red-800"
+                                                on:click=move |_| on_delete(slot_id)
+                                            >
+                                                {if is_recurring { "Delete series" } else { "Delete" }}
                                             </button>
                                         </div>
                                     </div>
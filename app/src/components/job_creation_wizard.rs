@@ -2,9 +2,13 @@ use leptos::*;
 use leptos::prelude::*;
 use serde::{Deserialize, Serialize};
 use shared::types::{JobType, AustralianState, CreateJobRequest};
+use shared::calendar;
+use shared::pay::{AwardRules, PayCalculator, PayEstimate};
+use shared::shifts::ShiftSchedule;
 use crate::components::ui::{Button, ButtonVariant, Alert, AlertVariant, LoadingSpinner, SpinnerSize};
 use crate::components::forms::Input;
-use crate::api::jobs::create_job;
+use crate::components::pay_estimate::PayEstimateSummary;
+use crate::api::jobs::{create_job, fetch_job_templates, post_from_template, save_job_template};
 use web_sys::{FormData, HtmlFormElement, Storage};
 use gloo_timers::callback::Interval;
 use js_sys::Date;
@@ -552,9 +556,81 @@ impl JobFormData {
             end_time: self.end_time.clone(),
             job_type: self.job_type.clone().unwrap_or(JobType::Pharmacist),
             is_urgent: self.is_urgent,
+            shifts: self.shift_schedule(),
         }
     }
 
+    /// The form filled in from a saved posting, keeping what the posting doesn't cover
+    pub fn from_create_request(&self, request: &CreateJobRequest) -> Self {
+        use chrono::Datelike;
+
+        let mut weekdays: Vec<chrono::Weekday> = match &request.shifts {
+            Some(ShiftSchedule { recurrence: Some(recurrence), .. }) => recurrence.pattern.days_of_week.clone(),
+            Some(schedule) => schedule.shifts.iter().map(|shift| shift.date.weekday()).collect(),
+            None => Vec::new(),
+        };
+        weekdays.sort_by_key(|day| day.num_days_from_monday());
+        weekdays.dedup();
+
+        Self {
+            title: request.title.clone(),
+            description: request.description.clone(),
+            pharmacy_name: request.pharmacy_name.clone(),
+            hourly_rate: Some(request.hourly_rate),
+            address: request.address.clone(),
+            suburb: request.suburb.clone(),
+            postcode: request.postcode.clone(),
+            state: AustralianState::from_abbrev(&request.state),
+            job_type: Some(request.job_type.clone()),
+            start_date: request.start_date.format("%Y-%m-%d").to_string(),
+            end_date: request.end_date.format("%Y-%m-%d").to_string(),
+            start_time: request.start_time.clone(),
+            end_time: request.end_time.clone(),
+            days_of_week: weekdays.iter().map(ToString::to_string).collect(),
+            is_urgent: request.is_urgent,
+            ..self.clone()
+        }
+    }
+
+    /// One shift per selected weekday between the start and end dates; `None` for a
+    /// single continuous booking
+    pub fn shift_schedule(&self) -> Option<ShiftSchedule> {
+        if self.days_of_week.is_empty() {
+            return None;
+        }
+        self.daily_schedule()
+    }
+
+    /// Shift total at the advertised rate, with award loadings when penalty rates apply
+    pub fn pay_estimate(&self) -> Option<PayEstimate> {
+        let rate = self.hourly_rate?;
+        let shifts = self.daily_schedule()?.generate().ok()?;
+        let rules = if self.penalty_rates { AwardRules::default() } else { AwardRules::flat() };
+        let job_type = self.job_type.clone().unwrap_or(JobType::Pharmacist);
+        let holidays = match (&self.state, shared::shifts::span(&shifts)) {
+            (Some(state), Some((starts_at, ends_at))) => {
+                calendar::holiday_dates(state, starts_at.date(), ends_at.date())
+            }
+            _ => Vec::new(),
+        };
+
+        let rate = rate.try_into().ok()?;
+        PayCalculator::new(&holidays).with_rules(rules).series(&shifts, &job_type, rate).ok()
+    }
+
+    /// The posting's hours on each selected weekday, or every day when none are selected
+    fn daily_schedule(&self) -> Option<ShiftSchedule> {
+        let from = chrono::NaiveDate::parse_from_str(&self.start_date, "%Y-%m-%d").ok()?;
+        let to = chrono::NaiveDate::parse_from_str(&self.end_date, "%Y-%m-%d").ok()?;
+        let weekdays = self
+            .days_of_week
+            .iter()
+            .filter_map(|day| day.parse::<chrono::Weekday>().ok())
+            .collect();
+
+        Some(ShiftSchedule::weekly(from, to, weekdays, self.start_time.clone(), self.end_time.clone()))
+    }
+
     pub fn validate_step(&self, step: &WizardStep) -> Vec<String> {
         let mut errors = Vec::new();
 
@@ -605,6 +681,9 @@ impl JobFormData {
                 if self.end_time.trim().is_empty() {
                     errors.push("End time is required".to_string());
                 }
+                if let Some(Err(e)) = self.shift_schedule().map(|schedule| schedule.generate()) {
+                    errors.push(format!("Shift pattern: {}", e));
+                }
             }
             WizardStep::Description => {
                 if self.description.trim().is_empty() {
@@ -977,6 +1056,7 @@ pub fn JobCreationWizard() -> impl IntoView {
                                 <div class="form-step-content">
                                     {move || match current_step.get() {
                                         WizardStep::BasicInfo => view! {
+                                            <TemplatePicker set_form_data=set_form_data />
                                             <BasicInfoStep form_data=form_data set_form_data=set_form_data />
                                         }.into_view(),
                                         WizardStep::JobDetails => view! {
@@ -996,6 +1076,7 @@ pub fn JobCreationWizard() -> impl IntoView {
                                         }.into_view(),
                                         WizardStep::Preview => view! {
                                             <PreviewStep form_data=form_data />
+                                            <SaveTemplateForm form_data=form_data />
                                         }.into_view(),
                                     }}
                                 </div>
@@ -1975,6 +2056,45 @@ fn ScheduleStep(
                     />
                 </div>
             </div>
+
+            <div>
+                <label class="block text-sm font-medium text-gray-700 mb-2">
+                    "Shift Days"
+                </label>
+                <div class="flex flex-wrap gap-2">
+                    {["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"].into_iter().map(|day| {
+                        view! {
+                            <button
+                                type="button"
+                                class=move || format!(
+                                    "px-3 py-1 rounded-full border text-sm transition-colors {}",
+                                    if form_data.get().days_of_week.iter().any(|d| d == day) {
+                                        "border-tiffany-blue bg-tiffany-blue/10 text-tiffany-dark"
+                                    } else {
+                                        "border-gray-200 hover:border-tiffany-blue/50"
+                                    }
+                                )
+                                on:click=move |_| set_form_data.update(|data| {
+                                    if let Some(index) = data.days_of_week.iter().position(|d| d == day) {
+                                        data.days_of_week.remove(index);
+                                    } else {
+                                        data.days_of_week.push(day.to_string());
+                                    }
+                                })
+                            >
+                                {day}
+                            </button>
+                        }
+                    }).collect_view()}
+                </div>
+                <p class="text-sm text-gray-500 mt-2">
+                    {move || match form_data.get().shift_schedule().map(|schedule| schedule.generate()) {
+                        Some(Ok(shifts)) => format!("{} separate shifts; applicants can choose which ones to take", shifts.len()),
+                        Some(Err(_)) => "Check the dates and times for this shift pattern".to_string(),
+                        None => "Leave empty for one continuous booking".to_string(),
+                    }}
+                </p>
+            </div>
         </div>
     }
 }
@@ -2046,6 +2166,99 @@ fn DescriptionStep(
     }
 }
 
+/// Pre-fill the wizard from one of the user's saved postings
+#[component]
+fn TemplatePicker(set_form_data: WriteSignal<JobFormData>) -> impl IntoView {
+    let templates = create_resource(|| (), |_| async move { fetch_job_templates().await });
+    let (load_error, set_load_error) = create_signal(None::<String>);
+
+    let load_template = move |id: String| {
+        if id.is_empty() {
+            return;
+        }
+        spawn_local(async move {
+            match post_from_template(&id).await {
+                Ok(request) => {
+                    set_form_data.update(|data| *data = data.from_create_request(&request));
+                    set_load_error.set(None);
+                }
+                Err(e) => set_load_error.set(Some(e)),
+            }
+        });
+    };
+
+    view! {
+        {move || templates.get().and_then(Result::ok).filter(|list| !list.is_empty()).map(|list| view! {
+            <div class="mb-6 p-4 rounded-lg border border-tiffany-blue/30 bg-tiffany-light/10">
+                <label class="block text-sm font-medium text-gray-700 mb-2">"Start from a template"</label>
+                <select
+                    class="w-full px-3 py-2 border border-gray-300 rounded-md"
+                    on:change=move |ev| load_template(event_target_value(&ev))
+                >
+                    <option value="">"Choose a saved posting..."</option>
+                    {list.into_iter().map(|template| view! {
+                        <option value=template.id>{template.name}</option>
+                    }).collect_view()}
+                </select>
+                <p class="text-sm text-gray-500 mt-2">"Dates move to the template's usual day next week"</p>
+                {move || load_error.get().map(|error| view! { <p class="mt-2 text-sm text-red-600">{error}</p> })}
+            </div>
+        })}
+    }
+}
+
+/// Save the finished posting as a template for next time
+#[component]
+fn SaveTemplateForm(form_data: ReadSignal<JobFormData>) -> impl IntoView {
+    let (name, set_name) = create_signal(String::new());
+    let (status, set_status) = create_signal(None::<Result<String, String>>);
+    let (is_saving, set_is_saving) = create_signal(false);
+
+    let save_template = move |_| {
+        let template_name = name.get();
+        if template_name.trim().is_empty() {
+            set_status.set(Some(Err("Give the template a name".to_string())));
+            return;
+        }
+        let job = form_data.get().to_create_request();
+        set_is_saving.set(true);
+
+        spawn_local(async move {
+            let result = save_job_template(template_name, job)
+                .await
+                .map(|template| format!("Saved as \"{}\"", template.name));
+            set_status.set(Some(result));
+            set_is_saving.set(false);
+        });
+    };
+
+    view! {
+        <div class="mt-6 p-4 rounded-lg border border-gray-200">
+            <h5 class="font-medium mb-2">"Save as Template"</h5>
+            <div class="flex gap-2">
+                <input
+                    class="flex-1 px-3 py-2 border border-gray-300 rounded-md"
+                    placeholder="e.g. Saturday locum"
+                    prop:value=move || name.get()
+                    on:input=move |ev| set_name.set(event_target_value(&ev))
+                />
+                <button
+                    type="button"
+                    class="px-4 py-2 rounded-md border border-tiffany-blue text-tiffany-dark hover:bg-tiffany-blue/10"
+                    on:click=save_template
+                    disabled=is_saving
+                >
+                    {move || if is_saving.get() { "Saving..." } else { "Save Template" }}
+                </button>
+            </div>
+            {move || status.get().map(|result| match result {
+                Ok(message) => view! { <p class="mt-2 text-sm text-green-600">{message}</p> }.into_view(),
+                Err(error) => view! { <p class="mt-2 text-sm text-red-600">{error}</p> }.into_view(),
+            })}
+        </div>
+    }
+}
+
 #[component]
 fn PreviewStep(form_data: ReadSignal<JobFormData>) -> impl IntoView {
     view! {
@@ -2081,15 +2294,27 @@ fn PreviewStep(form_data: ReadSignal<JobFormData>) -> impl IntoView {
                     <div>
                         <h5 class="font-medium mb-2">"Schedule"</h5>
                         <p class="text-gray-600">
-                            {move || format!("{} to {} | {} - {}", 
+                            {move || format!("{} to {} | {} - {}{}", 
                                 form_data.get().start_date,
                                 form_data.get().end_date,
                                 form_data.get().start_time,
-                                form_data.get().end_time
+                                form_data.get().end_time,
+                                if form_data.get().days_of_week.is_empty() {
+                                    String::new()
+                                } else {
+                                    format!(" | {}", form_data.get().days_of_week.join(", "))
+                                }
                             )}
                         </p>
                     </div>
 
+                    {move || form_data.get().pay_estimate().map(|estimate| view! {
+                        <div>
+                            <h5 class="font-medium mb-2">"Estimated Shift Total"</h5>
+                            <PayEstimateSummary estimate=estimate />
+                        </div>
+                    })}
+
                     <div>
                         <h5 class="font-medium mb-2">"Description"</h5>
                         <p class="text-gray-600">{move || form_data.get().description}</p>
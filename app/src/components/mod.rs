@@ -16,6 +16,7 @@ pub mod advanced_filters;
 pub mod calendar_system;
 pub mod analytics_dashboard;
 pub mod mapbox;
+pub mod pay_estimate;
 
 pub use header::Header;
 pub use sidebar::Sidebar;
@@ -33,4 +34,5 @@ pub use application_board::ApplicationBoard;
 pub use advanced_filters::AdvancedFilters;
 pub use calendar_system::CalendarSystem;
 pub use analytics_dashboard::AnalyticsDashboard;
-pub use mapbox::{MapboxComponent, MapboxScripts, MapLocation, MapCenter};
\ No newline at end of file
+pub use mapbox::{MapboxComponent, MapboxScripts, MapLocation, MapCenter};
+pub use pay_estimate::PayEstimateSummary;
\ No newline at end of file
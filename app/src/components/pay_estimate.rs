@@ -0,0 +1,45 @@
+use leptos::*;
+use leptos::prelude::*;
+use shared::pay::PayEstimate;
+use crate::utils::format_currency;
+
+/// Itemised award pay for a set of shifts, with the total first
+#[component]
+pub fn PayEstimateSummary(estimate: PayEstimate) -> impl IntoView {
+    let shift_count = estimate.shifts.len();
+    let below_minimum = estimate.below_award_minimum;
+
+    view! {
+        <div class="space-y-3">
+            <div class="flex items-baseline justify-between">
+                <span class="text-2xl font-bold text-green-700">{format_currency(f64::try_from(estimate.total).unwrap_or_default())}</span>
+                <span class="text-sm text-gray-500">
+                    {format!(
+                        "{} shift{} · {:.1} hours",
+                        shift_count,
+                        if shift_count == 1 { "" } else { "s" },
+                        estimate.hours
+                    )}
+                </span>
+            </div>
+            <table class="w-full text-sm">
+                <tbody>
+                    {estimate.lines.into_iter().map(|line| view! {
+                        <tr class="border-t border-gray-100">
+                            <td class="py-1 text-gray-700">{line.category.display_name()}</td>
+                            <td class="py-1 text-gray-500 text-right">
+                                {format!("{:.1}h × {}", line.hours, format_currency(f64::try_from(line.rate).unwrap_or_default()))}
+                            </td>
+                            <td class="py-1 text-gray-900 text-right">{format_currency(f64::try_from(line.amount).unwrap_or_default())}</td>
+                        </tr>
+                    }).collect_view()}
+                </tbody>
+            </table>
+            <Show when=move || below_minimum>
+                <p class="text-sm text-amber-700">
+                    "The base rate is below the award minimum for this role."
+                </p>
+            </Show>
+        </div>
+    }
+}
@@ -1,7 +1,8 @@
 use leptos::*;
 use leptos::prelude::*;
 use leptos_router::*;
-use crate::api::jobs::fetch_job_by_id;
+use crate::api::jobs::{fetch_job_by_id, fetch_job_pay};
+use crate::components::pay_estimate::PayEstimateSummary;
 use crate::components::ui::{Button, ButtonVariant, ButtonSize, LoadingSpinner, SpinnerSize, Alert, AlertVariant};
 
 #[component]
@@ -16,6 +17,13 @@ pub fn JobDetail() -> impl IntoView {
         }
     );
 
+    let pay_resource = create_resource(
+        job_id,
+        |id| async move {
+            fetch_job_pay(&id).await
+        }
+    );
+
     view! {
         <div class="max-w-4xl mx-auto px-4 sm:px-6 lg:px-8 py-8">
             <Suspense
@@ -71,6 +79,14 @@ pub fn JobDetail() -> impl IntoView {
                                         </div>
                                     </div>
 
+                                    // Pay estimate, when the job's shifts are known
+                                    {move || pay_resource.get().and_then(Result::ok).map(|estimate| view! {
+                                        <div class="mb-8">
+                                            <h2 class="text-xl font-semibold text-gray-900 mb-4">"Estimated Shift Total"</h2>
+                                            <PayEstimateSummary estimate=estimate />
+                                        </div>
+                                    })}
+
                                     // Description
                                     <div class="mb-8">
                                         <h2 class="text-xl font-semibold text-gray-900 mb-4">"Description"</h2>
@@ -0,0 +1,24 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+/// Loco Platform API server
+#[derive(Debug, Default, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// TOML config file. Defaults to `CONFIG_FILE`, then `loco.toml` if there is one
+    #[arg(long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Settings profile: development, test, staging or production. Defaults to `APP_ENV`
+    #[arg(long)]
+    pub profile: Option<String>,
+
+    /// Override a setting, e.g. `--set port=8080`; wins over the file and environment
+    #[arg(long = "set", value_name = "KEY=VALUE")]
+    pub overrides: Vec<String>,
+
+    /// Print every setting with where it came from, secrets redacted, then exit
+    #[arg(long)]
+    pub print_config: bool,
+}
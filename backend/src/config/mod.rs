@@ -0,0 +1,546 @@
+//! Typed configuration, layered from built-in profile defaults, a TOML config
+//! file, the environment (including `.env`) and `--set` overrides, each winning
+//! over the one before. Every problem is reported at once, and secrets are
+//! wrapped in [`Secret`] so they never reach logs or `--print-config`.
+
+pub mod cli;
+pub mod layers;
+pub mod secret;
+
+use std::{fmt, io, path::PathBuf, str::FromStr, time::Duration};
+
+use tracing_subscriber::EnvFilter;
+use validator::ValidateEmail;
+
+pub use cli::Cli;
+pub use layers::{Layers, Source};
+pub use secret::Secret;
+
+use layers::{find_setting, DEVELOPMENT_JWT_SECRET};
+
+/// Read when neither `--config` nor `CONFIG_FILE` names a file; optional
+const DEFAULT_CONFIG_FILE: &str = "loco.toml";
+
+/// Shortest JWT signing key accepted outside development
+const MIN_JWT_SECRET_LEN: usize = 32;
+
+/// Shortest superadmin password accepted outside development
+const MIN_SUPERADMIN_PASSWORD_LEN: usize = 12;
+
+const MAX_TASK_WORKERS: usize = 64;
+
+/// Which deployment the settings are for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    Development,
+    Test,
+    Staging,
+    Production,
+}
+
+impl Profile {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Profile::Development => "development",
+            Profile::Test => "test",
+            Profile::Staging => "staging",
+            Profile::Production => "production",
+        }
+    }
+
+    /// A developer's machine or CI, where built-in secrets are acceptable
+    pub fn is_local(self) -> bool {
+        matches!(self, Profile::Development | Profile::Test)
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Profile {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "development" => Ok(Profile::Development),
+            "test" => Ok(Profile::Test),
+            "staging" => Ok(Profile::Staging),
+            "production" => Ok(Profile::Production),
+            _ => Err(format!(
+                "unknown profile {:?}; expected development, test, staging or production",
+                name
+            )),
+        }
+    }
+}
+
+/// One thing wrong with the configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigProblem {
+    /// The setting, or the file, at fault
+    pub key: String,
+    pub message: String,
+}
+
+impl ConfigProblem {
+    pub fn new(key: impl ToString, message: impl Into<String>) -> Self {
+        Self {
+            key: key.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.key, self.message)
+    }
+}
+
+/// Everything wrong with the configuration, so it can all be fixed in one go
+#[derive(Debug, thiserror::Error)]
+#[error("invalid configuration:{}", .problems.iter().map(|problem| format!("\n  - {}", problem)).collect::<String>())]
+pub struct ConfigError {
+    pub problems: Vec<ConfigProblem>,
+}
+
+/// Database connection pool settings
+#[derive(Debug, Clone)]
+pub struct DatabaseConfig {
+    pub url: Secret<String>,
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub connect_timeout: Duration,
+    pub acquire_timeout: Duration,
+    pub idle_timeout: Duration,
+    pub max_lifetime: Duration,
+    pub sqlx_logging: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub profile: Profile,
+    pub database: DatabaseConfig,
+    pub supabase_url: Option<String>,
+    pub supabase_anon_key: Option<Secret<String>>,
+    pub mapbox_token: Option<Secret<String>>,
+    pub jwt_secret: Secret<String>,
+    pub superadmin_email: String,
+    pub superadmin_password: Option<Secret<String>>,
+    pub log_level: String,
+    pub api_url: String,
+    pub frontend_url: String,
+    pub app_version: String,
+    pub host: String,
+    pub port: u16,
+    pub upload_dir: String,
+    pub smtp_host: Option<String>,
+    pub smtp_port: u16,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<Secret<String>>,
+    pub from_email: String,
+    pub realtime_backplane: String,
+    pub task_workers: usize,
+}
+
+/// Gather every layer: the profile from `--profile` or `APP_ENV`, then its
+/// defaults, the config file, the environment and `--set` overrides
+pub fn gather(cli: &Cli) -> Layers {
+    dotenvy::dotenv().ok();
+    let env = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+
+    let (name, source) = match (&cli.profile, env("APP_ENV")) {
+        (Some(name), _) => (name.clone(), Source::Cli),
+        (None, Some(name)) => (name, Source::Env("APP_ENV")),
+        (None, None) => (Profile::Development.to_string(), Source::Default),
+    };
+    let (profile, unknown_profile) = match name.parse() {
+        Ok(profile) => (profile, None),
+        Err(message) => (Profile::Development, Some(ConfigProblem::new("profile", message))),
+    };
+    let mut layers = Layers::new(profile, source);
+    if let Some(problem) = unknown_profile {
+        layers.problem(problem);
+    }
+
+    let named = cli.config.clone().or_else(|| env("CONFIG_FILE").map(PathBuf::from));
+    let path = named.clone().unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_FILE));
+    match std::fs::read_to_string(&path) {
+        Ok(text) => layers.file(&path, &text),
+        Err(e) if named.is_none() && e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => layers.problem(ConfigProblem::new(path.display(), format!("can't read config file: {}", e))),
+    }
+
+    layers.env(env);
+    layers.overrides(&cli.overrides);
+    layers
+}
+
+/// Typed reads of gathered values, noting a problem for each that is missing or
+/// unparseable instead of stopping at the first
+struct Reader<'a> {
+    layers: &'a Layers,
+    problems: Vec<ConfigProblem>,
+}
+
+impl<'a> Reader<'a> {
+    fn optional<T>(&mut self, key: &str) -> Option<T>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let (value, source) = self.layers.get(key)?;
+        match value.parse() {
+            Ok(value) => Some(value),
+            Err(e) => {
+                let shown = match find_setting(key) {
+                    Some(setting) if setting.secret => secret::REDACTED.to_string(),
+                    _ => format!("{:?}", value),
+                };
+                self.problems
+                    .push(ConfigProblem::new(key, format!("{} from {} is not valid: {}", shown, source, e)));
+                None
+            }
+        }
+    }
+
+    fn required<T>(&mut self, key: &str) -> T
+    where
+        T: FromStr + Default,
+        T::Err: fmt::Display,
+    {
+        if self.layers.get(key).is_none() {
+            let env = find_setting(key).map_or(key, |setting| setting.env);
+            self.problems.push(ConfigProblem::new(
+                key,
+                format!("required in {}; set {} or `{}` in the config file", self.layers.profile(), env, key),
+            ));
+        }
+        self.optional(key).unwrap_or_default()
+    }
+
+    fn secret(&mut self, key: &str) -> Secret<String> {
+        Secret::new(self.required(key))
+    }
+
+    fn optional_secret(&mut self, key: &str) -> Option<Secret<String>> {
+        self.optional(key).map(Secret::new)
+    }
+
+    fn seconds(&mut self, key: &str) -> Duration {
+        Duration::from_secs(self.required(key))
+    }
+}
+
+/// Values from `.env.example` and old built-in defaults, never real secrets
+fn is_placeholder(value: &str) -> bool {
+    let value = value.to_ascii_lowercase();
+    value == DEVELOPMENT_JWT_SECRET
+        || value == "changeme"
+        || value.starts_with("your_")
+        || value.starts_with("your-")
+        || value.starts_with("demo-")
+        || value.contains("change-this")
+        || value.contains("change-in-production")
+}
+
+fn is_http_url(value: &str) -> bool {
+    url::Url::parse(value).is_ok_and(|url| matches!(url.scheme(), "http" | "https"))
+}
+
+impl Config {
+    /// Gather and check the configuration for the command line
+    pub fn load(cli: &Cli) -> Result<Self, ConfigError> {
+        Self::from_layers(&gather(cli))
+    }
+
+    /// Read and check gathered settings, reporting every problem found
+    pub fn from_layers(layers: &Layers) -> Result<Self, ConfigError> {
+        let mut read = Reader {
+            layers,
+            problems: Vec::new(),
+        };
+        let config = Config {
+            profile: layers.profile(),
+            database: DatabaseConfig {
+                url: read.secret("database.url"),
+                max_connections: read.required("database.max_connections"),
+                min_connections: read.required("database.min_connections"),
+                connect_timeout: read.seconds("database.connect_timeout_secs"),
+                acquire_timeout: read.seconds("database.acquire_timeout_secs"),
+                idle_timeout: read.seconds("database.idle_timeout_secs"),
+                max_lifetime: read.seconds("database.max_lifetime_secs"),
+                sqlx_logging: read.required("database.sqlx_logging"),
+            },
+            supabase_url: read.optional("supabase_url"),
+            supabase_anon_key: read.optional_secret("supabase_anon_key"),
+            mapbox_token: read.optional_secret("mapbox_token"),
+            jwt_secret: read.secret("jwt_secret"),
+            superadmin_email: read.required("superadmin_email"),
+            superadmin_password: read.optional_secret("superadmin_password"),
+            log_level: read.required("log_level"),
+            api_url: read.required("api_url"),
+            frontend_url: read.required("frontend_url"),
+            app_version: read.required("app_version"),
+            host: read.required("host"),
+            port: read.required("port"),
+            upload_dir: read.required("upload_dir"),
+            smtp_host: read.optional("smtp_host"),
+            smtp_port: read.required("smtp_port"),
+            smtp_username: read.optional("smtp_username"),
+            smtp_password: read.optional_secret("smtp_password"),
+            from_email: read.required("from_email"),
+            realtime_backplane: read.required("realtime_backplane"),
+            task_workers: read.required("task_workers"),
+        };
+
+        let mut problems = layers.problems().to_vec();
+        problems.append(&mut read.problems);
+        // A setting that is missing or unparseable has a stand-in value; don't
+        // pile rule violations on top of that
+        let rules: Vec<ConfigProblem> = config
+            .validate()
+            .into_iter()
+            .filter(|rule| !problems.iter().any(|problem| problem.key == rule.key))
+            .collect();
+        problems.extend(rules);
+
+        if problems.is_empty() {
+            Ok(config)
+        } else {
+            Err(ConfigError { problems })
+        }
+    }
+
+    /// Rules across settings and for the profile
+    fn validate(&self) -> Vec<ConfigProblem> {
+        let mut problems = Vec::new();
+        let mut check = |ok: bool, key: &str, message: &str| {
+            if !ok {
+                problems.push(ConfigProblem::new(key, message));
+            }
+        };
+
+        let database_url = self.database.url.expose();
+        check(
+            database_url.starts_with("postgres://") || database_url.starts_with("postgresql://"),
+            "database.url",
+            "must be a postgres:// URL; PostgreSQL is the only database supported",
+        );
+        check(self.database.max_connections > 0, "database.max_connections", "must be at least 1");
+        check(
+            self.database.min_connections <= self.database.max_connections,
+            "database.min_connections",
+            "must not be more than database.max_connections",
+        );
+        check(self.port != 0, "port", "must not be 0");
+        check(self.smtp_port != 0, "smtp_port", "must not be 0");
+        check(is_http_url(&self.api_url), "api_url", "must be an http:// or https:// URL");
+        check(is_http_url(&self.frontend_url), "frontend_url", "must be an http:// or https:// URL");
+        check(
+            self.supabase_url.as_deref().is_none_or(is_http_url),
+            "supabase_url",
+            "must be an http:// or https:// URL",
+        );
+        check(
+            self.supabase_url.is_none() || self.supabase_anon_key.is_some(),
+            "supabase_anon_key",
+            "required when supabase_url is set",
+        );
+        check(
+            EnvFilter::try_new(&self.log_level).is_ok(),
+            "log_level",
+            "must be a log filter such as `info` or `backend=debug,info`",
+        );
+        check(
+            matches!(self.realtime_backplane.as_str(), "postgres" | "memory"),
+            "realtime_backplane",
+            "must be \"postgres\" or \"memory\"",
+        );
+        check(
+            (1..=MAX_TASK_WORKERS).contains(&self.task_workers),
+            "task_workers",
+            "must be between 1 and 64",
+        );
+        check(self.superadmin_email.validate_email(), "superadmin_email", "must be an email address");
+        check(self.from_email.validate_email(), "from_email", "must be an email address");
+        check(
+            self.smtp_username.is_some() == self.smtp_password.is_some(),
+            "smtp_password",
+            "smtp_username and smtp_password must be set together",
+        );
+
+        if !self.profile.is_local() {
+            let jwt_secret = self.jwt_secret.expose();
+            check(
+                jwt_secret.len() >= MIN_JWT_SECRET_LEN,
+                "jwt_secret",
+                "must be at least 32 characters; generate one with `openssl rand -base64 48`",
+            );
+            check(!is_placeholder(jwt_secret), "jwt_secret", "is a placeholder, not a real secret");
+            if let Some(password) = &self.superadmin_password {
+                check(
+                    password.expose().len() >= MIN_SUPERADMIN_PASSWORD_LEN,
+                    "superadmin_password",
+                    "must be at least 12 characters",
+                );
+                check(
+                    !is_placeholder(password.expose()),
+                    "superadmin_password",
+                    "is a placeholder, not a real password",
+                );
+            }
+            for (key, secret) in [
+                ("supabase_anon_key", &self.supabase_anon_key),
+                ("mapbox_token", &self.mapbox_token),
+                ("smtp_password", &self.smtp_password),
+            ] {
+                check(
+                    !secret.as_ref().is_some_and(|secret| is_placeholder(secret.expose())),
+                    key,
+                    "is a placeholder, not a real secret",
+                );
+            }
+        }
+
+        problems
+    }
+
+    /// Log where the settings came from, calling out secrets left on their
+    /// development defaults
+    pub fn log_summary(&self, layers: &Layers) {
+        let sources = layers.summary();
+        if sources.is_empty() {
+            tracing::info!("⚙️ Loaded {} configuration from built-in defaults", self.profile);
+        } else {
+            tracing::info!("⚙️ Loaded {} configuration: {}", self.profile, sources);
+        }
+        for setting in layers.default_secrets() {
+            tracing::warn!(
+                "🔑 {} is the built-in development value; set {} before deploying",
+                setting.key,
+                setting.env
+            );
+        }
+        tracing::debug!("Effective configuration: {:?}", self);
+    }
+
+    pub fn is_development(&self) -> bool {
+        self.profile == Profile::Development
+    }
+
+    pub fn is_production(&self) -> bool {
+        self.profile == Profile::Production
+    }
+
+    /// Whether a Supabase project is configured
+    pub fn has_supabase(&self) -> bool {
+        self.supabase_url.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layers(profile: Profile, settings: &[&str]) -> Layers {
+        let mut layers = Layers::new(profile, Source::Default);
+        let overrides: Vec<String> = settings.iter().map(|setting| setting.to_string()).collect();
+        layers.overrides(&overrides);
+        layers
+    }
+
+    fn problem_keys(layers: &Layers) -> Vec<String> {
+        let error = Config::from_layers(layers).expect_err("configuration should be invalid");
+        error.problems.into_iter().map(|problem| problem.key).collect()
+    }
+
+    #[test]
+    fn test_development_runs_on_defaults() {
+        let config = Config::from_layers(&layers(Profile::Development, &[])).unwrap();
+        assert!(config.is_development());
+        assert_eq!(config.port, 3070);
+        assert_eq!(config.database.max_connections, 20);
+        assert_eq!(config.database.min_connections, 1);
+        assert_eq!(config.database.max_lifetime, Duration::from_secs(1800));
+        assert!(config.superadmin_password.is_none());
+        assert!(!config.has_supabase());
+    }
+
+    #[test]
+    fn test_production_requires_real_secrets() {
+        assert_eq!(problem_keys(&layers(Profile::Production, &[])), ["database.url", "jwt_secret"]);
+
+        let placeholders = layers(
+            Profile::Production,
+            &[
+                "database.url=postgres://db.internal/loco",
+                "jwt_secret=your-super-secret-jwt-key-change-this-in-production",
+                "superadmin_password=demo-password",
+            ],
+        );
+        assert_eq!(problem_keys(&placeholders), ["jwt_secret", "superadmin_password"]);
+
+        let config = Config::from_layers(&layers(
+            Profile::Production,
+            &[
+                "database.url=postgres://db.internal/loco",
+                "jwt_secret=4f9c1b7e2d8a6f3c0e5b9d1a7c4e8f2b6d0a3c9e",
+            ],
+        ))
+        .unwrap();
+        assert!(config.is_production());
+        assert_eq!(config.log_level, "info");
+    }
+
+    #[test]
+    fn test_every_problem_is_reported() {
+        let invalid = layers(
+            Profile::Development,
+            &[
+                "port=http",
+                "database.url=sqlite://:memory:",
+                "database.min_connections=50",
+                "task_workers=0",
+                "realtime_backplane=redis",
+                "smtp_username=mailer",
+                "supabase_url=https://project.supabase.co",
+            ],
+        );
+        assert_eq!(
+            problem_keys(&invalid),
+            [
+                "port",
+                "database.url",
+                "database.min_connections",
+                "supabase_anon_key",
+                "realtime_backplane",
+                "task_workers",
+                "smtp_password",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_problems_never_show_secrets() {
+        let mysql = layers(Profile::Production, &["database.url=mysql://root:hunter2@db/loco"]);
+        let error = Config::from_layers(&mysql).unwrap_err().to_string();
+        assert!(error.starts_with("invalid configuration:\n  - "));
+        assert!(error.contains("database.url: must be a postgres:// URL"));
+        assert!(!error.contains("hunter2"));
+
+        let config = Config::from_layers(&layers(Profile::Development, &[])).unwrap();
+        assert!(!format!("{:?}", config).contains(DEVELOPMENT_JWT_SECRET));
+    }
+
+    #[test]
+    fn test_profile_names() {
+        assert_eq!("Production".parse::<Profile>(), Ok(Profile::Production));
+        assert!("prod".parse::<Profile>().is_err());
+        assert!(Profile::Test.is_local());
+        assert!(!Profile::Staging.is_local());
+    }
+}
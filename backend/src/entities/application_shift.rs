@@ -0,0 +1,55 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use serde::{Deserialize, Serialize};
+
+/// A shift an application is for
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "application_shift")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    pub application_id: Uuid,
+    pub shift_id: Uuid,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::application::Entity",
+        from = "Column::ApplicationId",
+        to = "super::application::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Application,
+    #[sea_orm(
+        belongs_to = "super::job_shift::Entity",
+        from = "Column::ShiftId",
+        to = "super::job_shift::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    JobShift,
+}
+
+impl Related<super::application::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Application.def()
+    }
+}
+
+impl Related<super::job_shift::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::JobShift.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {
+    fn new() -> Self {
+        Self {
+            id: Set(Uuid::new_v4()),
+            ..ActiveModelTrait::default()
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use serde::{Deserialize, Serialize};
+
+/// One entry in the append-only audit trail. Rows are never updated or deleted
+/// (the table refuses it) and each carries the hash of the previous row.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "audit_event")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// Position in the hash chain, gapless from 1
+    pub seq: i64,
+
+    // Who, where and from which request; no actor means the system did it
+    pub actor_id: Option<Uuid>,
+    pub tenant_id: Option<Uuid>,
+    pub ip: Option<String>,
+    pub request_id: Option<String>,
+
+    // What happened to what
+    pub action: AuditAction,
+    pub entity_type: String,
+    pub entity_id: Option<String>,
+    // Only the fields that changed, with secrets removed
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub before: Option<Json>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub after: Option<Json>,
+
+    pub prev_hash: String,
+    pub hash: String,
+
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(Some(50))")]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    #[sea_orm(string_value = "login")]
+    Login,
+    #[sea_orm(string_value = "login_failed")]
+    LoginFailed,
+    #[sea_orm(string_value = "role_changed")]
+    RoleChanged,
+    #[sea_orm(string_value = "job_status_changed")]
+    JobStatusChanged,
+    #[sea_orm(string_value = "application_status_changed")]
+    ApplicationStatusChanged,
+    #[sea_orm(string_value = "data_exported")]
+    DataExported,
+    /// A webhook endpoint was added, changed or removed, or a delivery replayed
+    #[sea_orm(string_value = "webhook_changed")]
+    WebhookChanged,
+    /// Anything else done with admin rights, such as verifying a registration
+    #[sea_orm(string_value = "admin_action")]
+    AdminAction,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {
+    fn new() -> Self {
+        Self {
+            id: Set(Uuid::new_v4()),
+            ..ActiveModelTrait::default()
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::audit_event::{self, AuditAction};
+
+/// An audit event committed with the change it describes, waiting to be appended
+/// to the hash chain as an `audit_event` with the same id
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "audit_outbox")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    pub actor_id: Option<Uuid>,
+    pub tenant_id: Option<Uuid>,
+    pub ip: Option<String>,
+    pub request_id: Option<String>,
+
+    pub action: AuditAction,
+    pub entity_type: String,
+    pub entity_id: Option<String>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub before: Option<Json>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub after: Option<Json>,
+
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    /// The event as it joins the chain at `seq`, after the event hashed `prev_hash`;
+    /// `hash` is left for the caller to fill in
+    pub fn into_event(self, seq: i64, prev_hash: String) -> audit_event::Model {
+        audit_event::Model {
+            id: self.id,
+            seq,
+            actor_id: self.actor_id,
+            tenant_id: self.tenant_id,
+            ip: self.ip,
+            request_id: self.request_id,
+            action: self.action,
+            entity_type: self.entity_type,
+            entity_id: self.entity_id,
+            before: self.before,
+            after: self.after,
+            prev_hash,
+            hash: String::new(),
+            created_at: self.created_at,
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use chrono::Weekday;
+use num_traits::ToPrimitive;
+use rust_decimal::Decimal;
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use serde::{Deserialize, Serialize};
+use shared::availability::{AvailabilitySlot, AvailabilityType, RecurringPattern, RecurringType, SLOT_TIME_FORMAT};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "availability")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    pub user_id: Uuid,
+
+    // First (or only) occurrence
+    pub date: Date,
+    pub start_time: Time,
+    pub end_time: Time,
+    pub availability_type: AvailabilityTypeDb,
+
+    // Preferences
+    #[sea_orm(column_type = "Text", nullable)]
+    pub notes: Option<String>,
+    pub job_type_preference: Option<String>,
+    pub hourly_rate: Option<Decimal>,
+
+    // Recurrence; a null type means a one-off slot
+    pub recurrence_type: Option<RecurrenceTypeDb>,
+    pub recurrence_interval: i32,
+    pub recurrence_days: i16, // Weekday bitmask, bit 0 = Monday
+    pub recurrence_end_date: Option<Date>,
+
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(Some(20))")]
+pub enum AvailabilityTypeDb {
+    #[sea_orm(string_value = "Available")]
+    Available,
+    #[sea_orm(string_value = "Busy")]
+    Busy,
+    #[sea_orm(string_value = "Preferred")]
+    Preferred,
+    #[sea_orm(string_value = "Unavailable")]
+    Unavailable,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(Some(20))")]
+pub enum RecurrenceTypeDb {
+    #[sea_orm(string_value = "Daily")]
+    Daily,
+    #[sea_orm(string_value = "Weekly")]
+    Weekly,
+    #[sea_orm(string_value = "Monthly")]
+    Monthly,
+    #[sea_orm(string_value = "Custom")]
+    Custom,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {
+    fn new() -> Self {
+        Self {
+            id: Set(Uuid::new_v4()),
+            recurrence_interval: Set(1),
+            recurrence_days: Set(0),
+            created_at: Set(chrono::Utc::now().into()),
+            updated_at: Set(chrono::Utc::now().into()),
+            ..ActiveModelTrait::default()
+        }
+    }
+}
+
+impl From<AvailabilityTypeDb> for AvailabilityType {
+    fn from(db_type: AvailabilityTypeDb) -> Self {
+        match db_type {
+            AvailabilityTypeDb::Available => AvailabilityType::Available,
+            AvailabilityTypeDb::Busy => AvailabilityType::Busy,
+            AvailabilityTypeDb::Preferred => AvailabilityType::Preferred,
+            AvailabilityTypeDb::Unavailable => AvailabilityType::Unavailable,
+        }
+    }
+}
+
+impl From<AvailabilityType> for AvailabilityTypeDb {
+    fn from(availability_type: AvailabilityType) -> Self {
+        match availability_type {
+            AvailabilityType::Available => AvailabilityTypeDb::Available,
+            AvailabilityType::Busy => AvailabilityTypeDb::Busy,
+            AvailabilityType::Preferred => AvailabilityTypeDb::Preferred,
+            AvailabilityType::Unavailable => AvailabilityTypeDb::Unavailable,
+        }
+    }
+}
+
+impl From<RecurrenceTypeDb> for RecurringType {
+    fn from(db_type: RecurrenceTypeDb) -> Self {
+        match db_type {
+            RecurrenceTypeDb::Daily => RecurringType::Daily,
+            RecurrenceTypeDb::Weekly => RecurringType::Weekly,
+            RecurrenceTypeDb::Monthly => RecurringType::Monthly,
+            RecurrenceTypeDb::Custom => RecurringType::Custom,
+        }
+    }
+}
+
+impl From<RecurringType> for RecurrenceTypeDb {
+    fn from(recurring_type: RecurringType) -> Self {
+        match recurring_type {
+            RecurringType::Daily => RecurrenceTypeDb::Daily,
+            RecurringType::Weekly => RecurrenceTypeDb::Weekly,
+            RecurringType::Monthly => RecurrenceTypeDb::Monthly,
+            RecurringType::Custom => RecurrenceTypeDb::Custom,
+        }
+    }
+}
+
+/// Pack weekdays into the `recurrence_days` bitmask
+pub fn weekday_mask(days: &[Weekday]) -> i16 {
+    days.iter()
+        .fold(0, |mask, day| mask | (1 << day.num_days_from_monday()))
+}
+
+/// Unpack the `recurrence_days` bitmask, Monday first
+pub fn weekdays_from_mask(mask: i16) -> Vec<Weekday> {
+    [
+        Weekday::Mon,
+        Weekday::Tue,
+        Weekday::Wed,
+        Weekday::Thu,
+        Weekday::Fri,
+        Weekday::Sat,
+        Weekday::Sun,
+    ]
+    .into_iter()
+    .filter(|day| mask & (1 << day.num_days_from_monday()) != 0)
+    .collect()
+}
+
+impl Model {
+    pub fn recurring_pattern(&self) -> Option<RecurringPattern> {
+        self.recurrence_type.map(|pattern_type| RecurringPattern {
+            pattern_type: pattern_type.into(),
+            end_date: self.recurrence_end_date,
+            days_of_week: weekdays_from_mask(self.recurrence_days),
+            interval: self.recurrence_interval.max(1) as u32,
+        })
+    }
+
+    /// The slot as the calendar sees it, dated on its first occurrence
+    pub fn to_slot(&self) -> AvailabilitySlot {
+        let recurring_pattern = self.recurring_pattern();
+        AvailabilitySlot {
+            id: self.id,
+            date: self.date,
+            start_time: self.start_time.format(SLOT_TIME_FORMAT).to_string(),
+            end_time: self.end_time.format(SLOT_TIME_FORMAT).to_string(),
+            availability_type: self.availability_type.into(),
+            notes: self.notes.clone(),
+            job_type_preference: self.job_type_preference.clone(),
+            hourly_rate: self.hourly_rate.and_then(|rate| rate.to_f64()),
+            is_recurring: recurring_pattern.is_some(),
+            recurring_pattern,
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "background_task")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    // What to run
+    pub kind: String,
+    #[sea_orm(column_type = "JsonBinary")]
+    pub payload: Json,
+
+    // Execution state
+    pub status: TaskStatus,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub run_at: DateTimeWithTimeZone,
+    pub locked_at: Option<DateTimeWithTimeZone>,
+    pub locked_by: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub last_error: Option<String>,
+
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+    pub completed_at: Option<DateTimeWithTimeZone>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(Some(20))")]
+pub enum TaskStatus {
+    #[sea_orm(string_value = "Pending")]
+    Pending,
+    #[sea_orm(string_value = "Running")]
+    Running,
+    #[sea_orm(string_value = "Completed")]
+    Completed,
+    /// Out of attempts (or not runnable); waits for an admin to retry it
+    #[sea_orm(string_value = "DeadLettered")]
+    DeadLettered,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl Model {
+    /// Whether another failure would dead-letter the task
+    pub fn is_last_attempt(&self) -> bool {
+        self.attempts >= self.max_attempts
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {
+    fn new() -> Self {
+        let now = chrono::Utc::now();
+        Self {
+            id: Set(Uuid::new_v4()),
+            status: Set(TaskStatus::Pending),
+            attempts: Set(0),
+            run_at: Set(now.into()),
+            created_at: Set(now.into()),
+            updated_at: Set(now.into()),
+            ..ActiveModelTrait::default()
+        }
+    }
+}
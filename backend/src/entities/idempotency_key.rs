@@ -0,0 +1,48 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use serde::{Deserialize, Serialize};
+
+/// The first response to an `Idempotency-Key`, kept so a retried POST replays it
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "idempotency_key")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    // A key is only unique per user, method and route
+    pub user_id: Uuid,
+    pub method: String,
+    pub route: String,
+    pub key: String,
+    /// SHA-256 of the request body, to catch a key reused for a different request
+    pub request_hash: String,
+
+    // None until the first request finishes. A status with no body marks a
+    // response too large or streamed to store, which is never replayed.
+    pub response_status: Option<i16>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub response_headers: Option<Json>,
+    pub response_body: Option<Vec<u8>>,
+
+    pub created_at: DateTimeWithTimeZone,
+    pub expires_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {
+    fn new() -> Self {
+        Self {
+            id: Set(Uuid::new_v4()),
+            created_at: Set(chrono::Utc::now().into()),
+            ..ActiveModelTrait::default()
+        }
+    }
+}
+
+impl Model {
+    pub fn is_complete(&self) -> bool {
+        self.response_status.is_some()
+    }
+}
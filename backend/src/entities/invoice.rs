@@ -0,0 +1,128 @@
+use rust_decimal::Decimal;
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use serde::{Deserialize, Serialize};
+use shared::invoices::InvoiceLine;
+
+/// A tax invoice from a locum to the pharmacy they worked for
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "invoice")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    pub number: String,
+    pub sequence: i32, // Per supplier
+
+    pub supplier_id: Uuid,
+    pub customer_id: Uuid,
+    pub application_id: Uuid,
+    pub job_id: Uuid,
+
+    // Parties as they stood when the invoice was issued
+    pub supplier_name: String,
+    pub supplier_abn: String,
+    pub gst_registered: bool,
+    pub customer_name: String,
+    #[sea_orm(column_type = "Text")]
+    pub customer_address: String,
+
+    // shared::invoices::InvoiceLine values
+    #[sea_orm(column_type = "JsonBinary")]
+    pub lines: Json,
+    pub subtotal: Decimal,
+    pub gst: Decimal,
+    pub total: Decimal,
+
+    pub issued_on: Date,
+    pub due_on: Date,
+    pub payment_terms_days: i32,
+    pub status: InvoiceStatus,
+    pub paid_at: Option<DateTimeWithTimeZone>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub notes: Option<String>,
+
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(Some(20))")]
+pub enum InvoiceStatus {
+    #[sea_orm(string_value = "Issued")]
+    Issued,
+    #[sea_orm(string_value = "Paid")]
+    Paid,
+    #[sea_orm(string_value = "Overdue")]
+    Overdue,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::application::Entity",
+        from = "Column::ApplicationId",
+        to = "super::application::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    Application,
+    #[sea_orm(
+        belongs_to = "super::job::Entity",
+        from = "Column::JobId",
+        to = "super::job::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    Job,
+    #[sea_orm(has_many = "super::timesheet::Entity")]
+    Timesheet,
+}
+
+impl Related<super::application::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Application.def()
+    }
+}
+
+impl Related<super::job::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Job.def()
+    }
+}
+
+impl Related<super::timesheet::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Timesheet.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {
+    fn new() -> Self {
+        let now = chrono::Utc::now();
+        Self {
+            id: Set(Uuid::new_v4()),
+            lines: Set(serde_json::json!([])),
+            status: Set(InvoiceStatus::Issued),
+            created_at: Set(now.into()),
+            updated_at: Set(now.into()),
+            ..ActiveModelTrait::default()
+        }
+    }
+}
+
+impl Model {
+    /// Line items; an unreadable value counts as none
+    pub fn lines(&self) -> Vec<InvoiceLine> {
+        serde_json::from_value(self.lines.clone()).unwrap_or_default()
+    }
+
+    /// Issued for GST-registered suppliers as a tax invoice
+    pub fn title(&self) -> &'static str {
+        if self.gst_registered {
+            "Tax Invoice"
+        } else {
+            "Invoice"
+        }
+    }
+}
@@ -3,7 +3,8 @@ use sea_orm::{Set, ActiveModelTrait};
 use serde::{Deserialize, Serialize};
 use async_trait::async_trait;
 use rust_decimal::Decimal;
-use shared::types::{JobId, UserId, JobType, JobStatus, AustralianState, Postcode};
+use shared::shifts::ShiftSchedule;
+use shared::types::{JobId, UserId, JobType, JobStatus, AustralianState, Postcode, CreateJobRequest};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "job")]
@@ -42,6 +43,10 @@ pub struct Model {
     pub contact_phone: Option<String>,
     pub application_deadline: Option<DateTimeWithTimeZone>,
     
+    // Lifecycle scheduling
+    pub publish_at: Option<DateTimeWithTimeZone>, // Draft jobs go live at this time
+    pub expiry_warning_sent_at: Option<DateTimeWithTimeZone>,
+    
     // Metrics
     pub view_count: i32,
     pub application_count: i32,
@@ -97,6 +102,8 @@ pub enum Relation {
     User,
     #[sea_orm(has_many = "super::application::Entity")]
     Application,
+    #[sea_orm(has_many = "super::job_shift::Entity")]
+    JobShift,
 }
 
 impl Related<super::user::Entity> for Entity {
@@ -111,6 +118,12 @@ impl Related<super::application::Entity> for Entity {
     }
 }
 
+impl Related<super::job_shift::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::JobShift.def()
+    }
+}
+
 
 #[async_trait]
 impl ActiveModelBehavior for ActiveModel {
@@ -241,6 +254,66 @@ impl Model {
     pub fn full_location(&self) -> String {
         format!("{}, {} {}", self.suburb, self.state, self.postcode)
     }
+
+    /// The job as the API returns it
+    pub fn to_job(&self) -> Result<shared::types::Job, shared::errors::AppError> {
+        use num_traits::ToPrimitive;
+
+        Ok(shared::types::Job {
+            id: self.job_id(),
+            title: self.title.clone(),
+            description: self.description.clone(),
+            pharmacy_name: self.pharmacy_name.clone(),
+            hourly_rate: self.hourly_rate.to_f64().unwrap_or_default(),
+            address: self.address.clone(),
+            suburb: self.suburb.clone(),
+            postcode: self.validated_postcode()?,
+            state: self.australian_state().ok_or_else(|| {
+                shared::errors::AppError::validation("state", "Invalid Australian state")
+            })?,
+            latitude: self.latitude,
+            longitude: self.longitude,
+            start_date: self.start_date.into(),
+            end_date: self.end_date.into(),
+            start_time: self.start_time.clone(),
+            end_time: self.end_time.clone(),
+            job_type: self.job_type.clone().into(),
+            status: self.status.clone().into(),
+            is_urgent: self.is_urgent,
+            distance_km: None,
+            created_at: self.created_at.into(),
+            updated_at: self.updated_at.into(),
+            created_by: self.creator_id(),
+        })
+    }
+
+    /// The job as it would be posted again, with `shifts` (its stored shifts) as explicit shifts
+    pub fn to_create_request(&self, shifts: &[super::job_shift::Model]) -> CreateJobRequest {
+        use num_traits::ToPrimitive;
+
+        CreateJobRequest {
+            title: self.title.clone(),
+            description: self.description.clone(),
+            pharmacy_name: self.pharmacy_name.clone(),
+            hourly_rate: self.hourly_rate.to_f64().unwrap_or_default(),
+            address: self.address.clone(),
+            suburb: self.suburb.clone(),
+            postcode: self.postcode.clone(),
+            state: self.state.clone(),
+            latitude: self.latitude,
+            longitude: self.longitude,
+            start_date: self.start_date.into(),
+            end_date: self.end_date.into(),
+            start_time: self.start_time.clone(),
+            end_time: self.end_time.clone(),
+            job_type: self.job_type.clone().into(),
+            is_urgent: self.is_urgent,
+            shifts: (!shifts.is_empty()).then(|| ShiftSchedule {
+                recurrence: None,
+                shifts: shifts.iter().map(super::job_shift::Model::to_spec).collect(),
+            }),
+        }
+    }
     
     /// Calculate distance to a given point (in kilometres)
     pub fn distance_to(&self, lat: f64, lng: f64) -> Option<f64> {
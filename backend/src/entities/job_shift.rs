@@ -0,0 +1,95 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use serde::{Deserialize, Serialize};
+use shared::availability::SLOT_TIME_FORMAT;
+use shared::shifts::{JobShift, ShiftSpec};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "job_shift")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    pub job_id: Uuid,
+
+    pub date: Date,
+    pub start_time: Time,
+    pub end_time: Time, // Before start_time for an overnight shift
+
+    // Set when an application covering the shift is accepted
+    pub filled_by: Option<Uuid>,
+    pub filled_at: Option<DateTimeWithTimeZone>,
+
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::job::Entity",
+        from = "Column::JobId",
+        to = "super::job::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Job,
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::FilledBy",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    FilledBy,
+    #[sea_orm(has_many = "super::application_shift::Entity")]
+    ApplicationShift,
+}
+
+impl Related<super::job::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Job.def()
+    }
+}
+
+impl Related<super::application_shift::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ApplicationShift.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {
+    fn new() -> Self {
+        Self {
+            id: Set(Uuid::new_v4()),
+            created_at: Set(chrono::Utc::now().into()),
+            ..ActiveModelTrait::default()
+        }
+    }
+}
+
+impl Model {
+    pub fn is_filled(&self) -> bool {
+        self.filled_by.is_some()
+    }
+
+    pub fn to_spec(&self) -> ShiftSpec {
+        ShiftSpec {
+            date: self.date,
+            start_time: self.start_time.format(SLOT_TIME_FORMAT).to_string(),
+            end_time: self.end_time.format(SLOT_TIME_FORMAT).to_string(),
+        }
+    }
+
+    pub fn to_shift(&self) -> JobShift {
+        let spec = self.to_spec();
+        JobShift {
+            id: self.id,
+            job_id: self.job_id,
+            date: spec.date,
+            start_time: spec.start_time,
+            end_time: spec.end_time,
+            filled_by: self.filled_by,
+            filled_at: self.filled_at.map(Into::into),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use serde::{Deserialize, Serialize};
+
+use super::job::JobStatusDb;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "job_status_change")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    pub job_id: Uuid,
+    pub from_status: JobStatusDb,
+    pub to_status: JobStatusDb,
+    pub reason: StatusChangeReason,
+
+    // None when the lifecycle scheduler made the change
+    pub changed_by: Option<Uuid>,
+
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(Some(40))")]
+pub enum StatusChangeReason {
+    #[sea_orm(string_value = "ScheduledPublish")]
+    ScheduledPublish,
+    #[sea_orm(string_value = "ApplicationDeadlinePassed")]
+    ApplicationDeadlinePassed,
+    #[sea_orm(string_value = "EndDatePassed")]
+    EndDatePassed,
+    #[sea_orm(string_value = "Manual")]
+    Manual,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::job::Entity",
+        from = "Column::JobId",
+        to = "super::job::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Job,
+}
+
+impl Related<super::job::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Job.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {
+    fn new() -> Self {
+        Self {
+            id: Set(Uuid::new_v4()),
+            created_at: Set(chrono::Utc::now().into()),
+            ..ActiveModelTrait::default()
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use serde::{Deserialize, Serialize};
+use shared::types::CreateJobRequest;
+
+/// A posting saved for reuse, private to its owner unless shared with a tenant
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "job_template")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    pub owner_id: Uuid,
+    pub tenant_id: Option<Uuid>, // Visible to every member of the tenant when set
+    pub source_job_id: Option<Uuid>,
+
+    pub name: String,
+    // shared::types::CreateJobRequest, with the dates it was saved with
+    #[sea_orm(column_type = "JsonBinary")]
+    pub job: Json,
+
+    pub use_count: i32,
+    pub last_used_at: Option<DateTimeWithTimeZone>,
+
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::OwnerId",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Owner,
+    #[sea_orm(
+        belongs_to = "super::tenants::Entity",
+        from = "Column::TenantId",
+        to = "super::tenants::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Tenant,
+    #[sea_orm(
+        belongs_to = "super::job::Entity",
+        from = "Column::SourceJobId",
+        to = "super::job::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    SourceJob,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Owner.def()
+    }
+}
+
+impl Related<super::tenants::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Tenant.def()
+    }
+}
+
+impl Related<super::job::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SourceJob.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {
+    fn new() -> Self {
+        let now = chrono::Utc::now();
+        Self {
+            id: Set(Uuid::new_v4()),
+            use_count: Set(0),
+            created_at: Set(now.into()),
+            updated_at: Set(now.into()),
+            ..ActiveModelTrait::default()
+        }
+    }
+}
+
+impl Model {
+    /// The saved posting
+    pub fn job(&self) -> Result<CreateJobRequest, serde_json::Error> {
+        serde_json::from_value(self.job.clone())
+    }
+}
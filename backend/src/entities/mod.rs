@@ -4,10 +4,35 @@ pub mod application;
 pub mod session;
 pub mod tenants;
 pub mod tenant_users;
+pub mod upload;
+pub mod conversation;
+pub mod conversation_participant;
+pub mod message;
+pub mod message_attachment;
+pub mod notification;
+pub mod notification_preference;
+pub mod job_status_change;
+pub mod background_task;
+pub mod availability;
+pub mod professional_profile;
+pub mod job_shift;
+pub mod application_shift;
+pub mod timesheet;
+pub mod timesheet_revision;
+pub mod timesheet_comment;
+pub mod invoice;
+pub mod job_template;
+pub mod audit_event;
+pub mod audit_outbox;
+pub mod idempotency_key;
+pub mod webhook_endpoint;
+pub mod webhook_delivery;
+pub mod saved_search;
 
 pub use user::Entity as User;
 pub use job::Entity as Job;
 pub use application::Entity as Application;
 pub use session::Entity as Session;
 pub use tenants::Entity as Tenants;
-pub use tenant_users::Entity as TenantUsers;
\ No newline at end of file
+pub use tenant_users::Entity as TenantUsers;
+pub use saved_search::Entity as SavedSearch;
\ No newline at end of file
@@ -0,0 +1,120 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "notification")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    pub user_id: Uuid,
+    pub event_type: NotificationEventType,
+
+    // Content
+    pub title: String,
+    #[sea_orm(column_type = "Text")]
+    pub body: String,
+    pub link: Option<String>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub data: Option<Json>,
+
+    // Delivery state
+    pub in_app: bool,
+    pub digest_pending: bool,
+    pub read_at: Option<DateTimeWithTimeZone>,
+    pub emailed_at: Option<DateTimeWithTimeZone>, // When the email was queued for sending
+
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(Some(40))")]
+pub enum NotificationEventType {
+    #[sea_orm(string_value = "ApplicationReceived")]
+    ApplicationReceived,
+    #[sea_orm(string_value = "ApplicationStatusChanged")]
+    ApplicationStatusChanged,
+    #[sea_orm(string_value = "JobExpiring")]
+    JobExpiring,
+    #[sea_orm(string_value = "SavedSearchMatch")]
+    SavedSearchMatch,
+    #[sea_orm(string_value = "NewMessage")]
+    NewMessage,
+}
+
+impl NotificationEventType {
+    /// Channels used for this event until the user sets their own preference
+    pub fn default_channels(&self) -> NotificationChannels {
+        match self {
+            NotificationEventType::ApplicationReceived => NotificationChannels { in_app: true, email: false, digest: true },
+            NotificationEventType::ApplicationStatusChanged => NotificationChannels { in_app: true, email: true, digest: false },
+            NotificationEventType::JobExpiring => NotificationChannels { in_app: true, email: true, digest: false },
+            NotificationEventType::SavedSearchMatch => NotificationChannels { in_app: true, email: false, digest: true },
+            NotificationEventType::NewMessage => NotificationChannels { in_app: true, email: false, digest: true },
+        }
+    }
+
+    /// Get display text for the event type
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            NotificationEventType::ApplicationReceived => "New applications",
+            NotificationEventType::ApplicationStatusChanged => "Application status changes",
+            NotificationEventType::JobExpiring => "Jobs about to expire",
+            NotificationEventType::SavedSearchMatch => "Saved search matches",
+            NotificationEventType::NewMessage => "New messages",
+        }
+    }
+}
+
+/// Which channels an event is delivered through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationChannels {
+    pub in_app: bool,
+    pub email: bool,
+    pub digest: bool,
+}
+
+impl NotificationChannels {
+    /// Check if the event should be delivered at all
+    pub fn any(&self) -> bool {
+        self.in_app || self.email || self.digest
+    }
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {
+    fn new() -> Self {
+        Self {
+            id: Set(Uuid::new_v4()),
+            in_app: Set(true),
+            digest_pending: Set(false),
+            created_at: Set(chrono::Utc::now().into()),
+            ..ActiveModelTrait::default()
+        }
+    }
+}
+
+impl Model {
+    /// Check if the notification has been read
+    pub fn is_read(&self) -> bool {
+        self.read_at.is_some()
+    }
+}
@@ -0,0 +1,93 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use serde::{Deserialize, Serialize};
+use shared::types::JobType;
+
+/// Travel range used until a professional sets their own
+pub const DEFAULT_MAX_TRAVEL_KM: i32 = 50;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "professional_profile")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub user_id: Uuid,
+
+    // Roles the professional will take, as shared::types::JobType values
+    #[sea_orm(column_type = "JsonBinary")]
+    pub job_types: Json,
+
+    // AHPRA registration
+    pub registration_number: Option<String>,
+    pub registration_status: RegistrationStatus,
+    pub registration_expires_on: Option<Date>,
+    pub verified_at: Option<DateTimeWithTimeZone>,
+    pub verified_by: Option<Uuid>,
+
+    pub max_travel_km: i32,
+
+    // Business details for locums who invoice through their own ABN
+    pub abn: Option<String>,
+    pub gst_registered: bool,
+
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(Some(20))")]
+pub enum RegistrationStatus {
+    #[sea_orm(string_value = "Unregistered")]
+    Unregistered,
+    /// Number supplied, waiting for an admin to check it against the AHPRA register
+    #[sea_orm(string_value = "Pending")]
+    Pending,
+    #[sea_orm(string_value = "Verified")]
+    Verified,
+    #[sea_orm(string_value = "Suspended")]
+    Suspended,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {
+    fn new() -> Self {
+        Self {
+            job_types: Set(serde_json::json!([])),
+            registration_status: Set(RegistrationStatus::Unregistered),
+            max_travel_km: Set(DEFAULT_MAX_TRAVEL_KM),
+            gst_registered: Set(false),
+            created_at: Set(chrono::Utc::now().into()),
+            updated_at: Set(chrono::Utc::now().into()),
+            ..ActiveModelTrait::default()
+        }
+    }
+}
+
+impl Model {
+    /// Eligible job types; an unreadable value counts as none
+    pub fn job_types(&self) -> Vec<JobType> {
+        serde_json::from_value(self.job_types.clone()).unwrap_or_default()
+    }
+
+    /// Whether the registration is verified and still current on `date`
+    pub fn is_registered_on(&self, date: Date) -> bool {
+        self.registration_status == RegistrationStatus::Verified
+            && !matches!(self.registration_expires_on, Some(expires) if expires < date)
+    }
+}
@@ -0,0 +1,82 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use serde::{Deserialize, Serialize};
+use shared::types::JobFilters;
+
+use super::job;
+
+/// A job search a user keeps, whose new matches are published on `search:{id}`
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "saved_search")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    pub user_id: Uuid,
+    pub name: String,
+    pub query: String,
+    // shared::types::JobFilters
+    #[sea_orm(column_type = "JsonBinary")]
+    pub filters: Json,
+
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {
+    fn new() -> Self {
+        Self {
+            id: Set(Uuid::new_v4()),
+            created_at: Set(chrono::Utc::now().into()),
+            ..ActiveModelTrait::default()
+        }
+    }
+}
+
+impl Model {
+    /// The saved filters; anything unreadable filters nothing
+    pub fn filters(&self) -> JobFilters {
+        serde_json::from_value(self.filters.clone()).unwrap_or_default()
+    }
+
+    /// Whether the search returns the job, by the same rules as
+    /// `JobRepository::search_jobs`
+    pub fn matches(&self, job: &job::Model) -> bool {
+        let query = self.query.trim();
+        if !query.is_empty()
+            && ![&job.title, &job.description, &job.pharmacy_name]
+                .iter()
+                .any(|field| field.contains(query))
+        {
+            return false;
+        }
+
+        let filters = self.filters();
+        let rate = |limit: f64| Decimal::from_f64_retain(limit).unwrap_or_default();
+        filters.job_type.is_none_or(|job_type| job::JobTypeDb::from(job_type) == job.job_type)
+            && filters.min_rate.is_none_or(|min| job.hourly_rate >= rate(min))
+            && filters.max_rate.is_none_or(|max| job.hourly_rate <= rate(max))
+            && filters.suburb.as_ref().is_none_or(|suburb| job.suburb.contains(suburb.as_str()))
+            && filters.state.as_ref().is_none_or(|state| &job.state == state)
+            && filters.is_urgent.is_none_or(|urgent| job.is_urgent == urgent)
+            && filters.start_date.is_none_or(|start| job.start_date >= start)
+            && filters.end_date.is_none_or(|end| job.end_date <= end)
+    }
+}
@@ -0,0 +1,145 @@
+use rust_decimal::Decimal;
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use serde::{Deserialize, Serialize};
+use shared::availability::SLOT_TIME_FORMAT;
+use shared::pay::ShiftPay;
+use shared::timesheets::TimesheetEntry;
+
+/// Hours a locum actually worked on one shift of an accepted application
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "timesheet")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    pub application_id: Uuid,
+    pub shift_id: Option<Uuid>,
+
+    pub date: Date,
+    pub start_time: Time,
+    pub end_time: Time, // Before start_time for an overnight shift
+    pub break_minutes: i32,
+
+    pub status: TimesheetStatus,
+
+    // Award pay worked out on approval, as a shared::pay::ShiftPay
+    pub pay_total: Option<Decimal>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub pay_breakdown: Option<Json>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<DateTimeWithTimeZone>,
+
+    // Set once the timesheet is billed
+    pub invoice_id: Option<Uuid>,
+
+    pub submitted_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(Some(20))")]
+pub enum TimesheetStatus {
+    #[sea_orm(string_value = "Submitted")]
+    Submitted,
+    #[sea_orm(string_value = "Approved")]
+    Approved,
+    #[sea_orm(string_value = "Disputed")]
+    Disputed,
+}
+
+impl TimesheetStatus {
+    /// Approved timesheets have been paid against and can't change
+    pub fn is_locked(&self) -> bool {
+        matches!(self, TimesheetStatus::Approved)
+    }
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::application::Entity",
+        from = "Column::ApplicationId",
+        to = "super::application::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Application,
+    #[sea_orm(
+        belongs_to = "super::job_shift::Entity",
+        from = "Column::ShiftId",
+        to = "super::job_shift::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    JobShift,
+    #[sea_orm(
+        belongs_to = "super::invoice::Entity",
+        from = "Column::InvoiceId",
+        to = "super::invoice::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    Invoice,
+    #[sea_orm(has_many = "super::timesheet_revision::Entity")]
+    TimesheetRevision,
+    #[sea_orm(has_many = "super::timesheet_comment::Entity")]
+    TimesheetComment,
+}
+
+impl Related<super::application::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Application.def()
+    }
+}
+
+impl Related<super::invoice::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Invoice.def()
+    }
+}
+
+impl Related<super::timesheet_revision::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TimesheetRevision.def()
+    }
+}
+
+impl Related<super::timesheet_comment::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TimesheetComment.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {
+    fn new() -> Self {
+        let now = chrono::Utc::now();
+        Self {
+            id: Set(Uuid::new_v4()),
+            break_minutes: Set(0),
+            status: Set(TimesheetStatus::Submitted),
+            submitted_at: Set(now.into()),
+            updated_at: Set(now.into()),
+            ..ActiveModelTrait::default()
+        }
+    }
+}
+
+impl Model {
+    pub fn to_entry(&self) -> TimesheetEntry {
+        TimesheetEntry {
+            shift_id: self.shift_id,
+            date: self.date,
+            start_time: self.start_time.format(SLOT_TIME_FORMAT).to_string(),
+            end_time: self.end_time.format(SLOT_TIME_FORMAT).to_string(),
+            break_minutes: u32::try_from(self.break_minutes).unwrap_or_default(),
+        }
+    }
+
+    /// The approved pay, itemised
+    pub fn pay(&self) -> Option<ShiftPay> {
+        self.pay_breakdown
+            .clone()
+            .and_then(|value| serde_json::from_value(value).ok())
+    }
+}
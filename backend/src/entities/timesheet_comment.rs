@@ -0,0 +1,46 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use serde::{Deserialize, Serialize};
+
+/// A message in a timesheet's discussion thread
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "timesheet_comment")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    pub timesheet_id: Uuid,
+    pub author_id: Option<Uuid>,
+    #[sea_orm(column_type = "Text")]
+    pub body: String,
+
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::timesheet::Entity",
+        from = "Column::TimesheetId",
+        to = "super::timesheet::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Timesheet,
+}
+
+impl Related<super::timesheet::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Timesheet.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {
+    fn new() -> Self {
+        Self {
+            id: Set(Uuid::new_v4()),
+            created_at: Set(chrono::Utc::now().into()),
+            ..ActiveModelTrait::default()
+        }
+    }
+}
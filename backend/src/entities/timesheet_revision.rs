@@ -0,0 +1,62 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use serde::{Deserialize, Serialize};
+
+/// A timesheet's times as they stood after a submission, edit or review
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "timesheet_revision")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    pub timesheet_id: Uuid,
+    pub revised_by: Option<Uuid>,
+    pub action: TimesheetAction,
+
+    pub start_time: Time,
+    pub end_time: Time,
+    pub break_minutes: i32,
+
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(Some(20))")]
+pub enum TimesheetAction {
+    #[sea_orm(string_value = "Submitted")]
+    Submitted,
+    #[sea_orm(string_value = "Edited")]
+    Edited,
+    #[sea_orm(string_value = "Approved")]
+    Approved,
+    #[sea_orm(string_value = "Disputed")]
+    Disputed,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::timesheet::Entity",
+        from = "Column::TimesheetId",
+        to = "super::timesheet::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Timesheet,
+}
+
+impl Related<super::timesheet::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Timesheet.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {
+    fn new() -> Self {
+        Self {
+            id: Set(Uuid::new_v4()),
+            created_at: Set(chrono::Utc::now().into()),
+            ..ActiveModelTrait::default()
+        }
+    }
+}
@@ -2,7 +2,7 @@ use sea_orm::entity::prelude::*;
 use sea_orm::{Set, ActiveModelTrait};
 use serde::{Deserialize, Serialize};
 use async_trait::async_trait;
-use shared::types::{UserId, AustralianState};
+use shared::types::{UserId, AustralianState, User};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "user")]
@@ -131,4 +131,39 @@ impl Model {
     pub fn user_id(&self) -> UserId {
         UserId(self.id)
     }
+
+    /// The user as the API shows them, without credentials
+    pub fn to_user(&self) -> User {
+        User {
+            id: self.id,
+            email: self.email.clone(),
+            first_name: self.first_name.clone(),
+            last_name: self.last_name.clone(),
+            phone: self.phone.clone(),
+            user_type: self.user_type.clone().into(),
+            is_active: self.is_active,
+            created_at: self.created_at.into(),
+            updated_at: self.updated_at.into(),
+        }
+    }
+}
+
+impl From<shared::types::UserType> for UserType {
+    fn from(user_type: shared::types::UserType) -> Self {
+        match user_type {
+            shared::types::UserType::Professional => UserType::Professional,
+            shared::types::UserType::Employer => UserType::Employer,
+            shared::types::UserType::SuperAdmin => UserType::SuperAdmin,
+        }
+    }
+}
+
+impl From<UserType> for shared::types::UserType {
+    fn from(user_type: UserType) -> Self {
+        match user_type {
+            UserType::Professional => shared::types::UserType::Professional,
+            UserType::Employer => shared::types::UserType::Employer,
+            UserType::SuperAdmin => shared::types::UserType::SuperAdmin,
+        }
+    }
 }
\ No newline at end of file
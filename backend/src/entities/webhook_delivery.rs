@@ -0,0 +1,81 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use serde::{Deserialize, Serialize};
+
+/// One event sent to one endpoint, with the outcome of its latest attempt
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "webhook_delivery")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    pub endpoint_id: Uuid,
+    pub event_id: Uuid, // Shared by every delivery and replay of the same event
+    pub event_type: String,
+    // shared::webhooks::WebhookPayload, exactly as sent
+    #[sea_orm(column_type = "JsonBinary")]
+    pub payload: Json,
+
+    // Latest attempt
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    pub response_status: Option<i16>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub response_body: Option<String>,
+    pub duration_ms: Option<i32>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub last_error: Option<String>,
+
+    pub replay_of: Option<Uuid>,
+
+    pub created_at: DateTimeWithTimeZone,
+    pub last_attempt_at: Option<DateTimeWithTimeZone>,
+    pub next_attempt_at: Option<DateTimeWithTimeZone>,
+    pub delivered_at: Option<DateTimeWithTimeZone>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(Some(20))")]
+pub enum DeliveryStatus {
+    /// Waiting for its first attempt or a retry
+    #[sea_orm(string_value = "Pending")]
+    Pending,
+    /// The endpoint answered with a 2xx
+    #[sea_orm(string_value = "Delivered")]
+    Delivered,
+    /// Out of attempts, or the endpoint was paused or removed; can be replayed
+    #[sea_orm(string_value = "Failed")]
+    Failed,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::webhook_endpoint::Entity",
+        from = "Column::EndpointId",
+        to = "super::webhook_endpoint::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Endpoint,
+}
+
+impl Related<super::webhook_endpoint::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Endpoint.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {
+    fn new() -> Self {
+        let now = chrono::Utc::now();
+        Self {
+            id: Set(Uuid::new_v4()),
+            status: Set(DeliveryStatus::Pending),
+            attempts: Set(0),
+            created_at: Set(now.into()),
+            next_attempt_at: Set(Some(now.into())),
+            ..ActiveModelTrait::default()
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use serde::{Deserialize, Serialize};
+use shared::webhooks::WebhookEventType;
+
+/// A URL a tenant's own systems listen on for Loco events
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "webhook_endpoint")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+
+    pub tenant_id: Uuid,
+    pub url: String,
+    // Signs every delivery; kept out of serialized copies such as audit events
+    #[serde(skip_serializing, default)]
+    pub secret: String,
+    // Vec<shared::webhooks::WebhookEventType>
+    #[sea_orm(column_type = "JsonBinary")]
+    pub event_types: Json,
+    pub description: Option<String>,
+    pub is_active: bool,
+
+    pub created_by: Uuid,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::tenants::Entity",
+        from = "Column::TenantId",
+        to = "super::tenants::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    Tenant,
+    #[sea_orm(has_many = "super::webhook_delivery::Entity")]
+    Deliveries,
+}
+
+impl Related<super::tenants::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Tenant.def()
+    }
+}
+
+impl Related<super::webhook_delivery::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Deliveries.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {
+    fn new() -> Self {
+        let now = chrono::Utc::now();
+        Self {
+            id: Set(Uuid::new_v4()),
+            is_active: Set(true),
+            created_at: Set(now.into()),
+            updated_at: Set(now.into()),
+            ..ActiveModelTrait::default()
+        }
+    }
+}
+
+impl Model {
+    /// The subscribed event types; unknown names are skipped
+    pub fn event_types(&self) -> Vec<WebhookEventType> {
+        self.event_types
+            .as_array()
+            .map(|names| {
+                names
+                    .iter()
+                    .filter_map(|name| serde_json::from_value(name.clone()).ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Whether the endpoint should be sent `event_type` events
+    pub fn wants(&self, event_type: WebhookEventType) -> bool {
+        self.is_active && self.event_types().contains(&event_type)
+    }
+}
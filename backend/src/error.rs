@@ -1,10 +1,12 @@
 use axum::{
-    http::StatusCode,
-    response::{IntoResponse, Json},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
 };
-use serde_json::json;
+use shared::errors::{FieldError, ProblemDetails, PROBLEM_JSON};
 use thiserror::Error;
 
+use crate::middleware::request_id::current_request_id;
+
 #[derive(Error, Debug)]
 pub enum AppError {
     // TODO: Replace with sea_orm::DbErr when database is integrated
@@ -49,34 +51,29 @@ pub enum AppError {
     
     #[error("Feature not implemented: {0}")]
     NotImplemented(String),
+
+    /// The resource changed since the client read it (`If-Match` did not match)
+    #[error("Precondition failed")]
+    PreconditionFailed,
+
+    /// The resource moved on while the request was being handled, e.g. someone else
+    /// approved it first
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    /// Several fields rejected at once, e.g. by a `Validate` derive
+    #[error("Validation failed for {} field(s)", .0.len())]
+    InvalidFields(Vec<FieldError>),
+
+    /// An error from the shared crate, reported with all its details
+    #[error(transparent)]
+    Detailed(shared::errors::AppError),
 }
 
-// Conversion from shared AppError to backend AppError
+// Shared errors keep their field details, retry hints and suggestions
 impl From<shared::errors::AppError> for AppError {
     fn from(err: shared::errors::AppError) -> Self {
-        match err {
-            shared::errors::AppError::Database(message) => AppError::Database(message),
-            shared::errors::AppError::DatabaseWithContext { message, .. } => AppError::Database(message),
-            shared::errors::AppError::Validation { field, message, .. } => {
-                AppError::Validation(format!("{}: {}", field, message))
-            },
-            shared::errors::AppError::Authentication { reason, .. } => {
-                tracing::warn!("Authentication error: {}", reason);
-                AppError::AuthenticationFailed
-            },
-            shared::errors::AppError::Authorisation { .. } => AppError::AuthorizationFailed,
-            shared::errors::AppError::NotFound => AppError::NotFound,
-            shared::errors::AppError::NotFoundDetailed { resource_type, id, .. } => {
-                tracing::info!("Resource not found: {} with ID {}", resource_type, id);
-                AppError::NotFound
-            },
-            shared::errors::AppError::InvalidInput { message, .. } => AppError::Validation(message),
-            shared::errors::AppError::Internal { message, error_id, .. } => {
-                tracing::error!("Internal error ({}): {}", error_id, message);
-                AppError::Internal(message)
-            },
-            _ => AppError::Internal(err.to_string()),
-        }
+        AppError::Detailed(err)
     }
 }
 
@@ -87,39 +84,190 @@ impl From<sea_orm::DbErr> for AppError {
     }
 }
 
-impl IntoResponse for AppError {
-    fn into_response(self) -> axum::response::Response {
-        let (status, error_message) = match &self {
-            AppError::Database(err) => {
-                tracing::error!("Database error: {}", err);
-                (StatusCode::INTERNAL_SERVER_ERROR, "Database error occurred".to_string())
+/// Split a "field: message" validation message into its field and message. Field
+/// names are snake_case paths such as `recurrence.pattern.interval`.
+fn field_error(message: &str) -> Option<FieldError> {
+    let (field, message) = message.split_once(": ")?;
+    let is_path = !field.is_empty()
+        && field.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || matches!(b, b'_' | b'.' | b'[' | b']'));
+    is_path.then(|| FieldError { field: field.to_string(), message: message.to_string() })
+}
+
+impl AppError {
+    /// The error as an RFC 7807 problem details body
+    pub fn to_problem(&self) -> ProblemDetails {
+        const TECHNICAL_DIFFICULTIES: &str = "We're experiencing technical difficulties. Please try again.";
+        const SOMETHING_WENT_WRONG: &str = "Something went wrong. Our team has been notified.";
+
+        match self {
+            AppError::Detailed(err) => err.to_problem(),
+            AppError::Database(_) => {
+                ProblemDetails::new(500, "database_error", "Internal server error", TECHNICAL_DIFFICULTIES)
+            }
+            AppError::Validation(message) => {
+                let errors: Vec<FieldError> = field_error(message).into_iter().collect();
+                let user_message = match errors.first() {
+                    Some(error) => format!("Please check your {}: {}", error.field.replace('_', " "), error.message),
+                    None => message.clone(),
+                };
+                ProblemDetails::new(400, "validation_failed", "Invalid request", user_message)
+                    .with_detail(message.clone())
+                    .with_errors(errors)
+            }
+            AppError::InvalidFields(errors) => {
+                ProblemDetails::new(400, "validation_failed", "Invalid request", "Please check the highlighted fields.")
+                    .with_detail(self.to_string())
+                    .with_errors(errors.clone())
             }
-            AppError::Validation(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
-            AppError::AuthenticationFailed => (StatusCode::UNAUTHORIZED, "Authentication failed".to_string()),
-            AppError::AuthorizationFailed => (StatusCode::FORBIDDEN, "Authorization failed".to_string()),
-            AppError::NotFound => (StatusCode::NOT_FOUND, "Resource not found".to_string()),
-            AppError::HashingFailed => (StatusCode::INTERNAL_SERVER_ERROR, "Password hashing failed".to_string()),
-            AppError::TokenGenerationFailed => (StatusCode::INTERNAL_SERVER_ERROR, "Token generation failed".to_string()),
-            AppError::InvalidToken => (StatusCode::UNAUTHORIZED, "Invalid or expired token".to_string()),
-            AppError::Internal(msg) => {
-                tracing::error!("Internal error: {}", msg);
-                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
+            AppError::BadRequest(message) => {
+                ProblemDetails::new(400, "bad_request", "Invalid request", message.clone()).with_detail(message.clone())
             }
-            AppError::Configuration(msg) => {
-                tracing::error!("Configuration error: {}", msg);
-                (StatusCode::INTERNAL_SERVER_ERROR, "Service configuration error".to_string())
+            AppError::AuthenticationFailed => ProblemDetails::new(
+                401,
+                "authentication_failed",
+                "Authentication required",
+                "Please check your login details and try again.",
+            ),
+            AppError::InvalidToken => ProblemDetails::new(
+                401,
+                "invalid_token",
+                "Authentication required",
+                "Your session has expired. Please sign in again.",
+            ),
+            AppError::Unauthorized => ProblemDetails::new(
+                401,
+                "unauthorized",
+                "Authentication required",
+                "Please sign in to continue.",
+            ),
+            AppError::AuthorizationFailed | AppError::Forbidden => ProblemDetails::new(
+                403,
+                "forbidden",
+                "Forbidden",
+                "You don't have permission to perform this action.",
+            ),
+            AppError::NotFound => {
+                ProblemDetails::new(404, "not_found", "Not found", "Sorry, we couldn't find that resource.")
             }
-            AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
-            AppError::Forbidden => (StatusCode::FORBIDDEN, "Access forbidden".to_string()),
-            AppError::Unauthorized => (StatusCode::UNAUTHORIZED, "Authentication required".to_string()),
-            AppError::NotImplemented(msg) => (StatusCode::NOT_IMPLEMENTED, msg.clone()),
-        };
-
-        let body = Json(json!({
-            "error": error_message,
-            "timestamp": chrono::Utc::now()
-        }));
-
-        (status, body).into_response()
+            AppError::PreconditionFailed => ProblemDetails::new(
+                412,
+                "precondition_failed",
+                "Changed by someone else",
+                "Someone else changed this since you opened it. Reload to see their changes, then try again.",
+            ),
+            AppError::Conflict(message) => {
+                ProblemDetails::new(409, "conflict", "Changed by someone else", message.clone())
+                    .with_detail(message.clone())
+            }
+            AppError::NotImplemented(feature) => ProblemDetails::new(
+                501,
+                "not_implemented",
+                "Not implemented",
+                "That isn't available yet.",
+            )
+            .with_detail(format!("{} is not implemented yet", feature)),
+            AppError::HashingFailed | AppError::TokenGenerationFailed | AppError::Internal(_) => {
+                ProblemDetails::new(500, "internal_error", "Internal server error", SOMETHING_WENT_WRONG)
+            }
+            AppError::Configuration(_) => {
+                ProblemDetails::new(500, "configuration_error", "Internal server error", SOMETHING_WENT_WRONG)
+            }
+        }
     }
-}
\ No newline at end of file
+}
+
+/// An error response in problem+json form, tagged with the request's correlation ID
+pub fn problem_response(mut problem: ProblemDetails) -> Response {
+    if let Some(request_id) = current_request_id() {
+        problem.correlation_id = Some(request_id);
+    }
+    let status = StatusCode::from_u16(problem.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    let retry_after = problem.retry_after;
+
+    let mut response = (status, Json(problem)).into_response();
+    let headers = response.headers_mut();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+    if let Some(seconds) = retry_after {
+        headers.insert(header::RETRY_AFTER, HeaderValue::from(seconds));
+    }
+    response
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let problem = self.to_problem();
+        let correlation_id = current_request_id().or_else(|| problem.correlation_id.clone()).unwrap_or_default();
+        if problem.is_server_error() {
+            tracing::error!(correlation_id = %correlation_id, code = %problem.code, "{}", self);
+        } else {
+            tracing::debug!(correlation_id = %correlation_id, code = %problem.code, "{}", self);
+        }
+
+        problem_response(problem)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::request_id::with_request_id;
+
+    async fn problem_of(response: Response) -> ProblemDetails {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[test]
+    fn test_validation_messages_name_their_field() {
+        let problem = AppError::Validation("payment_terms_days: Must be 90 or less".to_string()).to_problem();
+        assert_eq!(problem.code, "validation_failed");
+        assert_eq!(problem.errors[0].field, "payment_terms_days");
+        assert_eq!(problem.user_message, "Please check your payment terms days: Must be 90 or less");
+
+        let problem = AppError::Validation("Only accepted applications can be invoiced".to_string()).to_problem();
+        assert!(problem.errors.is_empty());
+        assert_eq!(problem.user_message, "Only accepted applications can be invoiced");
+    }
+
+    #[test]
+    fn test_conflicts_explain_what_changed() {
+        let problem = AppError::Conflict("The timesheet changed while it was being approved".to_string()).to_problem();
+        assert_eq!(problem.status, 409);
+        assert_eq!(problem.code, "conflict");
+        assert_eq!(problem.user_message, "The timesheet changed while it was being approved");
+    }
+
+    #[tokio::test]
+    async fn test_problem_response() {
+        let response = with_request_id("req-7", async { AppError::NotFound.into_response() }).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+
+        let problem = problem_of(response).await;
+        assert_eq!(problem.code, "not_found");
+        assert_eq!(problem.correlation_id.as_deref(), Some("req-7"));
+    }
+
+    #[tokio::test]
+    async fn test_shared_errors_keep_their_details() {
+        let limited: AppError = shared::errors::AppError::RateLimit {
+            resource: "search".to_string(),
+            retry_after_seconds: 30,
+            limit: 60,
+        }
+        .into();
+        let response = limited.into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "30");
+        assert_eq!(problem_of(response).await.retry_after, Some(30));
+
+        let response = AppError::from(shared::errors::AppError::validation("postcode", "Must be 4 digits")).into_response();
+        let problem = problem_of(response).await;
+        assert_eq!(problem.status, 400);
+        assert_eq!(problem.errors[0].field, "postcode");
+
+        let problem = problem_of(AppError::Database("relation \"job\" does not exist".to_string()).into_response()).await;
+        assert_eq!(problem.detail, None);
+        assert!(!problem.user_message.contains("relation"));
+    }
+}
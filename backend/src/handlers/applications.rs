@@ -6,6 +6,7 @@ use axum::{
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
+use shared::types::CursorParams;
 
 use crate::{
     entities::{
@@ -13,8 +14,12 @@ use crate::{
         job,
         user,
     },
-    repository::PaginationParams,
-    services::application_service::ApplicationService,
+    services::{
+        application_service::{
+            BulkItemOutcome, DEFAULT_REJECTION_TEMPLATE, MAX_BULK_APPLICATIONS,
+        },
+        notification_service::NotificationService,
+    },
     error::AppError,
     middleware::auth::AuthContext,
     AppState,
@@ -29,6 +34,9 @@ pub struct CreateApplicationRequest {
     pub experience_years: Option<i32>,
     pub registration_number: Option<String>,
     pub preferred_contact_method: Option<String>,
+    /// Shifts being applied for; empty means every shift of the job
+    #[serde(default)]
+    pub shift_ids: Vec<Uuid>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -59,6 +67,45 @@ pub struct ApplicationFilters {
     pub include_withdrawn: bool,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum BulkApplicationAction {
+    Shortlist,
+    Reject,
+    Export,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BulkApplicationRequest {
+    pub action: BulkApplicationAction,
+    pub application_ids: Vec<Uuid>,
+    /// Message sent with rejections; supports {first_name}, {last_name}, {job_title} and {pharmacy_name}
+    pub message_template: Option<String>,
+    /// When true, no application is changed unless every item succeeds
+    #[serde(default)]
+    pub all_or_nothing: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkApplicationItemResult {
+    pub application_id: Uuid,
+    pub success: bool,
+    pub status: Option<ApplicationStatus>,
+    pub message: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkApplicationResponse {
+    pub job_id: Uuid,
+    pub action: BulkApplicationAction,
+    pub all_or_nothing: bool,
+    pub applied: bool,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BulkApplicationItemResult>,
+    pub export_csv: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ApplicationResponse {
     pub id: Uuid,
@@ -148,12 +195,15 @@ pub async fn create_application(
     let service_req = shared::types::CreateApplicationRequest {
         job_id: req.job_id,
         cover_letter: req.cover_letter,
+        shift_ids: req.shift_ids,
     };
     
     let application = state.application_service
         .create_application(service_req, auth.user_id())
         .await?;
     
+    NotificationService::from(&state).application_received(&application).await;
+    
     let response = ApplicationResponse::from_model(application);
     Ok((StatusCode::CREATED, Json(response)))
 }
@@ -182,24 +232,19 @@ pub async fn get_application(
     }))
 }
 
-/// List applications with filtering and pagination
+/// List applications with filtering, newest first, a cursor page at a time
 #[axum::debug_handler]
 pub async fn list_applications(
     Query(filters): Query<ApplicationFilters>,
-    Query(pagination): Query<PaginationParams>,
+    Query(page): Query<CursorParams>,
     State(state): State<AppState>,
     auth: AuthContext,
 ) -> Result<impl IntoResponse, AppError> {
     let applications = state.application_service
-        .list_applications(filters, pagination, auth.user_id())
+        .list_applications(filters, page, auth.user_id())
         .await?;
 
-    let response: Vec<ApplicationResponse> = applications
-        .into_iter()
-        .map(ApplicationResponse::from_model)
-        .collect();
-
-    Ok(Json(response))
+    Ok(Json(applications.map(ApplicationResponse::from_model)))
 }
 
 /// Update application details (for applicants)
@@ -246,6 +291,10 @@ pub async fn update_application_status(
         .update_application_status(application_id, shared_status, Some(auth.user_id()))
         .await?;
 
+    NotificationService::from(&state)
+        .application_status_changed(vec![(application.clone(), None)])
+        .await;
+
     let response = ApplicationResponse::from_model(application);
     Ok(Json(response))
 }
@@ -281,32 +330,130 @@ pub async fn delete_application(
 #[axum::debug_handler]
 pub async fn get_job_applications(
     Path(job_id): Path<Uuid>,
-    Query(pagination): Query<PaginationParams>,
+    Query(page): Query<CursorParams>,
     State(state): State<AppState>,
     auth: AuthContext,
 ) -> Result<impl IntoResponse, AppError> {
     let applications = state.application_service
-        .get_applications_for_job(job_id, pagination, auth.user_id())
+        .get_applications_for_job(job_id, page, auth.user_id())
         .await?;
 
     // TODO: Fix ApplicationWithJobAndUser mapping
-    let response: Vec<ApplicationWithJobAndUser> = applications
+    let response = applications.map(|app| ApplicationWithJobAndUser {
+        application: ApplicationResponse::from_model(app),
+        job: None,
+        user: None,
+    });
+
+    Ok(Json(response))
+}
+
+/// Shortlist, reject or export many applications for a job (employers/admins)
+pub async fn bulk_application_action(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(job_id): Path<Uuid>,
+    Json(req): Json<BulkApplicationRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if !auth.is_employer() && !auth.is_admin() {
+        return Err(AppError::Forbidden);
+    }
+
+    if req.application_ids.is_empty() {
+        return Err(AppError::Validation("At least one application ID is required".to_string()));
+    }
+
+    if req.application_ids.len() > MAX_BULK_APPLICATIONS {
+        return Err(AppError::Validation(format!(
+            "A maximum of {} applications can be processed at once",
+            MAX_BULK_APPLICATIONS
+        )));
+    }
+
+    // Admins may act on any job; employers only on their own
+    let employer_user_id = if auth.is_admin() { None } else { Some(auth.user_id()) };
+
+    let outcomes = match req.action {
+        BulkApplicationAction::Shortlist => {
+            state.application_service
+                .bulk_update_application_status(
+                    job_id,
+                    &req.application_ids,
+                    shared::types::ApplicationStatus::Shortlisted,
+                    None,
+                    req.all_or_nothing,
+                    employer_user_id,
+                )
+                .await?
+        }
+        BulkApplicationAction::Reject => {
+            let template = req.message_template.as_deref().unwrap_or(DEFAULT_REJECTION_TEMPLATE);
+            state.application_service
+                .bulk_update_application_status(
+                    job_id,
+                    &req.application_ids,
+                    shared::types::ApplicationStatus::Rejected,
+                    Some(template),
+                    req.all_or_nothing,
+                    employer_user_id,
+                )
+                .await?
+        }
+        BulkApplicationAction::Export => {
+            state.application_service
+                .export_job_applications(job_id, &req.application_ids, employer_user_id)
+                .await?
+        }
+    };
+
+    let failed = outcomes.iter().filter(|o| !o.is_success()).count();
+    let succeeded = outcomes.len() - failed;
+    let applied = !(req.all_or_nothing && failed > 0);
+
+    if applied && !matches!(req.action, BulkApplicationAction::Export) {
+        let changes = outcomes
+            .iter()
+            .filter(|o| o.is_success())
+            .filter_map(|o| o.application.clone().map(|app| (app, o.message.clone())))
+            .collect();
+        NotificationService::from(&state).application_status_changed(changes).await;
+    }
+
+    let export_csv = match req.action {
+        BulkApplicationAction::Export if applied => Some(applications_to_csv(&outcomes)),
+        _ => None,
+    };
+
+    let results = outcomes
         .into_iter()
-        .map(|app| ApplicationWithJobAndUser {
-            application: ApplicationResponse::from_model(app),
-            job: None,
-            user: None,
+        .map(|outcome| BulkApplicationItemResult {
+            application_id: outcome.application_id,
+            success: outcome.error.is_none(),
+            status: outcome.application.map(|app| app.status),
+            message: outcome.message,
+            error: outcome.error,
         })
         .collect();
 
-    Ok(Json(response))
+    let status = if applied { StatusCode::OK } else { StatusCode::UNPROCESSABLE_ENTITY };
+
+    Ok((status, Json(BulkApplicationResponse {
+        job_id,
+        action: req.action,
+        all_or_nothing: req.all_or_nothing,
+        applied,
+        succeeded,
+        failed,
+        results,
+        export_csv,
+    })))
 }
 
 /// Get applications by a specific user
 #[axum::debug_handler]
 pub async fn get_user_applications(
     Path(user_id): Path<Uuid>,
-    Query(pagination): Query<PaginationParams>,
+    Query(page): Query<CursorParams>,
     State(state): State<AppState>,
     auth: AuthContext,
 ) -> Result<impl IntoResponse, AppError> {
@@ -316,18 +463,15 @@ pub async fn get_user_applications(
     }
 
     let applications = state.application_service
-        .get_applications_by_user(user_id, pagination)
+        .get_applications_by_user(user_id, page)
         .await?;
 
     // TODO: Fix ApplicationWithJobAndUser mapping
-    let response: Vec<ApplicationWithJobAndUser> = applications
-        .into_iter()
-        .map(|app| ApplicationWithJobAndUser {
-            application: ApplicationResponse::from_model(app),
-            job: None,
-            user: None,
-        })
-        .collect();
+    let response = applications.map(|app| ApplicationWithJobAndUser {
+        application: ApplicationResponse::from_model(app),
+        job: None,
+        user: None,
+    });
 
     Ok(Json(response))
 }
@@ -345,6 +489,55 @@ pub async fn get_application_stats(
     Ok(Json(stats))
 }
 
+/// Render successfully exported applications as CSV
+fn applications_to_csv(outcomes: &[BulkItemOutcome]) -> String {
+    let mut csv = String::from(
+        "application_id,first_name,last_name,email,phone,status,applied_at,experience_years,registration_number\n",
+    );
+
+    for outcome in outcomes {
+        let Some(app) = &outcome.application else { continue };
+        let applicant = outcome.applicant.as_ref();
+        let applied_at: DateTime<Utc> = app.applied_at.into();
+
+        let fields = [
+            app.id.to_string(),
+            applicant.map(|u| u.first_name.clone()).unwrap_or_default(),
+            applicant.map(|u| u.last_name.clone()).unwrap_or_default(),
+            applicant.map(|u| u.email.clone()).unwrap_or_default(),
+            applicant.and_then(|u| u.phone.clone()).unwrap_or_default(),
+            app.status_display().to_string(),
+            applied_at.to_rfc3339(),
+            app.experience_years.map(|y| y.to_string()).unwrap_or_default(),
+            app.registration_number.clone().unwrap_or_default(),
+        ];
+
+        let row: Vec<String> = fields.iter().map(|f| csv_escape(f)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// Characters that make a spreadsheet read a cell as a formula
+const FORMULA_TRIGGERS: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+/// Quote a CSV field when it contains separators, quotes or newlines, and stop
+/// spreadsheets running applicant-supplied text as a formula by prefixing `'`
+fn csv_escape(field: &str) -> String {
+    let field = if field.starts_with(FORMULA_TRIGGERS) {
+        format!("'{}", field)
+    } else {
+        field.to_string()
+    };
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
+
 impl ApplicationResponse {
     pub fn from_model(model: application::Model) -> Self {
         let status_display = model.status_display().to_string();
@@ -428,4 +621,29 @@ impl UserSummary {
             user_type: format!("{:?}", model.user_type),
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_fields_are_quoted_and_formulas_neutralised() {
+        let cases = [
+            ("Jane", "Jane"),
+            ("Smith, Jr", "\"Smith, Jr\""),
+            ("say \"hi\"", "\"say \"\"hi\"\"\""),
+            ("=HYPERLINK(\"http://evil.example\")", "\"'=HYPERLINK(\"\"http://evil.example\"\")\""),
+            ("+61 412 345 678", "'+61 412 345 678"),
+            ("-2+3", "'-2+3"),
+            ("@SUM(A1)", "'@SUM(A1)"),
+            ("\t=1", "'\t=1"),
+            ("\r=1", "\"'\r=1\""),
+            ("a=b", "a=b"),
+            ("", ""),
+        ];
+        for (field, escaped) in cases {
+            assert_eq!(csv_escape(field), escaped, "{:?}", field);
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use axum::{
+    extract::{Query, State},
+    response::{IntoResponse, Json},
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use shared::types::CursorParams;
+use uuid::Uuid;
+
+use crate::{
+    entities::audit_event::{self, AuditAction},
+    error::AppError,
+    middleware::{auth::AuthContext, rbac::Permission},
+    services::audit_service::{AuditFilters, AuditService},
+    AppState,
+};
+
+#[derive(Debug, Serialize)]
+pub struct AuditEventResponse {
+    pub id: Uuid,
+    pub seq: i64,
+    pub actor_id: Option<Uuid>,
+    pub tenant_id: Option<Uuid>,
+    pub ip: Option<String>,
+    pub request_id: Option<String>,
+    pub action: AuditAction,
+    pub entity_type: String,
+    pub entity_id: Option<String>,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+    pub prev_hash: String,
+    pub hash: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditEventResponse {
+    fn from_model(model: audit_event::Model) -> Self {
+        Self {
+            id: model.id,
+            seq: model.seq,
+            actor_id: model.actor_id,
+            tenant_id: model.tenant_id,
+            ip: model.ip,
+            request_id: model.request_id,
+            action: model.action,
+            entity_type: model.entity_type,
+            entity_id: model.entity_id,
+            before: model.before,
+            after: model.after,
+            prev_hash: model.prev_hash,
+            hash: model.hash,
+            created_at: model.created_at.into(),
+        }
+    }
+}
+
+fn require_audit_access(auth: &AuthContext) -> Result<(), AppError> {
+    if auth.has_permission(&Permission::ViewAuditLog) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden)
+    }
+}
+
+/// Search the audit trail by actor, tenant, action, entity and time, newest first (admin only)
+pub async fn list_audit_events(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Query(filters): Query<AuditFilters>,
+    Query(page): Query<CursorParams>,
+) -> Result<impl IntoResponse, AppError> {
+    require_audit_access(&auth)?;
+
+    let events = AuditService::from(&state).list(filters, page).await?;
+    Ok(Json(events.map(AuditEventResponse::from_model)))
+}
+
+/// Recompute the hash chain and report the first event that does not fit (admin only)
+pub async fn verify_audit_chain(
+    State(state): State<AppState>,
+    auth: AuthContext,
+) -> Result<impl IntoResponse, AppError> {
+    require_audit_access(&auth)?;
+
+    let verification = AuditService::from(&state).verify().await?;
+    if !verification.valid {
+        tracing::error!(
+            "🔏 Audit chain broken at event {:?}: {:?}",
+            verification.broken_at_seq, verification.problem
+        );
+    }
+    Ok(Json(verification))
+}
@@ -8,7 +8,12 @@ use serde_json::json;
 use uuid::Uuid;
 
 use crate::{
-    services::supabase_auth::{SupabaseAuthService, SignUpInput, SignInInput, UpdateUserInput},
+    entities::audit_event::AuditAction,
+    services::{
+        audit_service::{AuditEntry, AuditService},
+        metrics::{self, DomainEvent},
+        supabase_auth::{SupabaseAuthService, SignUpInput, SignInInput, UpdateUserInput},
+    },
     AppState, AppError,
 };
 use shared::types::UserType;
@@ -51,7 +56,7 @@ pub struct UserInfo {
 
 /// User login endpoint with Supabase
 pub async fn login(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Json(request): Json<LoginRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     tracing::info!("Login attempt for email: {}", request.email);
@@ -67,13 +72,21 @@ pub async fn login(
         password: request.password,
     };
     
-    let auth_response = auth_service
-        .sign_in(sign_in_input)
-        .await
-        .map_err(|e| {
+    let auth_response = match auth_service.sign_in(sign_in_input).await {
+        Ok(auth_response) => auth_response,
+        Err(e) => {
             tracing::error!("Supabase sign in failed: {}", e);
-            AppError::AuthenticationFailed
-        })?;
+            metrics::record(DomainEvent::LoginFailed);
+            // The caller gets the same rejection whether or not the attempt could be audited
+            if let Err(e) = AuditService::from(&state)
+                .record(AuditEntry::new(AuditAction::LoginFailed, "user", &request.email))
+                .await
+            {
+                tracing::error!("Failed to audit failed login: {}", e);
+            }
+            return Err(AppError::AuthenticationFailed);
+        }
+    };
     
     // Extract user metadata
     let user_metadata = &auth_response.user.user_metadata;
@@ -96,6 +109,13 @@ pub async fn login(
         },
     };
     
+    AuditService::from(&state)
+        .record(
+            AuditEntry::new(AuditAction::Login, "user", response.user.id)
+                .actor(response.user.id)
+                .tenant(response.user.tenant_id),
+        )
+        .await?;
     tracing::info!("Successful login for user: {}", response.user.id);
     
     Ok((
@@ -0,0 +1,144 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json},
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use shared::availability::{parse_slot_time, AvailabilityRequest, AvailabilitySlot};
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    middleware::{auth::AuthContext, rbac::Permission},
+    services::availability_service::{self, AvailabilityService, AvailableUser},
+    AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct AvailabilityRange {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ShiftWindowQuery {
+    pub date: NaiveDate,
+    pub start_time: String,
+    pub end_time: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AvailableUserResponse {
+    pub user_id: Uuid,
+    pub name: String,
+    pub suburb: Option<String>,
+    pub state: Option<String>,
+    pub preferred: bool,
+    pub slots: Vec<AvailabilitySlot>,
+}
+
+impl AvailableUserResponse {
+    fn from_candidate(candidate: AvailableUser) -> Self {
+        Self {
+            user_id: candidate.user.id,
+            name: candidate.user.full_name(),
+            suburb: candidate.user.suburb,
+            state: candidate.user.state,
+            preferred: candidate.preferred,
+            slots: candidate.slots,
+        }
+    }
+}
+
+/// List the current user's availability, with recurring slots expanded into
+/// one entry per date between `from` and `to`
+pub async fn list_availability(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Query(range): Query<AvailabilityRange>,
+) -> Result<impl IntoResponse, AppError> {
+    let (from, to) = availability_service::resolve_range(range.from, range.to)?;
+    let slots = AvailabilityService::from(&state)
+        .list(auth.user_id(), from, to)
+        .await?;
+
+    Ok(Json(slots))
+}
+
+/// Get one of the current user's slots as stored, dated on its first occurrence
+pub async fn get_availability(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(slot_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let slot = AvailabilityService::from(&state)
+        .get(auth.user_id(), slot_id)
+        .await?;
+
+    Ok(Json(slot.to_slot()))
+}
+
+/// Add a one-off or recurring slot for the current user
+pub async fn create_availability(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Json(req): Json<AvailabilityRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let slot = AvailabilityService::from(&state)
+        .create(auth.user_id(), req.into_slot(Uuid::nil()))
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(slot.to_slot())))
+}
+
+/// Replace one of the current user's slots; recurring slots change as a whole series
+pub async fn update_availability(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(slot_id): Path<Uuid>,
+    Json(req): Json<AvailabilityRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let slot = AvailabilityService::from(&state)
+        .update(auth.user_id(), slot_id, req.into_slot(slot_id))
+        .await?;
+
+    Ok(Json(slot.to_slot()))
+}
+
+/// Delete one of the current user's slots (the whole series if it recurs)
+pub async fn delete_availability(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(slot_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    AvailabilityService::from(&state)
+        .delete(auth.user_id(), slot_id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Find who is available for a shift window (employers and admins)
+pub async fn find_available(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Query(query): Query<ShiftWindowQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    if !auth.has_permission(&Permission::ViewAvailability) {
+        return Err(AppError::Forbidden);
+    }
+
+    let start = parse_slot_time("start_time", &query.start_time)?;
+    let end = parse_slot_time("end_time", &query.end_time)?;
+    let candidates = AvailabilityService::from(&state)
+        .available_for(query.date, start, end)
+        .await?;
+
+    Ok(Json(
+        candidates
+            .into_iter()
+            .map(AvailableUserResponse::from_candidate)
+            .collect::<Vec<_>>(),
+    ))
+}
@@ -0,0 +1,16 @@
+use axum::response::{Html, IntoResponse, Json};
+
+use crate::openapi;
+
+/// Self-contained page that renders `openapi.json`, so the docs work without a CDN
+const DOCS_PAGE: &str = include_str!("docs.html");
+
+/// The v1 API's OpenAPI 3 document
+pub async fn openapi_json() -> impl IntoResponse {
+    Json(openapi::spec())
+}
+
+/// API documentation browsable in the browser
+pub async fn docs_page() -> impl IntoResponse {
+    Html(DOCS_PAGE)
+}
@@ -9,12 +9,16 @@ use chrono::{DateTime, Utc};
 
 use crate::{
     entities::job,
-    services::EnhancedJobService,
+    services::{
+        job_scheduler::JobScheduler,
+        metrics::{self, DomainEvent},
+        EnhancedJobService,
+    },
     middleware::auth::AuthContext,
     repository::PaginationParams,
     AppError, AppState,
 };
-use shared::types::{CreateJobRequest, JobFilters, AustralianState};
+use shared::types::{CreateJobRequest, CursorParams, JobFilters, AustralianState};
 use rust_decimal::Decimal;
 use num_traits::ToPrimitive;
 
@@ -26,6 +30,7 @@ pub async fn create_job(
 ) -> Result<impl IntoResponse, AppError> {
     let service = EnhancedJobService::from(&state);
     let job = service.create_job(req, auth.user_id()).await?;
+    metrics::record(DomainEvent::JobPosted);
     
     let response = JobResponse::from_model(job);
     Ok((StatusCode::CREATED, Json(response)))
@@ -43,28 +48,16 @@ pub async fn get_job(
     Ok(Json(response))
 }
 
-/// List jobs with filtering and pagination
+/// List jobs with filtering, newest first, a cursor page at a time
 pub async fn list_jobs(
     State(state): State<AppState>,
     Query(filters): Query<JobFilters>,
-    Query(pagination): Query<PaginationParams>,
+    Query(page): Query<CursorParams>,
 ) -> Result<impl IntoResponse, AppError> {
     let service = EnhancedJobService::from(&state);
-    let (jobs, total) = service.list_jobs(filters, pagination.clone()).await?;
-    
-    let job_responses: Vec<JobResponse> = jobs
-        .into_iter()
-        .map(JobResponse::from_model)
-        .collect();
-    
-    let response = JobListResponse {
-        jobs: job_responses,
-        total,
-        page: pagination.page(),
-        page_size: pagination.page_size(),
-    };
+    let jobs = service.list_jobs(filters, page).await?;
     
-    Ok(Json(response))
+    Ok(Json(jobs.map(JobResponse::from_model)))
 }
 
 /// Update job status
@@ -74,12 +67,12 @@ pub async fn update_job_status(
     Path(job_id): Path<Uuid>,
     Json(req): Json<UpdateJobStatusRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    // For now, return a simple success response since the enhanced service needs work
-    Ok(Json(serde_json::json!({
-        "message": "Job status update functionality coming soon",
-        "job_id": job_id,
-        "status": format!("{:?}", req.status)
-    })))
+    let service = EnhancedJobService::from(&state);
+    let job = service
+        .update_job_status(job_id, req.status, auth.user_id(), auth.is_admin())
+        .await?;
+
+    Ok(Json(JobResponse::from_model(job)))
 }
 
 /// Delete job
@@ -94,57 +87,34 @@ pub async fn delete_job(
     Ok(StatusCode::NO_CONTENT)
 }
 
-/// Get jobs posted by the authenticated user
+/// Get jobs posted by the authenticated user, newest first
 pub async fn get_my_jobs(
     State(state): State<AppState>,
     auth: AuthContext,
-    Query(pagination): Query<PaginationParams>,
+    Query(page): Query<CursorParams>,
 ) -> Result<impl IntoResponse, AppError> {
     let service = EnhancedJobService::from(&state);
-    let (jobs, total) = service.get_jobs_by_user(auth.user_id(), pagination.clone()).await?;
-    
-    let job_responses: Vec<JobResponse> = jobs
-        .into_iter()
-        .map(JobResponse::from_model)
-        .collect();
+    let jobs = service.get_jobs_by_user(auth.user_id(), page).await?;
     
-    let response = JobListResponse {
-        jobs: job_responses,
-        total,
-        page: pagination.page(),
-        page_size: pagination.page_size(),
-    };
-    
-    Ok(Json(response))
+    Ok(Json(jobs.map(JobResponse::from_model)))
 }
 
-/// Search jobs by text
+/// Search jobs by text, newest first
 pub async fn search_jobs(
     State(state): State<AppState>,
     Query(params): Query<SearchJobsQuery>,
     Query(filters): Query<JobFilters>,
-    Query(pagination): Query<PaginationParams>,
+    Query(page): Query<CursorParams>,
 ) -> Result<impl IntoResponse, AppError> {
     let service = EnhancedJobService::from(&state);
     let query = params.q.unwrap_or_default();
-    let (jobs, total) = service.search_jobs(&query, filters, pagination.clone()).await?;
-    
-    let job_responses: Vec<JobResponse> = jobs
-        .into_iter()
-        .map(JobResponse::from_model)
-        .collect();
+    let jobs = service.search_jobs(&query, filters, page).await?;
     
-    let response = JobListResponse {
-        jobs: job_responses,
-        total,
-        page: pagination.page(),
-        page_size: pagination.page_size(),
-    };
-    
-    Ok(Json(response))
+    Ok(Json(jobs.map(JobResponse::from_model)))
 }
 
-/// Find jobs near a location
+/// Find jobs near a location, closest first. Paged by page number, since distance
+/// order has no creation-time cursor to follow on from.
 pub async fn find_jobs_nearby(
     State(state): State<AppState>,
     Query(params): Query<LocationSearchQuery>,
@@ -188,6 +158,20 @@ pub async fn get_job_statistics(
     Ok(Json(stats))
 }
 
+/// Schedule a draft job to go live at `publish_at`, or clear the schedule
+pub async fn schedule_job_publish(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(job_id): Path<Uuid>,
+    Json(req): Json<SchedulePublishRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let job = JobScheduler::from(&state)
+        .schedule_publish(job_id, auth.user_id(), auth.is_admin(), req.publish_at)
+        .await?;
+
+    Ok(Json(JobResponse::from_model(job)))
+}
+
 /// Get global job statistics (admin only)
 pub async fn get_global_job_statistics(
     State(state): State<AppState>,
@@ -202,7 +186,13 @@ pub async fn get_global_job_statistics(
 
 #[derive(Debug, Deserialize)]
 pub struct UpdateJobStatusRequest {
-    pub status: String, // Simplified for now
+    /// `Active`, `Closed`, `Draft`, `Filled`, `Cancelled` or `Expired`
+    pub status: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SchedulePublishRequest {
+    pub publish_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -4,9 +4,14 @@ use axum::{
     response::{IntoResponse, Json},
 };
 use serde_json::json;
-use sea_orm::DatabaseConnection;
 
-use crate::AppState;
+use crate::{
+    services::{
+        health_service::{CheckStatus, HealthService},
+        metrics::{self, ProcessStats},
+    },
+    AppState,
+};
 
 /// Health check endpoint
 pub async fn health_check() -> impl IntoResponse {
@@ -19,26 +24,22 @@ pub async fn health_check() -> impl IntoResponse {
     }))
 }
 
-/// Detailed health check with dependencies
+/// Detailed health check: every dependency with its latency
 pub async fn health_detailed(
     State(state): State<AppState>,
 ) -> impl IntoResponse {
-    let db_status = check_database_health(&state.db).await;
-    
-    let overall_status = if db_status == "healthy" { "healthy" } else { "degraded" };
+    let report = HealthService::from(&state).detailed().await;
     
     Json(json!({
-        "status": overall_status,
+        "status": report.status,
+        "ready": report.ready,
         "service": "loco-platform-backend",
         "version": state.config.app_version,
         "timestamp": chrono::Utc::now(),
-        "environment": state.config.app_env,
+        "environment": state.config.profile.as_str(),
         "uptime": get_uptime(),
-        "checks": {
-            "database": db_status,
-            "memory": check_memory_usage(),
-            "external_apis": "demo_mode"
-        },
+        "memory": check_memory_usage(),
+        "checks": report.checks,
         "metadata": {
             "rust_version": env!("CARGO_PKG_RUST_VERSION"),
             "build_target": std::env::consts::ARCH,
@@ -48,21 +49,30 @@ pub async fn health_detailed(
     }))
 }
 
-/// Kubernetes readiness probe
+/// Kubernetes readiness probe; fails while the database is unreachable or
+/// migrations are pending
 pub async fn readiness_check(
     State(state): State<AppState>,
 ) -> impl IntoResponse {
-    let db_status = check_database_health(&state.db).await;
+    let report = HealthService::from(&state).readiness().await;
     
-    if db_status == "healthy" {
+    if report.ready {
         (StatusCode::OK, Json(json!({
             "status": "ready",
+            "checks": report.checks,
             "timestamp": chrono::Utc::now()
         })))
     } else {
+        let failing: Vec<&str> = report
+            .checks
+            .iter()
+            .filter(|check| check.critical && check.status == CheckStatus::Unhealthy)
+            .map(|check| check.name)
+            .collect();
         (StatusCode::SERVICE_UNAVAILABLE, Json(json!({
             "status": "not_ready",
-            "reason": "database_unavailable",
+            "reason": failing,
+            "checks": report.checks,
             "timestamp": chrono::Utc::now()
         })))
     }
@@ -77,25 +87,15 @@ pub async fn liveness_check() -> impl IntoResponse {
     }))
 }
 
-/// Check database connectivity
-async fn check_database_health(_db: &DatabaseConnection) -> String {
-    // For now, assume database is healthy if we have a connection
-    // In production, you'd ping the database
-    if let Ok(_) = std::env::var("DATABASE_URL") {
-        "healthy".to_string()
-    } else {
-        "demo_mode".to_string()
-    }
-}
-
-/// Get application uptime
+/// Time since the process started
 fn get_uptime() -> String {
-    // This is a simplified uptime - in production you'd track actual start time
-    format!("{}s", std::process::id())
+    format!("{}s", metrics::global().uptime().as_secs())
 }
 
-/// Check memory usage (simplified)
+/// Resident memory of the process
 fn check_memory_usage() -> String {
-    // In production, you'd use a proper memory monitoring library
-    "available".to_string()
-}
\ No newline at end of file
+    match ProcessStats::current().resident_memory_bytes {
+        Some(bytes) => format!("{:.1} MiB resident", bytes as f64 / (1024.0 * 1024.0)),
+        None => "unknown".to_string(),
+    }
+}
@@ -0,0 +1,152 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{Html, IntoResponse, Json},
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use shared::invoices::{format_abn, CreateInvoiceRequest, InvoiceLine};
+use uuid::Uuid;
+
+use crate::{
+    entities::invoice::{self, InvoiceStatus},
+    error::AppError,
+    middleware::auth::AuthContext,
+    services::invoice_service::{render_invoice_html, InvoiceService},
+    AppState,
+};
+
+#[derive(Debug, Serialize)]
+pub struct InvoiceResponse {
+    pub id: Uuid,
+    pub number: String,
+    pub title: &'static str,
+    pub status: InvoiceStatus,
+    pub application_id: Uuid,
+    pub job_id: Uuid,
+    pub supplier_id: Uuid,
+    pub supplier_name: String,
+    pub supplier_abn: String,
+    pub gst_registered: bool,
+    pub customer_id: Uuid,
+    pub customer_name: String,
+    pub customer_address: String,
+    pub lines: Vec<InvoiceLine>,
+    #[serde(serialize_with = "rust_decimal::serde::str::serialize")]
+    pub subtotal: Decimal,
+    #[serde(serialize_with = "rust_decimal::serde::str::serialize")]
+    pub gst: Decimal,
+    #[serde(serialize_with = "rust_decimal::serde::str::serialize")]
+    pub total: Decimal,
+    pub issued_on: NaiveDate,
+    pub due_on: NaiveDate,
+    pub payment_terms_days: i32,
+    pub paid_at: Option<DateTime<Utc>>,
+    pub notes: Option<String>,
+    pub document_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateInvoiceStatusRequest {
+    pub status: InvoiceStatus,
+}
+
+impl InvoiceResponse {
+    fn from_model(model: invoice::Model) -> Self {
+        Self {
+            title: model.title(),
+            lines: model.lines(),
+            supplier_abn: format_abn(&model.supplier_abn),
+            paid_at: model.paid_at.map(Into::into),
+            document_url: format!("/api/v1/invoices/{}/document", model.id),
+            id: model.id,
+            number: model.number,
+            status: model.status,
+            application_id: model.application_id,
+            job_id: model.job_id,
+            supplier_id: model.supplier_id,
+            supplier_name: model.supplier_name,
+            gst_registered: model.gst_registered,
+            customer_id: model.customer_id,
+            customer_name: model.customer_name,
+            customer_address: model.customer_address,
+            subtotal: model.subtotal,
+            gst: model.gst,
+            total: model.total,
+            issued_on: model.issued_on,
+            due_on: model.due_on,
+            payment_terms_days: model.payment_terms_days,
+            notes: model.notes,
+        }
+    }
+}
+
+/// Invoices the current user issued or received
+pub async fn list_invoices(
+    State(state): State<AppState>,
+    auth: AuthContext,
+) -> Result<impl IntoResponse, AppError> {
+    let invoices: Vec<InvoiceResponse> = InvoiceService::from(&state)
+        .list(auth.user_id(), auth.is_admin())
+        .await?
+        .into_iter()
+        .map(InvoiceResponse::from_model)
+        .collect();
+
+    Ok(Json(serde_json::json!({
+        "invoices": invoices,
+        "total": invoices.len()
+    })))
+}
+
+/// Invoice an accepted application's approved timesheets and any allowances (the locum)
+pub async fn create_invoice(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(application_id): Path<Uuid>,
+    Json(request): Json<CreateInvoiceRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let invoice = InvoiceService::from(&state)
+        .create(application_id, auth.user_id(), &request)
+        .await?;
+    tracing::info!("🧾 Invoice {} issued by {} for application {}", invoice.number, auth.user_id(), application_id);
+
+    Ok((StatusCode::CREATED, Json(InvoiceResponse::from_model(invoice))))
+}
+
+pub async fn get_invoice(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(invoice_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let invoice = InvoiceService::from(&state)
+        .get(invoice_id, auth.user_id(), auth.is_admin())
+        .await?;
+    Ok(Json(InvoiceResponse::from_model(invoice)))
+}
+
+/// The invoice as a printable HTML document
+pub async fn get_invoice_document(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(invoice_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let invoice = InvoiceService::from(&state)
+        .get(invoice_id, auth.user_id(), auth.is_admin())
+        .await?;
+    Ok(Html(render_invoice_html(&invoice)))
+}
+
+/// Mark an invoice paid, or reopen it (the supplier or an admin)
+pub async fn update_invoice_status(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(invoice_id): Path<Uuid>,
+    Json(request): Json<UpdateInvoiceStatusRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let invoice = InvoiceService::from(&state)
+        .set_status(invoice_id, auth.user_id(), auth.is_admin(), request.status)
+        .await?;
+    Ok(Json(InvoiceResponse::from_model(invoice)))
+}
@@ -0,0 +1,172 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Json},
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use shared::job_templates::{
+    CreateJobTemplateRequest, RepostDates, TemplateFromJobRequest, UpdateJobTemplateRequest,
+};
+use shared::shifts::JobShift;
+use uuid::Uuid;
+
+use crate::{
+    entities::{job_shift, job_template},
+    error::AppError,
+    handlers::enhanced_jobs::JobResponse,
+    middleware::auth::AuthContext,
+    services::job_template_service::JobTemplateService,
+    AppState,
+};
+
+#[derive(Debug, Serialize)]
+pub struct JobTemplateResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub owner_id: Uuid,
+    pub tenant_id: Option<Uuid>,
+    pub source_job_id: Option<Uuid>,
+    pub job: serde_json::Value,
+    pub use_count: i32,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl JobTemplateResponse {
+    fn from_model(model: job_template::Model) -> Self {
+        Self {
+            id: model.id,
+            name: model.name,
+            owner_id: model.owner_id,
+            tenant_id: model.tenant_id,
+            source_job_id: model.source_job_id,
+            job: model.job,
+            use_count: model.use_count,
+            last_used_at: model.last_used_at.map(Into::into),
+            created_at: model.created_at.into(),
+            updated_at: model.updated_at.into(),
+        }
+    }
+}
+
+/// Templates the current user can post from, their own and their tenants'
+pub async fn list_job_templates(
+    State(state): State<AppState>,
+    auth: AuthContext,
+) -> Result<impl IntoResponse, AppError> {
+    let templates: Vec<JobTemplateResponse> = JobTemplateService::from(&state)
+        .list(auth.user_id())
+        .await?
+        .into_iter()
+        .map(JobTemplateResponse::from_model)
+        .collect();
+
+    Ok(Json(serde_json::json!({
+        "templates": templates,
+        "total": templates.len()
+    })))
+}
+
+/// Save a posting from the job wizard as a template
+pub async fn create_job_template(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Json(request): Json<CreateJobTemplateRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let template = JobTemplateService::from(&state)
+        .create(auth.user_id(), auth.is_admin(), &request)
+        .await?;
+    Ok((StatusCode::CREATED, Json(JobTemplateResponse::from_model(template))))
+}
+
+pub async fn get_job_template(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(template_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let template = JobTemplateService::from(&state)
+        .get(template_id, auth.user_id(), auth.is_admin())
+        .await?;
+    Ok(Json(JobTemplateResponse::from_model(template)))
+}
+
+/// Rename a template or replace its posting (template owner or admin)
+pub async fn update_job_template(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(template_id): Path<Uuid>,
+    Json(request): Json<UpdateJobTemplateRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let template = JobTemplateService::from(&state)
+        .update(template_id, auth.user_id(), auth.is_admin(), &request)
+        .await?;
+    Ok(Json(JobTemplateResponse::from_model(template)))
+}
+
+pub async fn delete_job_template(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(template_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    JobTemplateService::from(&state)
+        .delete(template_id, auth.user_id(), auth.is_admin())
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// A `CreateJobRequest` pre-filled from a template with its dates moved, for the
+/// wizard to review and submit
+pub async fn post_from_template(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(template_id): Path<Uuid>,
+    Json(dates): Json<RepostDates>,
+) -> Result<impl IntoResponse, AppError> {
+    let (template, job) = JobTemplateService::from(&state)
+        .prefill(template_id, auth.user_id(), auth.is_admin(), &dates)
+        .await?;
+
+    Ok(Json(serde_json::json!({
+        "template_id": template.id,
+        "template_name": template.name,
+        "job": job
+    })))
+}
+
+/// Save a job, with its shifts, as a template (job owner or admin)
+pub async fn save_job_as_template(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(job_id): Path<Uuid>,
+    Json(request): Json<TemplateFromJobRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let template = JobTemplateService::from(&state)
+        .create_from_job(job_id, auth.user_id(), auth.is_admin(), &request)
+        .await?;
+    Ok((StatusCode::CREATED, Json(JobTemplateResponse::from_model(template))))
+}
+
+/// Copy a job and its shifts as a new draft with the dates moved (job owner or admin)
+pub async fn clone_job(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(job_id): Path<Uuid>,
+    Json(dates): Json<RepostDates>,
+) -> Result<impl IntoResponse, AppError> {
+    let (job, shifts) = JobTemplateService::from(&state)
+        .clone_job(job_id, auth.user_id(), auth.is_admin(), &dates)
+        .await?;
+    tracing::info!("📋 Job {} cloned as draft {} by {}", job_id, job.id, auth.user_id());
+    let shifts: Vec<JobShift> = shifts.iter().map(job_shift::Model::to_shift).collect();
+
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({
+            "source_job_id": job_id,
+            "job": JobResponse::from_model(job),
+            "shifts": shifts
+        })),
+    ))
+}
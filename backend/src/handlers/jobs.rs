@@ -1,33 +1,31 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Json},
 };
 use uuid::Uuid;
 use chrono::Utc;
 
-use shared::types::{SearchRequest, SearchResponse, CreateJobRequest, JobFilters};
+use shared::types::{CreateJobRequest, CursorParams, JobFilters, SearchRequest};
 use shared::validation::{ValidatedCreateJobRequest, ValidatedJobSearchRequest};
 use crate::{
     AppState, 
     AppError,
-    middleware::validation::ValidatedJson,
+    middleware::{auth::AuthContext, http_cache, validation::ValidatedJson},
+    repository::Cursor,
+    services::metrics::{self, DomainEvent},
 };
 
-/// Get all jobs with optional filtering
+/// Get jobs with optional filtering, newest first, a cursor page at a time
 pub async fn list_jobs(
     State(state): State<AppState>,
     Query(filters): Query<JobFilters>,
+    Query(page): Query<CursorParams>,
 ) -> Result<impl IntoResponse, AppError> {
-    let (jobs, total) = crate::services::JobService::list_jobs(filters, Some(1), Some(20)).await?;
+    let after = Cursor::from_params(&page)?;
+    let jobs = crate::services::JobService::list_jobs(filters, after, page.limit()).await?;
     
-    Ok(Json(SearchResponse {
-        jobs,
-        total_count: total,
-        page: 1,
-        limit: 20,
-        has_more: total > 20,
-    }))
+    Ok(Json(jobs))
 }
 
 /// Get a specific job by ID
@@ -35,7 +33,7 @@ pub async fn get_job(
     State(state): State<AppState>,
     Path(job_id): Path<Uuid>,
 ) -> Result<impl IntoResponse, AppError> {
-    match crate::services::JobService::get_job(job_id).await? {
+    match crate::services::JobService::get_job(&state.db, job_id).await? {
         Some(job) => Ok(Json(job)),
         None => Err(AppError::NotFound),
     }
@@ -64,24 +62,39 @@ pub async fn create_job(
         end_time: "17:00".to_string(),
         job_type: request.job_type,
         is_urgent: request.is_urgent,
+        shifts: request.shifts,
     };
     
     // TODO: Add authentication to get user_id
     let user_id = Uuid::new_v4(); // Mock user ID for demo
     let job = crate::services::JobService::create_job(job_request, user_id).await?;
+    metrics::record(DomainEvent::JobPosted);
     
     Ok((StatusCode::CREATED, Json(job)))
 }
 
-/// Update an existing job
+/// Update an existing job (its poster or an admin). With `If-Match`, only if the
+/// job is still the version the client last read, so concurrent edits can't
+/// silently overwrite each other.
 pub async fn update_job(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
+    auth: AuthContext,
     Path(job_id): Path<Uuid>,
+    headers: HeaderMap,
     Json(request): Json<CreateJobRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    // Demo mode: Use service
-    match crate::services::JobService::update_job(job_id, request).await? {
-        Some(job) => Ok(Json(job)),
+    let if_match = |current: &shared::types::Job| http_cache::if_match_allows(&headers, &http_cache::etag_for(current));
+    let updated = crate::services::JobService::update_job(
+        &state.db,
+        job_id,
+        auth.user_id(),
+        auth.is_admin(),
+        request,
+        if_match,
+    )
+    .await?;
+    match updated {
+        Some(job) => Ok(([(header::ETAG, http_cache::etag_for(&job))], Json(job))),
         None => Err(AppError::NotFound),
     }
 }
@@ -105,17 +118,12 @@ pub async fn search_jobs(
     Json(request): Json<SearchRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     // Demo mode: Use service
-    let (jobs, total) = crate::services::JobService::list_jobs(
-        request.filters,
-        request.page,
-        request.limit,
-    ).await?;
+    let page = CursorParams {
+        cursor: request.cursor,
+        limit: request.limit,
+    };
+    let after = Cursor::from_params(&page)?;
+    let jobs = crate::services::JobService::list_jobs(request.filters, after, page.limit()).await?;
     
-    Ok(Json(SearchResponse {
-        jobs,
-        total_count: total,
-        page: request.page.unwrap_or(1),
-        limit: request.limit.unwrap_or(20),
-        has_more: false,
-    }))
+    Ok(Json(jobs))
 }
\ No newline at end of file
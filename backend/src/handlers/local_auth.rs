@@ -9,7 +9,12 @@ use uuid::Uuid;
 use chrono::Utc;
 
 use crate::{
-    services::{AuthService, UserService},
+    entities::audit_event::AuditAction,
+    services::{
+        audit_service::{AuditEntry, AuditService},
+        metrics::{self, DomainEvent},
+        AuthService, UserService,
+    },
     AppState, AppError,
 };
 use shared::types::{User, UserType};
@@ -47,6 +52,14 @@ pub async fn local_login(
     let user = if state.demo_mode {
         // Demo mode: accept any email/password combination with demo credentials
         if request.password.len() < 4 {
+            metrics::record(DomainEvent::LoginFailed);
+            // The caller gets the same rejection whether or not the attempt could be audited
+            if let Err(e) = AuditService::from(&state)
+                .record(AuditEntry::new(AuditAction::LoginFailed, "user", &request.email))
+                .await
+            {
+                tracing::error!("Failed to audit failed login: {}", e);
+            }
             return Err(AppError::Validation("Password too short for demo".to_string()));
         }
         
@@ -68,7 +81,7 @@ pub async fn local_login(
     };
     
     // Generate JWT token
-    let jwt_secret = &state.config.jwt_secret;
+    let jwt_secret = state.config.jwt_secret.expose();
         
     let token = AuthService::generate_token(
         user.id,
@@ -88,6 +101,9 @@ pub async fn local_login(
         user,
     };
     
+    AuditService::from(&state)
+        .record(AuditEntry::new(AuditAction::Login, "user", response.user.id).actor(response.user.id))
+        .await?;
     tracing::info!("Successful local login for user: {}", response.user.id);
     
     Ok((
@@ -135,7 +151,7 @@ pub async fn local_register(
     };
     
     // Generate JWT token
-    let jwt_secret = &state.config.jwt_secret;
+    let jwt_secret = state.config.jwt_secret.expose();
         
     let token = AuthService::generate_token(
         user.id,
@@ -193,7 +209,7 @@ pub async fn verify_token(
     let token = AuthService::extract_token_from_header(auth_header)?;
     
     // Validate token
-    let jwt_secret = &state.config.jwt_secret;
+    let jwt_secret = state.config.jwt_secret.expose();
         
     let claims = AuthService::validate_token(token, jwt_secret)?;
     
@@ -220,7 +236,7 @@ pub async fn refresh_token(
     let token = AuthService::extract_token_from_header(auth_header)?;
     
     // Validate current token
-    let jwt_secret = &state.config.jwt_secret;
+    let jwt_secret = state.config.jwt_secret.expose();
         
     let claims = AuthService::validate_token(token, jwt_secret)?;
     
@@ -0,0 +1,123 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::{IntoResponse, Json},
+};
+use chrono::{DateTime, Utc};
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    entities::{job, user},
+    error::AppError,
+    middleware::auth::AuthContext,
+    services::matching_service::{self, MatchScore, MatchingService},
+    AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct MatchParams {
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecommendedJob {
+    pub job_id: Uuid,
+    pub title: String,
+    pub pharmacy_name: String,
+    pub location: String,
+    pub hourly_rate: f64,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+    pub start_time: String,
+    pub end_time: String,
+    pub is_urgent: bool,
+    #[serde(flatten)]
+    pub fit: MatchScore,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatchedCandidate {
+    pub user_id: Uuid,
+    pub name: String,
+    pub suburb: Option<String>,
+    pub postcode: Option<String>,
+    #[serde(flatten)]
+    pub fit: MatchScore,
+}
+
+impl RecommendedJob {
+    fn new(job: job::Model, fit: MatchScore) -> Self {
+        Self {
+            location: job.full_location(),
+            hourly_rate: job.hourly_rate.to_f64().unwrap_or_default(),
+            job_id: job.id,
+            title: job.title,
+            pharmacy_name: job.pharmacy_name,
+            start_date: job.start_date.into(),
+            end_date: job.end_date.into(),
+            start_time: job.start_time,
+            end_time: job.end_time,
+            is_urgent: job.is_urgent,
+            fit,
+        }
+    }
+}
+
+impl MatchedCandidate {
+    fn new(user: user::Model, fit: MatchScore) -> Self {
+        Self {
+            name: user.full_name(),
+            user_id: user.id,
+            suburb: user.suburb,
+            postcode: user.postcode,
+            fit,
+        }
+    }
+}
+
+/// Open jobs matched to the current professional's availability, location, rate and credentials
+pub async fn recommendations(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Query(params): Query<MatchParams>,
+) -> Result<impl IntoResponse, AppError> {
+    if !auth.is_professional() {
+        return Err(AppError::Forbidden);
+    }
+
+    let matches = MatchingService::from(&state)
+        .recommendations(auth.user_id(), matching_service::match_limit(params.limit))
+        .await?;
+    let recommendations: Vec<RecommendedJob> = matches
+        .into_iter()
+        .map(|(job, fit)| RecommendedJob::new(job, fit))
+        .collect();
+
+    Ok(Json(serde_json::json!({
+        "recommendations": recommendations,
+        "total": recommendations.len()
+    })))
+}
+
+/// Professionals best suited to a job (the job's poster or an admin)
+pub async fn job_candidates(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(job_id): Path<Uuid>,
+    Query(params): Query<MatchParams>,
+) -> Result<impl IntoResponse, AppError> {
+    let matches = MatchingService::from(&state)
+        .candidates(job_id, auth.user_id(), auth.is_admin(), matching_service::match_limit(params.limit))
+        .await?;
+    let candidates: Vec<MatchedCandidate> = matches
+        .into_iter()
+        .map(|(user, fit)| MatchedCandidate::new(user, fit))
+        .collect();
+
+    Ok(Json(serde_json::json!({
+        "job_id": job_id,
+        "candidates": candidates,
+        "total": candidates.len()
+    })))
+}
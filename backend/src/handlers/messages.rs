@@ -0,0 +1,379 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    entities::conversation_participant,
+    error::AppError,
+    handlers::{
+        uploads::UploadResponse,
+        websocket::{self, AttachmentNotification, ChatMessageNotification},
+    },
+    middleware::{auth::AuthContext, rbac::Permission},
+    services::{
+        messaging_service::{self, ConversationSummary, MessageWithAttachments, MessagingService},
+        notification_service::{NotificationEvent, NotificationService},
+    },
+    AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct StartConversationRequest {
+    pub job_id: Option<Uuid>,
+    pub application_id: Option<Uuid>,
+    /// Required when a job's poster starts a conversation about their own job
+    pub recipient_id: Option<Uuid>,
+    pub subject: Option<String>,
+    /// Optional first message
+    pub body: Option<String>,
+    #[serde(default)]
+    pub attachment_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SendMessageRequest {
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub attachment_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MessageHistoryQuery {
+    /// Only return messages sent before this time (for paging backwards)
+    pub before: Option<DateTime<Utc>>,
+    pub limit: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MarkReadRequest {
+    /// Mark messages read up to this time; defaults to now
+    pub up_to: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ParticipantResponse {
+    pub user_id: Uuid,
+    pub last_read_at: Option<DateTime<Utc>>,
+    pub joined_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConversationResponse {
+    pub id: Uuid,
+    pub job_id: Option<Uuid>,
+    pub application_id: Option<Uuid>,
+    pub subject: Option<String>,
+    pub created_by: Uuid,
+    pub participants: Vec<ParticipantResponse>,
+    pub unread_count: u64,
+    pub last_message_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MessageResponse {
+    pub id: Uuid,
+    pub conversation_id: Uuid,
+    pub sender_id: Uuid,
+    pub body: String,
+    pub attachments: Vec<UploadResponse>,
+    /// Participants other than the sender who have read the message
+    pub read_by: Vec<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MessageHistoryResponse {
+    pub conversation_id: Uuid,
+    pub messages: Vec<MessageResponse>,
+    pub has_more: bool,
+    /// Pass as `before` to fetch the previous page
+    pub next_before: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReadReceiptResponse {
+    pub conversation_id: Uuid,
+    pub user_id: Uuid,
+    pub last_read_at: Option<DateTime<Utc>>,
+}
+
+impl ConversationResponse {
+    fn from_summary(summary: ConversationSummary) -> Self {
+        let conversation = summary.conversation;
+        Self {
+            id: conversation.id,
+            job_id: conversation.job_id,
+            application_id: conversation.application_id,
+            subject: conversation.subject,
+            created_by: conversation.created_by,
+            participants: summary
+                .participants
+                .into_iter()
+                .map(|p| ParticipantResponse {
+                    user_id: p.user_id,
+                    last_read_at: p.last_read_at.map(Into::into),
+                    joined_at: p.joined_at.into(),
+                })
+                .collect(),
+            unread_count: summary.unread_count,
+            last_message_at: conversation.last_message_at.map(Into::into),
+            created_at: conversation.created_at.into(),
+        }
+    }
+}
+
+impl MessageResponse {
+    fn from_message(item: MessageWithAttachments, participants: &[conversation_participant::Model]) -> Self {
+        let read_by = messaging_service::read_by(&item.message, participants);
+        let message = item.message;
+        Self {
+            id: message.id,
+            conversation_id: message.conversation_id,
+            sender_id: message.sender_id,
+            body: message.body,
+            attachments: item.attachments.into_iter().map(UploadResponse::from_model).collect(),
+            read_by,
+            created_at: message.created_at.into(),
+        }
+    }
+
+    fn to_notification(&self) -> ChatMessageNotification {
+        ChatMessageNotification {
+            id: self.id.to_string(),
+            conversation_id: self.conversation_id.to_string(),
+            sender_id: self.sender_id.to_string(),
+            body: self.body.clone(),
+            attachments: self
+                .attachments
+                .iter()
+                .map(|a| AttachmentNotification {
+                    id: a.id.to_string(),
+                    file_name: a.file_name.clone(),
+                    content_type: a.content_type.clone(),
+                    size_bytes: a.size_bytes,
+                    url: a.url.clone(),
+                })
+                .collect(),
+            created_at: self.created_at,
+        }
+    }
+}
+
+fn require_send_permission(auth: &AuthContext) -> Result<(), AppError> {
+    if auth.has_permission(&Permission::SendMessage) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden)
+    }
+}
+
+/// Persist a message, push it to every participant's open connections and notify the recipients
+async fn send_and_deliver(
+    state: &AppState,
+    service: &MessagingService,
+    conversation_id: Uuid,
+    sender_id: Uuid,
+    body: &str,
+    attachment_ids: &[Uuid],
+) -> Result<MessageResponse, AppError> {
+    let (message, participants) = service
+        .send_message(conversation_id, sender_id, body, attachment_ids)
+        .await?;
+
+    let response = MessageResponse::from_message(message, &participants);
+    let recipients: Vec<Uuid> = participants.iter().map(|p| p.user_id).collect();
+    websocket::send_chat_message(&recipients, response.to_notification()).await;
+
+    let events = recipients
+        .iter()
+        .filter(|id| **id != sender_id)
+        .map(|id| NotificationEvent::new_message(*id, conversation_id, &response.body))
+        .collect();
+    NotificationService::from(state).dispatch_all(events).await;
+
+    Ok(response)
+}
+
+/// List the current user's conversations with unread counts
+pub async fn list_conversations(
+    State(state): State<AppState>,
+    auth: AuthContext,
+) -> Result<impl IntoResponse, AppError> {
+    let conversations = MessagingService::from(&state)
+        .list_conversations(auth.user_id())
+        .await?
+        .into_iter()
+        .map(ConversationResponse::from_summary)
+        .collect::<Vec<_>>();
+
+    Ok(Json(conversations))
+}
+
+/// Start a conversation about a job or application, optionally with a first message
+pub async fn start_conversation(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Json(req): Json<StartConversationRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    require_send_permission(&auth)?;
+
+    let service = MessagingService::from(&state);
+    let summary = service
+        .start_conversation(
+            auth.user_id(),
+            auth.is_admin(),
+            req.job_id,
+            req.application_id,
+            req.recipient_id,
+            req.subject,
+        )
+        .await?;
+
+    let has_first_message =
+        req.body.as_deref().is_some_and(|b| !b.trim().is_empty()) || !req.attachment_ids.is_empty();
+    let summary = if has_first_message {
+        let conversation_id = summary.conversation.id;
+        send_and_deliver(
+            &state,
+            &service,
+            conversation_id,
+            auth.user_id(),
+            req.body.as_deref().unwrap_or_default(),
+            &req.attachment_ids,
+        )
+        .await?;
+        service.get_conversation(conversation_id, auth.user_id(), true).await?
+    } else {
+        summary
+    };
+
+    Ok((StatusCode::CREATED, Json(ConversationResponse::from_summary(summary))))
+}
+
+/// Get a single conversation
+pub async fn get_conversation(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(conversation_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let summary = MessagingService::from(&state)
+        .get_conversation(
+            conversation_id,
+            auth.user_id(),
+            auth.has_permission(&Permission::ViewAllMessages),
+        )
+        .await?;
+
+    Ok(Json(ConversationResponse::from_summary(summary)))
+}
+
+/// Message history for a conversation, paged backwards with `before`
+pub async fn list_messages(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(conversation_id): Path<Uuid>,
+    Query(query): Query<MessageHistoryQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let page = MessagingService::from(&state)
+        .message_history(
+            conversation_id,
+            auth.user_id(),
+            auth.has_permission(&Permission::ViewAllMessages),
+            query.before,
+            query.limit,
+        )
+        .await?;
+
+    let next_before = if page.has_more {
+        page.messages.first().map(|m| m.message.created_at.into())
+    } else {
+        None
+    };
+    let participants = page.participants;
+    let messages = page
+        .messages
+        .into_iter()
+        .map(|m| MessageResponse::from_message(m, &participants))
+        .collect();
+
+    Ok(Json(MessageHistoryResponse {
+        conversation_id,
+        messages,
+        has_more: page.has_more,
+        next_before,
+    }))
+}
+
+/// Send a message to a conversation
+pub async fn send_message(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(conversation_id): Path<Uuid>,
+    Json(req): Json<SendMessageRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    require_send_permission(&auth)?;
+
+    let response = send_and_deliver(
+        &state,
+        &MessagingService::from(&state),
+        conversation_id,
+        auth.user_id(),
+        &req.body,
+        &req.attachment_ids,
+    )
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// Mark a conversation as read and notify the other participants
+pub async fn mark_conversation_read(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(conversation_id): Path<Uuid>,
+    body: Option<Json<MarkReadRequest>>,
+) -> Result<impl IntoResponse, AppError> {
+    let up_to = body.and_then(|Json(req)| req.up_to);
+    let service = MessagingService::from(&state);
+
+    let participant = service
+        .mark_read(conversation_id, auth.user_id(), up_to)
+        .await?;
+    let last_read_at: Option<DateTime<Utc>> = participant.last_read_at.map(Into::into);
+
+    if let Some(read_at) = last_read_at {
+        let summary = service.get_conversation(conversation_id, auth.user_id(), false).await?;
+        let others: Vec<Uuid> = summary
+            .participants
+            .iter()
+            .map(|p| p.user_id)
+            .filter(|id| *id != auth.user_id())
+            .collect();
+        websocket::send_messages_read(&others, conversation_id, auth.user_id(), read_at).await;
+    }
+
+    Ok(Json(ReadReceiptResponse {
+        conversation_id,
+        user_id: auth.user_id(),
+        last_read_at,
+    }))
+}
+
+/// Total unread messages for the current user
+pub async fn unread_count(
+    State(state): State<AppState>,
+    auth: AuthContext,
+) -> Result<impl IntoResponse, AppError> {
+    let unread_count = MessagingService::from(&state)
+        .total_unread(auth.user_id())
+        .await?;
+
+    Ok(Json(serde_json::json!({ "unread_count": unread_count })))
+}
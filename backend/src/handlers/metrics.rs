@@ -0,0 +1,25 @@
+use axum::{
+    extract::State,
+    http::header,
+    response::IntoResponse,
+};
+
+use crate::{
+    handlers::websocket,
+    services::metrics::{self, PoolStats, ProcessStats},
+    AppState,
+};
+
+/// Content type of the Prometheus text exposition format
+const PROMETHEUS_TEXT: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Prometheus scrape endpoint: process stats, request histograms, pool and
+/// WebSocket gauges and domain counters
+pub async fn metrics(State(state): State<AppState>) -> impl IntoResponse {
+    let body = metrics::global().render(
+        &ProcessStats::current(),
+        PoolStats::of(&state.db).as_ref(),
+        &websocket::socket_stats(),
+    );
+    ([(header::CONTENT_TYPE, PROMETHEUS_TEXT)], body)
+}
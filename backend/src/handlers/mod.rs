@@ -7,6 +7,22 @@ pub mod local_auth;
 pub mod websocket;
 pub mod search;
 pub mod applications;
+pub mod uploads;
+pub mod messages;
+pub mod notifications;
+pub mod tasks;
+pub mod availability;
+pub mod professional_profiles;
+pub mod matching;
+pub mod shifts;
+pub mod pay;
+pub mod timesheets;
+pub mod invoices;
+pub mod job_templates;
+pub mod docs;
+pub mod metrics;
+pub mod audit;
+pub mod webhooks;
 
 use axum::{
     response::{IntoResponse, Json},
@@ -19,7 +35,8 @@ pub async fn root() -> impl IntoResponse {
         "name": "Loco Platform API",
         "version": "0.1.0",
         "description": "Professional pharmacy job marketplace API",
-        "documentation": "https://api.locoplatform.com.au/docs",
+        "documentation": "/api/v1/docs",
+        "openapi": "/api/v1/openapi.json",
         "endpoints": {
             "health": {
                 "basic": "/health",
@@ -27,12 +44,14 @@ pub async fn root() -> impl IntoResponse {
                 "ready": "/health/ready",
                 "live": "/health/live"
             },
+            "metrics": "/metrics",
             "auth": {
                 "login": "/api/v1/auth/login",
                 "register": "/api/v1/auth/register",
                 "logout": "/api/v1/auth/logout",
                 "refresh": "/api/v1/auth/refresh",
                 "profile": "/api/v1/profile",
+                "professional_profile": "/api/v1/profile/professional",
                 "local": {
                     "login": "/api/v1/auth/local/login",
                     "register": "/api/v1/auth/local/register",
@@ -53,18 +72,96 @@ pub async fn root() -> impl IntoResponse {
                     "stats": "/api/v1/jobs/enhanced/stats",
                     "my_jobs": "/api/v1/jobs/enhanced/my",
                     "my_stats": "/api/v1/jobs/enhanced/my/stats"
-                }
+                },
+                "schedule_publish": "/api/v1/jobs/:id/publish-at",
+                "candidates": "/api/v1/jobs/:id/candidates",
+                "shifts": "/api/v1/jobs/:id/shifts",
+                "pay": "/api/v1/jobs/:id/pay",
+                "clone": "/api/v1/jobs/:id/clone",
+                "save_as_template": "/api/v1/jobs/:id/template"
+            },
+            "job_templates": {
+                "list": "/api/v1/job-templates",
+                "create": "/api/v1/job-templates",
+                "get": "/api/v1/job-templates/:id",
+                "update": "/api/v1/job-templates/:id",
+                "delete": "/api/v1/job-templates/:id",
+                "post": "/api/v1/job-templates/:id/post"
             },
             "users": {
+                "list": "/api/v1/users",
                 "get": "/api/v1/users/:id",
                 "update": "/api/v1/users/:id",
-                "delete": "/api/v1/users/:id"
+                "delete": "/api/v1/users/:id",
+                "registration": "/api/v1/users/:id/registration"
             },
+            "recommendations": "/api/v1/recommendations",
             "applications": {
                 "list": "/api/v1/applications",
                 "create": "/api/v1/applications",
                 "get": "/api/v1/applications/:id",
-                "update": "/api/v1/applications/:id"
+                "update": "/api/v1/applications/:id",
+                "shifts": "/api/v1/applications/:id/shifts",
+                "pay": "/api/v1/applications/:id/pay",
+                "timesheets": "/api/v1/applications/:id/timesheets",
+                "invoice": "/api/v1/applications/:id/invoices",
+                "bulk": "/api/v1/jobs/:id/applications/bulk"
+            },
+            "timesheets": {
+                "get": "/api/v1/timesheets/:id",
+                "update": "/api/v1/timesheets/:id",
+                "approve": "/api/v1/timesheets/:id/approve",
+                "dispute": "/api/v1/timesheets/:id/dispute",
+                "comments": "/api/v1/timesheets/:id/comments"
+            },
+            "invoices": {
+                "list": "/api/v1/invoices",
+                "get": "/api/v1/invoices/:id",
+                "document": "/api/v1/invoices/:id/document",
+                "status": "/api/v1/invoices/:id/status"
+            },
+            "messages": {
+                "conversations": "/api/v1/conversations",
+                "conversation": "/api/v1/conversations/:id",
+                "history": "/api/v1/conversations/:id/messages",
+                "send": "/api/v1/conversations/:id/messages",
+                "read": "/api/v1/conversations/:id/read",
+                "unread": "/api/v1/messages/unread"
+            },
+            "notifications": {
+                "list": "/api/v1/notifications",
+                "read": "/api/v1/notifications/:id/read",
+                "read_all": "/api/v1/notifications/read-all",
+                "preferences": "/api/v1/notifications/preferences"
+            },
+            "webhooks": {
+                "list": "/api/v1/webhooks",
+                "create": "/api/v1/webhooks",
+                "get": "/api/v1/webhooks/:id",
+                "update": "/api/v1/webhooks/:id",
+                "delete": "/api/v1/webhooks/:id",
+                "deliveries": "/api/v1/webhooks/:id/deliveries",
+                "replay": "/api/v1/webhooks/:id/deliveries/:delivery_id/replay"
+            },
+            "availability": {
+                "list": "/api/v1/availability",
+                "create": "/api/v1/availability",
+                "get": "/api/v1/availability/:id",
+                "update": "/api/v1/availability/:id",
+                "delete": "/api/v1/availability/:id",
+                "search": "/api/v1/availability/search"
+            },
+            "uploads": {
+                "create": "/api/v1/uploads",
+                "get": "/api/v1/uploads/:id"
+            },
+            "admin": {
+                "tasks": "/api/v1/admin/tasks",
+                "task": "/api/v1/admin/tasks/:id",
+                "retry_task": "/api/v1/admin/tasks/:id/retry",
+                "audit_events": "/api/v1/admin/audit-events",
+                "verify_audit_chain": "/api/v1/admin/audit-events/verify",
+                "change_role": "/api/v1/users/:id/role"
             },
             "search": {
                 "advanced": "/api/v1/search/advanced",
@@ -87,7 +184,23 @@ pub async fn root() -> impl IntoResponse {
             "Professional networking",
             "JWT authentication",
             "Enhanced job search",
-            "Application tracking"
+            "Application tracking",
+            "In-app messaging",
+            "Notification centre",
+            "Availability calendar",
+            "Shift matching",
+            "Multi-shift jobs",
+            "Award pay estimates",
+            "Timesheets",
+            "Invoicing with GST",
+            "Job templates and cloning",
+            "OpenAPI document and API docs",
+            "Prometheus metrics",
+            "Tamper-evident audit trail",
+            "Idempotency keys for safe POST retries",
+            "Cursor pagination for infinite scroll",
+            "ETags and conditional requests for job reads",
+            "Signed outbound webhooks"
         ]
     }))
 }
@@ -0,0 +1,170 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::{IntoResponse, Json},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared::types::{CursorParams, ListResponse};
+use uuid::Uuid;
+
+use crate::{
+    entities::notification::{self, NotificationChannels, NotificationEventType},
+    error::AppError,
+    middleware::auth::AuthContext,
+    services::notification_service::NotificationService,
+    AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationFilters {
+    #[serde(default)]
+    pub unread_only: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotificationResponse {
+    pub id: Uuid,
+    pub event_type: NotificationEventType,
+    pub title: String,
+    pub body: String,
+    pub link: Option<String>,
+    pub data: Option<serde_json::Value>,
+    pub is_read: bool,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotificationListResponse {
+    #[serde(flatten)]
+    pub page: ListResponse<NotificationResponse>,
+    pub unread_count: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NotificationPreferenceEntry {
+    pub event_type: NotificationEventType,
+    #[serde(skip_deserializing)]
+    pub description: String,
+    pub in_app: bool,
+    pub email: bool,
+    pub digest: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdatePreferencesRequest {
+    pub preferences: Vec<NotificationPreferenceEntry>,
+}
+
+impl NotificationResponse {
+    fn from_model(model: notification::Model) -> Self {
+        Self {
+            is_read: model.is_read(),
+            id: model.id,
+            event_type: model.event_type,
+            title: model.title,
+            body: model.body,
+            link: model.link,
+            data: model.data,
+            read_at: model.read_at.map(Into::into),
+            created_at: model.created_at.into(),
+        }
+    }
+}
+
+fn preference_entries(preferences: Vec<(NotificationEventType, NotificationChannels)>) -> Vec<NotificationPreferenceEntry> {
+    preferences
+        .into_iter()
+        .map(|(event_type, channels)| NotificationPreferenceEntry {
+            description: event_type.display_name().to_string(),
+            event_type,
+            in_app: channels.in_app,
+            email: channels.email,
+            digest: channels.digest,
+        })
+        .collect()
+}
+
+/// List the current user's notifications, newest first
+pub async fn list_notifications(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Query(filters): Query<NotificationFilters>,
+    Query(page): Query<CursorParams>,
+) -> Result<impl IntoResponse, AppError> {
+    let service = NotificationService::from(&state);
+    let notifications = service
+        .list(auth.user_id(), filters.unread_only, page)
+        .await?;
+    let unread_count = service.unread_count(auth.user_id()).await?;
+
+    Ok(Json(NotificationListResponse {
+        page: notifications.map(NotificationResponse::from_model),
+        unread_count,
+    }))
+}
+
+/// Mark a single notification as read
+pub async fn mark_notification_read(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(notification_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let notification = NotificationService::from(&state)
+        .mark_read(auth.user_id(), notification_id)
+        .await?;
+
+    Ok(Json(NotificationResponse::from_model(notification)))
+}
+
+/// Mark all of the current user's notifications as read
+pub async fn mark_all_notifications_read(
+    State(state): State<AppState>,
+    auth: AuthContext,
+) -> Result<impl IntoResponse, AppError> {
+    let updated = NotificationService::from(&state)
+        .mark_all_read(auth.user_id())
+        .await?;
+
+    Ok(Json(serde_json::json!({ "updated": updated })))
+}
+
+/// Get the current user's channel preferences for every event type
+pub async fn get_preferences(
+    State(state): State<AppState>,
+    auth: AuthContext,
+) -> Result<impl IntoResponse, AppError> {
+    let preferences = NotificationService::from(&state)
+        .preferences(auth.user_id())
+        .await?;
+
+    Ok(Json(preference_entries(preferences)))
+}
+
+/// Update channel preferences; event types not included are left unchanged
+pub async fn update_preferences(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Json(req): Json<UpdatePreferencesRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let updates = req
+        .preferences
+        .into_iter()
+        .map(|entry| {
+            (
+                entry.event_type,
+                NotificationChannels {
+                    in_app: entry.in_app,
+                    email: entry.email,
+                    digest: entry.digest,
+                },
+            )
+        })
+        .collect();
+
+    let preferences = NotificationService::from(&state)
+        .update_preferences(auth.user_id(), updates)
+        .await?;
+
+    Ok(Json(preference_entries(preferences)))
+}
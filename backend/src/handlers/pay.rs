@@ -0,0 +1,44 @@
+use axum::{
+    extract::{Path, State},
+    response::{IntoResponse, Json},
+};
+use uuid::Uuid;
+
+use crate::{
+    error::AppError,
+    middleware::auth::AuthContext,
+    services::pay_service::PayService,
+    AppState,
+};
+
+/// Itemised award pay for a job's shifts at the advertised rate
+pub async fn job_pay(
+    State(state): State<AppState>,
+    Path(job_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let (job, estimate) = PayService::from(&state).for_job(job_id).await?;
+
+    Ok(Json(serde_json::json!({
+        "job_id": job.id,
+        "job_type": job.job_type,
+        "estimate": estimate
+    })))
+}
+
+/// Itemised pay for the shifts an application or offer covers
+pub async fn application_pay(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(application_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let (application, estimate) = PayService::from(&state)
+        .for_application(application_id, auth.user_id(), auth.is_admin())
+        .await?;
+
+    Ok(Json(serde_json::json!({
+        "application_id": application.id,
+        "job_id": application.job_id,
+        "status": application.status,
+        "estimate": estimate
+    })))
+}
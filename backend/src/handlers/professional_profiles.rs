@@ -0,0 +1,141 @@
+use axum::{
+    extract::{Path, State},
+    response::{IntoResponse, Json},
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use shared::types::JobType;
+use uuid::Uuid;
+
+use crate::{
+    entities::professional_profile::{self, RegistrationStatus, DEFAULT_MAX_TRAVEL_KM},
+    error::AppError,
+    middleware::{auth::AuthContext, rbac::Permission},
+    services::professional_profile_service::ProfessionalProfileService,
+    AppState,
+};
+
+#[derive(Debug, Serialize)]
+pub struct ProfessionalProfileResponse {
+    pub user_id: Uuid,
+    pub job_types: Vec<JobType>,
+    pub registration_number: Option<String>,
+    pub registration_status: RegistrationStatus,
+    pub registration_expires_on: Option<NaiveDate>,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub max_travel_km: i32,
+    pub abn: Option<String>,
+    pub gst_registered: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateProfessionalProfileRequest {
+    #[serde(default)]
+    pub job_types: Vec<JobType>,
+    pub registration_number: Option<String>,
+    pub max_travel_km: Option<i32>,
+    pub abn: Option<String>,
+    #[serde(default)]
+    pub gst_registered: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RegistrationReviewRequest {
+    pub status: RegistrationStatus,
+    pub expires_on: Option<NaiveDate>,
+}
+
+impl ProfessionalProfileResponse {
+    fn from_model(model: professional_profile::Model) -> Self {
+        Self {
+            job_types: model.job_types(),
+            user_id: model.user_id,
+            registration_number: model.registration_number,
+            registration_status: model.registration_status,
+            registration_expires_on: model.registration_expires_on,
+            verified_at: model.verified_at.map(Into::into),
+            max_travel_km: model.max_travel_km,
+            abn: model.abn,
+            gst_registered: model.gst_registered,
+        }
+    }
+
+    /// What a professional who has not filled in their profile sees
+    fn empty(user_id: Uuid) -> Self {
+        Self {
+            user_id,
+            job_types: Vec::new(),
+            registration_number: None,
+            registration_status: RegistrationStatus::Unregistered,
+            registration_expires_on: None,
+            verified_at: None,
+            max_travel_km: DEFAULT_MAX_TRAVEL_KM,
+            abn: None,
+            gst_registered: false,
+        }
+    }
+}
+
+/// Get the current user's matching profile
+pub async fn get_professional_profile(
+    State(state): State<AppState>,
+    auth: AuthContext,
+) -> Result<impl IntoResponse, AppError> {
+    let profile = ProfessionalProfileService::from(&state)
+        .find(auth.user_id())
+        .await?;
+
+    Ok(Json(profile.map_or_else(
+        || ProfessionalProfileResponse::empty(auth.user_id()),
+        ProfessionalProfileResponse::from_model,
+    )))
+}
+
+/// Set the roles, registration number and travel range used for matching, and the
+/// business details used on invoices
+pub async fn update_professional_profile(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Json(req): Json<UpdateProfessionalProfileRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if !auth.is_professional() {
+        return Err(AppError::Forbidden);
+    }
+
+    let profile = ProfessionalProfileService::from(&state)
+        .update(
+            auth.user_id(),
+            req.job_types,
+            req.registration_number,
+            req.max_travel_km.unwrap_or(DEFAULT_MAX_TRAVEL_KM),
+            req.abn,
+            req.gst_registered,
+        )
+        .await?;
+
+    Ok(Json(ProfessionalProfileResponse::from_model(profile)))
+}
+
+/// Record the result of checking a professional's AHPRA registration (admin only)
+pub async fn review_registration(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(user_id): Path<Uuid>,
+    Json(req): Json<RegistrationReviewRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if !auth.has_permission(&Permission::EditAllUsers) {
+        return Err(AppError::Forbidden);
+    }
+
+    let profile = ProfessionalProfileService::from(&state)
+        .set_registration_status(user_id, auth.user_id(), req.status, req.expires_on)
+        .await?;
+    tracing::info!(
+        "🪪 Registration for {} marked {:?} by {}",
+        user_id,
+        profile.registration_status,
+        auth.user_id()
+    );
+
+    Ok(Json(ProfessionalProfileResponse::from_model(profile)))
+}
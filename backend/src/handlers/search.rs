@@ -3,6 +3,7 @@ use axum::{
     http::StatusCode,
     response::{IntoResponse, Json},
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use uuid::Uuid;
@@ -10,8 +11,9 @@ use uuid::Uuid;
 use crate::{
     AppState, 
     AppError,
-    services::{SearchService, search_service::AdvancedSearchFilters},
-    middleware::validation::ValidatedJson,
+    entities::saved_search,
+    services::{SearchService, search_service::AdvancedSearchFilters, saved_search_service::SavedSearchService},
+    middleware::{auth::AuthContext, validation::ValidatedJson},
 };
 use shared::validation::ValidatedJobSearchRequest;
 
@@ -151,29 +153,38 @@ pub async fn trending_searches(
     })))
 }
 
-/// Saved searches for authenticated users
+/// The current user's saved searches, newest first
 pub async fn get_saved_searches(
-    State(_state): State<AppState>,
-    // TODO: Add auth context extraction
+    State(state): State<AppState>,
+    auth: AuthContext,
 ) -> Result<impl IntoResponse, AppError> {
-    // TODO: Implement saved searches retrieval
-    // For now, return empty list
+    let saved_searches: Vec<SavedSearchResponse> = SavedSearchService::from(&state)
+        .list(auth.user_id())
+        .await?
+        .into_iter()
+        .map(SavedSearchResponse::from_model)
+        .collect();
+
     Ok(Json(json!({
-        "saved_searches": []
+        "saved_searches": saved_searches
     })))
 }
 
-/// Save a search for later
+/// Save a search; newly published jobs matching it are pushed to `search:{id}`
 pub async fn save_search(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
+    auth: AuthContext,
     Json(request): Json<SaveSearchRequest>,
 ) -> Result<impl IntoResponse, AppError> {
-    // TODO: Implement search saving
-    // For now, just return success
-    Ok(Json(json!({
+    let search = SavedSearchService::from(&state)
+        .save(auth.user_id(), &request.name, &request.query, request.filters)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(json!({
         "message": "Search saved successfully",
-        "search_id": Uuid::new_v4()
-    })))
+        "search_id": search.id,
+        "topic": format!("search:{}", search.id)
+    }))))
 }
 
 /// Job recommendations based on user profile and search history
@@ -235,9 +246,33 @@ pub struct RecommendationParams {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SaveSearchRequest {
+    pub name: String,
+    #[serde(default)]
+    pub query: String,
+    // shared::types::JobFilters
+    #[serde(default)]
+    pub filters: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SavedSearchResponse {
+    pub id: Uuid,
     pub name: String,
     pub query: String,
     pub filters: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl SavedSearchResponse {
+    fn from_model(search: saved_search::Model) -> Self {
+        Self {
+            id: search.id,
+            name: search.name,
+            query: search.query,
+            filters: search.filters,
+            created_at: search.created_at.into(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -0,0 +1,70 @@
+use axum::{
+    extract::{Path, State},
+    response::{IntoResponse, Json},
+};
+use shared::shifts::{JobShift, ShiftSchedule};
+use uuid::Uuid;
+
+use crate::{
+    entities::job_shift,
+    error::AppError,
+    middleware::auth::AuthContext,
+    services::shift_service::ShiftService,
+    AppState,
+};
+
+fn shift_list(job_id: Uuid, models: Vec<job_shift::Model>) -> serde_json::Value {
+    let open = models.iter().filter(|shift| !shift.is_filled()).count();
+    let shifts: Vec<JobShift> = models.iter().map(job_shift::Model::to_shift).collect();
+
+    serde_json::json!({
+        "job_id": job_id,
+        "shifts": shifts,
+        "total": shifts.len(),
+        "open": open
+    })
+}
+
+/// List a job's shifts with their filled state
+pub async fn list_job_shifts(
+    State(state): State<AppState>,
+    _auth: AuthContext,
+    Path(job_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let shifts = ShiftService::from(&state).list(job_id).await?;
+    Ok(Json(shift_list(job_id, shifts)))
+}
+
+/// Replace a job's shifts from a recurrence rule and/or explicit shifts (job owner or admin)
+pub async fn replace_job_shifts(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(job_id): Path<Uuid>,
+    Json(schedule): Json<ShiftSchedule>,
+) -> Result<impl IntoResponse, AppError> {
+    let shifts = ShiftService::from(&state)
+        .replace(job_id, auth.user_id(), auth.is_admin(), &schedule)
+        .await?;
+    tracing::info!("🗓️ Job {} now has {} shifts", job_id, shifts.len());
+
+    Ok(Json(shift_list(job_id, shifts)))
+}
+
+/// Shifts an application is for (the applicant, the job's poster or an admin)
+pub async fn get_application_shifts(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(application_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let (application, shifts) = ShiftService::from(&state)
+        .for_application(application_id, auth.user_id(), auth.is_admin())
+        .await?;
+    let shifts: Vec<JobShift> = shifts.iter().map(job_shift::Model::to_shift).collect();
+
+    Ok(Json(serde_json::json!({
+        "application_id": application_id,
+        "job_id": application.job_id,
+        "whole_job": shifts.is_empty(),
+        "shifts": shifts
+    })))
+}
@@ -0,0 +1,107 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::{IntoResponse, Json},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared::types::CursorParams;
+use uuid::Uuid;
+
+use crate::{
+    entities::background_task::{self, TaskStatus},
+    error::AppError,
+    middleware::{auth::AuthContext, rbac::Permission},
+    services::task_queue::TaskQueue,
+    AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct TaskFilters {
+    /// Defaults to dead-lettered tasks, the ones that need attention
+    pub status: Option<TaskStatus>,
+    pub kind: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TaskResponse {
+    pub id: Uuid,
+    pub kind: String,
+    pub payload: serde_json::Value,
+    pub status: TaskStatus,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub run_at: DateTime<Utc>,
+    pub locked_by: Option<String>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl TaskResponse {
+    fn from_model(model: background_task::Model) -> Self {
+        Self {
+            id: model.id,
+            kind: model.kind,
+            payload: model.payload,
+            status: model.status,
+            attempts: model.attempts,
+            max_attempts: model.max_attempts,
+            run_at: model.run_at.into(),
+            locked_by: model.locked_by,
+            last_error: model.last_error,
+            created_at: model.created_at.into(),
+            completed_at: model.completed_at.map(Into::into),
+        }
+    }
+}
+
+fn require_manage_system(auth: &AuthContext) -> Result<(), AppError> {
+    if auth.has_permission(&Permission::ManageSystem) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden)
+    }
+}
+
+/// List background tasks (admin only)
+pub async fn list_tasks(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Query(filters): Query<TaskFilters>,
+    Query(page): Query<CursorParams>,
+) -> Result<impl IntoResponse, AppError> {
+    require_manage_system(&auth)?;
+
+    let status = filters.status.unwrap_or(TaskStatus::DeadLettered);
+    let result = TaskQueue::from(&state)
+        .list(Some(status), filters.kind, page)
+        .await?;
+
+    Ok(Json(result.map(TaskResponse::from_model)))
+}
+
+/// Get a single background task (admin only)
+pub async fn get_task(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(task_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    require_manage_system(&auth)?;
+
+    let task = TaskQueue::from(&state).get(task_id).await?;
+    Ok(Json(TaskResponse::from_model(task)))
+}
+
+/// Requeue a dead-lettered task with a fresh set of attempts (admin only)
+pub async fn retry_task(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(task_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    require_manage_system(&auth)?;
+
+    let task = TaskQueue::from(&state).retry(task_id).await?;
+    tracing::info!("🔁 Task {} ({}) requeued by {}", task.id, task.kind, auth.user_id());
+
+    Ok(Json(TaskResponse::from_model(task)))
+}
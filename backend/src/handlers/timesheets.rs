@@ -0,0 +1,178 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Json},
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use shared::pay::ShiftPay;
+use shared::timesheets::TimesheetEntry;
+use uuid::Uuid;
+
+use crate::{
+    entities::timesheet::{self, TimesheetStatus},
+    error::AppError,
+    middleware::auth::AuthContext,
+    services::timesheet_service::{TimesheetDetail, TimesheetService},
+    AppState,
+};
+
+#[derive(Debug, Serialize)]
+pub struct TimesheetResponse {
+    pub id: Uuid,
+    pub application_id: Uuid,
+    pub shift_id: Option<Uuid>,
+    pub date: NaiveDate,
+    pub start_time: String,
+    pub end_time: String,
+    pub break_minutes: u32,
+    pub status: TimesheetStatus,
+    /// Award pay, once approved
+    pub pay_total: Option<f64>,
+    pub pay: Option<ShiftPay>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub submitted_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DisputeTimesheetRequest {
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TimesheetCommentRequest {
+    pub body: String,
+}
+
+impl TimesheetResponse {
+    fn from_model(model: timesheet::Model) -> Self {
+        let entry = model.to_entry();
+        Self {
+            pay: model.pay(),
+            pay_total: model.pay_total.and_then(|total| total.to_f64()),
+            id: model.id,
+            application_id: model.application_id,
+            shift_id: model.shift_id,
+            date: entry.date,
+            start_time: entry.start_time,
+            end_time: entry.end_time,
+            break_minutes: entry.break_minutes,
+            status: model.status,
+            reviewed_by: model.reviewed_by,
+            reviewed_at: model.reviewed_at.map(Into::into),
+            submitted_at: model.submitted_at.into(),
+            updated_at: model.updated_at.into(),
+        }
+    }
+}
+
+fn detail_json(detail: TimesheetDetail) -> serde_json::Value {
+    serde_json::json!({
+        "timesheet": TimesheetResponse::from_model(detail.timesheet),
+        "history": detail.revisions,
+        "comments": detail.comments
+    })
+}
+
+/// An accepted application's timesheets (the locum, the job's poster or an admin)
+pub async fn list_application_timesheets(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(application_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let timesheets: Vec<TimesheetResponse> = TimesheetService::from(&state)
+        .list_for_application(application_id, auth.user_id(), auth.is_admin())
+        .await?
+        .into_iter()
+        .map(TimesheetResponse::from_model)
+        .collect();
+
+    Ok(Json(serde_json::json!({
+        "application_id": application_id,
+        "total": timesheets.len(),
+        "timesheets": timesheets
+    })))
+}
+
+/// Submit the hours worked on a shift (the accepted locum)
+pub async fn submit_timesheet(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(application_id): Path<Uuid>,
+    Json(entry): Json<TimesheetEntry>,
+) -> Result<impl IntoResponse, AppError> {
+    let timesheet = TimesheetService::from(&state)
+        .submit(application_id, auth.user_id(), &entry)
+        .await?;
+    tracing::info!("🕒 Timesheet {} submitted for application {}", timesheet.id, application_id);
+
+    Ok((StatusCode::CREATED, Json(TimesheetResponse::from_model(timesheet))))
+}
+
+/// A timesheet with its edit history and comment thread
+pub async fn get_timesheet(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(timesheet_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let detail = TimesheetService::from(&state)
+        .get(timesheet_id, auth.user_id(), auth.is_admin())
+        .await?;
+    Ok(Json(detail_json(detail)))
+}
+
+/// Correct a submitted or disputed timesheet (the locum)
+pub async fn update_timesheet(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(timesheet_id): Path<Uuid>,
+    Json(entry): Json<TimesheetEntry>,
+) -> Result<impl IntoResponse, AppError> {
+    let timesheet = TimesheetService::from(&state)
+        .edit(timesheet_id, auth.user_id(), &entry)
+        .await?;
+    Ok(Json(TimesheetResponse::from_model(timesheet)))
+}
+
+/// Approve a timesheet and lock in its award pay (the job's poster or an admin)
+pub async fn approve_timesheet(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(timesheet_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let timesheet = TimesheetService::from(&state)
+        .approve(timesheet_id, auth.user_id(), auth.is_admin())
+        .await?;
+    tracing::info!("✅ Timesheet {} approved by {}", timesheet_id, auth.user_id());
+
+    Ok(Json(TimesheetResponse::from_model(timesheet)))
+}
+
+/// Dispute a timesheet with a reason that opens its comment thread (the job's poster or an admin)
+pub async fn dispute_timesheet(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(timesheet_id): Path<Uuid>,
+    Json(request): Json<DisputeTimesheetRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let detail = TimesheetService::from(&state)
+        .dispute(timesheet_id, auth.user_id(), auth.is_admin(), &request.reason)
+        .await?;
+    Ok(Json(detail_json(detail)))
+}
+
+/// Add a comment to a timesheet's thread
+pub async fn add_timesheet_comment(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(timesheet_id): Path<Uuid>,
+    Json(request): Json<TimesheetCommentRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let comment = TimesheetService::from(&state)
+        .comment(timesheet_id, auth.user_id(), auth.is_admin(), &request.body)
+        .await?;
+    Ok((StatusCode::CREATED, Json(comment)))
+}
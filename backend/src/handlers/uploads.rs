@@ -0,0 +1,109 @@
+use axum::{
+    extract::{Multipart, Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    entities::upload,
+    error::AppError,
+    middleware::{auth::AuthContext, rbac::Permission},
+    services::{messaging_service::MessagingService, upload_service::UploadService},
+    AppState,
+};
+
+#[derive(Debug, Serialize)]
+pub struct UploadResponse {
+    pub id: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub url: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl UploadResponse {
+    pub fn from_model(upload: upload::Model) -> Self {
+        Self {
+            url: format!("/api/v1/uploads/{}", upload.id),
+            id: upload.id,
+            file_name: upload.file_name,
+            content_type: upload.content_type,
+            size_bytes: upload.size_bytes,
+            created_at: upload.created_at.into(),
+        }
+    }
+}
+
+/// Upload a file (multipart form, field `file`)
+pub async fn upload_file(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, AppError> {
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Invalid multipart body: {}", e)))?
+    {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let file_name = field.file_name().unwrap_or("file").to_string();
+        let content_type = field
+            .content_type()
+            .unwrap_or("application/octet-stream")
+            .to_string();
+        let bytes = field
+            .bytes()
+            .await
+            .map_err(|e| AppError::BadRequest(format!("Failed to read upload: {}", e)))?;
+
+        let upload = UploadService::from(&state)
+            .store(auth.user_id(), &file_name, &content_type, &bytes)
+            .await?;
+
+        return Ok((StatusCode::CREATED, Json(UploadResponse::from_model(upload))));
+    }
+
+    Err(AppError::Validation("Multipart body must include a `file` field".to_string()))
+}
+
+/// Download an upload; visible to its owner and to participants of conversations it is attached to
+pub async fn download_upload(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(upload_id): Path<Uuid>,
+) -> Result<Response, AppError> {
+    let uploads = UploadService::from(&state);
+    let upload = uploads.find_by_id(upload_id).await?;
+
+    let allowed = upload.owner_id == auth.user_id()
+        || auth.has_permission(&Permission::ViewAllMessages)
+        || MessagingService::from(&state)
+            .can_access_upload(upload.id, auth.user_id())
+            .await?;
+    if !allowed {
+        return Err(AppError::NotFound);
+    }
+
+    let bytes = uploads.read(&upload).await?;
+    let disposition = format!(
+        "attachment; filename=\"{}\"",
+        upload.file_name.replace(['"', '\\'], "_")
+    );
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, upload.content_type),
+            (header::CONTENT_DISPOSITION, disposition),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+        ],
+        bytes,
+    )
+        .into_response())
+}
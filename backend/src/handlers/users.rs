@@ -5,61 +5,32 @@ use axum::{
 };
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use shared::types::CursorParams;
 
-use crate::{AppState, AppError};
+use crate::{
+    middleware::{auth::AuthContext, rbac::Permission},
+    services::UserService,
+    AppState, AppError,
+};
 
 #[derive(Debug, Deserialize)]
 pub struct ListUsersQuery {
-    pub page: Option<u32>,
-    pub limit: Option<u32>,
     pub search: Option<String>,
 }
 
-/// List all users (admin only)
+/// List users, newest first, a cursor page at a time (admin only)
 pub async fn list_users(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
+    auth: AuthContext,
     Query(params): Query<ListUsersQuery>,
+    Query(page): Query<CursorParams>,
 ) -> Result<impl IntoResponse, AppError> {
-    let page = params.page.unwrap_or(1);
-    let limit = params.limit.unwrap_or(20);
-    
-    // Demo mode - return mock user list
-    Ok(Json(json!({
-        "users": [
-            {
-                "id": Uuid::new_v4(),
-                "email": "admin@loco-platform.com",
-                "first_name": "Admin",
-                "last_name": "User",
-                "user_type": "SuperAdmin",
-                "is_active": true,
-                "created_at": "2025-01-01T00:00:00Z"
-            },
-            {
-                "id": Uuid::new_v4(),
-                "email": "employer@pharmacy.com",
-                "first_name": "John",
-                "last_name": "Smith",
-                "user_type": "Employer",
-                "is_active": true,
-                "created_at": "2025-01-02T00:00:00Z"
-            },
-            {
-                "id": Uuid::new_v4(),
-                "email": "pharmacist@example.com",
-                "first_name": "Jane",
-                "last_name": "Doe",
-                "user_type": "Professional",
-                "is_active": true,
-                "created_at": "2025-01-03T00:00:00Z"
-            }
-        ],
-        "total": 3,
-        "page": page,
-        "limit": limit,
-        "has_more": false
-    })))
+    if !auth.has_permission(&Permission::ViewAllUsers) {
+        return Err(AppError::Forbidden);
+    }
+
+    let users = UserService::list_users(&state.db, params.search.as_deref(), page).await?;
+    Ok(Json(users))
 }
 
 /// Get user profile
@@ -105,6 +76,26 @@ pub async fn delete_user(
     }
 }
 
+/// Change a user's role (admin only)
+pub async fn change_user_role(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(user_id): Path<Uuid>,
+    Json(request): Json<ChangeRoleRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if !auth.has_permission(&Permission::EditAllUsers) {
+        return Err(AppError::Forbidden);
+    }
+
+    let user = UserService::change_role(&state.db, user_id, auth.user_id(), request.user_type).await?;
+    Ok(Json(user))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChangeRoleRequest {
+    pub user_type: shared::types::UserType,
+}
+
 #[derive(serde::Deserialize)]
 pub struct UserUpdateRequest {
     pub first_name: Option<String>,
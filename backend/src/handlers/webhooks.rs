@@ -0,0 +1,238 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared::types::CursorParams;
+use shared::webhooks::{CreateWebhookEndpointRequest, UpdateWebhookEndpointRequest, WebhookEventType};
+use uuid::Uuid;
+
+use crate::{
+    entities::{
+        webhook_delivery::{self, DeliveryStatus},
+        webhook_endpoint,
+    },
+    error::AppError,
+    middleware::{auth::AuthContext, rbac::Permission},
+    services::webhook_service::WebhookService,
+    AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct WebhookEndpointFilters {
+    pub tenant_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WebhookDeliveryFilters {
+    pub status: Option<DeliveryStatus>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookEndpointResponse {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub url: String,
+    pub event_types: Vec<WebhookEventType>,
+    pub description: Option<String>,
+    pub active: bool,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A new endpoint with its signing secret, which is not shown again
+#[derive(Debug, Serialize)]
+pub struct CreatedWebhookEndpointResponse {
+    #[serde(flatten)]
+    pub endpoint: WebhookEndpointResponse,
+    pub secret: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookDeliveryResponse {
+    pub id: Uuid,
+    pub endpoint_id: Uuid,
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    pub response_status: Option<i16>,
+    pub response_body: Option<String>,
+    pub duration_ms: Option<i32>,
+    pub last_error: Option<String>,
+    pub replay_of: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl WebhookEndpointResponse {
+    fn from_model(model: webhook_endpoint::Model) -> Self {
+        Self {
+            event_types: model.event_types(),
+            id: model.id,
+            tenant_id: model.tenant_id,
+            url: model.url,
+            description: model.description,
+            active: model.is_active,
+            created_by: model.created_by,
+            created_at: model.created_at.into(),
+            updated_at: model.updated_at.into(),
+        }
+    }
+}
+
+impl WebhookDeliveryResponse {
+    fn from_model(model: webhook_delivery::Model) -> Self {
+        Self {
+            id: model.id,
+            endpoint_id: model.endpoint_id,
+            event_id: model.event_id,
+            event_type: model.event_type,
+            payload: model.payload,
+            status: model.status,
+            attempts: model.attempts,
+            response_status: model.response_status,
+            response_body: model.response_body,
+            duration_ms: model.duration_ms,
+            last_error: model.last_error,
+            replay_of: model.replay_of,
+            created_at: model.created_at.into(),
+            last_attempt_at: model.last_attempt_at.map(Into::into),
+            next_attempt_at: model.next_attempt_at.map(Into::into),
+            delivered_at: model.delivered_at.map(Into::into),
+        }
+    }
+}
+
+/// Webhooks are set up by the people who post jobs for a tenant
+fn require_webhook_access(auth: &AuthContext) -> Result<(), AppError> {
+    if auth.has_permission(&Permission::CreateJob) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden)
+    }
+}
+
+/// Webhook endpoints in your tenants
+pub async fn list_webhook_endpoints(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Query(filters): Query<WebhookEndpointFilters>,
+) -> Result<impl IntoResponse, AppError> {
+    require_webhook_access(&auth)?;
+
+    let endpoints: Vec<WebhookEndpointResponse> = WebhookService::from(&state)
+        .list(filters.tenant_id, auth.user_id(), auth.is_admin())
+        .await?
+        .into_iter()
+        .map(WebhookEndpointResponse::from_model)
+        .collect();
+
+    Ok(Json(serde_json::json!({
+        "total": endpoints.len(),
+        "endpoints": endpoints
+    })))
+}
+
+/// Register an endpoint; the response carries the signing secret, shown only once
+pub async fn create_webhook_endpoint(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Json(request): Json<CreateWebhookEndpointRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    require_webhook_access(&auth)?;
+
+    let endpoint = WebhookService::from(&state)
+        .create(&request, auth.user_id(), auth.is_admin())
+        .await?;
+    tracing::info!("🪝 Webhook endpoint {} added for tenant {} by {}", endpoint.id, endpoint.tenant_id, auth.user_id());
+
+    let secret = endpoint.secret.clone();
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatedWebhookEndpointResponse {
+            endpoint: WebhookEndpointResponse::from_model(endpoint),
+            secret,
+        }),
+    ))
+}
+
+pub async fn get_webhook_endpoint(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(endpoint_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    require_webhook_access(&auth)?;
+
+    let endpoint = WebhookService::from(&state)
+        .get(endpoint_id, auth.user_id(), auth.is_admin())
+        .await?;
+    Ok(Json(WebhookEndpointResponse::from_model(endpoint)))
+}
+
+/// Change an endpoint's URL, events or description, or pause and resume it
+pub async fn update_webhook_endpoint(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(endpoint_id): Path<Uuid>,
+    Json(request): Json<UpdateWebhookEndpointRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    require_webhook_access(&auth)?;
+
+    let endpoint = WebhookService::from(&state)
+        .update(endpoint_id, &request, auth.user_id(), auth.is_admin())
+        .await?;
+    Ok(Json(WebhookEndpointResponse::from_model(endpoint)))
+}
+
+/// Remove an endpoint along with its delivery log
+pub async fn delete_webhook_endpoint(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(endpoint_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    require_webhook_access(&auth)?;
+
+    WebhookService::from(&state)
+        .delete(endpoint_id, auth.user_id(), auth.is_admin())
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// An endpoint's delivery log, newest first, a cursor page at a time
+pub async fn list_webhook_deliveries(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(endpoint_id): Path<Uuid>,
+    Query(filters): Query<WebhookDeliveryFilters>,
+    Query(page): Query<CursorParams>,
+) -> Result<impl IntoResponse, AppError> {
+    require_webhook_access(&auth)?;
+
+    let deliveries = WebhookService::from(&state)
+        .deliveries(endpoint_id, filters.status, page, auth.user_id(), auth.is_admin())
+        .await?;
+    Ok(Json(deliveries.map(WebhookDeliveryResponse::from_model)))
+}
+
+/// Send a logged delivery again as a new delivery
+pub async fn replay_webhook_delivery(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path((endpoint_id, delivery_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    require_webhook_access(&auth)?;
+
+    let replay = WebhookService::from(&state)
+        .replay(endpoint_id, delivery_id, auth.user_id(), auth.is_admin())
+        .await?;
+    tracing::info!("🔁 Webhook delivery {} replayed as {} by {}", delivery_id, replay.id, auth.user_id());
+
+    Ok((StatusCode::ACCEPTED, Json(WebhookDeliveryResponse::from_model(replay))))
+}
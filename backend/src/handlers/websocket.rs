@@ -1,21 +1,42 @@
 use axum::{
     extract::{
         ws::{Message, WebSocket, WebSocketUpgrade},
-        State,
+        Query, State,
     },
-    response::Response,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
 };
 use futures_util::{sink::SinkExt, stream::StreamExt};
+use num_traits::ToPrimitive;
 use serde::{Deserialize, Serialize};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter};
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    fmt,
     sync::{Arc, Mutex},
 };
 use tokio::sync::broadcast;
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
-use crate::AppState;
+use crate::{
+    entities::{job, tenant_users, Job, SavedSearch, TenantUsers},
+    services::{
+        metrics::SocketStats,
+        realtime_backplane::{self, Envelope},
+        AuthService,
+    },
+    AppState,
+};
+
+/// Upper bound on topics a single connection may hold
+pub const MAX_TOPICS_PER_CONNECTION: usize = 100;
+
+/// Recent events kept per topic so reconnecting clients can resume
+pub const REPLAY_EVENTS_PER_TOPIC: usize = 100;
+
+/// Topics with replay history; the least recently used are dropped beyond this
+const MAX_REPLAY_TOPICS: usize = 10_000;
 
 // WebSocket message types for real-time communication
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,19 +47,69 @@ pub enum WsMessage {
     JobUpdated { job: JobNotification },
     JobDeleted { job_id: String },
     JobApplicationReceived { job_id: String, application_count: u32 },
+    JobStatusChanged { job_id: String, from: String, to: String, reason: String },
     
     // System messages
     SystemNotification { message: String, level: NotificationLevel },
     UserConnected { user_id: String },
     UserDisconnected { user_id: String },
     
+    // Messaging (delivered only to conversation participants)
+    MessageReceived { message: ChatMessageNotification },
+    MessagesRead { conversation_id: String, user_id: String, read_at: chrono::DateTime<chrono::Utc> },
+    
+    // Notification centre (delivered only to the recipient)
+    NotificationCreated { notification: NotificationPayload },
+    
     // Client messages
-    Subscribe { topics: Vec<String> },
+    /// `resume` maps a topic to the last sequence number the client saw
+    Subscribe {
+        topics: Vec<String>,
+        #[serde(default)]
+        resume: HashMap<String, u64>,
+    },
     Unsubscribe { topics: Vec<String> },
+    SubscriptionResult { subscribed: Vec<String>, rejected: Vec<String> },
+    /// Events were missed that can no longer be replayed; refetch these topics
+    ResyncRequired { topics: Vec<String> },
     Ping,
     Pong,
 }
 
+/// A message as sent to a client, with its sequence number on each of the
+/// client's topics it was published to
+#[derive(Debug, Clone, Serialize)]
+pub struct OutboundMessage {
+    #[serde(flatten)]
+    pub message: WsMessage,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub seq: BTreeMap<String, u64>,
+}
+
+impl OutboundMessage {
+    /// The envelope's message, keeping only the sequences for topics the connection holds
+    fn for_connection(envelope: &Envelope, topics: &HashSet<Topic>) -> Self {
+        Self {
+            message: envelope.message.clone(),
+            seq: envelope
+                .sequences
+                .iter()
+                .filter(|(topic, _)| Topic::parse(topic).is_some_and(|t| topics.contains(&t)))
+                .map(|(topic, seq)| (topic.clone(), *seq))
+                .collect(),
+        }
+    }
+}
+
+impl From<WsMessage> for OutboundMessage {
+    fn from(message: WsMessage) -> Self {
+        Self {
+            message,
+            seq: BTreeMap::new(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JobNotification {
     pub id: String,
@@ -52,6 +123,52 @@ pub struct JobNotification {
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+impl JobNotification {
+    pub fn from_job(job: &job::Model) -> Self {
+        let hourly_rate = job.hourly_rate.round().to_i32();
+        Self {
+            id: job.id.to_string(),
+            title: job.title.clone(),
+            company: Some(job.pharmacy_name.clone()),
+            location: Some(format!("{}, {}", job.suburb, job.state)),
+            job_type: Some(format!("{:?}", job.job_type)),
+            salary_range_start: hourly_rate,
+            salary_range_end: hourly_rate,
+            is_urgent: job.is_urgent,
+            created_at: job.created_at.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatMessageNotification {
+    pub id: String,
+    pub conversation_id: String,
+    pub sender_id: String,
+    pub body: String,
+    pub attachments: Vec<AttachmentNotification>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentNotification {
+    pub id: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationPayload {
+    pub id: String,
+    pub event_type: String,
+    pub title: String,
+    pub body: String,
+    pub link: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum NotificationLevel {
     Info,
@@ -60,20 +177,205 @@ pub enum NotificationLevel {
     Error,
 }
 
+/// A channel that connections subscribe to, written as `jobs`, `job:{id}`,
+/// `user:{id}`, `tenant:{id}` or `search:{id}`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Topic {
+    /// Platform-wide announcements; every connection holds it
+    System,
+    /// Public feed of posted, updated and removed jobs
+    Jobs,
+    Job(Uuid),
+    User(Uuid),
+    Tenant(Uuid),
+    /// Newly published jobs matching one of the owner's saved searches
+    Search(Uuid),
+}
+
+impl Topic {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "system" => return Some(Topic::System),
+            "jobs" => return Some(Topic::Jobs),
+            _ => {}
+        }
+
+        let (kind, id) = value.split_once(':')?;
+        let id = Uuid::parse_str(id).ok()?;
+        match kind {
+            "job" => Some(Topic::Job(id)),
+            "user" => Some(Topic::User(id)),
+            "tenant" => Some(Topic::Tenant(id)),
+            "search" => Some(Topic::Search(id)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Topic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Topic::System => write!(f, "system"),
+            Topic::Jobs => write!(f, "jobs"),
+            Topic::Job(id) => write!(f, "job:{}", id),
+            Topic::User(id) => write!(f, "user:{}", id),
+            Topic::Tenant(id) => write!(f, "tenant:{}", id),
+            Topic::Search(id) => write!(f, "search:{}", id),
+        }
+    }
+}
+
+/// The authenticated user behind a connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SocketUser {
+    pub user_id: Uuid,
+    pub is_admin: bool,
+}
+
+/// A live WebSocket connection, the user it was opened for and its subscriptions
+#[derive(Debug, Clone)]
+pub struct ClientConnection {
+    pub sender: broadcast::Sender<OutboundMessage>,
+    pub user: Option<SocketUser>,
+    pub topics: HashSet<Topic>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WsConnectParams {
+    pub token: Option<String>,
+}
+
 // Global WebSocket connection manager
-pub type ConnectionManager = Arc<Mutex<HashMap<Uuid, broadcast::Sender<WsMessage>>>>;
+pub type ConnectionManager = Arc<Mutex<HashMap<Uuid, ClientConnection>>>;
 
 lazy_static::lazy_static! {
     pub static ref CONNECTIONS: ConnectionManager = Arc::new(Mutex::new(HashMap::new()));
+    static ref REPLAY: Mutex<ReplayBuffer> = Mutex::new(ReplayBuffer::default());
+}
+
+/// Recent events seen by this instance, per topic
+#[derive(Default)]
+struct ReplayBuffer {
+    next_index: u64,
+    topics: HashMap<String, TopicHistory>,
+}
+
+#[derive(Default)]
+struct TopicHistory {
+    latest_seq: u64,
+    last_index: u64,
+    /// (arrival index, sequence number, event)
+    events: VecDeque<(u64, u64, Arc<Envelope>)>,
+}
+
+impl ReplayBuffer {
+    fn record(&mut self, envelope: Arc<Envelope>) {
+        let index = self.next_index;
+        self.next_index += 1;
+
+        for (topic, seq) in &envelope.sequences {
+            let history = self.topics.entry(topic.clone()).or_default();
+            history.latest_seq = history.latest_seq.max(*seq);
+            history.last_index = index;
+            history.events.push_back((index, *seq, envelope.clone()));
+            if history.events.len() > REPLAY_EVENTS_PER_TOPIC {
+                history.events.pop_front();
+            }
+        }
+
+        if self.topics.len() > MAX_REPLAY_TOPICS {
+            // Drop the least recently active tenth in one go
+            let mut by_age: Vec<(u64, String)> = self
+                .topics
+                .iter()
+                .map(|(topic, history)| (history.last_index, topic.clone()))
+                .collect();
+            by_age.sort_unstable();
+            for (_, topic) in by_age.into_iter().take(MAX_REPLAY_TOPICS / 10) {
+                self.topics.remove(&topic);
+            }
+        }
+    }
+
+    /// Events after each given position in arrival order, and the topics whose
+    /// gap can't be filled from the buffer. `current` is the last sequence given
+    /// out on each topic, or `None` if it couldn't be looked up.
+    fn replay(
+        &self,
+        positions: &HashMap<String, u64>,
+        current: Option<&HashMap<String, u64>>,
+    ) -> (Vec<Arc<Envelope>>, Vec<String>) {
+        let mut events = BTreeMap::new();
+        let mut resync = Vec::new();
+
+        for (topic, position) in positions {
+            let latest = current.map(|current| current.get(topic).copied().unwrap_or(0));
+            // A pruned counter starts again from 1, leaving the client ahead of it
+            if latest.is_some_and(|latest| *position > latest) {
+                resync.push(topic.clone());
+                continue;
+            }
+            let Some(history) = self.topics.get(topic) else {
+                // Nothing buffered, e.g. after a restart or eviction: the client is
+                // only up to date if nothing has been published since its position
+                if latest.map_or(*position > 0, |latest| latest > *position) {
+                    resync.push(topic.clone());
+                }
+                continue;
+            };
+            if history.latest_seq <= *position {
+                continue;
+            }
+
+            let oldest = history.events.iter().map(|(_, seq, _)| *seq).min().unwrap_or(u64::MAX);
+            if oldest > position + 1 {
+                resync.push(topic.clone());
+                continue;
+            }
+
+            for (index, seq, envelope) in &history.events {
+                if seq > position {
+                    events.insert(*index, envelope.clone());
+                }
+            }
+        }
+
+        (events.into_values().collect(), resync)
+    }
 }
 
 // WebSocket upgrade handler
 pub async fn websocket_handler(
     ws: WebSocketUpgrade,
-    State(_state): State<AppState>,
+    Query(params): Query<WsConnectParams>,
+    headers: HeaderMap,
+    State(state): State<AppState>,
 ) -> Response {
     info!("WebSocket connection requested");
-    ws.on_upgrade(handle_socket)
+
+    // Browsers cannot set headers on upgrade requests, so the JWT comes in the
+    // query string or the login cookie
+    let token = params.token.or_else(|| {
+        headers
+            .get(header::COOKIE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(AuthService::extract_token_from_cookie)
+            .map(str::to_string)
+    });
+
+    let user = match token {
+        Some(token) => match AuthService::validate_token(&token, state.config.jwt_secret.expose()) {
+            Ok(claims) => Some(SocketUser {
+                user_id: claims.sub,
+                is_admin: claims.user_type == "SuperAdmin",
+            }),
+            Err(_) => return StatusCode::UNAUTHORIZED.into_response(),
+        },
+        None => None,
+    };
+
+    let db = state.db.clone();
+    ws.on_upgrade(move |socket| handle_socket(socket, user, db))
 }
 
 // Demo WebSocket upgrade handler for demo mode
@@ -83,17 +385,33 @@ pub async fn demo_websocket_handler(ws: WebSocketUpgrade) -> Response {
 }
 
 // Handle individual WebSocket connections
-async fn handle_socket(socket: WebSocket) {
+async fn handle_socket(socket: WebSocket, user: Option<SocketUser>, db: DatabaseConnection) {
     let connection_id = Uuid::new_v4();
-    info!("New WebSocket connection established: {}", connection_id);
+    let user_id = user.map(|u| u.user_id);
+    info!("New WebSocket connection established: {} (user: {:?})", connection_id, user_id);
 
     let (mut sender, mut receiver) = socket.split();
-    let (tx, mut rx) = broadcast::channel::<WsMessage>(100);
+    let (tx, mut rx) = broadcast::channel::<OutboundMessage>(100);
 
-    // Store connection in global manager
+    // Store connection in global manager; signed-in users always receive their own events
     {
         let mut connections = CONNECTIONS.lock().unwrap();
-        connections.insert(connection_id, tx.clone());
+        connections.insert(
+            connection_id,
+            ClientConnection {
+                sender: tx.clone(),
+                user,
+                topics: [Some(Topic::System), user_id.map(Topic::User)]
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+            },
+        );
+    }
+
+    // Let the user's other sessions know a new one has connected
+    if let Some(user_id) = user_id {
+        publish(&[Topic::User(user_id)], WsMessage::UserConnected { user_id: user_id.to_string() }).await;
     }
 
     // Send welcome message
@@ -125,7 +443,7 @@ async fn handle_socket(socket: WebSocket) {
             match msg {
                 Message::Text(text) => {
                     if let Ok(ws_msg) = serde_json::from_str::<WsMessage>(&text) {
-                        handle_client_message(connection_id, ws_msg, &tx).await;
+                        handle_client_message(connection_id, user, ws_msg, &tx, &db).await;
                     }
                 }
                 Message::Binary(_) => {
@@ -155,6 +473,10 @@ async fn handle_socket(socket: WebSocket) {
         let mut connections = CONNECTIONS.lock().unwrap();
         connections.remove(&connection_id);
     }
+
+    if let Some(user_id) = user_id {
+        publish(&[Topic::User(user_id)], WsMessage::UserDisconnected { user_id: user_id.to_string() }).await;
+    }
     
     info!("WebSocket connection cleaned up: {}", connection_id);
 }
@@ -254,31 +576,148 @@ async fn handle_demo_socket(socket: WebSocket) {
     info!("Demo WebSocket connection cleaned up: {}", connection_id);
 }
 
+/// Access rules that need no database lookup; `None` means the topic's
+/// resource has to be checked
+fn static_topic_access(user: Option<SocketUser>, topic: &Topic) -> Option<bool> {
+    match (topic, user) {
+        (Topic::System | Topic::Jobs, _) => Some(true),
+        (_, Some(user)) if user.is_admin => Some(true),
+        (Topic::User(id), Some(user)) => Some(*id == user.user_id),
+        (Topic::Tenant(_) | Topic::Search(_), Some(_)) => None,
+        (Topic::Job(_), _) => None,
+        (_, None) => Some(false),
+    }
+}
+
+/// Whether a connection may subscribe to a topic
+async fn can_subscribe(db: &DatabaseConnection, user: Option<SocketUser>, topic: &Topic) -> bool {
+    if let Some(allowed) = static_topic_access(user, topic) {
+        return allowed;
+    }
+
+    let result = match topic {
+        // Published jobs are public; drafts and closed jobs only to their poster
+        Topic::Job(job_id) => Job::find_by_id(*job_id).one(db).await.map(|job| {
+            job.is_some_and(|job| {
+                job.deleted_at.is_none()
+                    && (matches!(job.status, job::JobStatusDb::Active)
+                        || user.is_some_and(|u| u.user_id == job.created_by))
+            })
+        }),
+        Topic::Tenant(tenant_id) => match user {
+            Some(user) => TenantUsers::find()
+                .filter(tenant_users::Column::TenantId.eq(*tenant_id))
+                .filter(tenant_users::Column::UserId.eq(user.user_id))
+                .count(db)
+                .await
+                .map(|count| count > 0),
+            None => Ok(false),
+        },
+        // Saved searches are private to the user who saved them
+        Topic::Search(search_id) => match user {
+            Some(user) => SavedSearch::find_by_id(*search_id)
+                .one(db)
+                .await
+                .map(|search| search.is_some_and(|search| search.user_id == user.user_id)),
+            None => Ok(false),
+        },
+        _ => Ok(false),
+    };
+
+    result.unwrap_or_else(|e| {
+        error!("Failed to check access to topic {}: {}", topic, e);
+        false
+    })
+}
+
 // Handle messages from clients
 async fn handle_client_message(
     connection_id: Uuid,
+    user: Option<SocketUser>,
     message: WsMessage,
-    sender: &broadcast::Sender<WsMessage>,
+    sender: &broadcast::Sender<OutboundMessage>,
+    db: &DatabaseConnection,
 ) {
     match message {
-        WsMessage::Subscribe { topics } => {
-            info!("Client {} subscribed to topics: {:?}", connection_id, topics);
-            
-            // Send confirmation
-            let confirmation = WsMessage::SystemNotification {
-                message: format!("Subscribed to {} topics", topics.len()),
-                level: NotificationLevel::Success,
+        WsMessage::Subscribe { topics, resume } => {
+            let mut allowed = Vec::new();
+            let mut rejected = Vec::new();
+
+            for raw in topics {
+                match Topic::parse(&raw) {
+                    Some(topic) if can_subscribe(db, user, &topic).await => allowed.push(topic),
+                    _ => rejected.push(raw),
+                }
+            }
+
+            let mut subscribed = Vec::new();
+            let mut held = HashSet::new();
+            {
+                let mut connections = CONNECTIONS.lock().unwrap();
+                if let Some(connection) = connections.get_mut(&connection_id) {
+                    for topic in allowed {
+                        if connection.topics.len() >= MAX_TOPICS_PER_CONNECTION
+                            && !connection.topics.contains(&topic)
+                        {
+                            rejected.push(topic.to_string());
+                            continue;
+                        }
+                        subscribed.push(topic.to_string());
+                        connection.topics.insert(topic);
+                    }
+                    held = connection.topics.clone();
+                }
+            }
+
+            info!(
+                "Client {} subscribed to {:?} (rejected {:?})",
+                connection_id, subscribed, rejected
+            );
+
+            // Only resume topics the client actually holds
+            let positions: HashMap<String, u64> = resume
+                .into_iter()
+                .filter(|(topic, _)| Topic::parse(topic).is_some_and(|t| held.contains(&t)))
+                .collect();
+            let topics: Vec<String> = positions.keys().cloned().collect();
+            let current = match realtime_backplane::current().current_sequences(&topics).await {
+                Ok(current) => Some(current),
+                Err(e) => {
+                    error!("Failed to look up realtime sequences for {}: {}", connection_id, e);
+                    None
+                }
             };
-            
-            if sender.send(confirmation).is_err() {
+            let (missed, resync) = REPLAY.lock().unwrap().replay(&positions, current.as_ref());
+
+            if sender.send(WsMessage::SubscriptionResult { subscribed, rejected }.into()).is_err() {
                 error!("Failed to send subscription confirmation to {}", connection_id);
             }
+            if !resync.is_empty() && sender.send(WsMessage::ResyncRequired { topics: resync }.into()).is_err() {
+                error!("Failed to send resync notice to {}", connection_id);
+            }
+            for envelope in missed {
+                if sender.send(OutboundMessage::for_connection(&envelope, &held)).is_err() {
+                    error!("Failed to replay missed events to {}", connection_id);
+                    break;
+                }
+            }
         }
         WsMessage::Unsubscribe { topics } => {
             info!("Client {} unsubscribed from topics: {:?}", connection_id, topics);
+
+            let mut connections = CONNECTIONS.lock().unwrap();
+            if let Some(connection) = connections.get_mut(&connection_id) {
+                // System and the user's own topic are tied to the connection, not the client's choice
+                let own_topic = connection.user.map(|u| Topic::User(u.user_id));
+                for topic in topics.iter().filter_map(|t| Topic::parse(t)) {
+                    if topic != Topic::System && Some(&topic) != own_topic.as_ref() {
+                        connection.topics.remove(&topic);
+                    }
+                }
+            }
         }
         WsMessage::Ping => {
-            if sender.send(WsMessage::Pong).is_err() {
+            if sender.send(WsMessage::Pong.into()).is_err() {
                 error!("Failed to send pong to {}", connection_id);
             }
         }
@@ -288,50 +727,277 @@ async fn handle_client_message(
     }
 }
 
-// Broadcast message to all connected clients
+// Broadcast message to all connected clients, on every instance
 pub async fn broadcast_to_all(message: WsMessage) {
+    publish(&[Topic::System], message).await;
+}
+
+/// Publish a message on the given topics through the realtime backplane
+pub async fn publish(topics: &[Topic], message: WsMessage) {
+    if let Err(e) = realtime_backplane::current().publish(topics, message).await {
+        error!("Failed to publish realtime event to {:?}: {}", topics, e);
+    }
+}
+
+/// Open connections on this instance, for the metrics endpoint
+pub fn socket_stats() -> SocketStats {
     let connections = CONNECTIONS.lock().unwrap();
-    let connection_count = connections.len();
-    
-    if connection_count == 0 {
-        return;
+    SocketStats {
+        connections: connections.len(),
+        authenticated: connections.values().filter(|connection| connection.user.is_some()).count(),
     }
+}
 
-    info!("Broadcasting message to {} connections", connection_count);
-    
-    for (connection_id, sender) in connections.iter() {
-        if sender.send(message.clone()).is_err() {
-            warn!("Failed to broadcast to connection {}", connection_id);
+/// Send an event once to every local connection subscribed to any of its topics;
+/// called by the backplane for events published on any instance
+pub fn deliver(envelope: Envelope) {
+    let envelope = Arc::new(envelope);
+    REPLAY.lock().unwrap().record(envelope.clone());
+
+    let topics = envelope.topics();
+    let connections = CONNECTIONS.lock().unwrap();
+
+    for (connection_id, connection) in connections.iter() {
+        let is_subscribed = topics.iter().any(|topic| connection.topics.contains(topic));
+
+        if is_subscribed
+            && connection
+                .sender
+                .send(OutboundMessage::for_connection(&envelope, &connection.topics))
+                .is_err()
+        {
+            warn!("Failed to send to connection {}", connection_id);
         }
     }
 }
 
+// Send a message to every connection opened by one of the given users
+pub async fn send_to_users(user_ids: &[Uuid], message: WsMessage) {
+    let topics: Vec<Topic> = user_ids.iter().copied().map(Topic::User).collect();
+    publish(&topics, message).await;
+}
+
+/// The public feed plus the job's own topic, when the id is well formed
+fn job_topics(job_id: &str) -> Vec<Topic> {
+    let mut topics = vec![Topic::Jobs];
+    topics.extend(Uuid::parse_str(job_id).ok().map(Topic::Job));
+    topics
+}
+
 // Broadcast job-related events
 pub async fn broadcast_job_posted(job: JobNotification) {
     let message = WsMessage::JobPosted { job };
-    broadcast_to_all(message).await;
+    publish(&[Topic::Jobs], message).await;
+}
+
+/// A newly published job, to each saved search it matches
+pub async fn broadcast_saved_search_matches(search_ids: &[Uuid], job: JobNotification) {
+    let topics: Vec<Topic> = search_ids.iter().copied().map(Topic::Search).collect();
+    let message = WsMessage::JobPosted { job };
+    publish(&topics, message).await;
 }
 
 pub async fn broadcast_job_updated(job: JobNotification) {
+    let topics = job_topics(&job.id);
     let message = WsMessage::JobUpdated { job };
-    broadcast_to_all(message).await;
+    publish(&topics, message).await;
 }
 
 pub async fn broadcast_job_deleted(job_id: String) {
+    let topics = job_topics(&job_id);
     let message = WsMessage::JobDeleted { job_id };
-    broadcast_to_all(message).await;
+    publish(&topics, message).await;
+}
+
+pub async fn broadcast_job_status_changed(job_id: Uuid, from: String, to: String, reason: String) {
+    let message = WsMessage::JobStatusChanged {
+        job_id: job_id.to_string(),
+        from,
+        to,
+        reason,
+    };
+    publish(&[Topic::Jobs, Topic::Job(job_id)], message).await;
 }
 
 pub async fn broadcast_application_received(job_id: String, application_count: u32) {
+    let Ok(id) = Uuid::parse_str(&job_id) else {
+        warn!("Not broadcasting application count for invalid job id {}", job_id);
+        return;
+    };
     let message = WsMessage::JobApplicationReceived {
         job_id,
         application_count,
     };
-    broadcast_to_all(message).await;
+    publish(&[Topic::Job(id)], message).await;
 }
 
 // System notification broadcasting
 pub async fn broadcast_system_notification(message: String, level: NotificationLevel) {
     let ws_message = WsMessage::SystemNotification { message, level };
     broadcast_to_all(ws_message).await;
-}
\ No newline at end of file
+}
+
+// Messaging events
+pub async fn send_chat_message(recipients: &[Uuid], message: ChatMessageNotification) {
+    send_to_users(recipients, WsMessage::MessageReceived { message }).await;
+}
+
+pub async fn send_messages_read(
+    recipients: &[Uuid],
+    conversation_id: Uuid,
+    user_id: Uuid,
+    read_at: chrono::DateTime<chrono::Utc>,
+) {
+    let message = WsMessage::MessagesRead {
+        conversation_id: conversation_id.to_string(),
+        user_id: user_id.to_string(),
+        read_at,
+    };
+    send_to_users(recipients, message).await;
+}
+
+// Notification centre events
+pub async fn send_notification(recipients: &[Uuid], notification: NotificationPayload) {
+    send_to_users(recipients, WsMessage::NotificationCreated { notification }).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(is_admin: bool) -> SocketUser {
+        SocketUser {
+            user_id: Uuid::new_v4(),
+            is_admin,
+        }
+    }
+
+    #[test]
+    fn topics_round_trip() {
+        let id = Uuid::new_v4();
+        for topic in [Topic::System, Topic::Jobs, Topic::Job(id), Topic::User(id), Topic::Tenant(id), Topic::Search(id)] {
+            assert_eq!(Topic::parse(&topic.to_string()), Some(topic));
+        }
+        assert_eq!(Topic::parse("job:not-a-uuid"), None);
+        assert_eq!(Topic::parse(&format!("team:{}", id)), None);
+        assert_eq!(Topic::parse("everything"), None);
+    }
+
+    #[test]
+    fn users_only_see_their_own_topic() {
+        let me = user(false);
+        assert_eq!(static_topic_access(Some(me), &Topic::User(me.user_id)), Some(true));
+        assert_eq!(static_topic_access(Some(me), &Topic::User(Uuid::new_v4())), Some(false));
+        assert_eq!(static_topic_access(Some(user(true)), &Topic::User(me.user_id)), Some(true));
+    }
+
+    #[test]
+    fn anonymous_connections_are_limited_to_public_topics() {
+        let id = Uuid::new_v4();
+        assert_eq!(static_topic_access(None, &Topic::Jobs), Some(true));
+        assert_eq!(static_topic_access(None, &Topic::Job(id)), None);
+        assert_eq!(static_topic_access(None, &Topic::User(id)), Some(false));
+        assert_eq!(static_topic_access(None, &Topic::Tenant(id)), Some(false));
+        assert_eq!(static_topic_access(None, &Topic::Search(id)), Some(false));
+    }
+
+    #[test]
+    fn tenant_membership_needs_a_lookup() {
+        let id = Uuid::new_v4();
+        assert_eq!(static_topic_access(Some(user(false)), &Topic::Tenant(id)), None);
+        assert_eq!(static_topic_access(Some(user(true)), &Topic::Tenant(id)), Some(true));
+    }
+
+    #[test]
+    fn saved_search_ownership_needs_a_lookup() {
+        let id = Uuid::new_v4();
+        assert_eq!(static_topic_access(Some(user(false)), &Topic::Search(id)), None);
+        assert_eq!(static_topic_access(Some(user(true)), &Topic::Search(id)), Some(true));
+    }
+
+    fn envelope(sequences: &[(&str, u64)]) -> Arc<Envelope> {
+        Arc::new(Envelope::new(
+            sequences.iter().map(|(t, s)| (t.to_string(), *s)).collect(),
+            WsMessage::Ping,
+        ))
+    }
+
+    #[test]
+    fn replay_returns_events_after_the_position_once() {
+        let mut buffer = ReplayBuffer::default();
+        let job = format!("job:{}", Uuid::new_v4());
+        buffer.record(envelope(&[("jobs", 1)]));
+        buffer.record(envelope(&[("jobs", 2), (job.as_str(), 1)]));
+        buffer.record(envelope(&[("jobs", 3)]));
+
+        let positions = HashMap::from([("jobs".to_string(), 1), (job.clone(), 0)]);
+        let current = HashMap::from([("jobs".to_string(), 3), (job.clone(), 1)]);
+        let (events, resync) = buffer.replay(&positions, Some(&current));
+
+        assert!(resync.is_empty());
+        let seqs: Vec<u64> = events.iter().map(|e| e.sequences["jobs"]).collect();
+        assert_eq!(seqs, vec![2, 3]);
+    }
+
+    #[test]
+    fn replay_asks_for_resync_when_history_is_gone() {
+        let mut buffer = ReplayBuffer::default();
+        for seq in 1..=(REPLAY_EVENTS_PER_TOPIC as u64 + 10) {
+            buffer.record(envelope(&[("jobs", seq)]));
+        }
+
+        let current = HashMap::from([("jobs".to_string(), REPLAY_EVENTS_PER_TOPIC as u64 + 10)]);
+
+        let (events, resync) = buffer.replay(&HashMap::from([("jobs".to_string(), 2)]), Some(&current));
+        assert!(events.is_empty());
+        assert_eq!(resync, vec!["jobs".to_string()]);
+
+        let (events, resync) = buffer.replay(&HashMap::from([("jobs".to_string(), 105)]), Some(&current));
+        assert_eq!(events.len(), 5);
+        assert!(resync.is_empty());
+    }
+
+    #[test]
+    fn replay_asks_for_resync_when_nothing_is_buffered_but_events_were_missed() {
+        // As after a restart: nothing buffered, but the topic has moved on
+        let buffer = ReplayBuffer::default();
+        let positions = HashMap::from([("jobs".to_string(), 4), ("system".to_string(), 7)]);
+        let current = HashMap::from([("jobs".to_string(), 9), ("system".to_string(), 7)]);
+
+        let (events, resync) = buffer.replay(&positions, Some(&current));
+        assert!(events.is_empty());
+        assert_eq!(resync, vec!["jobs".to_string()]);
+
+        // Sequences unknown: anything the client had seen may have been missed
+        let (_, resync) = buffer.replay(&HashMap::from([("jobs".to_string(), 4)]), None);
+        assert_eq!(resync, vec!["jobs".to_string()]);
+        let (_, resync) = buffer.replay(&HashMap::from([("jobs".to_string(), 0)]), None);
+        assert!(resync.is_empty());
+    }
+
+    #[test]
+    fn replay_asks_for_resync_when_the_counter_was_reset() {
+        let mut buffer = ReplayBuffer::default();
+        buffer.record(envelope(&[("jobs", 1)]));
+
+        let current = HashMap::from([("jobs".to_string(), 1)]);
+        let (events, resync) = buffer.replay(&HashMap::from([("jobs".to_string(), 40)]), Some(&current));
+        assert!(events.is_empty());
+        assert_eq!(resync, vec!["jobs".to_string()]);
+    }
+
+    #[test]
+    fn outbound_messages_only_carry_held_sequences() {
+        let job = Topic::Job(Uuid::new_v4());
+        let event = envelope(&[("jobs", 4), (job.to_string().as_str(), 9)]);
+        let held = HashSet::from([job.clone()]);
+
+        let json = serde_json::to_value(OutboundMessage::for_connection(&event, &held)).unwrap();
+        assert_eq!(json["type"], "Ping");
+        assert_eq!(json["seq"][job.to_string()], 9);
+        assert!(json["seq"].get("jobs").is_none());
+
+        let plain = serde_json::to_value(OutboundMessage::from(WsMessage::Pong)).unwrap();
+        assert!(plain.get("seq").is_none());
+    }
+}
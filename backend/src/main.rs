@@ -8,18 +8,19 @@ mod entities;
 mod repository;
 mod database;
 mod db;
+mod openapi;
 
 // Re-export commonly used types
 pub use error::AppError;
 
+use clap::Parser;
 use axum::{
-    extract::DefaultBodyLimit,
     http::{HeaderValue, Method},
     middleware as axum_middleware,
-    routing::{get, post, put, delete},
+    routing::{get, post},
     Router,
 };
-use std::{net::SocketAddr, time::Duration};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
 use tower::ServiceBuilder;
 use tower_http::{
     cors::CorsLayer,
@@ -33,9 +34,22 @@ use sea_orm::DatabaseConnection;
 use tokio::signal;
 
 use crate::{
-    config::Config,
-    handlers::{jobs, enhanced_jobs, health, users, auth, local_auth, websocket, search, applications, root as handlers},
-    services::ApplicationService,
+    config::{Cli, Config},
+    middleware::CachePolicy,
+    handlers::{jobs, health, users, websocket, metrics, root as handlers},
+    services::{
+        audit_service::{self, AuditService},
+        email_service::{self, EmailSender},
+        health_service::HealthService,
+        idempotency_service::{self, IdempotencyService},
+        invoice_service::{self, InvoiceService},
+        job_scheduler::{self, JobScheduler},
+        notification_service::{self, NotificationService},
+        realtime_backplane,
+        task_queue::{self, TaskRegistry, TaskWorker},
+        webhook_service::{self, WebhookSender},
+        ApplicationService,
+    },
 };
 
 #[derive(Clone)]
@@ -43,43 +57,91 @@ pub struct AppState {
     pub config: Config,
     pub db: DatabaseConnection,
     pub application_service: ApplicationService,
+    pub mailer: Arc<dyn EmailSender>,
     pub demo_mode: bool,
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // Initialize structured logging with multiple layers
+    // Load configuration: profile defaults, config file, environment, then --set
+    let cli = Cli::parse();
+    let layers = config::gather(&cli);
+    let config = Config::from_layers(&layers);
+    if cli.print_config {
+        print!("{}", layers.render());
+        if let Err(e) = &config {
+            println!("# {}", e.to_string().replace('\n', "\n# "));
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    let config = config?;
+
+    // Initialize structured logging with multiple layers; RUST_LOG wins over log_level
     tracing_subscriber::registry()
         .with(
             tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "backend=debug,tower_http=debug,axum::rejection=trace".into()),
+                .unwrap_or_else(|_| config.log_level.as_str().into()),
         )
         .with(tracing_subscriber::fmt::layer())
         .init();
     
     tracing::info!("🦀 Starting Loco Platform - Rust Web Application");
-    
-    // Load configuration
-    let config = Config::from_env()?;
+    config.log_summary(&layers);
+
+    // Uptime in /metrics and /health/detailed counts from here
+    services::metrics::global();
     
     // Setup database connection - no fallbacks
     tracing::info!("🔗 Setting up database connection...");
     
-    let db = database::setup_database().await
+    let db = database::setup_database(&config.database).await
         .map_err(|e| anyhow::anyhow!("Database connection failed: {}. Please ensure PostgreSQL is running and database is configured.", e))?;
         
     tracing::info!("✅ Database connection established successfully");
     
+    // Real-time events reach WebSocket clients on every instance through the backplane
+    realtime_backplane::install(realtime_backplane::backplane_from_config(&config, &db).await?);
+    
     let application_service = ApplicationService::new(db.clone());
+    let mailer = email_service::email_sender_from_config(&config)?;
     let state = AppState { 
         config: config.clone(), 
         db, 
         application_service,
+        mailer,
         demo_mode: true, // Enable demo mode for development
     };
     
+    // Report every dependency's health before taking traffic
+    HealthService::from(&state).log_startup_diagnostics().await;
+    
+    // Background delivery of notification digests
+    notification_service::spawn_digest_task(NotificationService::from(&state));
+    
+    // Mark unpaid invoices overdue once their due date passes
+    invoice_service::spawn_overdue_task(InvoiceService::from(&state));
+    
+    // Publish, close and expire jobs as their scheduled times pass
+    job_scheduler::spawn_scheduler(JobScheduler::from(&state));
+    
+    // Forget idempotency keys once their stored responses expire
+    idempotency_service::spawn_cleanup_task(IdempotencyService::from(&state));
+    
+    // Append audit events committed with the changes they describe to the hash chain
+    audit_service::spawn_chain_task(AuditService::from(&state));
+    
+    // Background task workers
+    let mut task_registry = TaskRegistry::default();
+    email_service::register_tasks(&mut task_registry, state.mailer.clone());
+    webhook_service::register_tasks(&mut task_registry, WebhookSender::new(state.db.clone(), config.profile.is_local()));
+    task_queue::spawn_workers(TaskWorker::new(state.db.clone(), task_registry), config.task_workers);
+    
     // Build middleware stack
     let middleware_stack = ServiceBuilder::new()
+        .layer(axum_middleware::from_fn(middleware::request_id_middleware)) // Correlation IDs for errors and logs
+        .layer(axum_middleware::from_fn(middleware::audit_context_middleware)) // Client address for audit events
+        .layer(axum_middleware::from_fn(middleware::http_metrics_middleware)) // Per-route request histograms
         .layer(TraceLayer::new_for_http())
         .layer(create_cors_layer());
 
@@ -91,14 +153,25 @@ async fn main() -> anyhow::Result<()> {
         .route("/health/detailed", get(health::health_detailed))
         .route("/health/ready", get(health::readiness_check))
         .route("/health/live", get(health::liveness_check))
+        .route("/metrics", get(metrics::metrics))
         
         // API v1 routes
-        .nest("/api/v1", api_v1_routes())
+        .nest(openapi::API_V1_PREFIX, api_v1_routes(&state))
         
         // Legacy API routes (for backward compatibility)
-        .route("/api/jobs", get(jobs::list_jobs).post(jobs::create_job))
+        .route(
+            "/api/jobs",
+            get(jobs::list_jobs)
+                .layer(axum_middleware::from_fn_with_state(CachePolicy::JOB_LISTINGS, middleware::conditional_get))
+                .post(jobs::create_job),
+        )
         .route("/api/jobs/search", post(jobs::search_jobs))
-        .route("/api/jobs/:id", get(jobs::get_job).put(jobs::update_job).delete(jobs::delete_job))
+        .route(
+            "/api/jobs/:id",
+            get(jobs::get_job)
+                .layer(axum_middleware::from_fn_with_state(CachePolicy::JOB_DETAILS, middleware::conditional_get))
+                .delete(jobs::delete_job),
+        )
         
         // User routes  
         .route("/api/users/:id", get(users::get_user).put(users::update_user).delete(users::delete_user))
@@ -128,6 +201,15 @@ fn create_cors_layer() -> CorsLayer {
             axum::http::header::AUTHORIZATION,
             axum::http::header::ACCEPT,
             axum::http::header::USER_AGENT,
+            axum::http::header::IF_MATCH,
+            axum::http::header::IF_NONE_MATCH,
+            axum::http::HeaderName::from_static(middleware::idempotency::IDEMPOTENCY_KEY_HEADER),
+        ])
+        // Lets the web client read a job's ETag to send back in `If-Match`, and
+        // tell a replayed POST from a fresh one
+        .expose_headers([
+            axum::http::header::ETAG,
+            axum::http::HeaderName::from_static(middleware::idempotency::IDEMPOTENT_REPLAYED_HEADER),
         ])
         // Australian domains and localhost for development
         .allow_origin("http://localhost:3070".parse::<HeaderValue>().unwrap())
@@ -142,71 +224,11 @@ fn create_cors_layer() -> CorsLayer {
 }
 
 /// Create API v1 routes with versioning
-fn api_v1_routes() -> Router<AppState> {
-    Router::new()
-        // Public routes (no authentication required)
-        .route("/auth/login", post(auth::login))
-        .route("/auth/register", post(auth::register))
-        .route("/auth/logout", post(auth::logout))
-        .route("/auth/forgot-password", post(auth::forgot_password))
-        .route("/auth/verify-otp", post(auth::verify_otp))
-        .route("/auth/oauth/:provider", get(auth::oauth_signin))
-        
-        // Local auth routes (JWT-based, no Supabase)
-        .route("/auth/local/login", post(local_auth::local_login))
-        .route("/auth/local/register", post(local_auth::local_register))
-        .route("/auth/local/logout", post(local_auth::local_logout))
-        .route("/auth/local/verify", get(local_auth::verify_token))
-        .route("/auth/local/refresh", post(local_auth::refresh_token))
-        .route("/jobs", get(jobs::list_jobs)) // Public job listing
-        .route("/jobs/search", post(jobs::search_jobs)) // Public job search
-        .route("/jobs/:id", get(jobs::get_job)) // Public job details
-        
-        // Enhanced job routes
-        .route("/jobs/enhanced", get(enhanced_jobs::list_jobs)) // Enhanced job listing with better filtering
-        .route("/jobs/enhanced/search", get(enhanced_jobs::search_jobs)) // Enhanced text search
-        .route("/jobs/enhanced/nearby", get(enhanced_jobs::find_jobs_nearby)) // Location-based search
-        .route("/jobs/enhanced/stats", get(enhanced_jobs::get_global_job_statistics)) // Global statistics
-        
-        // Advanced search routes
-        .route("/search/advanced", post(search::advanced_search))
-        .route("/search/quick", get(search::quick_search))
-        .route("/search/suggestions", get(search::search_suggestions))
-        .route("/search/trending", get(search::trending_searches))
-        .route("/search/recommendations", get(search::job_recommendations))
-        
-        // Protected routes (require authentication)
-        .route("/jobs", post(jobs::create_job))
-        .route("/jobs/:id", put(jobs::update_job).delete(jobs::delete_job))
-        
-        // Enhanced job routes (protected)
-        .route("/jobs/enhanced", post(enhanced_jobs::create_job)) // Create job with enhanced features
-        .route("/jobs/enhanced/:id", get(enhanced_jobs::get_job).delete(enhanced_jobs::delete_job)) // Enhanced job operations
-        .route("/jobs/enhanced/:id/status", put(enhanced_jobs::update_job_status)) // Update job status
-        .route("/jobs/enhanced/my", get(enhanced_jobs::get_my_jobs)) // Get user's jobs
-        .route("/jobs/enhanced/my/stats", get(enhanced_jobs::get_job_statistics)) // User's job statistics
-        
-        // User management routes (protected)
-        .route("/users/:id", get(users::get_user).put(users::update_user).delete(users::delete_user))
-        
-        // Authentication routes
-        .route("/auth/refresh", post(auth::refresh_token))
-        
-        // Profile routes (protected)
-        .route("/profile", get(auth::get_profile).put(auth::update_profile))
-        
-        // Application routes (protected)
-        .route("/applications", get(applications::list_applications).post(applications::create_application))
-        .route("/applications/:id", get(applications::get_application).put(applications::update_application).delete(applications::delete_application))
-        .route("/applications/:id/status", put(applications::update_application_status))
-        .route("/applications/:id/withdraw", put(applications::withdraw_application))
-        .route("/applications/stats", get(applications::get_application_stats))
-        .route("/jobs/:id/applications", get(applications::get_job_applications))
-        .route("/users/:id/applications", get(applications::get_user_applications))
-        
-        // Protected search routes
-        .route("/search/saved", get(search::get_saved_searches))
-        .route("/search/save", post(search::save_search))
+fn api_v1_routes(state: &AppState) -> Router<AppState> {
+    // Every route and its OpenAPI description are declared together in `openapi`
+    openapi::routes()
+        // Replay retried POSTs that carry an Idempotency-Key
+        .layer(axum_middleware::from_fn_with_state(state.clone(), middleware::idempotency_middleware))
 }
 
 /// Legacy API routes (backward compatibility)
@@ -226,7 +248,7 @@ async fn start_server_with_graceful_shutdown(app: Router, port: u16) -> anyhow::
     let listener = tokio::net::TcpListener::bind(addr).await?;
     
     // Start server with graceful shutdown
-    axum::serve(listener, app)
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
         .with_graceful_shutdown(shutdown_signal())
         .await?;
     
@@ -0,0 +1,104 @@
+use std::{
+    net::SocketAddr,
+    sync::{Arc, OnceLock},
+};
+
+use axum::{
+    extract::{ConnectInfo, Request},
+    http::HeaderMap,
+    middleware::Next,
+    response::Response,
+};
+use uuid::Uuid;
+
+/// Who is making the request and from where, for the audit trail
+#[derive(Debug, Default)]
+struct AuditContext {
+    ip: Option<String>,
+    /// Filled in once the request authenticates
+    actor: OnceLock<Uuid>,
+}
+
+tokio::task_local! {
+    static AUDIT_CONTEXT: Arc<AuditContext>;
+}
+
+/// Address of the client: the first `X-Forwarded-For` hop when behind the load
+/// balancer, otherwise the peer address
+fn client_ip(headers: &HeaderMap, peer: Option<SocketAddr>) -> Option<String> {
+    headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .map(str::trim)
+        .filter(|ip| !ip.is_empty())
+        .map(str::to_string)
+        .or_else(|| peer.map(|addr| addr.ip().to_string()))
+}
+
+/// Remember the client's address so audit events recorded while handling the
+/// request can say where it came from
+pub async fn audit_context_middleware(request: Request, next: Next) -> Response {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| *addr);
+    let context = AuditContext {
+        ip: client_ip(request.headers(), peer),
+        actor: OnceLock::new(),
+    };
+    AUDIT_CONTEXT.scope(Arc::new(context), next.run(request)).await
+}
+
+/// Record the authenticated user as the actor of the current request
+pub fn set_actor(user_id: Uuid) {
+    let _ = AUDIT_CONTEXT.try_with(|context| context.actor.set(user_id));
+}
+
+/// Authenticated user of the current request, if any
+pub fn current_actor() -> Option<Uuid> {
+    AUDIT_CONTEXT.try_with(|context| context.actor.get().copied()).ok().flatten()
+}
+
+/// Client address of the current request, if any
+pub fn current_ip() -> Option<String> {
+    AUDIT_CONTEXT.try_with(|context| context.ip.clone()).ok().flatten()
+}
+
+/// Run `f` as if handling a request from `ip`
+#[cfg(test)]
+pub async fn with_client<F: std::future::Future>(ip: &str, f: F) -> F::Output {
+    let context = AuditContext { ip: Some(ip.to_string()), actor: OnceLock::new() };
+    AUDIT_CONTEXT.scope(Arc::new(context), f).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_ip_prefers_forwarded_for() {
+        let peer: SocketAddr = "10.0.0.5:51234".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        assert_eq!(client_ip(&headers, Some(peer)).as_deref(), Some("10.0.0.5"));
+        assert_eq!(client_ip(&headers, None), None);
+
+        headers.insert("x-forwarded-for", "203.0.113.7, 10.0.0.1".parse().unwrap());
+        assert_eq!(client_ip(&headers, Some(peer)).as_deref(), Some("203.0.113.7"));
+    }
+
+    #[tokio::test]
+    async fn test_actor_is_scoped_to_the_request() {
+        let user_id = Uuid::new_v4();
+        set_actor(user_id);
+        assert_eq!(current_actor(), None);
+
+        let (actor, ip) = with_client("203.0.113.7", async {
+            set_actor(user_id);
+            (current_actor(), current_ip())
+        })
+        .await;
+        assert_eq!(actor, Some(user_id));
+        assert_eq!(ip.as_deref(), Some("203.0.113.7"));
+    }
+}
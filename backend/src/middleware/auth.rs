@@ -1,12 +1,16 @@
 use axum::{
     extract::{Request, State, FromRequestParts},
-    http::{HeaderMap, StatusCode, request::Parts},
+    http::{HeaderMap, request::Parts},
     middleware::Next,
     response::Response,
     async_trait,
 };
 use uuid::Uuid;
 
+use super::{
+    audit_context,
+    rbac::{get_role_permissions, Permission, Role},
+};
 use crate::{services::AuthService, AppState, AppError};
 
 #[derive(Clone, Debug)]
@@ -28,10 +32,6 @@ impl AuthContext {
         self.user_id
     }
     
-    pub fn email(&self) -> &str {
-        &self.email
-    }
-    
     pub fn is_admin(&self) -> bool {
         self.user_type == "SuperAdmin"
     }
@@ -43,6 +43,21 @@ impl AuthContext {
     pub fn is_professional(&self) -> bool {
         self.user_type == "Professional"
     }
+    
+    /// Map the token's user type onto an RBAC role
+    pub fn role(&self) -> Role {
+        match self.user_type.as_str() {
+            "SuperAdmin" => Role::SuperAdmin,
+            "Employer" => Role::Employer,
+            "Professional" => Role::Professional,
+            _ => Role::Guest,
+        }
+    }
+    
+    /// Check whether the user's role grants a permission
+    pub fn has_permission(&self, permission: &Permission) -> bool {
+        get_role_permissions(&self.role()).contains(permission)
+    }
 }
 
 #[async_trait]
@@ -50,17 +65,18 @@ impl<S> FromRequestParts<S> for AuthContext
 where 
     S: Send + Sync,
 {
-    type Rejection = StatusCode;
+    type Rejection = AppError;
     
     async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
         parts.extensions
             .get::<AuthenticatedUser>()
+            .inspect(|user| audit_context::set_actor(user.user_id))
             .map(|user| AuthContext {
                 user_id: user.user_id,
                 email: user.email.clone(),
                 user_type: user.user_type.clone(),
             })
-            .ok_or(StatusCode::UNAUTHORIZED)
+            .ok_or(AppError::Unauthorized)
     }
 }
 
@@ -70,20 +86,20 @@ pub async fn auth_middleware(
     headers: HeaderMap,
     mut request: Request,
     next: Next,
-) -> Result<Response, StatusCode> {
+) -> Result<Response, AppError> {
     // Get Authorization header
     let auth_header = headers
         .get("Authorization")
         .and_then(|h| h.to_str().ok())
-        .ok_or(StatusCode::UNAUTHORIZED)?;
+        .ok_or(AppError::Unauthorized)?;
     
     // Extract token from header
     let token = AuthService::extract_token_from_header(auth_header)
-        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+        .map_err(|_| AppError::InvalidToken)?;
     
     // Validate token
-    let claims = AuthService::validate_token(token, &state.config.jwt_secret)
-        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+    let claims = AuthService::validate_token(token, state.config.jwt_secret.expose())
+        .map_err(|_| AppError::InvalidToken)?;
     
     // Create authenticated user and add to request extensions
     let auth_user = AuthenticatedUser {
@@ -108,7 +124,7 @@ pub async fn optional_auth_middleware(
     if let Some(auth_header) = headers.get("Authorization").and_then(|h| h.to_str().ok()) {
         // Try to extract and validate token
         if let Ok(token) = AuthService::extract_token_from_header(auth_header) {
-            if let Ok(claims) = AuthService::validate_token(token, &state.config.jwt_secret) {
+            if let Ok(claims) = AuthService::validate_token(token, state.config.jwt_secret.expose()) {
                 let auth_user = AuthenticatedUser {
                     user_id: claims.sub,
                     email: claims.email,
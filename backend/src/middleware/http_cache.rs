@@ -0,0 +1,173 @@
+use axum::{
+    body::{to_bytes, Body, HttpBody},
+    extract::{Request, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// `Cache-Control` for a route whose responses can be cached
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachePolicy(pub &'static str);
+
+impl CachePolicy {
+    /// Job listings: new postings show up within half a minute
+    pub const JOB_LISTINGS: Self = Self("public, max-age=30, stale-while-revalidate=30");
+    /// A single job: reused for a minute, then revalidated with `If-None-Match`
+    pub const JOB_DETAILS: Self = Self("public, max-age=60, must-revalidate");
+    /// Platform-wide statistics move slowly
+    pub const JOB_STATISTICS: Self = Self("public, max-age=300");
+}
+
+/// Larger responses are passed through untagged
+const MAX_TAGGED_BODY_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Strong ETag for a response body: the first 128 bits of its SHA-256
+pub fn etag_for_bytes(body: &[u8]) -> String {
+    format!("\"{}\"", hex::encode(&Sha256::digest(body)[..16]))
+}
+
+/// The ETag a GET returns for `value` sent as JSON
+pub fn etag_for<T: Serialize>(value: &T) -> String {
+    etag_for_bytes(&serde_json::to_vec(value).unwrap_or_default())
+}
+
+/// Entity tags listed in a conditional header, or `None` if it is absent
+fn entity_tags(headers: &HeaderMap, name: &HeaderName) -> Option<Vec<String>> {
+    let mut values = headers.get_all(name).iter().peekable();
+    values.peek()?;
+    Some(
+        values
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect(),
+    )
+}
+
+/// Whether `If-None-Match` names the current representation. Uses the weak
+/// comparison, so a cache's `W/` copy of the tag still matches.
+fn not_modified(headers: &HeaderMap, etag: &str) -> bool {
+    let current = etag.trim_start_matches("W/");
+    entity_tags(headers, &header::IF_NONE_MATCH)
+        .is_some_and(|tags| tags.iter().any(|tag| tag == "*" || tag.trim_start_matches("W/") == current))
+}
+
+/// Whether a write may go ahead under the request's `If-Match`: always without
+/// the header, otherwise only if it names the current representation. Uses the
+/// strong comparison, so weak tags never match.
+pub fn if_match_allows(headers: &HeaderMap, etag: &str) -> bool {
+    match entity_tags(headers, &header::IF_MATCH) {
+        None => true,
+        Some(tags) => tags.iter().any(|tag| tag == "*" || (!tag.starts_with("W/") && tag == etag)),
+    }
+}
+
+/// Tag successful GETs with a strong ETag of the body and the route's
+/// `Cache-Control`, and answer a matching `If-None-Match` with 304 Not Modified.
+/// Layered on each cacheable route with its policy as the state.
+pub async fn conditional_get(State(policy): State<CachePolicy>, request: Request, next: Next) -> Response {
+    if !matches!(*request.method(), Method::GET | Method::HEAD) {
+        return next.run(request).await;
+    }
+    let mut conditions = HeaderMap::new();
+    for value in request.headers().get_all(header::IF_NONE_MATCH) {
+        conditions.append(header::IF_NONE_MATCH, value.clone());
+    }
+
+    let response = next.run(request).await;
+    let taggable = response.status() == StatusCode::OK
+        && response
+            .body()
+            .size_hint()
+            .upper()
+            .is_some_and(|size| size <= MAX_TAGGED_BODY_BYTES);
+    if !taggable {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::error!("Failed to read response body for its ETag: {}", e);
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+    let etag = etag_for_bytes(&body);
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        parts.headers.insert(header::ETAG, value);
+    }
+    parts.headers.insert(header::CACHE_CONTROL, HeaderValue::from_static(policy.0));
+
+    if not_modified(&conditions, &etag) {
+        let mut response = Response::new(Body::empty());
+        *response.status_mut() = StatusCode::NOT_MODIFIED;
+        for name in [header::ETAG, header::CACHE_CONTROL, header::VARY] {
+            if let Some(value) = parts.headers.get(&name) {
+                response.headers_mut().insert(name, value.clone());
+            }
+        }
+        return response;
+    }
+    Response::from_parts(parts, Body::from(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware::from_fn_with_state, routing::get, Json, Router};
+    use serde_json::json;
+    use tower::Service;
+
+    fn conditions(name: HeaderName, value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_static(value));
+        headers
+    }
+
+    #[test]
+    fn test_etag_matches_the_json_sent() {
+        let job = json!({ "id": 42, "title": "Locum pharmacist" });
+        assert_eq!(etag_for(&job), etag_for_bytes(&serde_json::to_vec(&job).unwrap()));
+        assert_ne!(etag_for(&job), etag_for(&json!({ "id": 42, "title": "Pharmacist" })));
+        assert_eq!(etag_for(&job).len(), 34);
+    }
+
+    #[test]
+    fn test_conditional_headers() {
+        let etag = "\"abc\"";
+        assert!(not_modified(&conditions(header::IF_NONE_MATCH, "\"xyz\", W/\"abc\""), etag));
+        assert!(not_modified(&conditions(header::IF_NONE_MATCH, "*"), etag));
+        assert!(!not_modified(&conditions(header::IF_NONE_MATCH, "\"xyz\""), etag));
+        assert!(!not_modified(&HeaderMap::new(), etag));
+
+        assert!(if_match_allows(&HeaderMap::new(), etag));
+        assert!(if_match_allows(&conditions(header::IF_MATCH, "\"abc\""), etag));
+        assert!(if_match_allows(&conditions(header::IF_MATCH, "*"), etag));
+        assert!(!if_match_allows(&conditions(header::IF_MATCH, "W/\"abc\""), etag));
+        assert!(!if_match_allows(&conditions(header::IF_MATCH, "\"xyz\""), etag));
+    }
+
+    #[tokio::test]
+    async fn test_revalidation_gets_not_modified() {
+        let app = Router::new()
+            .route("/jobs", get(|| async { Json(json!({ "items": [] })) }))
+            .layer(from_fn_with_state(CachePolicy::JOB_LISTINGS, conditional_get));
+
+        let mut app = app;
+        let first = app.call(Request::get("/jobs").body(Body::empty()).unwrap()).await.unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(first.headers()[header::CACHE_CONTROL], CachePolicy::JOB_LISTINGS.0);
+        let etag = first.headers()[header::ETAG].clone();
+
+        let again = Request::get("/jobs").header(header::IF_NONE_MATCH, etag.clone()).body(Body::empty()).unwrap();
+        let second = app.call(again).await.unwrap();
+        assert_eq!(second.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(second.headers()[header::ETAG], etag);
+        assert!(to_bytes(second.into_body(), usize::MAX).await.unwrap().is_empty());
+    }
+}
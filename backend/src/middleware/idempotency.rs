@@ -0,0 +1,296 @@
+use axum::{
+    body::{to_bytes, Body, HttpBody},
+    extract::{Request, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+use shared::errors::ProblemDetails;
+use uuid::Uuid;
+
+use super::auth::AuthenticatedUser;
+use crate::{
+    error::problem_response,
+    services::{
+        idempotency_service::{Claim, IdempotencyScope, IdempotencyService, StoredResponse},
+        upload_service::MAX_UPLOAD_BYTES,
+        AuthService,
+    },
+    AppState,
+};
+
+/// Header a client sets to make a POST safe to retry
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Set on responses replayed from an earlier request with the same key
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// Longest key accepted
+const MAX_KEY_LEN: usize = 255;
+
+/// Largest request body buffered to fingerprint it; the upload limit, the largest
+/// body any route accepts
+const MAX_REQUEST_BYTES: usize = MAX_UPLOAD_BYTES + 64 * 1024;
+
+/// Larger responses are passed through without being stored for replay
+const MAX_STORED_RESPONSE_BYTES: u64 = 1024 * 1024;
+
+/// Response headers kept for replay; the rest belong to the original exchange
+const REPLAYED_HEADERS: [HeaderName; 3] = [header::CONTENT_TYPE, header::LOCATION, header::ETAG];
+
+fn usable_key(key: &str) -> bool {
+    !key.is_empty() && key.len() <= MAX_KEY_LEN && key.bytes().all(|b| b.is_ascii_graphic())
+}
+
+/// The signed-in user making the request, if any, from a Bearer token or the
+/// `auth-token` cookie the web client signs in with
+fn requester(state: &AppState, request: &Request) -> Option<Uuid> {
+    if let Some(user) = request.extensions().get::<AuthenticatedUser>() {
+        return Some(user.user_id);
+    }
+    let headers = request.headers();
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| AuthService::extract_token_from_header(value).ok())
+        .or_else(|| {
+            headers
+                .get_all(header::COOKIE)
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .find_map(AuthService::extract_token_from_cookie)
+        })?;
+    AuthService::validate_token(token, state.config.jwt_secret.expose())
+        .ok()
+        .map(|claims| claims.sub)
+}
+
+fn replayed_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    REPLAYED_HEADERS
+        .iter()
+        .filter_map(|name| {
+            let value = headers.get(name)?.to_str().ok()?;
+            Some((name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+fn replay(stored: StoredResponse) -> Response {
+    let mut response = Response::new(Body::from(stored.body));
+    *response.status_mut() = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+    let headers = response.headers_mut();
+    for (name, value) in stored.headers {
+        if let (Ok(name), Ok(value)) = (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+            headers.insert(name, value);
+        }
+    }
+    headers.insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    response
+}
+
+fn problem(status: u16, code: &str, title: &str, message: &str) -> Response {
+    problem_response(ProblemDetails::new(status, code, title, message))
+}
+
+/// What becomes of a key once its request has been answered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    /// Store the response and replay it to repeats
+    Store,
+    /// Too large or streamed to store; repeats are refused instead
+    Seal,
+    /// Free the key so a retry runs the request again
+    Release,
+}
+
+fn outcome(response: &Response) -> Outcome {
+    // A server error is not an answer; the client's retry should run the request again
+    if response.status().is_server_error() {
+        return Outcome::Release;
+    }
+    match response.body().size_hint().upper() {
+        Some(size) if size <= MAX_STORED_RESPONSE_BYTES => Outcome::Store,
+        _ => Outcome::Seal,
+    }
+}
+
+async fn seal(service: &IdempotencyService, id: Uuid, status: StatusCode) {
+    if let Err(e) = service.complete_unreplayable(id, status.as_u16()).await {
+        // The request has happened; a retry will wait out the in-flight timeout
+        tracing::error!("Failed to mark idempotency key {} used: {}", id, e);
+    }
+}
+
+/// Store the response for replay, or record that it cannot be replayed, or free
+/// the key if the request failed
+async fn finish(service: &IdempotencyService, id: Uuid, response: Response) -> Response {
+    match outcome(&response) {
+        Outcome::Store => {}
+        Outcome::Seal => {
+            seal(service, id, response.status()).await;
+            return response;
+        }
+        Outcome::Release => {
+            if let Err(e) = service.release(id).await {
+                tracing::error!("Failed to release idempotency key {}: {}", id, e);
+            }
+            return response;
+        }
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::error!("Failed to read response for idempotency key {}: {}", id, e);
+            seal(service, id, parts.status).await;
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+    let stored = StoredResponse {
+        status: parts.status.as_u16(),
+        headers: replayed_headers(&parts.headers),
+        body: body.to_vec(),
+    };
+    if let Err(e) = service.complete(id, &stored).await {
+        // The request has happened; a retry will wait out the in-flight timeout
+        tracing::error!("Failed to store response for idempotency key {}: {}", id, e);
+    }
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// Make POSTs carrying an `Idempotency-Key` safe to retry. The first response for
+/// each key, user and route is stored and replayed to repeats of the request; a
+/// repeat arriving while the first is still running, or after a response too
+/// large to store, gets 409, and reusing a key for a different body gets 422. Requests without a key or a signed-in user pass
+/// straight through.
+pub async fn idempotency_middleware(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    if request.method() != Method::POST {
+        return next.run(request).await;
+    }
+    let Some(key) = request.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return next.run(request).await;
+    };
+    let Some(key) = key.to_str().ok().filter(|key| usable_key(key)).map(str::to_string) else {
+        return problem(
+            400,
+            "invalid_idempotency_key",
+            "Invalid request",
+            "Idempotency-Key must be 1 to 255 visible ASCII characters.",
+        );
+    };
+    let Some(user_id) = requester(&state, &request) else {
+        return next.run(request).await;
+    };
+
+    let (parts, body) = request.into_parts();
+    let Ok(body) = to_bytes(body, MAX_REQUEST_BYTES).await else {
+        return problem(413, "payload_too_large", "Payload too large", "The request body is too large.");
+    };
+    let scope = IdempotencyScope {
+        user_id,
+        method: parts.method.to_string(),
+        route: parts.uri.path().to_string(),
+        key,
+    };
+
+    let service = IdempotencyService::from(&state);
+    let claim = match service.claim(&scope, &hex::encode(Sha256::digest(&body))).await {
+        Ok(claim) => claim,
+        Err(e) => return e.into_response(),
+    };
+    match claim {
+        Claim::Acquired(id) => {
+            let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+            finish(&service, id, response).await
+        }
+        Claim::Completed(stored) => {
+            tracing::debug!("🔑 Replaying {} {} for key {}", scope.method, scope.route, scope.key);
+            replay(stored)
+        }
+        Claim::InFlight => {
+            let mut problem = ProblemDetails::new(
+                409,
+                "idempotency_key_in_use",
+                "Request in progress",
+                "This request is still being processed. Please wait a moment before retrying.",
+            );
+            problem.retry_after = Some(1);
+            problem_response(problem)
+        }
+        Claim::Unreplayable => problem(
+            409,
+            "idempotency_key_used",
+            "Request already processed",
+            "This request was already processed, but its response cannot be sent again. Fetch the result instead of retrying.",
+        ),
+        Claim::Mismatch => problem(
+            422,
+            "idempotency_key_reused",
+            "Idempotency key reused",
+            "This Idempotency-Key was already used for a different request. Use a new key for each new request.",
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usable_keys() {
+        assert!(usable_key("3f2b9c1e-8d4a-4c1b-9f0e-1a2b3c4d5e6f"));
+        assert!(usable_key("post-job:42"));
+        assert!(!usable_key(""));
+        assert!(!usable_key("has space"));
+        assert!(!usable_key(&"k".repeat(MAX_KEY_LEN + 1)));
+    }
+
+    #[tokio::test]
+    async fn test_replay_restores_the_stored_response() {
+        let response = replay(StoredResponse {
+            status: 201,
+            headers: vec![
+                ("content-type".to_string(), "application/json".to_string()),
+                ("location".to_string(), "/api/v1/jobs/42".to_string()),
+            ],
+            body: b"{\"id\":42}".to_vec(),
+        });
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.headers()[header::LOCATION], "/api/v1/jobs/42");
+        assert_eq!(response.headers()[IDEMPOTENT_REPLAYED_HEADER], "true");
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"{\"id\":42}");
+    }
+
+    #[test]
+    fn test_only_server_errors_free_the_key() {
+        let response = |status: StatusCode, body: Body| {
+            let mut response = Response::new(body);
+            *response.status_mut() = status;
+            response
+        };
+        let stream = || Body::from_stream(futures_util::stream::iter([Ok::<_, std::io::Error>("chunk")]));
+        let oversized = || Body::from(vec![0u8; MAX_STORED_RESPONSE_BYTES as usize + 1]);
+
+        assert_eq!(outcome(&response(StatusCode::CREATED, Body::from("{}"))), Outcome::Store);
+        assert_eq!(outcome(&response(StatusCode::UNPROCESSABLE_ENTITY, Body::from("{}"))), Outcome::Store);
+        assert_eq!(outcome(&response(StatusCode::OK, oversized())), Outcome::Seal);
+        assert_eq!(outcome(&response(StatusCode::OK, stream())), Outcome::Seal);
+        assert_eq!(outcome(&response(StatusCode::INTERNAL_SERVER_ERROR, Body::from("{}"))), Outcome::Release);
+        assert_eq!(outcome(&response(StatusCode::BAD_GATEWAY, stream())), Outcome::Release);
+    }
+
+    #[test]
+    fn test_only_replayable_headers_are_kept() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        headers.insert(header::SET_COOKIE, HeaderValue::from_static("auth-token=secret"));
+        headers.insert("x-request-id", HeaderValue::from_static("req-1"));
+        assert_eq!(
+            replayed_headers(&headers),
+            vec![("content-type".to_string(), "application/json".to_string())]
+        );
+    }
+}
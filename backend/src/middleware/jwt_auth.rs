@@ -29,7 +29,7 @@ pub async fn jwt_auth_middleware(
         .map_err(|_| StatusCode::UNAUTHORIZED)?;
     
     // Validate token
-    let claims = AuthService::validate_token(token, &state.config.jwt_secret)
+    let claims = AuthService::validate_token(token, state.config.jwt_secret.expose())
         .map_err(|_| StatusCode::UNAUTHORIZED)?;
     
     // Add user info to request extensions for use in handlers
@@ -78,7 +78,7 @@ pub async fn optional_jwt_auth_middleware(
     if let Some(auth_header) = headers.get("Authorization").and_then(|h| h.to_str().ok()) {
         // Try to extract and validate token
         if let Ok(token) = AuthService::extract_token_from_header(auth_header) {
-            if let Ok(claims) = AuthService::validate_token(token, &state.config.jwt_secret) {
+            if let Ok(claims) = AuthService::validate_token(token, state.config.jwt_secret.expose()) {
                 // Add user info to request extensions
                 request.extensions_mut().insert(CurrentUser {
                     id: claims.sub,
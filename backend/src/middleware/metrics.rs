@@ -0,0 +1,27 @@
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::services::metrics;
+
+/// Route label for requests that matched no route, so stray paths share one series
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Time every request into the per-route HTTP histograms served at `/metrics`
+pub async fn http_metrics_middleware(request: Request, next: Next) -> Response {
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+
+    let started = Instant::now();
+    let response = next.run(request).await;
+    metrics::global().observe_request(&method, &route, response.status().as_u16(), started.elapsed());
+    response
+}
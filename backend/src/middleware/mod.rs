@@ -5,6 +5,11 @@ pub mod supabase_auth;
 pub mod jwt_auth;
 pub mod validation;
 pub mod rbac;
+pub mod request_id;
+pub mod metrics;
+pub mod audit_context;
+pub mod idempotency;
+pub mod http_cache;
 
 // Re-export middleware
 pub use auth::auth_middleware;
@@ -12,4 +17,9 @@ pub use cors::cors_layer;
 pub use logging::request_tracing;
 pub use jwt_auth::{jwt_auth_middleware, optional_jwt_auth_middleware, CurrentUser};
 pub use validation::{ValidatedJson, validation_middleware, rate_limit_middleware};
+pub use request_id::request_id_middleware;
+pub use metrics::http_metrics_middleware;
+pub use audit_context::audit_context_middleware;
+pub use idempotency::idempotency_middleware;
+pub use http_cache::{conditional_get, CachePolicy};
 pub use rbac::{require_permission, require_role, check_permission, check_roles, Role, Permission, RequestExt};
\ No newline at end of file
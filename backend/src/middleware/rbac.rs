@@ -18,6 +18,8 @@ use shared::types::UserType;
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Role {
     SuperAdmin,
+    // No user type maps to a tenant admin yet; the permission set below is ready for one
+    #[allow(dead_code)]
     Admin,
     Employer,
     Professional,
@@ -61,10 +63,14 @@ pub enum Permission {
     ManageTenants,
     ManageSystem,
     ViewAnalytics,
+    ViewAuditLog,
     
     // Messaging permissions
     SendMessage,
     ViewAllMessages,
+
+    // Availability permissions
+    ViewAvailability,
 }
 
 /// Define role-permission mappings
@@ -78,8 +84,9 @@ pub fn get_role_permissions(role: &Role) -> HashSet<Permission> {
                 CreateJob, EditJob, DeleteJob, ViewAllJobs, ViewOwnJobs,
                 ViewAllUsers, EditAllUsers, DeleteAllUsers, ViewOwnProfile, EditOwnProfile,
                 ViewAllApplications, ViewOwnApplications, CreateApplication, UpdateApplicationStatus,
-                ManageTenants, ManageSystem, ViewAnalytics,
+                ManageTenants, ManageSystem, ViewAnalytics, ViewAuditLog,
                 SendMessage, ViewAllMessages,
+                ViewAvailability,
             ].into_iter().collect()
         },
         Role::Admin => {
@@ -90,6 +97,7 @@ pub fn get_role_permissions(role: &Role) -> HashSet<Permission> {
                 ViewAllApplications, ViewOwnApplications, UpdateApplicationStatus,
                 ViewAnalytics,
                 SendMessage, ViewAllMessages,
+                ViewAvailability,
             ].into_iter().collect()
         },
         Role::Employer => {
@@ -99,6 +107,7 @@ pub fn get_role_permissions(role: &Role) -> HashSet<Permission> {
                 ViewOwnProfile, EditOwnProfile,
                 ViewOwnApplications, UpdateApplicationStatus,
                 SendMessage,
+                ViewAvailability,
             ].into_iter().collect()
         },
         Role::Professional => {
@@ -135,7 +144,7 @@ pub async fn require_permission(
         .map_err(|_| StatusCode::UNAUTHORIZED)?;
     
     // Validate token
-    let claims = AuthService::validate_token(token, &state.config.jwt_secret)
+    let claims = AuthService::validate_token(token, state.config.jwt_secret.expose())
         .map_err(|_| StatusCode::UNAUTHORIZED)?;
     
     // Parse user role from claims
@@ -179,7 +188,7 @@ pub async fn require_role(
         .map_err(|_| StatusCode::UNAUTHORIZED)?;
     
     // Validate token
-    let claims = AuthService::validate_token(token, &state.config.jwt_secret)
+    let claims = AuthService::validate_token(token, state.config.jwt_secret.expose())
         .map_err(|_| StatusCode::UNAUTHORIZED)?;
     
     // Parse user role from claims
@@ -0,0 +1,74 @@
+use axum::{
+    extract::Request,
+    http::HeaderValue,
+    middleware::Next,
+    response::Response,
+};
+use uuid::Uuid;
+
+/// Header carrying the request's correlation ID, taken from the client when it
+/// sends a usable one and echoed on every response
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest client-supplied request ID that is kept
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Correlation ID of the request being handled, if any
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+fn usable(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'))
+}
+
+/// Give every request a correlation ID that error responses and logs can refer to
+pub async fn request_id_middleware(request: Request, next: Next) -> Response {
+    let id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| usable(id))
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let mut response = REQUEST_ID.scope(id.clone(), next.run(request)).await;
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+/// Run `f` as if handling the request with this ID
+#[cfg(test)]
+pub async fn with_request_id<F: std::future::Future>(id: &str, f: F) -> F::Output {
+    REQUEST_ID.scope(id.to_string(), f).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usable_request_ids() {
+        assert!(usable("3f2b9c1e-8d4a-4c1b-9f0e-1a2b3c4d5e6f"));
+        assert!(usable("edge.req_42"));
+        assert!(!usable(""));
+        assert!(!usable("has spaces"));
+        assert!(!usable("line\nbreak"));
+        assert!(!usable(&"a".repeat(MAX_REQUEST_ID_LEN + 1)));
+    }
+
+    #[tokio::test]
+    async fn test_current_request_id_is_scoped() {
+        assert_eq!(current_request_id(), None);
+        let id = with_request_id("req-1", async { current_request_id() }).await;
+        assert_eq!(id.as_deref(), Some("req-1"));
+    }
+}
@@ -28,7 +28,7 @@ pub async fn supabase_auth_middleware(
         .ok_or(StatusCode::UNAUTHORIZED)?;
 
     // Decode and validate JWT
-    let claims = verify_supabase_jwt(token, &app_state.config.jwt_secret)
+    let claims = verify_supabase_jwt(token, app_state.config.jwt_secret.expose())
         .map_err(|_| StatusCode::UNAUTHORIZED)?;
 
     // Add claims to request extensions
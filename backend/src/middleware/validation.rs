@@ -3,12 +3,13 @@ use axum::{
     http::StatusCode,
     middleware::Next,
     response::{Response, IntoResponse},
-    Json,
 };
 use serde::de::DeserializeOwned;
-use serde_json::json;
+use shared::errors::FieldError;
 use validator::Validate;
 
+use crate::error::AppError;
+
 /// Validation middleware for request bodies
 pub async fn validation_middleware<T>(
     request: Request,
@@ -58,43 +59,38 @@ pub enum ValidationError {
     ValidationFailed(validator::ValidationErrors),
 }
 
+impl From<ValidationError> for AppError {
+    fn from(err: ValidationError) -> Self {
+        match err {
+            ValidationError::InvalidJson(msg) => AppError::BadRequest(format!("Invalid JSON: {}", msg)),
+            ValidationError::ValidationFailed(errors) => AppError::InvalidFields(field_errors(&errors)),
+        }
+    }
+}
+
 impl IntoResponse for ValidationError {
     fn into_response(self) -> Response {
-        let (status, error_message) = match self {
-            ValidationError::InvalidJson(msg) => {
-                (StatusCode::BAD_REQUEST, format!("Invalid JSON: {}", msg))
-            }
-            ValidationError::ValidationFailed(errors) => {
-                let error_messages = format_validation_errors(&errors);
-                (StatusCode::BAD_REQUEST, error_messages)
-            }
-        };
-
-        let body = json!({
-            "error": error_message,
-            "status": "error",
-            "code": status.as_u16(),
-        });
-
-        (status, Json(body)).into_response()
+        AppError::from(self).into_response()
     }
 }
 
-/// Format validation errors into a readable string
-fn format_validation_errors(errors: &validator::ValidationErrors) -> String {
-    let mut messages = Vec::new();
-    
-    for (field, field_errors) in errors.field_errors() {
-        for error in field_errors {
-            let message = match &error.message {
-                Some(msg) => format!("{}: {}", field, msg),
-                None => format!("{}: validation failed", field),
-            };
-            messages.push(message);
-        }
-    }
-    
-    messages.join(", ")
+/// Each failed field with its messages, in field order
+pub(crate) fn field_errors(errors: &validator::ValidationErrors) -> Vec<FieldError> {
+    let mut fields: Vec<_> = errors.field_errors().into_iter().collect();
+    fields.sort_by_key(|(field, _)| *field);
+
+    fields
+        .into_iter()
+        .flat_map(|(field, field_errors)| {
+            field_errors.iter().map(move |error| FieldError {
+                field: field.to_string(),
+                message: match &error.message {
+                    Some(msg) => msg.to_string(),
+                    None => format!("Failed the {} check", error.code),
+                },
+            })
+        })
+        .collect()
 }
 
 /// Australian-specific validation functions
@@ -135,11 +131,15 @@ pub mod validators {
     
     /// Validate Australian Business Number (ABN)
     pub fn validate_abn(abn: &str) -> Result<(), ValidationError> {
-        let cleaned = abn.chars().filter(|c| c.is_numeric()).collect::<String>();
+        let cleaned = abn.chars().filter(|c| c.is_ascii_digit()).collect::<String>();
         
         if cleaned.len() != 11 {
             return Err(ValidationError::new("invalid_abn_length"));
         }
+        // The checksum subtracts 1 from the first digit, which is never 0
+        if cleaned.starts_with('0') {
+            return Err(ValidationError::new("invalid_abn_checksum"));
+        }
         
         // ABN validation algorithm
         let digits: Vec<u32> = cleaned.chars()
@@ -245,4 +245,19 @@ pub async fn request_size_limit_middleware(
     }
     
     Ok(next.run(request).await)
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::validators::validate_abn;
+
+    #[test]
+    fn test_validate_abn() {
+        assert!(validate_abn("51 824 753 556").is_ok());
+        assert!(validate_abn("53004085616").is_ok());
+        assert!(validate_abn("51824753557").is_err());
+        assert!(validate_abn("5182475355").is_err());
+        // A leading zero can't pass the checksum and used to underflow
+        assert!(validate_abn("01824753556").is_err());
+        assert!(validate_abn("٥1824753556").is_err());
+    }
+}
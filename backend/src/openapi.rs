@@ -0,0 +1,741 @@
+//! The v1 routes and their OpenAPI 3 description.
+//!
+//! Each operation is listed once, with its handler and the shared request and
+//! response types it uses. `api_v1_routes()` mounts the handlers from the same list
+//! the document is built from, so a route can't go undocumented. Their schemas come from `schemars`, which reads the
+//! `#[validate(...)]` attributes, so the document carries the same length, range
+//! and pattern constraints the handlers enforce. Bodies and responses typed in the
+//! backend only are described as plain JSON objects.
+
+use axum::{
+    extract::DefaultBodyLimit,
+    handler::Handler,
+    middleware as axum_middleware,
+    routing::{self, MethodRouter},
+    Router,
+};
+use schemars::{
+    gen::{SchemaGenerator, SchemaSettings},
+    schema::{ObjectValidation, Schema},
+    JsonSchema,
+};
+use serde_json::{json, Map, Value};
+use shared::{
+    availability::{AvailabilityRequest, AvailabilitySlot},
+    errors::{ProblemDetails, PROBLEM_JSON},
+    invoices::CreateInvoiceRequest,
+    job_templates::{CreateJobTemplateRequest, RepostDates, TemplateFromJobRequest, UpdateJobTemplateRequest},
+    shifts::ShiftSchedule,
+    timesheets::TimesheetEntry,
+    types::{
+        ApplicationFilters, CreateApplicationRequest, CreateJobRequest, CursorParams, Job, JobFilters,
+        ListResponse, SearchRequest, UpdateApplicationRequest, User,
+    },
+    validation::{ValidatedCreateJobRequest, ValidatedJobSearchRequest},
+    webhooks::{CreateWebhookEndpointRequest, UpdateWebhookEndpointRequest, WebhookPayload},
+};
+use std::sync::OnceLock;
+
+use crate::{
+    handlers::{
+        applications, audit, auth, availability, docs, enhanced_jobs, invoices, job_templates, jobs, local_auth,
+        matching, messages, notifications, pay, professional_profiles, search, shifts, tasks, timesheets, uploads,
+        users, webhooks,
+    },
+    middleware::{self, CachePolicy},
+    services::upload_service::MAX_UPLOAD_BYTES,
+    AppState,
+};
+
+/// Where the v1 routes are mounted
+pub const API_V1_PREFIX: &str = "/api/v1";
+
+type SchemaFn = fn(&mut SchemaGenerator) -> Schema;
+
+/// A `$ref` to `T` in `components/schemas`
+fn reference<T: JsonSchema>(gen: &mut SchemaGenerator) -> Schema {
+    gen.subschema_for::<T>()
+}
+
+/// `T`'s own schema, for splitting query structs into parameters
+fn inline<T: JsonSchema>(gen: &mut SchemaGenerator) -> Schema {
+    T::json_schema(gen)
+}
+
+enum Body {
+    Json(Option<SchemaFn>),
+    Multipart,
+}
+
+/// One method on one path
+struct Operation {
+    method: &'static str,
+    path: &'static str,
+    handler: MethodRouter<AppState>,
+    tag: &'static str,
+    summary: &'static str,
+    public: bool,
+    query: Vec<SchemaFn>,
+    body: Option<Body>,
+    status: u16,
+    response: Option<SchemaFn>,
+    media_type: &'static str,
+    cache_control: Option<&'static str>,
+    if_match: bool,
+}
+
+impl Operation {
+    fn new(
+        method: &'static str,
+        path: &'static str,
+        handler: MethodRouter<AppState>,
+        tag: &'static str,
+        summary: &'static str,
+    ) -> Self {
+        Self {
+            method,
+            path,
+            handler,
+            tag,
+            summary,
+            public: false,
+            query: Vec::new(),
+            body: None,
+            status: 200,
+            response: None,
+            media_type: "application/json",
+            cache_control: None,
+            if_match: false,
+        }
+    }
+
+    fn get<H: Handler<T, AppState>, T: 'static>(path: &'static str, handler: H, tag: &'static str, summary: &'static str) -> Self {
+        Self::new("get", path, routing::get(handler), tag, summary)
+    }
+
+    fn post<H: Handler<T, AppState>, T: 'static>(path: &'static str, handler: H, tag: &'static str, summary: &'static str) -> Self {
+        Self::new("post", path, routing::post(handler), tag, summary)
+    }
+
+    fn put<H: Handler<T, AppState>, T: 'static>(path: &'static str, handler: H, tag: &'static str, summary: &'static str) -> Self {
+        Self::new("put", path, routing::put(handler), tag, summary)
+    }
+
+    fn delete<H: Handler<T, AppState>, T: 'static>(path: &'static str, handler: H, tag: &'static str, summary: &'static str) -> Self {
+        Self::new("delete", path, routing::delete(handler), tag, summary).no_content()
+    }
+
+    /// Needs no bearer token
+    fn public(mut self) -> Self {
+        self.public = true;
+        self
+    }
+
+    /// Query parameters, one per field of `T`
+    fn query<T: JsonSchema>(mut self) -> Self {
+        self.query.push(inline::<T>);
+        self
+    }
+
+    fn body<T: JsonSchema>(mut self) -> Self {
+        self.body = Some(Body::Json(Some(reference::<T>)));
+        self
+    }
+
+    /// A JSON body typed in the backend only
+    fn json_body(mut self) -> Self {
+        self.body = Some(Body::Json(None));
+        self
+    }
+
+    fn multipart(mut self) -> Self {
+        self.body = Some(Body::Multipart);
+        self
+    }
+
+    /// Accepts bodies up to `bytes` instead of axum's 2 MB default
+    fn body_limit(mut self, bytes: usize) -> Self {
+        self.handler = self.handler.layer(DefaultBodyLimit::max(bytes));
+        self
+    }
+
+    fn returns<T: JsonSchema>(mut self) -> Self {
+        self.response = Some(reference::<T>);
+        self
+    }
+
+    fn created(mut self) -> Self {
+        self.status = 201;
+        self
+    }
+
+    /// Queued rather than done
+    fn accepted(mut self) -> Self {
+        self.status = 202;
+        self
+    }
+
+    fn no_content(mut self) -> Self {
+        self.status = 204;
+        self
+    }
+
+    fn produces(mut self, media_type: &'static str) -> Self {
+        self.media_type = media_type;
+        self
+    }
+
+    /// Tagged with an ETag and `policy`, and revalidated with `If-None-Match`
+    /// (middleware::http_cache)
+    fn cached(mut self, policy: CachePolicy) -> Self {
+        self.cache_control = Some(policy.0);
+        self.handler = self
+            .handler
+            .layer(axum_middleware::from_fn_with_state(policy, middleware::conditional_get));
+        self
+    }
+
+    /// Refused with 412 if `If-Match` names an outdated ETag
+    fn if_match(mut self) -> Self {
+        self.if_match = true;
+        self
+    }
+
+    fn to_json(&self, gen: &mut SchemaGenerator) -> Value {
+        let mut parameters: Vec<Value> = path_parameters(self.path);
+        for query in &self.query {
+            parameters.extend(query_parameters(query(gen)));
+        }
+        // Signed-in POSTs can be retried safely with a key (middleware::idempotency)
+        if self.method == "post" && !self.public {
+            parameters.push(json!({ "$ref": "#/components/parameters/IdempotencyKey" }));
+        }
+        if self.cache_control.is_some() {
+            parameters.push(json!({ "$ref": "#/components/parameters/IfNoneMatch" }));
+        }
+        if self.if_match {
+            parameters.push(json!({ "$ref": "#/components/parameters/IfMatch" }));
+        }
+
+        let mut operation = json!({
+            "tags": [self.tag],
+            "summary": self.summary,
+            "operationId": operation_id(self.method, self.path),
+            "responses": self.responses(gen),
+        });
+        if !parameters.is_empty() {
+            operation["parameters"] = Value::Array(parameters);
+        }
+        if let Some(body) = &self.body {
+            let content = match body {
+                Body::Json(schema) => json!({ "application/json": { "schema": schema_or_object(*schema, gen) } }),
+                Body::Multipart => json!({
+                    "multipart/form-data": {
+                        "schema": {
+                            "type": "object",
+                            "properties": { "file": { "type": "string", "format": "binary" } },
+                            "required": ["file"]
+                        }
+                    }
+                }),
+            };
+            operation["requestBody"] = json!({ "required": true, "content": content });
+        }
+        if self.public {
+            operation["security"] = json!([]);
+        }
+        operation
+    }
+
+    fn responses(&self, gen: &mut SchemaGenerator) -> Value {
+        let problem = json!({ "$ref": "#/components/responses/Problem" });
+        let mut responses = Map::new();
+        let success = match self.status {
+            204 => json!({ "description": "No content" }),
+            status => {
+                let schema = match self.media_type {
+                    "application/json" => schema_or_object(self.response, gen),
+                    _ => json!({ "type": "string" }),
+                };
+                json!({
+                    "description": match status {
+                        201 => "Created",
+                        202 => "Accepted",
+                        _ => "OK",
+                    },
+                    "content": { self.media_type: { "schema": schema } }
+                })
+            }
+        };
+        responses.insert(self.status.to_string(), success);
+        if let Some(cache_control) = self.cache_control {
+            responses[&self.status.to_string()]["headers"] = json!({
+                "ETag": { "schema": { "type": "string" } },
+                "Cache-Control": { "schema": { "type": "string", "example": cache_control } }
+            });
+            responses.insert("304".to_string(), json!({ "description": "Not modified since the ETag in If-None-Match" }));
+        }
+        if self.if_match {
+            responses[&self.status.to_string()]["headers"] = json!({ "ETag": { "schema": { "type": "string" } } });
+            responses.insert("412".to_string(), problem.clone());
+        }
+        if !self.public {
+            responses.insert("401".to_string(), problem.clone());
+        }
+        responses.insert("default".to_string(), problem);
+        Value::Object(responses)
+    }
+}
+
+fn schema_or_object(schema: Option<SchemaFn>, gen: &mut SchemaGenerator) -> Value {
+    match schema {
+        Some(schema) => schema_json(schema(gen)),
+        None => json!({ "type": "object" }),
+    }
+}
+
+fn schema_json(schema: Schema) -> Value {
+    serde_json::to_value(schema).unwrap_or_else(|_| json!({}))
+}
+
+/// e.g. `get_jobs_id_pay`
+fn operation_id(method: &str, path: &str) -> String {
+    let mut id = method.to_string();
+    for part in path.split(|c: char| !c.is_ascii_alphanumeric()).filter(|part| !part.is_empty()) {
+        id.push('_');
+        id.push_str(part);
+    }
+    id
+}
+
+fn path_parameters(path: &str) -> Vec<Value> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+        .map(|name| {
+            let schema = match name {
+                "id" | "delivery_id" => json!({ "type": "string", "format": "uuid" }),
+                _ => json!({ "type": "string" }),
+            };
+            json!({ "name": name, "in": "path", "required": true, "schema": schema })
+        })
+        .collect()
+}
+
+fn query_parameters(schema: Schema) -> Vec<Value> {
+    let Schema::Object(object) = schema else {
+        return Vec::new();
+    };
+    let Some(validation) = object.object else {
+        return Vec::new();
+    };
+    let ObjectValidation { properties, required, .. } = *validation;
+    properties
+        .into_iter()
+        .map(|(name, property)| {
+            json!({
+                "name": name,
+                "in": "query",
+                "required": required.contains(&name),
+                "schema": schema_json(property),
+            })
+        })
+        .collect()
+}
+
+/// `/jobs/:id/pay` for the documented path `/jobs/{id}/pay`
+fn axum_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix('{').and_then(|name| name.strip_suffix('}')) {
+            Some(name) => format!(":{}", name),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Every operation under `/api/v1`
+fn operations() -> Vec<Operation> {
+    vec![
+        // Auth
+        Operation::post("/auth/login", auth::login, "auth", "Sign in with email and password").public().json_body(),
+        Operation::post("/auth/register", auth::register, "auth", "Create an account").public().json_body().created(),
+        Operation::post("/auth/logout", auth::logout, "auth", "Sign out").public(),
+        Operation::post("/auth/forgot-password", auth::forgot_password, "auth", "Email a password reset code").public().json_body(),
+        Operation::post("/auth/verify-otp", auth::verify_otp, "auth", "Check a one-time code").public().json_body(),
+        Operation::get("/auth/oauth/{provider}", auth::oauth_signin, "auth", "Start an OAuth sign-in").public(),
+        Operation::post("/auth/local/login", local_auth::local_login, "auth", "Sign in without Supabase").public().json_body(),
+        Operation::post("/auth/local/register", local_auth::local_register, "auth", "Create a local account").public().json_body().created(),
+        Operation::post("/auth/local/logout", local_auth::local_logout, "auth", "Sign out of a local session").public(),
+        Operation::get("/auth/local/verify", local_auth::verify_token, "auth", "Check a local access token").public(),
+        Operation::post("/auth/local/refresh", local_auth::refresh_token, "auth", "Refresh a local access token").public(),
+        Operation::post("/auth/refresh", auth::refresh_token, "auth", "Refresh an access token").public(),
+
+        // Jobs
+        Operation::get("/jobs", jobs::list_jobs, "jobs", "List jobs")
+            .public()
+            .query::<JobFilters>()
+            .query::<CursorParams>()
+            .returns::<ListResponse<Job>>()
+            .cached(CachePolicy::JOB_LISTINGS),
+        Operation::post("/jobs/search", jobs::search_jobs, "jobs", "Search jobs").public().body::<SearchRequest>().returns::<ListResponse<Job>>(),
+        Operation::get("/jobs/{id}", jobs::get_job, "jobs", "Get a job").public().returns::<Job>().cached(CachePolicy::JOB_DETAILS),
+        Operation::get("/jobs/{id}/pay", pay::job_pay, "pay", "Award pay estimate for a job's shifts").public(),
+        Operation::get("/jobs/enhanced", enhanced_jobs::list_jobs, "jobs", "List jobs with filters and paging")
+            .public()
+            .query::<JobFilters>()
+            .query::<CursorParams>(),
+        Operation::get("/jobs/enhanced/search", enhanced_jobs::search_jobs, "jobs", "Full-text job search")
+            .public()
+            .query::<JobFilters>()
+            .query::<CursorParams>(),
+        Operation::get("/jobs/enhanced/nearby", enhanced_jobs::find_jobs_nearby, "jobs", "Jobs near a location").public().query::<JobFilters>(),
+        Operation::get("/jobs/enhanced/stats", enhanced_jobs::get_global_job_statistics, "jobs", "Job statistics across the platform")
+            .public()
+            .cached(CachePolicy::JOB_STATISTICS),
+        Operation::post("/jobs", jobs::create_job, "jobs", "Post a job").body::<ValidatedCreateJobRequest>().returns::<Job>().created(),
+        Operation::put("/jobs/{id}", jobs::update_job, "jobs", "Update a job").body::<CreateJobRequest>().returns::<Job>().if_match(),
+        Operation::delete("/jobs/{id}", jobs::delete_job, "jobs", "Delete a job"),
+        Operation::post("/jobs/enhanced", enhanced_jobs::create_job, "jobs", "Post a job as the current user").body::<CreateJobRequest>().created(),
+        Operation::get("/jobs/enhanced/{id}", enhanced_jobs::get_job, "jobs", "Get a job with its posting details"),
+        Operation::delete("/jobs/enhanced/{id}", enhanced_jobs::delete_job, "jobs", "Delete one of your jobs"),
+        Operation::put("/jobs/enhanced/{id}/status", enhanced_jobs::update_job_status, "jobs", "Change a job's status").json_body(),
+        Operation::get("/jobs/enhanced/my", enhanced_jobs::get_my_jobs, "jobs", "Jobs you have posted").query::<CursorParams>(),
+        Operation::get("/jobs/enhanced/my/stats", enhanced_jobs::get_job_statistics, "jobs", "Statistics for the jobs you have posted"),
+        Operation::put("/jobs/{id}/publish-at", enhanced_jobs::schedule_job_publish, "jobs", "Schedule a draft job to go live").json_body(),
+        Operation::get("/jobs/{id}/shifts", shifts::list_job_shifts, "shifts", "A job's shift series"),
+        Operation::put("/jobs/{id}/shifts", shifts::replace_job_shifts, "shifts", "Replace a job's shift series").body::<ShiftSchedule>(),
+        Operation::post("/jobs/{id}/clone", job_templates::clone_job, "job templates", "Copy a job as a draft with its dates moved")
+            .body::<RepostDates>()
+            .created(),
+        Operation::post("/jobs/{id}/template", job_templates::save_job_as_template, "job templates", "Save a job as a template")
+            .body::<TemplateFromJobRequest>()
+            .created(),
+
+        // Search
+        Operation::post("/search/advanced", search::advanced_search, "search", "Search jobs with every filter").public().body::<ValidatedJobSearchRequest>(),
+        Operation::get("/search/quick", search::quick_search, "search", "Quick search by keyword").public(),
+        Operation::get("/search/suggestions", search::search_suggestions, "search", "Search-as-you-type suggestions").public(),
+        Operation::get("/search/trending", search::trending_searches, "search", "Popular searches").public(),
+        Operation::get("/search/recommendations", search::job_recommendations, "search", "Jobs recommended from a search").public(),
+        Operation::get("/search/saved", search::get_saved_searches, "search", "Your saved searches"),
+        Operation::post("/search/save", search::save_search, "search", "Save a search and get its realtime topic").json_body().created(),
+
+        // Users and profiles
+        Operation::get("/users", users::list_users, "users", "List users (admin)").query::<CursorParams>().returns::<ListResponse<User>>(),
+        Operation::get("/users/{id}", users::get_user, "users", "Get a user"),
+        Operation::put("/users/{id}", users::update_user, "users", "Update a user").json_body(),
+        Operation::delete("/users/{id}", users::delete_user, "users", "Delete a user"),
+        Operation::put("/users/{id}/registration", professional_profiles::review_registration, "users", "Record an AHPRA registration check (admin)").json_body(),
+        Operation::put("/users/{id}/role", users::change_user_role, "users", "Change a user's role (admin)").json_body().returns::<User>(),
+        Operation::get("/profile", auth::get_profile, "users", "Your profile"),
+        Operation::put("/profile", auth::update_profile, "users", "Update your profile").json_body(),
+        Operation::get("/profile/professional", professional_profiles::get_professional_profile, "users", "Your professional profile"),
+        Operation::put("/profile/professional", professional_profiles::update_professional_profile, "users", "Update your professional profile").json_body(),
+
+        // Applications
+        Operation::get("/applications", applications::list_applications, "applications", "Your applications")
+            .query::<ApplicationFilters>()
+            .query::<CursorParams>(),
+        Operation::post("/applications", applications::create_application, "applications", "Apply for a job").body::<CreateApplicationRequest>().created(),
+        Operation::get("/applications/{id}", applications::get_application, "applications", "Get an application"),
+        Operation::put("/applications/{id}", applications::update_application, "applications", "Update an application").body::<UpdateApplicationRequest>(),
+        Operation::delete("/applications/{id}", applications::delete_application, "applications", "Delete an application"),
+        Operation::put("/applications/{id}/status", applications::update_application_status, "applications", "Move an application to a new status").json_body(),
+        Operation::put("/applications/{id}/withdraw", applications::withdraw_application, "applications", "Withdraw an application"),
+        Operation::get("/applications/{id}/shifts", shifts::get_application_shifts, "shifts", "Shifts an application covers"),
+        Operation::get("/applications/{id}/pay", pay::application_pay, "pay", "Award pay for an application's shifts"),
+        Operation::get("/applications/{id}/timesheets", timesheets::list_application_timesheets, "timesheets", "Timesheets for an application"),
+        Operation::post("/applications/{id}/timesheets", timesheets::submit_timesheet, "timesheets", "Submit a timesheet")
+            .body::<TimesheetEntry>()
+            .created(),
+        Operation::post("/applications/{id}/invoices", invoices::create_invoice, "invoices", "Invoice approved timesheets")
+            .body::<CreateInvoiceRequest>()
+            .created(),
+        Operation::get("/applications/stats", applications::get_application_stats, "applications", "Application statistics").query::<ApplicationFilters>(),
+        Operation::get("/jobs/{id}/applications", applications::get_job_applications, "applications", "Applications for a job").query::<CursorParams>(),
+        Operation::post("/jobs/{id}/applications/bulk", applications::bulk_application_action, "applications", "Act on several applications at once").json_body(),
+        Operation::get("/users/{id}/applications", applications::get_user_applications, "applications", "A user's applications").query::<CursorParams>(),
+
+        // Timesheets
+        Operation::get("/timesheets/{id}", timesheets::get_timesheet, "timesheets", "Get a timesheet with its history"),
+        Operation::put("/timesheets/{id}", timesheets::update_timesheet, "timesheets", "Edit a timesheet").body::<TimesheetEntry>(),
+        Operation::post("/timesheets/{id}/approve", timesheets::approve_timesheet, "timesheets", "Approve a timesheet"),
+        Operation::post("/timesheets/{id}/dispute", timesheets::dispute_timesheet, "timesheets", "Dispute a timesheet").json_body(),
+        Operation::post("/timesheets/{id}/comments", timesheets::add_timesheet_comment, "timesheets", "Comment on a timesheet").json_body().created(),
+
+        // Invoices
+        Operation::get("/invoices", invoices::list_invoices, "invoices", "Your invoices"),
+        Operation::get("/invoices/{id}", invoices::get_invoice, "invoices", "Get an invoice"),
+        Operation::get("/invoices/{id}/document", invoices::get_invoice_document, "invoices", "Printable tax invoice").produces("text/html"),
+        Operation::put("/invoices/{id}/status", invoices::update_invoice_status, "invoices", "Change an invoice's status").json_body(),
+
+        // Job templates
+        Operation::get("/job-templates", job_templates::list_job_templates, "job templates", "Templates you can post from"),
+        Operation::post("/job-templates", job_templates::create_job_template, "job templates", "Save a posting as a template")
+            .body::<CreateJobTemplateRequest>()
+            .created(),
+        Operation::get("/job-templates/{id}", job_templates::get_job_template, "job templates", "Get a template"),
+        Operation::put("/job-templates/{id}", job_templates::update_job_template, "job templates", "Rename a template or replace its posting")
+            .body::<UpdateJobTemplateRequest>(),
+        Operation::delete("/job-templates/{id}", job_templates::delete_job_template, "job templates", "Delete a template"),
+        Operation::post("/job-templates/{id}/post", job_templates::post_from_template, "job templates", "A posting pre-filled from a template")
+            .body::<RepostDates>(),
+
+        // Messaging
+        Operation::get("/conversations", messages::list_conversations, "messages", "Your conversations"),
+        Operation::post("/conversations", messages::start_conversation, "messages", "Start a conversation").json_body().created(),
+        Operation::get("/conversations/{id}", messages::get_conversation, "messages", "Get a conversation"),
+        Operation::get("/conversations/{id}/messages", messages::list_messages, "messages", "Message history"),
+        Operation::post("/conversations/{id}/messages", messages::send_message, "messages", "Send a message").json_body().created(),
+        Operation::post("/conversations/{id}/read", messages::mark_conversation_read, "messages", "Mark a conversation read"),
+        Operation::get("/messages/unread", messages::unread_count, "messages", "Unread message count"),
+
+        // Notifications
+        Operation::get("/notifications", notifications::list_notifications, "notifications", "Your notifications").query::<CursorParams>(),
+        Operation::post("/notifications/read-all", notifications::mark_all_notifications_read, "notifications", "Mark every notification read"),
+        Operation::post("/notifications/{id}/read", notifications::mark_notification_read, "notifications", "Mark a notification read"),
+        Operation::get("/notifications/preferences", notifications::get_preferences, "notifications", "Your notification preferences"),
+        Operation::put("/notifications/preferences", notifications::update_preferences, "notifications", "Update your notification preferences").json_body(),
+
+        // Availability
+        Operation::get("/availability", availability::list_availability, "availability", "Your availability, recurring slots expanded")
+            .returns::<Vec<AvailabilitySlot>>(),
+        Operation::post("/availability", availability::create_availability, "availability", "Add an availability slot")
+            .body::<AvailabilityRequest>()
+            .returns::<AvailabilitySlot>()
+            .created(),
+        Operation::get("/availability/search", availability::find_available, "availability", "Locums free for a shift"),
+        Operation::get("/availability/{id}", availability::get_availability, "availability", "Get an availability slot").returns::<AvailabilitySlot>(),
+        Operation::put("/availability/{id}", availability::update_availability, "availability", "Replace an availability slot")
+            .body::<AvailabilityRequest>()
+            .returns::<AvailabilitySlot>(),
+        Operation::delete("/availability/{id}", availability::delete_availability, "availability", "Delete an availability slot"),
+
+        // Matching
+        Operation::get("/recommendations", matching::recommendations, "matching", "Jobs that suit you"),
+        Operation::get("/jobs/{id}/candidates", matching::job_candidates, "matching", "Locums who suit a job"),
+
+        // Uploads
+        Operation::post("/uploads", uploads::upload_file, "uploads", "Upload a file")
+            .multipart()
+            .body_limit(MAX_UPLOAD_BYTES + 64 * 1024)
+            .created(),
+        Operation::get("/uploads/{id}", uploads::download_upload, "uploads", "Download an upload").produces("application/octet-stream"),
+
+        // Outbound webhooks
+        Operation::get("/webhooks", webhooks::list_webhook_endpoints, "webhooks", "Webhook endpoints in your tenants"),
+        Operation::post("/webhooks", webhooks::create_webhook_endpoint, "webhooks", "Add a webhook endpoint; the response holds its signing secret")
+            .body::<CreateWebhookEndpointRequest>()
+            .created(),
+        Operation::get("/webhooks/{id}", webhooks::get_webhook_endpoint, "webhooks", "Get a webhook endpoint"),
+        Operation::put("/webhooks/{id}", webhooks::update_webhook_endpoint, "webhooks", "Change, pause or resume a webhook endpoint")
+            .body::<UpdateWebhookEndpointRequest>(),
+        Operation::delete("/webhooks/{id}", webhooks::delete_webhook_endpoint, "webhooks", "Remove a webhook endpoint and its delivery log"),
+        Operation::get("/webhooks/{id}/deliveries", webhooks::list_webhook_deliveries, "webhooks", "A webhook endpoint's delivery log")
+            .query::<CursorParams>(),
+        Operation::post("/webhooks/{id}/deliveries/{delivery_id}/replay", webhooks::replay_webhook_delivery, "webhooks", "Send a logged delivery again")
+            .accepted(),
+
+        // Background tasks
+        Operation::get("/admin/tasks", tasks::list_tasks, "admin", "Background tasks (admin)").query::<CursorParams>(),
+        Operation::get("/admin/tasks/{id}", tasks::get_task, "admin", "Get a background task (admin)"),
+        Operation::post("/admin/tasks/{id}/retry", tasks::retry_task, "admin", "Retry a failed background task (admin)"),
+
+        // Audit trail
+        Operation::get("/admin/audit-events", audit::list_audit_events, "admin", "Search the audit trail (admin)").query::<CursorParams>(),
+        Operation::get("/admin/audit-events/verify", audit::verify_audit_chain, "admin", "Check the audit trail's hash chain (admin)"),
+
+        // Documentation
+        Operation::get("/openapi.json", docs::openapi_json, "docs", "This document").public(),
+        Operation::get("/docs", docs::docs_page, "docs", "Interactive API documentation").public().produces("text/html"),
+    ]
+}
+
+fn build() -> Value {
+    let mut gen = SchemaSettings::openapi3().into_generator();
+    let problem = schema_json(gen.subschema_for::<ProblemDetails>());
+    // No route returns it, but it is the body of every webhook delivery
+    gen.subschema_for::<WebhookPayload>();
+
+    let mut paths = Map::new();
+    for operation in operations() {
+        let item = paths
+            .entry(operation.path.to_string())
+            .or_insert_with(|| json!({}));
+        item[operation.method] = operation.to_json(&mut gen);
+    }
+
+    let schemas: Map<String, Value> = gen
+        .take_definitions()
+        .into_iter()
+        .map(|(name, schema)| (name, schema_json(schema)))
+        .collect();
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Loco Platform API",
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": "Pharmacy locum job marketplace. Errors are RFC 7807 problem details."
+        },
+        "servers": [{ "url": API_V1_PREFIX }],
+        "security": [{ "bearerAuth": [] }],
+        "paths": paths,
+        "components": {
+            "schemas": schemas,
+            "securitySchemes": {
+                "bearerAuth": { "type": "http", "scheme": "bearer", "bearerFormat": "JWT" }
+            },
+            "parameters": {
+                "IdempotencyKey": {
+                    "name": "Idempotency-Key",
+                    "in": "header",
+                    "required": false,
+                    "description": "Makes the request safe to retry. The first response is replayed for 24 hours to repeats with the same key and body; a repeat while the first is still running gets 409, and the same key with a different body gets 422.",
+                    "schema": { "type": "string", "minLength": 1, "maxLength": 255 }
+                },
+                "IfNoneMatch": {
+                    "name": "If-None-Match",
+                    "in": "header",
+                    "required": false,
+                    "description": "ETags of copies the client already has. If one is current the response is 304 with no body.",
+                    "schema": { "type": "string" }
+                },
+                "IfMatch": {
+                    "name": "If-Match",
+                    "in": "header",
+                    "required": false,
+                    "description": "The ETag the client last read. If the job has changed since, the update is refused with 412 and nothing is written.",
+                    "schema": { "type": "string" }
+                }
+            },
+            "responses": {
+                "Problem": {
+                    "description": "Error",
+                    "content": { PROBLEM_JSON: { "schema": problem } }
+                }
+            }
+        }
+    })
+}
+
+/// The v1 routes, each with the handler and layers its operation names
+pub fn routes() -> Router<AppState> {
+    operations()
+        .into_iter()
+        .fold(Router::new(), |router, operation| router.route(&axum_path(operation.path), operation.handler))
+}
+
+/// The v1 API's OpenAPI document, built on first use
+pub fn spec() -> &'static Value {
+    static SPEC: OnceLock<Value> = OnceLock::new();
+    SPEC.get_or_init(build)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_operation_is_routed_once() {
+        let operations = operations();
+        assert!(operations.len() > 100, "only {} operations", operations.len());
+
+        let mut seen = std::collections::HashSet::new();
+        for operation in &operations {
+            assert!(seen.insert((operation.method, operation.path)), "{} {} listed twice", operation.method, operation.path);
+        }
+        // Panics if two operations claim the same method on a path
+        let _ = routes();
+    }
+
+    #[test]
+    fn test_schemas_carry_validation_constraints() {
+        let schemas = &spec()["components"]["schemas"];
+        let job = &schemas["CreateJobRequest"]["properties"];
+        assert_eq!(job["title"]["minLength"], 3);
+        assert_eq!(job["title"]["maxLength"], 100);
+        assert_eq!(job["hourly_rate"]["minimum"], 15.0);
+        assert_eq!(job["postcode"]["pattern"], r"^\d{4}$");
+
+        let search = &schemas["ValidatedJobSearchRequest"]["properties"];
+        assert_eq!(search["radius_km"]["maximum"], 500.0);
+        assert_eq!(schemas["ValidatedCreateJobRequest"]["properties"]["postcode"]["pattern"], r"^\d{4}$");
+    }
+
+    #[test]
+    fn test_axum_path() {
+        assert_eq!(axum_path("/jobs/{id}/pay"), "/jobs/:id/pay");
+        assert_eq!(axum_path("/webhooks/{id}/deliveries/{delivery_id}/replay"), "/webhooks/:id/deliveries/:delivery_id/replay");
+        assert_eq!(operation_id("get", "/jobs/{id}/pay"), "get_jobs_id_pay");
+    }
+
+    #[test]
+    fn test_signed_in_posts_accept_an_idempotency_key() {
+        let key = json!({ "$ref": "#/components/parameters/IdempotencyKey" });
+        let paths = &spec()["paths"];
+        assert!(paths["/jobs"]["post"]["parameters"].as_array().unwrap().contains(&key));
+        assert!(paths["/applications"]["post"]["parameters"].as_array().unwrap().contains(&key));
+        assert!(paths["/auth/login"]["post"].get("parameters").is_none());
+    }
+
+    #[test]
+    fn test_cached_reads_and_conditional_updates() {
+        let paths = &spec()["paths"];
+        let if_none_match = json!({ "$ref": "#/components/parameters/IfNoneMatch" });
+        for path in ["/jobs", "/jobs/{id}", "/jobs/enhanced/stats"] {
+            let get = &paths[path]["get"];
+            assert!(get["parameters"].as_array().unwrap().contains(&if_none_match), "{} takes no If-None-Match", path);
+            assert!(get["responses"]["304"].is_object());
+            assert!(get["responses"]["200"]["headers"]["ETag"].is_object());
+        }
+        assert!(paths["/jobs/search"]["post"]["responses"]["304"].is_null());
+
+        let update = &paths["/jobs/{id}"]["put"];
+        let if_match = json!({ "$ref": "#/components/parameters/IfMatch" });
+        assert!(update["parameters"].as_array().unwrap().contains(&if_match));
+        assert!(update["responses"]["412"].is_object());
+    }
+
+    #[test]
+    fn test_cursor_paged_lists() {
+        let paths = &spec()["paths"];
+        for path in ["/jobs", "/applications", "/users", "/notifications", "/admin/audit-events", "/webhooks/{id}/deliveries"] {
+            let names: Vec<&str> = paths[path]["get"]["parameters"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter_map(|parameter| parameter["name"].as_str())
+                .collect();
+            assert!(names.contains(&"cursor") && names.contains(&"limit"), "{} is not cursor paged", path);
+        }
+
+        let schemas = &spec()["components"]["schemas"];
+        let jobs = paths["/jobs"]["get"]["responses"]["200"]["content"]["application/json"]["schema"]["$ref"]
+            .as_str()
+            .unwrap();
+        let envelope = &schemas[jobs.trim_start_matches("#/components/schemas/")]["properties"];
+        assert!(envelope["items"].is_object());
+        assert!(envelope["next_cursor"].is_object());
+    }
+
+    #[test]
+    fn test_money_is_documented_as_decimal_strings() {
+        let schemas = &spec()["components"]["schemas"];
+        let allowance = &schemas["Allowance"]["properties"];
+        assert_eq!(allowance["unit_price"]["type"], "string");
+        assert_eq!(allowance["quantity"]["type"], "string");
+    }
+
+    #[test]
+    fn test_webhook_payload_is_documented() {
+        let schemas = &spec()["components"]["schemas"];
+        let payload = &schemas["WebhookPayload"]["properties"];
+        assert!(payload["type"].is_object());
+        assert!(payload["tenant_id"].is_object());
+
+        let replay = &spec()["paths"]["/webhooks/{id}/deliveries/{delivery_id}/replay"]["post"];
+        assert!(replay["responses"]["202"].is_object());
+        assert_eq!(replay["parameters"][1]["schema"]["format"], "uuid");
+    }
+}
@@ -3,7 +3,8 @@ use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr};
 use uuid::Uuid;
 
 use crate::entities::application;
-use super::{BaseRepository, PaginationParams, PaginatedResult};
+use shared::types::ListResponse;
+use super::{cursor_page, BaseRepository, Cursor};
 
 #[derive(Clone)]
 pub struct ApplicationRepository;
@@ -20,16 +21,34 @@ impl BaseRepository<application::ActiveModel, application::Model> for Applicatio
         application::Entity::find_by_id(id).one(db).await
     }
 
-    async fn create(&self, db: &DatabaseConnection, model: application::ActiveModel) -> Result<application::Model, DbErr> {
-        model.insert(db).await
+    async fn delete(&self, db: &DatabaseConnection, id: Uuid) -> Result<(), DbErr> {
+        application::Entity::delete_by_id(id).exec(db).await?;
+        Ok(())
     }
+}
 
-    async fn update(&self, db: &DatabaseConnection, model: application::ActiveModel) -> Result<application::Model, DbErr> {
-        model.update(db).await
+impl ApplicationRepository {
+    /// Applications for a job, newest first, one page after `after`
+    pub async fn find_by_job(
+        &self,
+        db: &DatabaseConnection,
+        job_id: Uuid,
+        after: Option<Cursor>,
+        limit: u32,
+    ) -> Result<ListResponse<application::Model>, DbErr> {
+        let select = application::Entity::find().filter(application::Column::JobId.eq(job_id));
+        cursor_page(db, select, after, limit).await
     }
 
-    async fn delete(&self, db: &DatabaseConnection, id: Uuid) -> Result<(), DbErr> {
-        application::Entity::delete_by_id(id).exec(db).await?;
-        Ok(())
+    /// Applications made by a user, newest first, one page after `after`
+    pub async fn find_by_user(
+        &self,
+        db: &DatabaseConnection,
+        user_id: Uuid,
+        after: Option<Cursor>,
+        limit: u32,
+    ) -> Result<ListResponse<application::Model>, DbErr> {
+        let select = application::Entity::find().filter(application::Column::UserId.eq(user_id));
+        cursor_page(db, select, after, limit).await
     }
-}
\ No newline at end of file
+}
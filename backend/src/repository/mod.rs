@@ -9,19 +9,25 @@ pub use application_repository::ApplicationRepository;
 pub use session_repository::SessionRepository;
 
 use async_trait::async_trait;
-use sea_orm::{DatabaseConnection, DbErr};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, SubsecRound, Utc};
+use sea_orm::{
+    sea_query::Expr, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Select,
+};
+use shared::types::{CursorParams, ListResponse};
 use uuid::Uuid;
 
+use crate::{entities, AppError};
+
 /// Base repository trait for common operations
 #[async_trait]
 pub trait BaseRepository<Entity, Model> {
     async fn find_by_id(&self, db: &DatabaseConnection, id: Uuid) -> Result<Option<Model>, DbErr>;
-    async fn create(&self, db: &DatabaseConnection, model: Entity) -> Result<Model, DbErr>;
-    async fn update(&self, db: &DatabaseConnection, model: Entity) -> Result<Model, DbErr>;
     async fn delete(&self, db: &DatabaseConnection, id: Uuid) -> Result<(), DbErr>;
 }
 
-/// Pagination helpers
+/// Page-number paging, for lists with no creation-time order to follow on from
 #[derive(Debug, Clone, serde::Deserialize)]
 pub struct PaginationParams {
     pub page: Option<u64>,
@@ -29,53 +35,306 @@ pub struct PaginationParams {
 }
 
 impl PaginationParams {
-    pub fn new(page: Option<u64>, page_size: Option<u64>) -> Self {
-        Self {
-            page,
-            page_size,
-        }
-    }
-    
     pub fn page(&self) -> u64 {
         self.page.unwrap_or(1).max(1)
     }
     
     pub fn page_size(&self) -> u64 {
-        self.page_size.unwrap_or(20).min(100).max(1)
+        self.page_size.unwrap_or(20).clamp(1, 100)
     }
-    
-    pub fn offset(&self) -> u64 {
-        (self.page() - 1) * self.page_size()
-    }
-}
-
-#[derive(Debug, Clone)]
-pub struct PaginatedResult<T> {
-    pub items: Vec<T>,
-    pub total_count: u64,
-    pub page: u64,
-    pub page_size: u64,
-    pub total_pages: u64,
-    pub has_next: bool,
-    pub has_prev: bool,
-}
-
-impl<T> PaginatedResult<T> {
-    pub fn new(items: Vec<T>, total_count: u64, pagination: PaginationParams) -> Self {
-        let page = pagination.page();
-        let page_size = pagination.page_size();
-        let total_pages = (total_count + page_size - 1) / page_size;
-        let has_next = page < total_pages;
-        let has_prev = page > 1;
-        
+}
+
+/// Position in a list ordered newest first on (created_at, id); the id orders
+/// rows created in the same microsecond
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl Cursor {
+    pub fn new(created_at: impl Into<DateTime<Utc>>, id: Uuid) -> Self {
         Self {
-            items,
-            total_count,
-            page,
-            page_size,
-            total_pages,
-            has_next,
-            has_prev,
+            // The precision Postgres stores and the cursor encodes
+            created_at: created_at.into().trunc_subsecs(6),
+            id,
         }
     }
-}
\ No newline at end of file
+
+    /// Opaque to clients: URL-safe base64 of `<created_at micros>:<id>`
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!("{}:{}", self.created_at.timestamp_micros(), self.id))
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let decoded = String::from_utf8(URL_SAFE_NO_PAD.decode(cursor).ok()?).ok()?;
+        let (micros, id) = decoded.split_once(':')?;
+        Some(Self {
+            created_at: DateTime::from_timestamp_micros(micros.parse().ok()?)?,
+            id: id.parse().ok()?,
+        })
+    }
+
+    /// Where the requested page starts; `None` for the first page
+    pub fn from_params(params: &CursorParams) -> Result<Option<Self>, AppError> {
+        params
+            .cursor
+            .as_deref()
+            .map(|cursor| {
+                Self::decode(cursor).ok_or_else(|| {
+                    AppError::Validation("cursor: Not a cursor from this list; start again without one".to_string())
+                })
+            })
+            .transpose()
+    }
+}
+
+/// An entity listed newest first with cursor paging
+pub trait Keyset: EntityTrait {
+    /// When the row was created
+    fn created_at_column() -> Self::Column;
+    fn id_column() -> Self::Column;
+    fn cursor(model: &Self::Model) -> Cursor;
+}
+
+/// One page of `select` after `after`, newest first. Fetches one row beyond the
+/// limit to learn whether another page follows.
+pub async fn cursor_page<E, C>(
+    db: &C,
+    select: Select<E>,
+    after: Option<Cursor>,
+    limit: u32,
+) -> Result<ListResponse<E::Model>, DbErr>
+where
+    E: Keyset,
+    C: ConnectionTrait,
+{
+    let (created_at, id) = (E::created_at_column(), E::id_column());
+    let mut select = select;
+    if let Some(after) = after {
+        select = select.filter(
+            Expr::tuple([Expr::col(created_at).into(), Expr::col(id).into()])
+                .lt(Expr::tuple([Expr::val(after.created_at).into(), Expr::val(after.id).into()])),
+        );
+    }
+    let rows = select
+        .order_by_desc(created_at)
+        .order_by_desc(id)
+        .limit(u64::from(limit) + 1)
+        .all(db)
+        .await?;
+    Ok(into_page(rows, limit, E::cursor))
+}
+
+/// Cursor paging for lists held in memory
+pub fn cursor_page_of<T>(
+    mut items: Vec<T>,
+    after: Option<Cursor>,
+    limit: u32,
+    position: impl Fn(&T) -> Cursor,
+) -> ListResponse<T> {
+    let key = |item: &T| {
+        let cursor = position(item);
+        (cursor.created_at, cursor.id)
+    };
+    items.sort_by_key(|item| std::cmp::Reverse(key(item)));
+    if let Some(after) = after {
+        items.retain(|item| key(item) < (after.created_at, after.id));
+    }
+    items.truncate(limit as usize + 1);
+    into_page(items, limit, position)
+}
+
+/// Trim a fetch of up to `limit + 1` rows to a page, with a cursor if more follow
+fn into_page<T>(mut rows: Vec<T>, limit: u32, position: impl Fn(&T) -> Cursor) -> ListResponse<T> {
+    let has_more = rows.len() > limit as usize;
+    rows.truncate(limit as usize);
+    let next_cursor = if has_more {
+        rows.last().map(|last| position(last).encode())
+    } else {
+        None
+    };
+    ListResponse::new(rows, next_cursor)
+}
+
+impl Keyset for entities::job::Entity {
+    fn created_at_column() -> Self::Column {
+        entities::job::Column::CreatedAt
+    }
+
+    fn id_column() -> Self::Column {
+        entities::job::Column::Id
+    }
+
+    fn cursor(model: &Self::Model) -> Cursor {
+        Cursor::new(model.created_at, model.id)
+    }
+}
+
+impl Keyset for entities::application::Entity {
+    fn created_at_column() -> Self::Column {
+        entities::application::Column::AppliedAt
+    }
+
+    fn id_column() -> Self::Column {
+        entities::application::Column::Id
+    }
+
+    fn cursor(model: &Self::Model) -> Cursor {
+        Cursor::new(model.applied_at, model.id)
+    }
+}
+
+impl Keyset for entities::user::Entity {
+    fn created_at_column() -> Self::Column {
+        entities::user::Column::CreatedAt
+    }
+
+    fn id_column() -> Self::Column {
+        entities::user::Column::Id
+    }
+
+    fn cursor(model: &Self::Model) -> Cursor {
+        Cursor::new(model.created_at, model.id)
+    }
+}
+
+impl Keyset for entities::notification::Entity {
+    fn created_at_column() -> Self::Column {
+        entities::notification::Column::CreatedAt
+    }
+
+    fn id_column() -> Self::Column {
+        entities::notification::Column::Id
+    }
+
+    fn cursor(model: &Self::Model) -> Cursor {
+        Cursor::new(model.created_at, model.id)
+    }
+}
+
+impl Keyset for entities::audit_event::Entity {
+    fn created_at_column() -> Self::Column {
+        entities::audit_event::Column::CreatedAt
+    }
+
+    fn id_column() -> Self::Column {
+        entities::audit_event::Column::Id
+    }
+
+    fn cursor(model: &Self::Model) -> Cursor {
+        Cursor::new(model.created_at, model.id)
+    }
+}
+
+impl Keyset for entities::background_task::Entity {
+    fn created_at_column() -> Self::Column {
+        entities::background_task::Column::CreatedAt
+    }
+
+    fn id_column() -> Self::Column {
+        entities::background_task::Column::Id
+    }
+
+    fn cursor(model: &Self::Model) -> Cursor {
+        Cursor::new(model.created_at, model.id)
+    }
+}
+
+impl Keyset for entities::webhook_delivery::Entity {
+    fn created_at_column() -> Self::Column {
+        entities::webhook_delivery::Column::CreatedAt
+    }
+
+    fn id_column() -> Self::Column {
+        entities::webhook_delivery::Column::Id
+    }
+
+    fn cursor(model: &Self::Model) -> Cursor {
+        Cursor::new(model.created_at, model.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Row {
+        created_at: DateTime<Utc>,
+        id: Uuid,
+    }
+
+    fn position(row: &Row) -> Cursor {
+        Cursor::new(row.created_at, row.id)
+    }
+
+    fn rows(count: i64) -> Vec<Row> {
+        let start = Utc::now();
+        (0..count)
+            .map(|minutes| Row {
+                created_at: start - chrono::Duration::minutes(minutes),
+                id: Uuid::new_v4(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = Cursor::new(DateTime::from_timestamp_micros(1_700_000_000_123_456).unwrap(), Uuid::new_v4());
+        assert_eq!(Cursor::decode(&cursor.encode()), Some(cursor));
+        assert_eq!(Cursor::decode("not-a-cursor"), None);
+        assert_eq!(Cursor::decode(&URL_SAFE_NO_PAD.encode("12:not-a-uuid")), None);
+
+        let params = CursorParams {
+            cursor: Some("garbage".to_string()),
+            limit: None,
+        };
+        assert!(matches!(Cursor::from_params(&params), Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn test_pages_cover_the_list_once() {
+        let all = rows(7);
+        let mut seen = Vec::new();
+        let mut after = None;
+        loop {
+            let page = cursor_page_of(all.clone(), after, 3, position);
+            seen.extend(page.items);
+            match page.next_cursor {
+                Some(next) => after = Cursor::decode(&next),
+                None => break,
+            }
+        }
+        assert_eq!(seen, all);
+    }
+
+    #[test]
+    fn test_new_rows_do_not_shift_later_pages() {
+        let mut all = rows(6);
+        let first = cursor_page_of(all.clone(), None, 3, position);
+        let after = first.next_cursor.as_deref().and_then(Cursor::decode);
+
+        // Posted while the client was reading the first page
+        all.push(Row {
+            created_at: Utc::now() + chrono::Duration::seconds(1),
+            id: Uuid::new_v4(),
+        });
+        let second = cursor_page_of(all.clone(), after, 3, position);
+        assert_eq!(second.items, all[3..6].to_vec());
+        assert!(!second.has_more);
+    }
+
+    #[test]
+    fn test_ties_on_created_at_are_ordered_by_id() {
+        let created_at = Utc::now();
+        let all: Vec<Row> = (0..4).map(|_| Row { created_at, id: Uuid::new_v4() }).collect();
+        let first = cursor_page_of(all.clone(), None, 2, position);
+        let second = cursor_page_of(all, first.next_cursor.as_deref().and_then(Cursor::decode), 2, position);
+        let mut ids: Vec<Uuid> = first.items.iter().chain(&second.items).map(|row| row.id).collect();
+        assert_eq!(ids.len(), 4);
+        ids.dedup();
+        assert_eq!(ids.len(), 4);
+        assert!(ids.windows(2).all(|pair| pair[0] > pair[1]));
+    }
+}
@@ -3,7 +3,7 @@ use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr};
 use uuid::Uuid;
 
 use crate::entities::session;
-use super::{BaseRepository, PaginationParams, PaginatedResult};
+use super::BaseRepository;
 
 pub struct SessionRepository;
 
@@ -19,14 +19,6 @@ impl BaseRepository<session::ActiveModel, session::Model> for SessionRepository
         session::Entity::find_by_id(id).one(db).await
     }
 
-    async fn create(&self, db: &DatabaseConnection, model: session::ActiveModel) -> Result<session::Model, DbErr> {
-        model.insert(db).await
-    }
-
-    async fn update(&self, db: &DatabaseConnection, model: session::ActiveModel) -> Result<session::Model, DbErr> {
-        model.update(db).await
-    }
-
     async fn delete(&self, db: &DatabaseConnection, id: Uuid) -> Result<(), DbErr> {
         session::Entity::delete_by_id(id).exec(db).await?;
         Ok(())
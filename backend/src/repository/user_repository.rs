@@ -3,7 +3,8 @@ use sea_orm::{entity::*, query::*, DatabaseConnection, DbErr};
 use uuid::Uuid;
 
 use crate::entities::user;
-use super::{BaseRepository, PaginationParams, PaginatedResult};
+use shared::types::ListResponse;
+use super::{cursor_page, BaseRepository, Cursor};
 
 pub struct UserRepository;
 
@@ -19,14 +20,6 @@ impl BaseRepository<user::ActiveModel, user::Model> for UserRepository {
         user::Entity::find_by_id(id).one(db).await
     }
 
-    async fn create(&self, db: &DatabaseConnection, model: user::ActiveModel) -> Result<user::Model, DbErr> {
-        model.insert(db).await
-    }
-
-    async fn update(&self, db: &DatabaseConnection, model: user::ActiveModel) -> Result<user::Model, DbErr> {
-        model.update(db).await
-    }
-
     async fn delete(&self, db: &DatabaseConnection, id: Uuid) -> Result<(), DbErr> {
         user::Entity::delete_by_id(id).exec(db).await?;
         Ok(())
@@ -41,4 +34,24 @@ impl UserRepository {
             .one(db)
             .await
     }
-}
\ No newline at end of file
+
+    /// Users whose email or name contains `search`, newest first, one page after `after`
+    pub async fn find_users(
+        &self,
+        db: &DatabaseConnection,
+        search: Option<&str>,
+        after: Option<Cursor>,
+        limit: u32,
+    ) -> Result<ListResponse<user::Model>, DbErr> {
+        let mut select = user::Entity::find();
+        if let Some(search) = search.map(str::trim).filter(|search| !search.is_empty()) {
+            select = select.filter(
+                Condition::any()
+                    .add(user::Column::Email.contains(search))
+                    .add(user::Column::FirstName.contains(search))
+                    .add(user::Column::LastName.contains(search)),
+            );
+        }
+        cursor_page(db, select, after, limit).await
+    }
+}
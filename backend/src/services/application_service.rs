@@ -1,13 +1,106 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use chrono::Utc;
 use uuid::Uuid;
 use crate::{
-    entities::application::{self, ApplicationStatus, Entity as Application},
-    repository::{ApplicationRepository, BaseRepository},
+    entities::{
+        application::{self, ApplicationStatus, Entity as Application},
+        audit_event::AuditAction,
+        job, user,
+    },
+    services::{
+        audit_service::{AuditEntry, AuditService},
+        metrics::{self, DomainEvent},
+        shift_service,
+        webhook_service::WebhookService,
+    },
+    repository::{ApplicationRepository, BaseRepository, Cursor},
     AppError, AppState,
 };
-use shared::types::{CreateApplicationRequest, UpdateApplicationRequest, ApplicationStatus as SharedApplicationStatus};
-use sea_orm::{DatabaseConnection, EntityTrait, Set, QueryFilter, ColumnTrait};
+use shared::types::{
+    ApplicationStatus as SharedApplicationStatus, CreateApplicationRequest, CursorParams, ListResponse,
+    UpdateApplicationRequest,
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    QueryFilter, Set, TransactionTrait,
+};
+
+/// Maximum number of applications that can be processed in one bulk request
+pub const MAX_BULK_APPLICATIONS: usize = 200;
+
+/// Default message used when rejecting applications in bulk without a template
+pub const DEFAULT_REJECTION_TEMPLATE: &str = "Dear {first_name}, thank you for your interest in the {job_title} position at {pharmacy_name}. Unfortunately your application has not been successful on this occasion.";
+
+/// Outcome of a single item in a bulk application operation
+#[derive(Debug, Clone)]
+pub struct BulkItemOutcome {
+    pub application_id: Uuid,
+    pub application: Option<application::Model>,
+    pub applicant: Option<user::Model>,
+    pub message: Option<String>,
+    pub error: Option<String>,
+}
+
+impl BulkItemOutcome {
+    fn failed(application_id: Uuid, error: impl Into<String>) -> Self {
+        Self {
+            application_id,
+            application: None,
+            applicant: None,
+            message: None,
+            error: Some(error.into()),
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Render a templated applicant message, replacing `{first_name}`, `{last_name}`,
+/// `{job_title}` and `{pharmacy_name}` placeholders
+pub fn render_message_template(template: &str, applicant: Option<&user::Model>, job: &job::Model) -> String {
+    let (first_name, last_name) = applicant
+        .map(|u| (u.first_name.as_str(), u.last_name.as_str()))
+        .unwrap_or(("Applicant", ""));
+
+    template
+        .replace("{first_name}", first_name)
+        .replace("{last_name}", last_name)
+        .replace("{job_title}", &job.title)
+        .replace("{pharmacy_name}", &job.pharmacy_name)
+}
+
+/// Check a status change against the application's transition rules
+pub fn check_status_transition(
+    application: &application::Model,
+    status: &ApplicationStatus,
+) -> Result<(), String> {
+    if application.possible_next_statuses().contains(status) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Cannot change status from {:?} to {:?}",
+            application.status, status
+        ))
+    }
+}
+
+/// A reviewer's status change to an application, ready to save
+fn status_change(
+    existing: &application::Model,
+    status: ApplicationStatus,
+    reviewed_by: Option<Uuid>,
+) -> application::ActiveModel {
+    let mut application: application::ActiveModel = existing.clone().into();
+    if reviewed_by.is_some() {
+        application.reviewed_by = Set(reviewed_by);
+    }
+    application.reviewed_at = Set(Some(Utc::now().into()));
+    application.status = Set(status);
+    application.updated_at = Set(Utc::now().into());
+    application
+}
 
 // Convert from shared ApplicationStatus to entity ApplicationStatus
 fn convert_status(status: SharedApplicationStatus) -> ApplicationStatus {
@@ -48,11 +141,16 @@ impl ApplicationService {
             ..Default::default()
         };
 
-        let repo = ApplicationRepository::new();
-        let application = repo
-            .create(&self.db, new_application)
+        // The application and its shift selection are saved together
+        let txn = self.db.begin().await?;
+        let application = new_application
+            .insert(&txn)
             .await
             .map_err(|e| AppError::Database(format!("Failed to create application: {}", e)))?;
+        shift_service::record_selection(&txn, application.id, application.job_id, &request.shift_ids).await?;
+        txn.commit().await?;
+        metrics::record(DomainEvent::ApplicationSubmitted);
+        WebhookService::new(self.db.clone()).application_created(&application).await;
 
         Ok(application)
     }
@@ -87,7 +185,7 @@ impl ApplicationService {
     ) -> Result<application::Model, AppError> {
         let existing = self.get_application_by_id(id, user_id).await?;
 
-        let mut application: application::ActiveModel = existing.into();
+        let mut application: application::ActiveModel = existing.clone().into();
         
         if let Some(cover_letter) = request.cover_letter {
             application.cover_letter = Set(Some(cover_letter));
@@ -106,12 +204,10 @@ impl ApplicationService {
 
         application.updated_at = Set(Utc::now().into());
 
-        let repo = ApplicationRepository::new();
-        let updated = repo
-            .update(&self.db, application)
-            .await
-            .map_err(|e| AppError::Database(format!("Failed to update application: {}", e)))?;
-
+        let txn = self.db.begin().await?;
+        let updated = self.save_status_change(&txn, &existing, application).await?;
+        txn.commit().await?;
+        self.status_changed(&existing, &updated).await;
         Ok(updated)
     }
 
@@ -133,47 +229,37 @@ impl ApplicationService {
     pub async fn get_applications_for_job(
         &self,
         job_id: Uuid,
-        _pagination: crate::repository::PaginationParams,
+        page: CursorParams,
         _user_id: Uuid,
-    ) -> Result<Vec<application::Model>, AppError> {
-        let applications = Application::find()
-            .filter(application::Column::JobId.eq(job_id))
-            .all(&self.db)
+    ) -> Result<ListResponse<application::Model>, AppError> {
+        let after = Cursor::from_params(&page)?;
+        ApplicationRepository::new()
+            .find_by_job(&self.db, job_id, after, page.limit())
             .await
-            .map_err(|e| AppError::Database(format!("Database error: {}", e)))?;
-        
-        Ok(applications)
+            .map_err(|e| AppError::Database(format!("Database error: {}", e)))
     }
 
     pub async fn get_applications_by_user(
         &self,
         user_id: Uuid,
-        _pagination: crate::repository::PaginationParams,
-    ) -> Result<Vec<application::Model>, AppError> {
-        let applications = Application::find()
-            .filter(application::Column::UserId.eq(user_id))
-            .all(&self.db)
+        page: CursorParams,
+    ) -> Result<ListResponse<application::Model>, AppError> {
+        let after = Cursor::from_params(&page)?;
+        ApplicationRepository::new()
+            .find_by_user(&self.db, user_id, after, page.limit())
             .await
-            .map_err(|e| AppError::Database(format!("Database error: {}", e)))?;
-        
-        Ok(applications)
+            .map_err(|e| AppError::Database(format!("Database error: {}", e)))
     }
 
     pub async fn list_applications(
         &self,
         _filters: crate::handlers::applications::ApplicationFilters,
-        _pagination: crate::repository::PaginationParams,
+        page: CursorParams,
         user_id: Uuid,
-    ) -> Result<Vec<application::Model>, AppError> {
+    ) -> Result<ListResponse<application::Model>, AppError> {
         // For now, just return applications for the user
-        // TODO: Implement proper filtering and pagination
-        let applications = Application::find()
-            .filter(application::Column::UserId.eq(user_id))
-            .all(&self.db)
-            .await
-            .map_err(|e| AppError::Database(format!("Database error: {}", e)))?;
-        
-        Ok(applications)
+        // TODO: Implement proper filtering
+        self.get_applications_by_user(user_id, page).await
     }
 
     pub async fn get_application_statistics(
@@ -233,10 +319,331 @@ impl ApplicationService {
         
         self.update_application(id, request, employer_user_id).await
     }
+
+    /// Update the status of many applications for a job in one go.
+    ///
+    /// Each item is checked against the transition rules and reported
+    /// individually. With `all_or_nothing`, nothing is written unless every
+    /// item passes, and the writes share a single transaction. Changes go
+    /// through the same steps as a single update, shift filling included. A
+    /// rendered message is returned for the applicant's notification and never
+    /// touches the reviewer's notes. Pass `None` as `employer_user_id` to skip
+    /// the job ownership check (admins).
+    pub async fn bulk_update_application_status(
+        &self,
+        job_id: Uuid,
+        application_ids: &[Uuid],
+        status: SharedApplicationStatus,
+        message_template: Option<&str>,
+        all_or_nothing: bool,
+        employer_user_id: Option<Uuid>,
+    ) -> Result<Vec<BulkItemOutcome>, AppError> {
+        let job = self.find_owned_job(job_id, employer_user_id).await?;
+        let target = convert_status(status);
+
+        // Validate every item before writing anything
+        let mut planned = Vec::new();
+        let mut outcomes = Vec::new();
+        for (application_id, found) in self.load_job_applications(job_id, application_ids).await? {
+            match found {
+                None => outcomes.push(BulkItemOutcome::failed(
+                    application_id,
+                    "Application not found for this job",
+                )),
+                Some((existing, applicant)) => match check_status_transition(&existing, &target) {
+                    Err(reason) => outcomes.push(BulkItemOutcome::failed(application_id, reason)),
+                    Ok(()) => {
+                        let message = message_template
+                            .map(|t| render_message_template(t, applicant.as_ref(), &job));
+                        outcomes.push(BulkItemOutcome {
+                            application_id,
+                            application: None,
+                            applicant: applicant.clone(),
+                            message,
+                            error: None,
+                        });
+                        planned.push((outcomes.len() - 1, existing));
+                    }
+                },
+            }
+        }
+
+        if all_or_nothing {
+            if outcomes.iter().any(|o| !o.is_success()) {
+                for (index, _) in &planned {
+                    outcomes[*index].error =
+                        Some("Not applied: another application in the batch failed".to_string());
+                }
+                return Ok(outcomes);
+            }
+
+            let txn = self.db.begin().await?;
+            let mut changes = Vec::with_capacity(planned.len());
+            for (index, existing) in planned {
+                let change = status_change(&existing, target.clone(), employer_user_id);
+                let updated = self.save_status_change(&txn, &existing, change).await?;
+                outcomes[index].application = Some(updated.clone());
+                changes.push((existing, updated));
+            }
+            txn.commit().await?;
+            for (existing, updated) in &changes {
+                self.status_changed(existing, updated).await;
+            }
+        } else {
+            // Each change commits on its own
+            for (index, existing) in planned {
+                let saved: Result<application::Model, AppError> = async {
+                    let txn = self.db.begin().await?;
+                    let change = status_change(&existing, target.clone(), employer_user_id);
+                    let updated = self.save_status_change(&txn, &existing, change).await?;
+                    txn.commit().await?;
+                    Ok(updated)
+                }
+                .await;
+                match saved {
+                    Ok(updated) => {
+                        self.status_changed(&existing, &updated).await;
+                        outcomes[index].application = Some(updated);
+                    }
+                    Err(e) => {
+                        tracing::error!("Bulk status update failed for {}: {}", outcomes[index].application_id, e);
+                        outcomes[index].error = Some(match e {
+                            AppError::Validation(reason) => reason,
+                            _ => "Failed to update application".to_string(),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(outcomes)
+    }
+
+    /// Collect applications (with applicant details) for export from a job
+    pub async fn export_job_applications(
+        &self,
+        job_id: Uuid,
+        application_ids: &[Uuid],
+        employer_user_id: Option<Uuid>,
+    ) -> Result<Vec<BulkItemOutcome>, AppError> {
+        self.find_owned_job(job_id, employer_user_id).await?;
+
+        let outcomes: Vec<BulkItemOutcome> = self
+            .load_job_applications(job_id, application_ids)
+            .await?
+            .into_iter()
+            .map(|(application_id, found)| match found {
+                None => BulkItemOutcome::failed(application_id, "Application not found for this job"),
+                Some((existing, applicant)) => BulkItemOutcome {
+                    application_id,
+                    application: Some(existing),
+                    applicant,
+                    message: None,
+                    error: None,
+                },
+            })
+            .collect();
+
+        let exported: Vec<Uuid> = outcomes
+            .iter()
+            .filter(|outcome| outcome.is_success())
+            .map(|outcome| outcome.application_id)
+            .collect();
+        AuditService::new(self.db.clone())
+            .record(
+                AuditEntry::new(AuditAction::DataExported, "job", job_id)
+                    .details(serde_json::json!({ "applications": exported })),
+            )
+            .await?;
+
+        Ok(outcomes)
+    }
+
+    /// Save a status change on the caller's transaction along with everything
+    /// that must commit with it: accepting fills the applicant's shifts, failing if
+    /// they have been taken meanwhile, and the change goes in the audit trail
+    async fn save_status_change(
+        &self,
+        txn: &impl ConnectionTrait,
+        existing: &application::Model,
+        application: application::ActiveModel,
+    ) -> Result<application::Model, AppError> {
+        let updated = application
+            .update(txn)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to update application: {}", e)))?;
+        if existing.status == updated.status {
+            return Ok(updated);
+        }
+
+        if updated.status == ApplicationStatus::Accepted {
+            shift_service::fill_for_application(txn, &updated).await?;
+        }
+        AuditService::new(self.db.clone())
+            .record_in(
+                txn,
+                AuditEntry::new(AuditAction::ApplicationStatusChanged, "application", updated.id)
+                    .change(existing, &updated),
+            )
+            .await?;
+        Ok(updated)
+    }
+
+    /// Follow up a committed status change: metrics and the employer's webhooks
+    async fn status_changed(&self, before: &application::Model, after: &application::Model) {
+        if before.status == after.status {
+            return;
+        }
+        if after.status == ApplicationStatus::Accepted {
+            metrics::record(DomainEvent::OfferAccepted);
+        }
+        WebhookService::new(self.db.clone()).application_status_changed(before, after).await;
+    }
+
+    /// Find a job and make sure the employer owns it
+    async fn find_owned_job(
+        &self,
+        job_id: Uuid,
+        employer_user_id: Option<Uuid>,
+    ) -> Result<job::Model, AppError> {
+        let job = job::Entity::find_by_id(job_id)
+            .filter(job::Column::DeletedAt.is_null())
+            .one(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Database error: {}", e)))?
+            .ok_or(AppError::NotFound)?;
+
+        if let Some(uid) = employer_user_id {
+            if job.created_by != uid {
+                return Err(AppError::Forbidden);
+            }
+        }
+
+        Ok(job)
+    }
+
+    /// Load the requested applications of a job, keeping request order and
+    /// dropping duplicate IDs. Missing or foreign applications map to `None`.
+    async fn load_job_applications(
+        &self,
+        job_id: Uuid,
+        application_ids: &[Uuid],
+    ) -> Result<Vec<(Uuid, Option<(application::Model, Option<user::Model>)>)>, AppError> {
+        let mut seen = HashSet::new();
+        let ids: Vec<Uuid> = application_ids
+            .iter()
+            .copied()
+            .filter(|id| seen.insert(*id))
+            .collect();
+
+        let mut found: HashMap<Uuid, (application::Model, Option<user::Model>)> = Application::find()
+            .filter(application::Column::Id.is_in(ids.clone()))
+            .filter(application::Column::JobId.eq(job_id))
+            .find_also_related(user::Entity)
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Database error: {}", e)))?
+            .into_iter()
+            .map(|(app, applicant)| (app.id, (app, applicant)))
+            .collect();
+
+        Ok(ids.into_iter().map(|id| (id, found.remove(&id))).collect())
+    }
 }
 
 impl From<&AppState> for ApplicationService {
     fn from(state: &AppState) -> Self {
         Self::new(state.db.clone())
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn test_application(status: ApplicationStatus) -> application::Model {
+        let now = Utc::now();
+        application::Model {
+            id: Uuid::new_v4(),
+            job_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            cover_letter: None,
+            resume_url: None,
+            availability_note: None,
+            experience_years: Some(3),
+            registration_number: None,
+            preferred_contact_method: None,
+            status,
+            reviewer_notes: None,
+            interview_scheduled_at: None,
+            reviewed_at: None,
+            reviewed_by: None,
+            applied_at: now.into(),
+            updated_at: now.into(),
+        }
+    }
+
+    fn test_job() -> job::Model {
+        let now = Utc::now();
+        job::Model {
+            id: Uuid::new_v4(),
+            title: "Locum Pharmacist".to_string(),
+            description: "Weekend cover".to_string(),
+            pharmacy_name: "Glenelg Beach Pharmacy".to_string(),
+            hourly_rate: Decimal::new(6500, 2),
+            address: "15 Jetty Road".to_string(),
+            suburb: "Glenelg".to_string(),
+            postcode: "5045".to_string(),
+            state: "SA".to_string(),
+            latitude: None,
+            longitude: None,
+            start_date: now.into(),
+            end_date: now.into(),
+            start_time: "09:00".to_string(),
+            end_time: "17:00".to_string(),
+            job_type: job::JobTypeDb::Pharmacist,
+            status: job::JobStatusDb::Active,
+            is_urgent: false,
+            requirements_text: None,
+            benefits_text: None,
+            contact_email: None,
+            contact_phone: None,
+            application_deadline: None,
+            publish_at: None,
+            expiry_warning_sent_at: None,
+            view_count: 0,
+            application_count: 0,
+            created_by: Uuid::new_v4(),
+            created_at: now.into(),
+            updated_at: now.into(),
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn test_status_transition_rules() {
+        let reviewing = test_application(ApplicationStatus::Reviewing);
+        assert!(check_status_transition(&reviewing, &ApplicationStatus::Shortlisted).is_ok());
+        assert!(check_status_transition(&reviewing, &ApplicationStatus::Rejected).is_ok());
+
+        let pending = test_application(ApplicationStatus::Pending);
+        assert!(check_status_transition(&pending, &ApplicationStatus::Shortlisted).is_err());
+
+        let withdrawn = test_application(ApplicationStatus::Withdrawn);
+        assert!(check_status_transition(&withdrawn, &ApplicationStatus::Rejected).is_err());
+    }
+
+    #[test]
+    fn test_render_message_template() {
+        let job = test_job();
+        let message = render_message_template(
+            "Hi {first_name}, the {job_title} role at {pharmacy_name} has been filled.",
+            None,
+            &job,
+        );
+        assert_eq!(
+            message,
+            "Hi Applicant, the Locum Pharmacist role at Glenelg Beach Pharmacy has been filled."
+        );
+    }
+}
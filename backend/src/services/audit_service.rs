@@ -0,0 +1,514 @@
+use std::time::Duration;
+
+use chrono::{DateTime, SecondsFormat, SubsecRound, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseBackend, DatabaseConnection,
+    EntityTrait, IntoActiveModel, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Statement,
+    TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use shared::types::{CursorParams, ListResponse};
+use uuid::Uuid;
+
+use crate::{
+    entities::{
+        audit_event::{self, AuditAction},
+        audit_outbox, tenant_users,
+    },
+    middleware::audit_context,
+    repository::{cursor_page, Cursor},
+    AppError, AppState,
+};
+
+/// `prev_hash` of the first event in the chain
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Advisory lock serialising appends so every event links to the one before it
+const CHAIN_LOCK_KEY: i64 = 0x6175_6469_7400;
+
+/// Events checked per query when verifying the chain
+const VERIFY_BATCH_SIZE: u64 = 1000;
+
+/// Outbox events appended to the chain per transaction
+const CHAIN_BATCH_SIZE: u64 = 500;
+
+/// How often committed outbox events are appended to the chain
+const CHAIN_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Fields left out of diffs because they change on every write
+const UNAUDITED_FIELDS: &[&str] = &["updated_at"];
+
+/// Fields whose values never reach the audit trail, only the fact they changed
+const REDACTED_FIELDS: &[&str] = &["password_hash"];
+
+const REDACTED: &str = "[redacted]";
+
+/// Only the fields of two JSON objects that differ, as (before, after). Anything
+/// other than a pair of objects is kept whole.
+pub fn diff(before: &Value, after: &Value) -> (Value, Value) {
+    let (Value::Object(old), Value::Object(new)) = (before, after) else {
+        return (before.clone(), after.clone());
+    };
+
+    let mut changed_before = Map::new();
+    let mut changed_after = Map::new();
+    let keys = old.keys().chain(new.keys().filter(|key| !old.contains_key(*key)));
+    for key in keys {
+        if UNAUDITED_FIELDS.contains(&key.as_str()) {
+            continue;
+        }
+        let (was, now) = (old.get(key).unwrap_or(&Value::Null), new.get(key).unwrap_or(&Value::Null));
+        if was == now {
+            continue;
+        }
+        if REDACTED_FIELDS.contains(&key.as_str()) {
+            changed_before.insert(key.clone(), json!(REDACTED));
+            changed_after.insert(key.clone(), json!(REDACTED));
+        } else {
+            changed_before.insert(key.clone(), was.clone());
+            changed_after.insert(key.clone(), now.clone());
+        }
+    }
+    (Value::Object(changed_before), Value::Object(changed_after))
+}
+
+/// JSON with object keys sorted at every level, so the same event always hashes
+/// the same however the database hands its JSON back
+fn canonical_json(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            let fields: Vec<String> = entries
+                .into_iter()
+                .map(|(key, value)| format!("{}:{}", Value::String(key.clone()), canonical_json(value)))
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(canonical_json).collect();
+            format!("[{}]", items.join(","))
+        }
+        scalar => scalar.to_string(),
+    }
+}
+
+/// SHA-256 over everything recorded about an event plus the previous event's hash
+pub fn chain_hash(event: &audit_event::Model) -> String {
+    let content = json!({
+        "seq": event.seq,
+        "actor_id": event.actor_id,
+        "tenant_id": event.tenant_id,
+        "ip": event.ip,
+        "request_id": event.request_id,
+        "action": event.action,
+        "entity_type": event.entity_type,
+        "entity_id": event.entity_id,
+        "before": event.before,
+        "after": event.after,
+        "created_at": event
+            .created_at
+            .with_timezone(&Utc)
+            .to_rfc3339_opts(SecondsFormat::Micros, true),
+        "prev_hash": event.prev_hash,
+    });
+    hex::encode(Sha256::digest(canonical_json(&content).as_bytes()))
+}
+
+/// Why `event` does not continue a chain whose last event had `prev_seq` and `prev_hash`
+fn broken_link(event: &audit_event::Model, prev_seq: i64, prev_hash: &str) -> Option<String> {
+    if event.seq != prev_seq + 1 {
+        Some(format!("Expected event {} but found event {}", prev_seq + 1, event.seq))
+    } else if event.prev_hash != prev_hash {
+        Some("Does not link to the event before it".to_string())
+    } else if chain_hash(event) != event.hash {
+        Some("Contents do not match the recorded hash".to_string())
+    } else {
+        None
+    }
+}
+
+/// Something to write to the audit trail. The actor and client address default
+/// to those of the request being handled.
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    action: AuditAction,
+    entity_type: &'static str,
+    entity_id: Option<String>,
+    actor_id: Option<Uuid>,
+    tenant_id: Option<Uuid>,
+    before: Option<Value>,
+    after: Option<Value>,
+}
+
+impl AuditEntry {
+    pub fn new(action: AuditAction, entity_type: &'static str, entity_id: impl ToString) -> Self {
+        Self {
+            action,
+            entity_type,
+            entity_id: Some(entity_id.to_string()),
+            actor_id: None,
+            tenant_id: None,
+            before: None,
+            after: None,
+        }
+    }
+
+    /// Act as `actor_id` rather than the request's authenticated user, e.g. at login
+    pub fn actor(mut self, actor_id: Uuid) -> Self {
+        self.actor_id = Some(actor_id);
+        self
+    }
+
+    /// Tenant the event belongs to; defaults to the actor's tenant
+    pub fn tenant(mut self, tenant_id: Option<Uuid>) -> Self {
+        self.tenant_id = tenant_id;
+        self
+    }
+
+    /// Record the fields that changed between two versions of a record
+    pub fn change<T: Serialize>(mut self, before: &T, after: &T) -> Self {
+        let (before, after) = diff(&to_value(before), &to_value(after));
+        self.before = Some(before);
+        self.after = Some(after);
+        self
+    }
+
+    /// Record details of an event that did not change a record, such as what was exported
+    pub fn details(mut self, details: Value) -> Self {
+        self.after = Some(details);
+        self
+    }
+}
+
+fn to_value<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct AuditFilters {
+    pub actor_id: Option<Uuid>,
+    pub tenant_id: Option<Uuid>,
+    pub action: Option<AuditAction>,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Result of walking the hash chain from the first event
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainVerification {
+    pub valid: bool,
+    /// Events confirmed intact, in order from the first
+    pub verified: u64,
+    /// Hash of the last intact event; keep a copy elsewhere to detect truncation
+    pub head_hash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broken_at_seq: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub problem: Option<String>,
+}
+
+/// Appends to and reads the audit trail
+#[derive(Clone)]
+pub struct AuditService {
+    db: DatabaseConnection,
+}
+
+impl AuditService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Write an event and append it to the chain straight away
+    pub async fn record(&self, entry: AuditEntry) -> Result<(), AppError> {
+        self.record_in(&self.db, entry).await?;
+        self.chain_pending().await?;
+        Ok(())
+    }
+
+    /// Write an event as part of the caller's transaction, so the change it
+    /// describes and the event commit or roll back together. It waits in the
+    /// outbox until the next `chain_pending` after that transaction commits; no
+    /// chain lock is taken here, however long the caller's transaction runs.
+    pub async fn record_in(&self, txn: &impl ConnectionTrait, entry: AuditEntry) -> Result<(), AppError> {
+        let actor_id = entry.actor_id.or_else(audit_context::current_actor);
+        let tenant_id = match (entry.tenant_id, actor_id) {
+            (Some(tenant_id), _) => Some(tenant_id),
+            (None, Some(actor_id)) => home_tenant(txn, actor_id).await?,
+            (None, None) => None,
+        };
+
+        audit_outbox::Model {
+            id: Uuid::new_v4(),
+            actor_id,
+            tenant_id,
+            ip: audit_context::current_ip(),
+            request_id: crate::middleware::request_id::current_request_id(),
+            action: entry.action,
+            entity_type: entry.entity_type.to_string(),
+            entity_id: entry.entity_id,
+            before: entry.before,
+            after: entry.after,
+            // Stored to the microsecond, so hash exactly what will be read back
+            created_at: Utc::now().trunc_subsecs(6).into(),
+        }
+        .into_active_model()
+        .reset_all()
+        .insert(txn)
+        .await?;
+        Ok(())
+    }
+
+    /// Append committed outbox events to the chain, oldest first. Each batch is a
+    /// short transaction of its own holding the chain lock, so appends are
+    /// serialised without any audited write waiting on another.
+    pub async fn chain_pending(&self) -> Result<u64, AppError> {
+        let mut chained = 0;
+        loop {
+            let txn = self.db.begin().await?;
+            txn.execute(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                "SELECT pg_advisory_xact_lock($1)",
+                [CHAIN_LOCK_KEY.into()],
+            ))
+            .await?;
+
+            let queued = audit_outbox::Entity::find()
+                .order_by_asc(audit_outbox::Column::CreatedAt)
+                .order_by_asc(audit_outbox::Column::Id)
+                .limit(CHAIN_BATCH_SIZE)
+                .all(&txn)
+                .await?;
+            if queued.is_empty() {
+                txn.commit().await?;
+                return Ok(chained);
+            }
+
+            let last = audit_event::Entity::find()
+                .order_by_desc(audit_event::Column::Seq)
+                .one(&txn)
+                .await?;
+            let (mut seq, mut prev_hash) = last
+                .map(|event| (event.seq + 1, event.hash))
+                .unwrap_or_else(|| (1, GENESIS_HASH.to_string()));
+
+            let batch = queued.len() as u64;
+            let ids: Vec<Uuid> = queued.iter().map(|queued| queued.id).collect();
+            for queued in queued {
+                let mut event = queued.into_event(seq, prev_hash);
+                event.hash = chain_hash(&event);
+                prev_hash = event.hash.clone();
+                seq += 1;
+                event.into_active_model().reset_all().insert(&txn).await?;
+            }
+            audit_outbox::Entity::delete_many()
+                .filter(audit_outbox::Column::Id.is_in(ids))
+                .exec(&txn)
+                .await?;
+            txn.commit().await?;
+
+            chained += batch;
+            if batch < CHAIN_BATCH_SIZE {
+                return Ok(chained);
+            }
+        }
+    }
+
+    /// Events matching the filters, newest first
+    pub async fn list(
+        &self,
+        filters: AuditFilters,
+        page: CursorParams,
+    ) -> Result<ListResponse<audit_event::Model>, AppError> {
+        self.chain_pending().await?;
+        let after = Cursor::from_params(&page)?;
+        // Newest first by when the change was made; `seq` is the order events
+        // joined the chain, which can lag behind a long transaction
+        let mut query = audit_event::Entity::find();
+        if let Some(actor_id) = filters.actor_id {
+            query = query.filter(audit_event::Column::ActorId.eq(actor_id));
+        }
+        if let Some(tenant_id) = filters.tenant_id {
+            query = query.filter(audit_event::Column::TenantId.eq(tenant_id));
+        }
+        if let Some(action) = filters.action {
+            query = query.filter(audit_event::Column::Action.eq(action));
+        }
+        if let Some(entity_type) = filters.entity_type {
+            query = query.filter(audit_event::Column::EntityType.eq(entity_type));
+        }
+        if let Some(entity_id) = filters.entity_id {
+            query = query.filter(audit_event::Column::EntityId.eq(entity_id));
+        }
+        if let Some(from) = filters.from {
+            query = query.filter(audit_event::Column::CreatedAt.gte(from));
+        }
+        if let Some(to) = filters.to {
+            query = query.filter(audit_event::Column::CreatedAt.lt(to));
+        }
+
+        Ok(cursor_page(&self.db, query, after, page.limit()).await?)
+    }
+
+    /// Walk the whole chain, stopping at the first event that was altered, removed
+    /// or inserted out of order
+    pub async fn verify(&self) -> Result<ChainVerification, AppError> {
+        self.chain_pending().await?;
+        let mut prev_seq = 0;
+        let mut prev_hash = GENESIS_HASH.to_string();
+        let mut verified = 0;
+
+        let mut pages = audit_event::Entity::find()
+            .order_by_asc(audit_event::Column::Seq)
+            .paginate(&self.db, VERIFY_BATCH_SIZE);
+        while let Some(batch) = pages.fetch_and_next().await? {
+            for event in batch {
+                if let Some(problem) = broken_link(&event, prev_seq, &prev_hash) {
+                    return Ok(ChainVerification {
+                        valid: false,
+                        verified,
+                        head_hash: prev_hash,
+                        broken_at_seq: Some(event.seq),
+                        problem: Some(problem),
+                    });
+                }
+                prev_seq = event.seq;
+                prev_hash = event.hash;
+                verified += 1;
+            }
+        }
+
+        Ok(ChainVerification {
+            valid: true,
+            verified,
+            head_hash: prev_hash,
+            broken_at_seq: None,
+            problem: None,
+        })
+    }
+}
+
+/// First tenant the user belongs to
+async fn home_tenant(db: &impl ConnectionTrait, user_id: Uuid) -> Result<Option<Uuid>, AppError> {
+    Ok(tenant_users::Entity::find()
+        .filter(tenant_users::Column::UserId.eq(user_id))
+        .order_by_asc(tenant_users::Column::CreatedAt)
+        .one(db)
+        .await?
+        .map(|membership| membership.tenant_id))
+}
+
+impl From<&AppState> for AuditService {
+    fn from(state: &AppState) -> Self {
+        Self::new(state.db.clone())
+    }
+}
+
+/// Periodically append events committed through `record_in` to the chain
+pub fn spawn_chain_task(service: AuditService) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(CHAIN_INTERVAL);
+
+        loop {
+            interval.tick().await;
+            if let Err(e) = service.chain_pending().await {
+                tracing::error!("Failed to append audit events to the chain: {}", e);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(seq: i64, prev_hash: &str, after: Value) -> audit_event::Model {
+        let mut event = audit_event::Model {
+            id: Uuid::new_v4(),
+            seq,
+            actor_id: Some(Uuid::new_v4()),
+            tenant_id: None,
+            ip: Some("203.0.113.7".to_string()),
+            request_id: Some("req-1".to_string()),
+            action: AuditAction::ApplicationStatusChanged,
+            entity_type: "application".to_string(),
+            entity_id: Some(Uuid::new_v4().to_string()),
+            before: Some(json!({ "status": "Pending" })),
+            after: Some(after),
+            prev_hash: prev_hash.to_string(),
+            hash: String::new(),
+            created_at: Utc::now().trunc_subsecs(6).into(),
+        };
+        event.hash = chain_hash(&event);
+        event
+    }
+
+    #[test]
+    fn test_diff_keeps_changed_fields_and_redacts_secrets() {
+        let before = json!({ "status": "Pending", "email": "a@example.com", "password_hash": "x", "updated_at": 1 });
+        let after = json!({ "status": "Shortlisted", "email": "a@example.com", "password_hash": "y", "updated_at": 2 });
+        let (before, after) = diff(&before, &after);
+        assert_eq!(before, json!({ "status": "Pending", "password_hash": REDACTED }));
+        assert_eq!(after, json!({ "status": "Shortlisted", "password_hash": REDACTED }));
+
+        let (before, after) = diff(&json!({}), &json!({ "role": "Employer" }));
+        assert_eq!(before, json!({ "role": null }));
+        assert_eq!(after, json!({ "role": "Employer" }));
+    }
+
+    #[test]
+    fn test_canonical_json_ignores_key_order() {
+        let a: Value = serde_json::from_str(r#"{"b":1,"a":{"d":[1,{"y":2,"x":1}],"c":"s"}}"#).unwrap();
+        let b: Value = serde_json::from_str(r#"{"a":{"c":"s","d":[1,{"x":1,"y":2}]},"b":1}"#).unwrap();
+        assert_eq!(canonical_json(&a), canonical_json(&b));
+        assert_eq!(canonical_json(&a), r#"{"a":{"c":"s","d":[1,{"x":1,"y":2}]},"b":1}"#);
+    }
+
+    #[test]
+    fn test_outbox_events_continue_the_chain() {
+        let first = event(1, GENESIS_HASH, json!({ "status": "Reviewing" }));
+        let queued = audit_outbox::Model {
+            id: Uuid::new_v4(),
+            actor_id: None,
+            tenant_id: None,
+            ip: None,
+            request_id: None,
+            action: AuditAction::JobStatusChanged,
+            entity_type: "job".to_string(),
+            entity_id: Some(Uuid::new_v4().to_string()),
+            before: Some(json!({ "status": "Active" })),
+            after: Some(json!({ "status": "Closed", "reason": "Manual" })),
+            created_at: Utc::now().trunc_subsecs(6).into(),
+        };
+        let mut second = queued.clone().into_event(2, first.hash.clone());
+        second.hash = chain_hash(&second);
+        assert_eq!(second.id, queued.id);
+        assert_eq!(second.created_at, queued.created_at);
+        assert_eq!(broken_link(&second, 1, &first.hash), None);
+    }
+
+    #[test]
+    fn test_chain_detects_tampering() {
+        let first = event(1, GENESIS_HASH, json!({ "status": "Reviewing" }));
+        let second = event(2, &first.hash, json!({ "status": "Shortlisted" }));
+        assert_eq!(broken_link(&first, 0, GENESIS_HASH), None);
+        assert_eq!(broken_link(&second, 1, &first.hash), None);
+
+        // Edited in place
+        let edited = audit_event::Model { after: Some(json!({ "status": "Offered" })), ..second.clone() };
+        assert!(broken_link(&edited, 1, &first.hash).unwrap().contains("hash"));
+
+        // Rehashed after editing, but the chain no longer leads to it
+        let mut forged = event(2, GENESIS_HASH, json!({ "status": "Offered" }));
+        forged.hash = chain_hash(&forged);
+        assert!(broken_link(&forged, 1, &first.hash).unwrap().contains("link"));
+
+        // An event removed from the middle
+        let third = event(3, &second.hash, json!({ "status": "Offered" }));
+        assert!(broken_link(&third, 1, &first.hash).unwrap().contains("Expected event 2"));
+    }
+}
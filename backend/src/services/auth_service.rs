@@ -93,4 +93,14 @@ impl AuthService {
             Err(AppError::InvalidToken)
         }
     }
+
+    /// Extract the JWT from the `auth-token` cookie set on login
+    pub fn extract_token_from_cookie(cookie_header: &str) -> Option<&str> {
+        cookie_header
+            .split(';')
+            .filter_map(|pair| pair.trim().split_once('='))
+            .find(|(name, _)| *name == "auth-token")
+            .map(|(_, value)| value)
+            .filter(|value| !value.is_empty())
+    }
 }
\ No newline at end of file
@@ -0,0 +1,249 @@
+use std::collections::{BTreeMap, HashMap};
+use chrono::{Duration, NaiveDate, NaiveTime, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    sea_query::Condition, ActiveModelBehavior, ActiveModelTrait, ColumnTrait, DatabaseConnection,
+    EntityTrait, ModelTrait, QueryFilter, QueryOrder, Set,
+};
+use shared::availability::{self, AvailabilitySlot, MAX_EXPANSION_DAYS};
+use uuid::Uuid;
+
+use crate::{
+    entities::{availability as availability_entity, user},
+    AppError, AppState,
+};
+
+/// Days shown when the calendar does not ask for a range
+pub const DEFAULT_RANGE_DAYS: i64 = 90;
+
+/// A user whose availability covers a shift window
+#[derive(Debug, Clone)]
+pub struct AvailableUser {
+    pub user: user::Model,
+    /// Their bookable slots on the shift date
+    pub slots: Vec<AvailabilitySlot>,
+    pub preferred: bool,
+}
+
+/// Resolve an optional `from..=to` range, rejecting empty or oversized ones
+pub fn resolve_range(from: Option<NaiveDate>, to: Option<NaiveDate>) -> Result<(NaiveDate, NaiveDate), AppError> {
+    let from = from.unwrap_or_else(|| Utc::now().date_naive());
+    let to = to.unwrap_or(from + Duration::days(DEFAULT_RANGE_DAYS - 1));
+
+    if to < from {
+        return Err(AppError::Validation("'to' must not be before 'from'".to_string()));
+    }
+    if (to - from).num_days() >= MAX_EXPANSION_DAYS {
+        return Err(AppError::Validation(format!(
+            "Date range cannot exceed {} days",
+            MAX_EXPANSION_DAYS
+        )));
+    }
+    Ok((from, to))
+}
+
+#[derive(Clone)]
+pub struct AvailabilityService {
+    db: DatabaseConnection,
+}
+
+impl AvailabilityService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Every occurrence of the user's slots between `from` and `to`, by date then start time
+    pub async fn list(&self, user_id: Uuid, from: NaiveDate, to: NaiveDate) -> Result<Vec<AvailabilitySlot>, AppError> {
+        let models = availability_entity::Entity::find()
+            .filter(availability_entity::Column::UserId.eq(user_id))
+            .filter(availability_entity::Column::Date.lte(to))
+            .filter(Self::may_occur_from(from))
+            .order_by_asc(availability_entity::Column::StartTime)
+            .all(&self.db)
+            .await?;
+
+        let mut slots: Vec<AvailabilitySlot> = models
+            .iter()
+            .flat_map(|model| model.to_slot().expand(from, to))
+            .collect();
+        // Sort is stable, so start-time order holds within each day
+        slots.sort_by_key(|slot| slot.date);
+        Ok(slots)
+    }
+
+    pub async fn get(&self, user_id: Uuid, slot_id: Uuid) -> Result<availability_entity::Model, AppError> {
+        availability_entity::Entity::find_by_id(slot_id)
+            .filter(availability_entity::Column::UserId.eq(user_id))
+            .one(&self.db)
+            .await?
+            .ok_or(AppError::NotFound)
+    }
+
+    pub async fn create(&self, user_id: Uuid, slot: AvailabilitySlot) -> Result<availability_entity::Model, AppError> {
+        let mut model = availability_entity::ActiveModel {
+            user_id: Set(user_id),
+            ..availability_entity::ActiveModel::new()
+        };
+        Self::apply(&mut model, &slot)?;
+        Ok(model.insert(&self.db).await?)
+    }
+
+    /// Replace a slot; for a recurring slot this changes the whole series
+    pub async fn update(&self, user_id: Uuid, slot_id: Uuid, slot: AvailabilitySlot) -> Result<availability_entity::Model, AppError> {
+        let mut model: availability_entity::ActiveModel = self.get(user_id, slot_id).await?.into();
+        Self::apply(&mut model, &slot)?;
+        model.updated_at = Set(Utc::now().into());
+        Ok(model.update(&self.db).await?)
+    }
+
+    pub async fn delete(&self, user_id: Uuid, slot_id: Uuid) -> Result<(), AppError> {
+        self.get(user_id, slot_id).await?.delete(&self.db).await?;
+        Ok(())
+    }
+
+    /// Users free for a shift on `date` from `start` to `end`; those who marked the
+    /// time as preferred come first
+    pub async fn available_for(&self, date: NaiveDate, start: NaiveTime, end: NaiveTime) -> Result<Vec<AvailableUser>, AppError> {
+        if end <= start {
+            return Err(AppError::Validation("end_time must be after start_time".to_string()));
+        }
+
+        let rows = availability_entity::Entity::find()
+            .filter(availability_entity::Column::Date.lte(date))
+            .filter(Self::may_occur_from(date))
+            .find_also_related(user::Entity)
+            .all(&self.db)
+            .await?;
+
+        let mut by_user: BTreeMap<Uuid, (user::Model, Vec<AvailabilitySlot>)> = BTreeMap::new();
+        for (model, owner) in rows {
+            let Some(owner) = owner.filter(|u| u.is_active) else {
+                continue;
+            };
+            by_user
+                .entry(owner.id)
+                .or_insert_with(|| (owner, Vec::new()))
+                .1
+                .extend(model.to_slot().expand(date, date));
+        }
+
+        let mut available: Vec<AvailableUser> = by_user
+            .into_values()
+            .filter(|(_, slots)| availability::is_available_for(slots, date, start, end))
+            .map(|(user, slots)| {
+                let slots: Vec<AvailabilitySlot> = slots
+                    .into_iter()
+                    .filter(|slot| slot.availability_type.is_bookable())
+                    .collect();
+                let preferred = slots
+                    .iter()
+                    .any(|slot| slot.availability_type == availability::AvailabilityType::Preferred);
+                AvailableUser { user, slots, preferred }
+            })
+            .collect();
+        available.sort_by_key(|candidate| !candidate.preferred);
+        Ok(available)
+    }
+
+    /// Stored (unexpanded) slots that may occur between `from` and `to`, by user;
+    /// every user when `user_ids` is `None`
+    pub async fn slots_by_user(
+        &self,
+        user_ids: Option<&[Uuid]>,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<HashMap<Uuid, Vec<AvailabilitySlot>>, AppError> {
+        let mut query = availability_entity::Entity::find()
+            .filter(availability_entity::Column::Date.lte(to))
+            .filter(Self::may_occur_from(from));
+        if let Some(user_ids) = user_ids {
+            query = query.filter(availability_entity::Column::UserId.is_in(user_ids.iter().copied()));
+        }
+
+        let mut by_user: HashMap<Uuid, Vec<AvailabilitySlot>> = HashMap::new();
+        for model in query.all(&self.db).await? {
+            by_user.entry(model.user_id).or_default().push(model.to_slot());
+        }
+        Ok(by_user)
+    }
+
+    /// One-off slots on or after `from`, and recurring slots that have not ended by then
+    fn may_occur_from(from: NaiveDate) -> Condition {
+        Condition::any()
+            .add(
+                Condition::all()
+                    .add(availability_entity::Column::RecurrenceType.is_null())
+                    .add(availability_entity::Column::Date.gte(from)),
+            )
+            .add(
+                Condition::all()
+                    .add(availability_entity::Column::RecurrenceType.is_not_null())
+                    .add(
+                        Condition::any()
+                            .add(availability_entity::Column::RecurrenceEndDate.is_null())
+                            .add(availability_entity::Column::RecurrenceEndDate.gte(from)),
+                    ),
+            )
+    }
+
+    fn apply(model: &mut availability_entity::ActiveModel, slot: &AvailabilitySlot) -> Result<(), AppError> {
+        slot.validate()?;
+        let (start, end) = slot.time_range()?;
+        let hourly_rate = match slot.hourly_rate {
+            Some(rate) => Some(
+                Decimal::from_f64_retain(rate)
+                    .map(|rate| rate.round_dp(2))
+                    .ok_or_else(|| AppError::Validation("Invalid hourly_rate".to_string()))?,
+            ),
+            None => None,
+        };
+        let pattern = slot.recurring_pattern.as_ref().filter(|_| slot.is_recurring);
+
+        model.date = Set(slot.date);
+        model.start_time = Set(start);
+        model.end_time = Set(end);
+        model.availability_type = Set(slot.availability_type.into());
+        model.notes = Set(slot.notes.clone());
+        model.job_type_preference = Set(slot.job_type_preference.clone());
+        model.hourly_rate = Set(hourly_rate);
+        model.recurrence_type = Set(pattern.map(|p| p.pattern_type.into()));
+        model.recurrence_interval = Set(pattern.map_or(1, |p| p.interval as i32));
+        model.recurrence_days = Set(pattern.map_or(0, |p| availability_entity::weekday_mask(&p.days_of_week)));
+        model.recurrence_end_date = Set(pattern.and_then(|p| p.end_date));
+        Ok(())
+    }
+}
+
+impl From<&AppState> for AvailabilityService {
+    fn from(state: &AppState) -> Self {
+        Self::new(state.db.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Weekday;
+    use crate::entities::availability::{weekday_mask, weekdays_from_mask};
+
+    #[test]
+    fn test_weekday_mask_round_trip() {
+        let days = vec![Weekday::Mon, Weekday::Wed, Weekday::Sun];
+        assert_eq!(weekday_mask(&days), 0b100_0101);
+        assert_eq!(weekdays_from_mask(weekday_mask(&days)), days);
+        assert!(weekdays_from_mask(0).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_range() {
+        let from = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+
+        let (start, end) = resolve_range(Some(from), None).unwrap();
+        assert_eq!(start, from);
+        assert_eq!((end - start).num_days(), DEFAULT_RANGE_DAYS - 1);
+
+        assert!(resolve_range(Some(from), Some(from - Duration::days(1))).is_err());
+        assert!(resolve_range(Some(from), Some(from + Duration::days(MAX_EXPANSION_DAYS))).is_err());
+        assert!(resolve_range(Some(from), Some(from + Duration::days(MAX_EXPANSION_DAYS - 1))).is_ok());
+    }
+}
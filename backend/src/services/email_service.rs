@@ -0,0 +1,110 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use lettre::{
+    message::Mailbox, transport::smtp::authentication::Credentials, Message, SmtpTransport,
+    Transport,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::Config,
+    services::task_queue::{Task, TaskRegistry},
+    AppError,
+};
+
+/// Outbound email delivery
+#[async_trait]
+pub trait EmailSender: Send + Sync {
+    async fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), AppError>;
+}
+
+/// Sends email through the configured SMTP relay
+pub struct SmtpEmailSender {
+    transport: SmtpTransport,
+    from: Mailbox,
+}
+
+impl SmtpEmailSender {
+    pub fn new(config: &Config, host: &str) -> Result<Self, AppError> {
+        let mut builder = SmtpTransport::relay(host)
+            .map_err(|e| AppError::Configuration(format!("Invalid SMTP host {}: {}", host, e)))?
+            .port(config.smtp_port);
+
+        if let (Some(username), Some(password)) = (&config.smtp_username, &config.smtp_password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.expose().clone()));
+        }
+
+        let from = config
+            .from_email
+            .parse()
+            .map_err(|e| AppError::Configuration(format!("Invalid FROM_EMAIL: {}", e)))?;
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl EmailSender for SmtpEmailSender {
+    async fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), AppError> {
+        let to: Mailbox = to
+            .parse()
+            .map_err(|e| AppError::Validation(format!("Invalid recipient address: {}", e)))?;
+        let email = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(subject)
+            .body(body.to_string())
+            .map_err(|e| AppError::Internal(format!("Failed to build email: {}", e)))?;
+
+        // SmtpTransport is blocking, keep it off the async workers
+        let transport = self.transport.clone();
+        tokio::task::spawn_blocking(move || transport.send(&email))
+            .await
+            .map_err(|e| AppError::Internal(format!("Email task failed: {}", e)))?
+            .map_err(|e| AppError::Internal(format!("Failed to send email: {}", e)))?;
+
+        Ok(())
+    }
+}
+
+/// Logs emails instead of sending them (used when SMTP is not configured)
+pub struct LogEmailSender;
+
+#[async_trait]
+impl EmailSender for LogEmailSender {
+    async fn send(&self, to: &str, subject: &str, _body: &str) -> Result<(), AppError> {
+        tracing::info!("📧 Email to {} (not sent, SMTP not configured): {}", to, subject);
+        Ok(())
+    }
+}
+
+/// Build the email sender for the current configuration
+pub fn email_sender_from_config(config: &Config) -> Result<Arc<dyn EmailSender>, AppError> {
+    match &config.smtp_host {
+        Some(host) => Ok(Arc::new(SmtpEmailSender::new(config, host)?)),
+        None => Ok(Arc::new(LogEmailSender)),
+    }
+}
+
+/// Background task that sends one email
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendEmail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+impl Task for SendEmail {
+    const KIND: &'static str = "send_email";
+}
+
+/// Register the email task handlers
+pub fn register_tasks(registry: &mut TaskRegistry, mailer: Arc<dyn EmailSender>) {
+    registry.register(move |email: SendEmail| {
+        let mailer = mailer.clone();
+        async move { mailer.send(&email.to, &email.subject, &email.body).await }
+    });
+}
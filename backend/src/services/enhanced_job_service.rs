@@ -1,12 +1,27 @@
+use chrono::Utc;
 use uuid::Uuid;
 use crate::{
-    entities::job::{self, Entity as Job},
-    repository::PaginationParams,
+    entities::{
+        audit_event::AuditAction,
+        job::{self, Entity as Job, JobStatusDb},
+        job_status_change::{self, StatusChangeReason},
+    },
+    handlers::websocket::{self, JobNotification},
+    repository::{Cursor, JobRepository, PaginationParams},
+    services::{
+        audit_service::{AuditEntry, AuditService},
+        saved_search_service::SavedSearchService,
+        webhook_service::WebhookService,
+        JobService,
+    },
     AppError, AppState,
 };
-use shared::types::{JobFilters, CreateJobRequest};
-use sea_orm::{DatabaseConnection, EntityTrait};
-use serde_json::Value;
+use shared::types::{CursorParams, JobFilters, CreateJobRequest, ListResponse};
+use sea_orm::{
+    ActiveEnum, ActiveModelBehavior, ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait,
+    QueryFilter, Set, TransactionTrait,
+};
+use serde_json::{json, Value};
 
 #[derive(Clone)]
 pub struct EnhancedJobService {
@@ -19,9 +34,7 @@ impl EnhancedJobService {
     }
 
     pub fn from(state: &AppState) -> Self {
-        Self {
-            db: state.db.clone(),
-        }
+        Self::new(state.db.clone())
     }
 
     /// Create a new job posting (simplified stub)
@@ -38,23 +51,29 @@ impl EnhancedJobService {
         Err(AppError::NotImplemented("Enhanced job retrieval not yet implemented".to_string()))
     }
 
-    /// List jobs with enhanced filtering (simplified stub)
+    /// List active jobs matching the filters, newest first
     pub async fn list_jobs(
         &self,
-        _filters: JobFilters,
-        _pagination: PaginationParams,
-    ) -> Result<(Vec<job::Model>, u64), AppError> {
-        Ok((Vec::new(), 0))
+        filters: JobFilters,
+        page: CursorParams,
+    ) -> Result<ListResponse<job::Model>, AppError> {
+        let after = Cursor::from_params(&page)?;
+        Ok(JobRepository::new()
+            .search_jobs(&self.db, None, filters, after, page.limit())
+            .await?)
     }
 
-    /// Search jobs with text search (simplified stub)
+    /// Search active jobs by title, description or pharmacy, newest first
     pub async fn search_jobs(
         &self,
-        _query: &str,
-        _filters: JobFilters,
-        _pagination: PaginationParams,
-    ) -> Result<(Vec<job::Model>, u64), AppError> {
-        Ok((Vec::new(), 0))
+        query: &str,
+        filters: JobFilters,
+        page: CursorParams,
+    ) -> Result<ListResponse<job::Model>, AppError> {
+        let after = Cursor::from_params(&page)?;
+        Ok(JobRepository::new()
+            .search_jobs(&self.db, Some(query), filters, after, page.limit())
+            .await?)
     }
 
     /// Find jobs near a location (simplified stub)
@@ -69,14 +88,79 @@ impl EnhancedJobService {
         Ok((Vec::new(), 0))
     }
 
-    /// Update job status (simplified stub)
+    /// Move a job to another status by hand, as its poster or an admin. The
+    /// change is recorded in the job's history and the audit trail in the same
+    /// transaction.
     pub async fn update_job_status(
         &self,
-        _job_id: Uuid,
-        _status: String,
-        _user_id: Uuid,
+        job_id: Uuid,
+        status: String,
+        user_id: Uuid,
+        is_admin: bool,
     ) -> Result<job::Model, AppError> {
-        Err(AppError::NotImplemented("Enhanced job status update not yet implemented".to_string()))
+        let to = JobStatusDb::try_from_value(&status)
+            .map_err(|_| AppError::Validation("status: Unknown job status".to_string()))?;
+        let job = Job::find_by_id(job_id)
+            .filter(job::Column::DeletedAt.is_null())
+            .one(&self.db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        JobService::check_can_edit(&job, user_id, is_admin)?;
+        if job.status == to {
+            return Ok(job);
+        }
+
+        let txn = self.db.begin().await?;
+        // Only move the job if it is still in the status it was read in
+        let updated = Job::update_many()
+            .set(job::ActiveModel {
+                status: Set(to.clone()),
+                updated_at: Set(Utc::now().into()),
+                ..Default::default()
+            })
+            .filter(job::Column::Id.eq(job.id))
+            .filter(job::Column::Status.eq(job.status.clone()))
+            .exec_with_returning(&txn)
+            .await?
+            .pop()
+            .ok_or_else(|| AppError::Conflict("The job's status changed while it was being updated".to_string()))?;
+
+        job_status_change::ActiveModel {
+            job_id: Set(job.id),
+            from_status: Set(job.status.clone()),
+            to_status: Set(to.clone()),
+            reason: Set(StatusChangeReason::Manual),
+            changed_by: Set(Some(user_id)),
+            ..job_status_change::ActiveModel::new()
+        }
+        .insert(&txn)
+        .await?;
+
+        AuditService::new(self.db.clone())
+            .record_in(
+                &txn,
+                AuditEntry::new(AuditAction::JobStatusChanged, "job", job.id).change(
+                    &json!({ "status": job.status }),
+                    &json!({ "status": to, "reason": StatusChangeReason::Manual }),
+                ),
+            )
+            .await?;
+        txn.commit().await?;
+
+        if to == JobStatusDb::Active {
+            websocket::broadcast_job_posted(JobNotification::from_job(&updated)).await;
+            SavedSearchService::new(self.db.clone()).job_published(&updated).await;
+            WebhookService::new(self.db.clone()).job_published(&updated).await;
+        }
+        websocket::broadcast_job_status_changed(
+            job.id,
+            format!("{:?}", job.status),
+            format!("{:?}", to),
+            format!("{:?}", StatusChangeReason::Manual),
+        )
+        .await;
+
+        Ok(updated)
     }
 
     /// Delete job (simplified stub)
@@ -84,13 +168,16 @@ impl EnhancedJobService {
         Err(AppError::NotImplemented("Enhanced job deletion not yet implemented".to_string()))
     }
 
-    /// Get jobs by user (simplified stub)
+    /// Jobs posted by a user, newest first
     pub async fn get_jobs_by_user(
         &self,
-        _user_id: Uuid,
-        _pagination: PaginationParams,
-    ) -> Result<(Vec<job::Model>, u64), AppError> {
-        Ok((Vec::new(), 0))
+        user_id: Uuid,
+        page: CursorParams,
+    ) -> Result<ListResponse<job::Model>, AppError> {
+        let after = Cursor::from_params(&page)?;
+        Ok(JobRepository::new()
+            .find_jobs_by_user(&self.db, user_id, after, page.limit())
+            .await?)
     }
 
     /// Get job statistics (simplified stub)
@@ -0,0 +1,315 @@
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+
+use migration::{Migrator, MigratorTrait};
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use tokio::net::TcpStream;
+
+use crate::{
+    config::Config,
+    db,
+    services::{metrics::PoolStats, task_queue::TaskQueue},
+    AppState,
+};
+
+/// Longest any single check may take before it counts as failed
+pub const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Pool use at which requests start waiting for connections
+const POOL_SATURATION_WARNING: f64 = 0.9;
+
+/// Queue lag at which background work is reported as falling behind
+const QUEUE_LAG_WARNING: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    /// Not configured, so not checked
+    Skipped,
+    Healthy,
+    /// Working, but close to a limit or falling behind
+    Degraded,
+    Unhealthy,
+}
+
+/// Outcome of checking one dependency
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    /// A failing critical check makes the instance unready
+    pub critical: bool,
+    pub latency_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    pub status: CheckStatus,
+    pub ready: bool,
+    pub checks: Vec<CheckResult>,
+}
+
+impl HealthReport {
+    fn new(checks: Vec<CheckResult>) -> Self {
+        let status = checks
+            .iter()
+            .map(|check| match (check.status, check.critical) {
+                // An optional dependency being down degrades the service rather than breaking it
+                (CheckStatus::Unhealthy, false) => CheckStatus::Degraded,
+                (status, _) => status,
+            })
+            .max()
+            .unwrap_or(CheckStatus::Healthy)
+            .max(CheckStatus::Healthy);
+        let ready = !checks
+            .iter()
+            .any(|check| check.critical && check.status == CheckStatus::Unhealthy);
+        Self { status, ready, checks }
+    }
+}
+
+/// What a check found, before its latency is attached
+type Finding = (CheckStatus, Option<String>);
+
+fn healthy() -> Finding {
+    (CheckStatus::Healthy, None)
+}
+
+fn finding(status: CheckStatus, message: impl Into<String>) -> Finding {
+    (status, Some(message.into()))
+}
+
+/// Run a check under `timeout`, timing it
+async fn timed<F>(name: &'static str, critical: bool, timeout: Duration, check: F) -> CheckResult
+where
+    F: Future<Output = Finding>,
+{
+    let started = Instant::now();
+    let (status, message) = tokio::time::timeout(timeout, check)
+        .await
+        .unwrap_or_else(|_| finding(CheckStatus::Unhealthy, format!("Timed out after {}ms", timeout.as_millis())));
+    CheckResult {
+        name,
+        status,
+        critical,
+        latency_ms: started.elapsed().as_secs_f64() * 1000.0,
+        message,
+    }
+}
+
+/// Checks the database, migrations, connection pool, optional services and the
+/// background queue
+#[derive(Clone)]
+pub struct HealthService {
+    db: DatabaseConnection,
+    config: Config,
+}
+
+impl HealthService {
+    pub fn new(db: DatabaseConnection, config: Config) -> Self {
+        Self { db, config }
+    }
+
+    /// Every check, run concurrently
+    pub async fn detailed(&self) -> HealthReport {
+        let (database, migrations, smtp, supabase, queue) = tokio::join!(
+            self.database(),
+            self.migrations(),
+            self.smtp(),
+            self.supabase(),
+            self.task_queue(),
+        );
+        HealthReport::new(vec![database, migrations, self.pool(), smtp, supabase, queue])
+    }
+
+    /// Only the checks an instance cannot serve traffic without
+    pub async fn readiness(&self) -> HealthReport {
+        let (database, migrations) = tokio::join!(self.database(), self.migrations());
+        HealthReport::new(vec![database, migrations])
+    }
+
+    /// Log every check once at startup so misconfiguration shows up before traffic does
+    pub async fn log_startup_diagnostics(&self) {
+        let report = self.detailed().await;
+        for check in &report.checks {
+            let message = check.message.as_deref().unwrap_or("");
+            match check.status {
+                CheckStatus::Healthy | CheckStatus::Skipped => tracing::info!(
+                    "🩺 {}: {:?} in {:.1}ms {}", check.name, check.status, check.latency_ms, message
+                ),
+                CheckStatus::Degraded | CheckStatus::Unhealthy => tracing::warn!(
+                    "🩺 {}: {:?} in {:.1}ms {}", check.name, check.status, check.latency_ms, message
+                ),
+            }
+        }
+        if !report.ready {
+            tracing::error!("🩺 Critical checks failed; /health/ready will report not ready");
+        }
+    }
+
+    async fn database(&self) -> CheckResult {
+        timed("database", true, CHECK_TIMEOUT, async {
+            match db::health_check(&self.db).await {
+                Ok(()) => healthy(),
+                Err(e) => finding(CheckStatus::Unhealthy, e.to_string()),
+            }
+        })
+        .await
+    }
+
+    async fn migrations(&self) -> CheckResult {
+        timed("migrations", true, CHECK_TIMEOUT, async {
+            match Migrator::get_pending_migrations(&self.db).await {
+                Ok(pending) if pending.is_empty() => healthy(),
+                Ok(pending) => finding(CheckStatus::Unhealthy, format!("{} migrations pending", pending.len())),
+                Err(e) => finding(CheckStatus::Unhealthy, e.to_string()),
+            }
+        })
+        .await
+    }
+
+    fn pool(&self) -> CheckResult {
+        let started = Instant::now();
+        let (status, message) = pool_finding(PoolStats::of(&self.db));
+        CheckResult {
+            name: "database_pool",
+            status,
+            critical: false,
+            latency_ms: started.elapsed().as_secs_f64() * 1000.0,
+            message,
+        }
+    }
+
+    async fn smtp(&self) -> CheckResult {
+        timed("smtp", false, CHECK_TIMEOUT, async {
+            let Some(host) = &self.config.smtp_host else {
+                return finding(CheckStatus::Skipped, "SMTP_HOST not set");
+            };
+            match TcpStream::connect((host.as_str(), self.config.smtp_port)).await {
+                Ok(_) => healthy(),
+                Err(e) => finding(CheckStatus::Unhealthy, format!("{}:{}: {}", host, self.config.smtp_port, e)),
+            }
+        })
+        .await
+    }
+
+    async fn supabase(&self) -> CheckResult {
+        timed("supabase", false, CHECK_TIMEOUT, async {
+            let (Some(supabase_url), Some(anon_key)) = (&self.config.supabase_url, &self.config.supabase_anon_key) else {
+                return finding(CheckStatus::Skipped, "SUPABASE_URL not set");
+            };
+            let url = format!("{}/auth/v1/health", supabase_url.trim_end_matches('/'));
+            let response = reqwest::Client::new()
+                .get(&url)
+                .header("apikey", anon_key.expose())
+                .send()
+                .await;
+            match response {
+                Ok(response) if response.status().is_success() => healthy(),
+                Ok(response) => finding(CheckStatus::Unhealthy, format!("Auth health returned {}", response.status())),
+                Err(e) => finding(CheckStatus::Unhealthy, e.to_string()),
+            }
+        })
+        .await
+    }
+
+    async fn task_queue(&self) -> CheckResult {
+        timed("task_queue", false, CHECK_TIMEOUT, async {
+            match TaskQueue::new(self.db.clone()).lag().await {
+                Ok(lag) => queue_finding(lag),
+                Err(e) => finding(CheckStatus::Unhealthy, e.to_string()),
+            }
+        })
+        .await
+    }
+}
+
+impl From<&AppState> for HealthService {
+    fn from(state: &AppState) -> Self {
+        Self::new(state.db.clone(), state.config.clone())
+    }
+}
+
+fn pool_finding(pool: Option<PoolStats>) -> Finding {
+    let Some(pool) = pool else {
+        return finding(CheckStatus::Skipped, "Not a pooled Postgres connection");
+    };
+    let message = format!("{} of {} connections in use", pool.size.saturating_sub(pool.idle), pool.max);
+    if pool.utilisation() >= POOL_SATURATION_WARNING {
+        finding(CheckStatus::Degraded, message)
+    } else {
+        finding(CheckStatus::Healthy, message)
+    }
+}
+
+fn queue_finding(lag: Duration) -> Finding {
+    let message = format!("Oldest due task has waited {}s", lag.as_secs());
+    if lag >= QUEUE_LAG_WARNING {
+        finding(CheckStatus::Degraded, message)
+    } else {
+        finding(CheckStatus::Healthy, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(name: &'static str, status: CheckStatus, critical: bool) -> CheckResult {
+        CheckResult { name, status, critical, latency_ms: 1.0, message: None }
+    }
+
+    #[test]
+    fn test_report_status_and_readiness() {
+        let report = HealthReport::new(vec![
+            check("database", CheckStatus::Healthy, true),
+            check("smtp", CheckStatus::Skipped, false),
+        ]);
+        assert_eq!(report.status, CheckStatus::Healthy);
+        assert!(report.ready);
+
+        // An optional dependency being down degrades but keeps the instance ready
+        let report = HealthReport::new(vec![
+            check("database", CheckStatus::Healthy, true),
+            check("supabase", CheckStatus::Unhealthy, false),
+        ]);
+        assert_eq!(report.status, CheckStatus::Degraded);
+        assert!(report.ready);
+
+        let report = HealthReport::new(vec![
+            check("database", CheckStatus::Healthy, true),
+            check("migrations", CheckStatus::Unhealthy, true),
+        ]);
+        assert_eq!(report.status, CheckStatus::Unhealthy);
+        assert!(!report.ready);
+    }
+
+    #[tokio::test]
+    async fn test_slow_check_times_out() {
+        let result = timed("slow", true, Duration::from_millis(20), async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            healthy()
+        })
+        .await;
+        assert_eq!(result.status, CheckStatus::Unhealthy);
+        assert_eq!(result.message.as_deref(), Some("Timed out after 20ms"));
+        assert!(result.latency_ms < 5000.0);
+    }
+
+    #[test]
+    fn test_pool_and_queue_thresholds() {
+        assert_eq!(pool_finding(None).0, CheckStatus::Skipped);
+        assert_eq!(pool_finding(Some(PoolStats { size: 10, idle: 6, max: 20 })).0, CheckStatus::Healthy);
+        let (status, message) = pool_finding(Some(PoolStats { size: 20, idle: 1, max: 20 }));
+        assert_eq!(status, CheckStatus::Degraded);
+        assert_eq!(message.as_deref(), Some("19 of 20 connections in use"));
+
+        assert_eq!(queue_finding(Duration::from_secs(3)).0, CheckStatus::Healthy);
+        assert_eq!(queue_finding(QUEUE_LAG_WARNING).0, CheckStatus::Degraded);
+    }
+}
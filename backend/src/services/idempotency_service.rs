@@ -0,0 +1,273 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use sea_orm::{
+    sea_query::{Condition, Expr, OnConflict},
+    ActiveModelBehavior, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set,
+};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use crate::{entities::idempotency_key, AppError, AppState};
+
+/// How long a stored response is replayed for
+pub const IDEMPOTENCY_KEY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A request still unfinished after this long belonged to an instance that died,
+/// so its key is free again
+const IN_FLIGHT_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// How often expired keys are deleted
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// What a key is unique within
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdempotencyScope {
+    pub user_id: Uuid,
+    pub method: String,
+    pub route: String,
+    pub key: String,
+}
+
+impl IdempotencyScope {
+    fn condition(&self) -> Condition {
+        Condition::all()
+            .add(idempotency_key::Column::UserId.eq(self.user_id))
+            .add(idempotency_key::Column::Method.eq(self.method.as_str()))
+            .add(idempotency_key::Column::Route.eq(self.route.as_str()))
+            .add(idempotency_key::Column::Key.eq(self.key.as_str()))
+    }
+}
+
+/// A finished response as stored for replay
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl StoredResponse {
+    fn from_model(model: idempotency_key::Model) -> Option<Self> {
+        let headers = match model.response_headers {
+            Some(Value::Object(headers)) => headers
+                .into_iter()
+                .filter_map(|(name, value)| value.as_str().map(|value| (name, value.to_string())))
+                .collect(),
+            _ => Vec::new(),
+        };
+        Some(Self {
+            status: u16::try_from(model.response_status?).ok()?,
+            headers,
+            body: model.response_body.unwrap_or_default(),
+        })
+    }
+}
+
+/// Outcome of presenting a key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Claim {
+    /// First use: run the request, then `complete` or `release` the key
+    Acquired(Uuid),
+    /// The first request with this key is still running
+    InFlight,
+    /// The key was first used with a different request body
+    Mismatch,
+    /// The first request finished; replay its response
+    Completed(StoredResponse),
+    /// The first request finished, but its response was too large or streamed to
+    /// store, so there is nothing to replay and it must not run again
+    Unreplayable,
+}
+
+/// How an earlier use of the key answers a repeat of it
+fn repeat_claim(existing: idempotency_key::Model, request_hash: &str) -> Claim {
+    if existing.request_hash != request_hash {
+        return Claim::Mismatch;
+    }
+    if existing.is_complete() && existing.response_body.is_none() {
+        return Claim::Unreplayable;
+    }
+    match StoredResponse::from_model(existing) {
+        Some(stored) => Claim::Completed(stored),
+        None => Claim::InFlight,
+    }
+}
+
+/// Stores the first response to each `Idempotency-Key`
+#[derive(Clone)]
+pub struct IdempotencyService {
+    db: DatabaseConnection,
+}
+
+impl IdempotencyService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Take the key for this request, or find out what became of its first use
+    pub async fn claim(&self, scope: &IdempotencyScope, request_hash: &str) -> Result<Claim, AppError> {
+        let now = Utc::now();
+        let in_flight_since = now - chrono::Duration::from_std(IN_FLIGHT_TIMEOUT).unwrap_or_default();
+        let ttl = chrono::Duration::from_std(IDEMPOTENCY_KEY_TTL).unwrap_or_default();
+
+        // Free the key if its last use expired or was abandoned mid-request
+        idempotency_key::Entity::delete_many()
+            .filter(scope.condition())
+            .filter(
+                Condition::any()
+                    .add(idempotency_key::Column::ExpiresAt.lte(now))
+                    .add(
+                        Condition::all()
+                            .add(idempotency_key::Column::ResponseStatus.is_null())
+                            .add(idempotency_key::Column::CreatedAt.lte(in_flight_since)),
+                    ),
+            )
+            .exec(&self.db)
+            .await?;
+
+        let id = Uuid::new_v4();
+        let claim = idempotency_key::ActiveModel {
+            id: Set(id),
+            user_id: Set(scope.user_id),
+            method: Set(scope.method.clone()),
+            route: Set(scope.route.clone()),
+            key: Set(scope.key.clone()),
+            request_hash: Set(request_hash.to_string()),
+            expires_at: Set((now + ttl).into()),
+            ..idempotency_key::ActiveModel::new()
+        };
+        let inserted = idempotency_key::Entity::insert(claim)
+            .on_conflict(
+                OnConflict::columns([
+                    idempotency_key::Column::UserId,
+                    idempotency_key::Column::Method,
+                    idempotency_key::Column::Route,
+                    idempotency_key::Column::Key,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .exec_without_returning(&self.db)
+            .await?;
+        if inserted == 1 {
+            return Ok(Claim::Acquired(id));
+        }
+
+        let existing = idempotency_key::Entity::find().filter(scope.condition()).one(&self.db).await?;
+        Ok(match existing {
+            Some(existing) => repeat_claim(existing, request_hash),
+            // Expired between the insert and the lookup; let the client try again
+            None => Claim::InFlight,
+        })
+    }
+
+    /// Store the response to replay for the key
+    pub async fn complete(&self, id: Uuid, response: &StoredResponse) -> Result<(), AppError> {
+        let headers: Map<String, Value> = response
+            .headers
+            .iter()
+            .map(|(name, value)| (name.clone(), Value::String(value.clone())))
+            .collect();
+        idempotency_key::Entity::update_many()
+            .col_expr(idempotency_key::Column::ResponseStatus, Expr::value(response.status as i16))
+            .col_expr(idempotency_key::Column::ResponseHeaders, Expr::value(Value::Object(headers)))
+            .col_expr(idempotency_key::Column::ResponseBody, Expr::value(response.body.clone()))
+            .filter(idempotency_key::Column::Id.eq(id))
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    /// Mark the key used without storing the response, so repeats are refused
+    /// rather than replayed or run again
+    pub async fn complete_unreplayable(&self, id: Uuid, status: u16) -> Result<(), AppError> {
+        idempotency_key::Entity::update_many()
+            .col_expr(idempotency_key::Column::ResponseStatus, Expr::value(status as i16))
+            .filter(idempotency_key::Column::Id.eq(id))
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    /// Give the key up without storing a response, so a retry runs the request again
+    pub async fn release(&self, id: Uuid) -> Result<(), AppError> {
+        idempotency_key::Entity::delete_by_id(id).exec(&self.db).await?;
+        Ok(())
+    }
+
+    /// Delete every expired key
+    pub async fn purge_expired(&self) -> Result<u64, AppError> {
+        let deleted = idempotency_key::Entity::delete_many()
+            .filter(idempotency_key::Column::ExpiresAt.lte(Utc::now()))
+            .exec(&self.db)
+            .await?;
+        Ok(deleted.rows_affected)
+    }
+}
+
+impl From<&AppState> for IdempotencyService {
+    fn from(state: &AppState) -> Self {
+        Self::new(state.db.clone())
+    }
+}
+
+/// Periodically delete idempotency keys past their TTL
+pub fn spawn_cleanup_task(service: IdempotencyService) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(CLEANUP_INTERVAL);
+
+        loop {
+            interval.tick().await;
+            match service.purge_expired().await {
+                Ok(0) => {}
+                Ok(purged) => tracing::debug!("🔑 Purged {} expired idempotency keys", purged),
+                Err(e) => tracing::error!("Failed to purge idempotency keys: {}", e),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn used_key(request_hash: &str, response_status: Option<i16>) -> idempotency_key::Model {
+        let now = Utc::now();
+        idempotency_key::Model {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            method: "POST".to_string(),
+            route: "/api/v1/jobs".to_string(),
+            key: "retry-1".to_string(),
+            request_hash: request_hash.to_string(),
+            response_status,
+            response_headers: response_status.map(|_| json!({ "content-type": "application/json" })),
+            response_body: response_status.map(|_| b"{\"id\":1}".to_vec()),
+            created_at: now.into(),
+            expires_at: (now + chrono::Duration::hours(24)).into(),
+        }
+    }
+
+    #[test]
+    fn test_repeat_claims() {
+        assert_eq!(repeat_claim(used_key("abc", None), "abc"), Claim::InFlight);
+        let unreplayable = idempotency_key::Model {
+            response_headers: None,
+            response_body: None,
+            ..used_key("abc", Some(200))
+        };
+        assert_eq!(repeat_claim(unreplayable.clone(), "abc"), Claim::Unreplayable);
+        assert_eq!(repeat_claim(unreplayable, "def"), Claim::Mismatch);
+        assert_eq!(repeat_claim(used_key("abc", Some(201)), "def"), Claim::Mismatch);
+        assert_eq!(
+            repeat_claim(used_key("abc", Some(201)), "abc"),
+            Claim::Completed(StoredResponse {
+                status: 201,
+                headers: vec![("content-type".to_string(), "application/json".to_string())],
+                body: b"{\"id\":1}".to_vec(),
+            })
+        );
+    }
+}
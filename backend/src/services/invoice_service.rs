@@ -0,0 +1,496 @@
+use std::collections::HashSet;
+use chrono::{Duration, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    sea_query::{Condition, Expr}, ActiveModelBehavior, ActiveModelTrait, ColumnTrait,
+    DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+};
+use shared::invoices::{
+    format_abn, invoice_number, CreateInvoiceRequest, InvoiceLine, InvoiceLineKind, InvoiceTotals,
+    DEFAULT_PAYMENT_TERMS_DAYS, GST_RATE, MAX_PAYMENT_TERMS_DAYS,
+};
+use shared::types::JobType;
+use uuid::Uuid;
+
+use crate::{
+    entities::{
+        application::{self, ApplicationStatus},
+        invoice::{self, InvoiceStatus},
+        job, professional_profile,
+        timesheet::{self, TimesheetStatus},
+        user,
+    },
+    AppError, AppState,
+};
+
+/// How often issued invoices past their due date are marked overdue
+pub const OVERDUE_SWEEP_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// Longest note printed on an invoice
+pub const MAX_NOTES_LENGTH: usize = 1000;
+
+/// The invoice line for an approved timesheet, at its award pay
+fn shift_line(timesheet: &timesheet::Model, job_type: &JobType) -> Result<InvoiceLine, AppError> {
+    let amount = timesheet
+        .pay_total
+        .ok_or_else(|| AppError::Internal(format!("Approved timesheet {} has no pay", timesheet.id)))?;
+    let entry = timesheet.to_entry();
+    let hours = timesheet.pay().map(|pay| pay.hours).unwrap_or_default();
+    let break_note = if entry.break_minutes > 0 {
+        format!(", {} min break", entry.break_minutes)
+    } else {
+        String::new()
+    };
+
+    Ok(InvoiceLine {
+        kind: InvoiceLineKind::Shift,
+        description: format!(
+            "{} shift {}, {}–{} ({:.2} hours{})",
+            job_type,
+            entry.date.format("%a %-d %b %Y"),
+            entry.start_time,
+            entry.end_time,
+            hours,
+            break_note
+        ),
+        quantity: Decimal::ONE,
+        unit_price: amount,
+        amount,
+        timesheet_id: Some(timesheet.id),
+    })
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn money(amount: Decimal) -> String {
+    format!("${:.2}", amount)
+}
+
+/// A printable HTML rendering of an invoice; browsers save it as a PDF from the print dialog
+pub fn render_invoice_html(invoice: &invoice::Model) -> String {
+    let rows: String = invoice
+        .lines()
+        .iter()
+        .map(|line| {
+            format!(
+                "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">${:.2}</td><td class=\"num\">${:.2}</td></tr>",
+                escape_html(&line.description),
+                line.quantity,
+                line.unit_price,
+                line.amount
+            )
+        })
+        .collect();
+    let gst_row = if invoice.gst_registered {
+        format!(
+            "<tr><td colspan=\"3\">GST ({:.0}%)</td><td class=\"num\">{}</td></tr>",
+            GST_RATE * Decimal::ONE_HUNDRED,
+            money(invoice.gst)
+        )
+    } else {
+        "<tr><td colspan=\"4\" class=\"note\">No GST has been charged: the supplier is not registered for GST.</td></tr>"
+            .to_string()
+    };
+    let notes = invoice
+        .notes
+        .as_deref()
+        .map(|notes| format!("<p class=\"notes\">{}</p>", escape_html(notes)))
+        .unwrap_or_default();
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title} {number}</title>
+<style>
+body {{ font-family: Helvetica, Arial, sans-serif; color: #111827; max-width: 800px; margin: 2rem auto; }}
+h1 {{ margin-bottom: 0; }}
+table {{ width: 100%; border-collapse: collapse; margin-top: 1.5rem; }}
+th, td {{ text-align: left; padding: 0.5rem; border-bottom: 1px solid #e5e7eb; }}
+.num {{ text-align: right; }}
+.parties {{ display: flex; justify-content: space-between; margin-top: 1.5rem; }}
+.total td {{ font-weight: bold; border-top: 2px solid #111827; }}
+.note, .notes {{ color: #6b7280; }}
+.status {{ text-transform: uppercase; letter-spacing: 0.05em; color: #6b7280; }}
+@media print {{ body {{ margin: 0; }} }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+<p>{number} <span class="status">{status:?}</span></p>
+<div class="parties">
+<div><strong>From</strong><br>{supplier}<br>ABN {abn}</div>
+<div><strong>To</strong><br>{customer}<br>{address}</div>
+<div>Issued {issued}<br>Due {due}</div>
+</div>
+<table>
+<thead><tr><th>Description</th><th class="num">Qty</th><th class="num">Unit price</th><th class="num">Amount</th></tr></thead>
+<tbody>
+{rows}
+<tr><td colspan="3">Subtotal</td><td class="num">{subtotal}</td></tr>
+{gst_row}
+<tr class="total"><td colspan="3">Total</td><td class="num">{total}</td></tr>
+</tbody>
+</table>
+<p>Payment terms: {terms} days. Please pay by {due}, quoting {number}.</p>
+{notes}
+</body>
+</html>
+"#,
+        title = invoice.title(),
+        number = escape_html(&invoice.number),
+        status = invoice.status,
+        supplier = escape_html(&invoice.supplier_name),
+        abn = format_abn(&invoice.supplier_abn),
+        customer = escape_html(&invoice.customer_name),
+        address = escape_html(&invoice.customer_address),
+        issued = invoice.issued_on.format("%-d %B %Y"),
+        due = invoice.due_on.format("%-d %B %Y"),
+        rows = rows,
+        subtotal = money(invoice.subtotal),
+        gst_row = gst_row,
+        total = money(invoice.total),
+        terms = invoice.payment_terms_days,
+        notes = notes,
+    )
+}
+
+#[derive(Clone)]
+pub struct InvoiceService {
+    db: DatabaseConnection,
+}
+
+impl InvoiceService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Invoices the user issued or was billed, newest first (every invoice for admins)
+    pub async fn list(&self, requester_id: Uuid, is_admin: bool) -> Result<Vec<invoice::Model>, AppError> {
+        let mut query = invoice::Entity::find();
+        if !is_admin {
+            query = query.filter(
+                Condition::any()
+                    .add(invoice::Column::SupplierId.eq(requester_id))
+                    .add(invoice::Column::CustomerId.eq(requester_id)),
+            );
+        }
+        Ok(query
+            .order_by_desc(invoice::Column::IssuedOn)
+            .order_by_desc(invoice::Column::CreatedAt)
+            .all(&self.db)
+            .await?)
+    }
+
+    /// An invoice, visible to its supplier, its customer and admins
+    pub async fn get(&self, invoice_id: Uuid, requester_id: Uuid, is_admin: bool) -> Result<invoice::Model, AppError> {
+        let invoice = invoice::Entity::find_by_id(invoice_id)
+            .one(&self.db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        if invoice.supplier_id != requester_id && invoice.customer_id != requester_id && !is_admin {
+            return Err(AppError::NotFound);
+        }
+        Ok(invoice)
+    }
+
+    /// Bill the pharmacy for an accepted application's approved timesheets (the locum only)
+    pub async fn create(
+        &self,
+        application_id: Uuid,
+        requester_id: Uuid,
+        request: &CreateInvoiceRequest,
+    ) -> Result<invoice::Model, AppError> {
+        let (application, job) = application::Entity::find_by_id(application_id)
+            .find_also_related(job::Entity)
+            .one(&self.db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        let job = job.ok_or(AppError::NotFound)?;
+        if application.user_id != requester_id {
+            return Err(if job.created_by == requester_id { AppError::Forbidden } else { AppError::NotFound });
+        }
+        if application.status != ApplicationStatus::Accepted {
+            return Err(AppError::Validation("Only accepted applications can be invoiced".to_string()));
+        }
+
+        let profile = professional_profile::Entity::find_by_id(requester_id).one(&self.db).await?;
+        let Some((abn, gst_registered)) = profile.and_then(|profile| Some((profile.abn?, profile.gst_registered)))
+        else {
+            return Err(AppError::Validation(
+                "Add your ABN to your professional profile before invoicing".to_string(),
+            ));
+        };
+        let payment_terms_days = request.payment_terms_days.unwrap_or(DEFAULT_PAYMENT_TERMS_DAYS);
+        if payment_terms_days > MAX_PAYMENT_TERMS_DAYS {
+            return Err(AppError::Validation(format!(
+                "payment_terms_days cannot be more than {}",
+                MAX_PAYMENT_TERMS_DAYS
+            )));
+        }
+        let notes = request.notes.as_deref().map(str::trim).filter(|notes| !notes.is_empty());
+        if notes.is_some_and(|notes| notes.chars().count() > MAX_NOTES_LENGTH) {
+            return Err(AppError::Validation(format!(
+                "Notes cannot be longer than {} characters",
+                MAX_NOTES_LENGTH
+            )));
+        }
+
+        let mut query = timesheet::Entity::find()
+            .filter(timesheet::Column::ApplicationId.eq(application_id))
+            .filter(timesheet::Column::Status.eq(TimesheetStatus::Approved))
+            .filter(timesheet::Column::InvoiceId.is_null())
+            .order_by_asc(timesheet::Column::Date)
+            .order_by_asc(timesheet::Column::StartTime);
+        let requested: HashSet<Uuid> = request.timesheet_ids.iter().copied().collect();
+        if !requested.is_empty() {
+            query = query.filter(timesheet::Column::Id.is_in(requested.iter().copied()));
+        }
+        let timesheets = query.all(&self.db).await?;
+        if timesheets.is_empty() {
+            return Err(AppError::Validation("There are no approved timesheets to invoice".to_string()));
+        }
+        if !requested.is_empty() && timesheets.len() != requested.len() {
+            return Err(AppError::Validation(
+                "timesheet_ids: Only approved timesheets that haven't been invoiced can be billed".to_string(),
+            ));
+        }
+
+        let job_type = JobType::from(job.job_type.clone());
+        let mut lines = timesheets
+            .iter()
+            .map(|timesheet| shift_line(timesheet, &job_type))
+            .collect::<Result<Vec<_>, _>>()?;
+        for allowance in &request.allowances {
+            lines.push(allowance.to_line()?);
+        }
+        let totals = InvoiceTotals::from_lines(&lines, gst_registered);
+        let encoded_lines = serde_json::to_value(&lines)
+            .map_err(|e| AppError::Internal(format!("Failed to encode invoice lines: {}", e)))?;
+
+        let issued_on = Utc::now().date_naive();
+        let txn = self.db.begin().await?;
+        // Numbers run per supplier: holding the supplier's row until commit stops two
+        // invoices being given the same one
+        let supplier = user::Entity::find_by_id(requester_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        let sequence = invoice::Entity::find()
+            .filter(invoice::Column::SupplierId.eq(requester_id))
+            .order_by_desc(invoice::Column::Sequence)
+            .one(&txn)
+            .await?
+            .map_or(1, |last| last.sequence + 1);
+
+        let invoice = invoice::ActiveModel {
+            number: Set(invoice_number(sequence)),
+            sequence: Set(sequence),
+            supplier_id: Set(requester_id),
+            customer_id: Set(job.created_by),
+            application_id: Set(application_id),
+            job_id: Set(job.id),
+            supplier_name: Set(supplier.full_name()),
+            supplier_abn: Set(abn),
+            gst_registered: Set(gst_registered),
+            customer_name: Set(job.pharmacy_name.clone()),
+            customer_address: Set(format!("{}, {}", job.address, job.full_location())),
+            lines: Set(encoded_lines),
+            subtotal: Set(totals.subtotal),
+            gst: Set(totals.gst),
+            total: Set(totals.total),
+            issued_on: Set(issued_on),
+            due_on: Set(issued_on + Duration::days(i64::from(payment_terms_days))),
+            payment_terms_days: Set(payment_terms_days as i32),
+            notes: Set(notes.map(str::to_string)),
+            ..invoice::ActiveModel::new()
+        }
+        .insert(&txn)
+        .await?;
+
+        // Claim the timesheets; another invoice may have taken one since they were read
+        let claimed = timesheet::Entity::update_many()
+            .col_expr(timesheet::Column::InvoiceId, Expr::value(invoice.id))
+            .filter(timesheet::Column::Id.is_in(timesheets.iter().map(|timesheet| timesheet.id)))
+            .filter(timesheet::Column::InvoiceId.is_null())
+            .exec(&txn)
+            .await?
+            .rows_affected;
+        if claimed != timesheets.len() as u64 {
+            return Err(AppError::Validation(
+                "Some of these timesheets have just been invoiced; please try again".to_string(),
+            ));
+        }
+        txn.commit().await?;
+
+        Ok(invoice)
+    }
+
+    /// Record that an invoice was paid, or reopen it (the supplier or an admin)
+    pub async fn set_status(
+        &self,
+        invoice_id: Uuid,
+        requester_id: Uuid,
+        is_admin: bool,
+        status: InvoiceStatus,
+    ) -> Result<invoice::Model, AppError> {
+        let invoice = self.get(invoice_id, requester_id, is_admin).await?;
+        if invoice.supplier_id != requester_id && !is_admin {
+            return Err(AppError::Forbidden);
+        }
+
+        let now = Utc::now();
+        let mut active: invoice::ActiveModel = invoice.clone().into();
+        match status {
+            InvoiceStatus::Paid => {
+                active.status = Set(InvoiceStatus::Paid);
+                active.paid_at = Set(Some(now.into()));
+            }
+            InvoiceStatus::Issued => {
+                active.status = Set(Self::unpaid_status(&invoice, now.date_naive()));
+                active.paid_at = Set(None);
+            }
+            InvoiceStatus::Overdue => {
+                return Err(AppError::Validation(
+                    "Invoices become overdue on their own once the due date passes".to_string(),
+                ));
+            }
+        }
+        active.updated_at = Set(now.into());
+        Ok(active.update(&self.db).await?)
+    }
+
+    /// Mark issued invoices due before `today` as overdue; returns how many changed
+    pub async fn mark_overdue(&self, today: NaiveDate) -> Result<u64, AppError> {
+        let result = invoice::Entity::update_many()
+            .col_expr(invoice::Column::Status, Expr::value(InvoiceStatus::Overdue))
+            .col_expr(invoice::Column::UpdatedAt, Expr::value(sea_orm::Value::from(Utc::now())))
+            .filter(invoice::Column::Status.eq(InvoiceStatus::Issued))
+            .filter(invoice::Column::DueOn.lt(today))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    fn unpaid_status(invoice: &invoice::Model, today: NaiveDate) -> InvoiceStatus {
+        if invoice.due_on < today {
+            InvoiceStatus::Overdue
+        } else {
+            InvoiceStatus::Issued
+        }
+    }
+}
+
+/// Periodically mark unpaid invoices past their due date as overdue
+pub fn spawn_overdue_task(service: InvoiceService) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(OVERDUE_SWEEP_INTERVAL);
+
+        loop {
+            interval.tick().await;
+            match service.mark_overdue(Utc::now().date_naive()).await {
+                Ok(0) => {}
+                Ok(marked) => tracing::info!("🧾 Marked {} invoices overdue", marked),
+                Err(e) => tracing::error!("Failed to mark overdue invoices: {}", e),
+            }
+        }
+    })
+}
+
+impl From<&AppState> for InvoiceService {
+    fn from(state: &AppState) -> Self {
+        Self::new(state.db.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invoice(gst_registered: bool) -> invoice::Model {
+        let now = Utc::now();
+        let lines = vec![InvoiceLine {
+            kind: InvoiceLineKind::Allowance,
+            description: "Travel <Dubbo & back>".to_string(),
+            quantity: Decimal::from(2),
+            unit_price: Decimal::from(50),
+            amount: Decimal::from(100),
+            timesheet_id: None,
+        }];
+        let totals = InvoiceTotals::from_lines(&lines, gst_registered);
+
+        invoice::Model {
+            id: Uuid::new_v4(),
+            number: invoice_number(3),
+            sequence: 3,
+            supplier_id: Uuid::new_v4(),
+            customer_id: Uuid::new_v4(),
+            application_id: Uuid::new_v4(),
+            job_id: Uuid::new_v4(),
+            supplier_name: "Sam Locum".to_string(),
+            supplier_abn: "51824753556".to_string(),
+            gst_registered,
+            customer_name: "Main St Pharmacy".to_string(),
+            customer_address: "1 Main St, Dubbo, NSW 2830".to_string(),
+            lines: serde_json::to_value(&lines).unwrap(),
+            subtotal: totals.subtotal,
+            gst: totals.gst,
+            total: totals.total,
+            issued_on: NaiveDate::from_ymd_opt(2025, 3, 10).unwrap(),
+            due_on: NaiveDate::from_ymd_opt(2025, 3, 24).unwrap(),
+            payment_terms_days: 14,
+            status: InvoiceStatus::Issued,
+            paid_at: None,
+            notes: None,
+            created_at: now.into(),
+            updated_at: now.into(),
+        }
+    }
+
+    #[test]
+    fn test_render_tax_invoice() {
+        let html = render_invoice_html(&invoice(true));
+
+        assert!(html.contains("<h1>Tax Invoice</h1>"));
+        assert!(html.contains("INV-00003"));
+        assert!(html.contains("ABN 51 824 753 556"));
+        assert!(html.contains("Travel &lt;Dubbo &amp; back&gt;"));
+        assert!(html.contains("GST (10%)"));
+        assert!(html.contains("$110.00"));
+        assert!(html.contains("Please pay by 24 March 2025"));
+    }
+
+    #[test]
+    fn test_render_invoice_without_gst() {
+        let html = render_invoice_html(&invoice(false));
+
+        assert!(html.contains("<h1>Invoice</h1>"));
+        assert!(html.contains("not registered for GST"));
+        assert!(!html.contains("$110.00"));
+    }
+
+    #[test]
+    fn test_reopened_invoice_past_due_is_overdue() {
+        let invoice = invoice(true);
+        assert_eq!(InvoiceService::unpaid_status(&invoice, invoice.due_on), InvoiceStatus::Issued);
+        assert_eq!(
+            InvoiceService::unpaid_status(&invoice, invoice.due_on + Duration::days(1)),
+            InvoiceStatus::Overdue
+        );
+    }
+}
@@ -0,0 +1,453 @@
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+use uuid::Uuid;
+use sea_orm::{
+    sea_query::{Condition, Expr}, ActiveModelBehavior, ActiveModelTrait, ColumnTrait,
+    DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+
+use crate::{
+    entities::{
+        audit_event::AuditAction,
+        job::{self, JobStatusDb},
+        job_status_change::{self, StatusChangeReason},
+    },
+    handlers::websocket::{self, JobNotification},
+    services::{
+        audit_service::{AuditEntry, AuditService},
+        notification_service::{NotificationEvent, NotificationService},
+        saved_search_service::SavedSearchService,
+        webhook_service::WebhookService,
+    },
+    AppError, AppState,
+};
+
+/// How often the scheduler looks for jobs that are due a transition
+pub const SCHEDULER_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// How far ahead of expiry job owners are warned
+pub const EXPIRY_WARNING_WINDOW_HOURS: i64 = 48;
+
+/// Source of the current time, swappable in tests
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The real wall clock
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A status change a job is due for at a given time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DueTransition {
+    pub to: JobStatusDb,
+    pub reason: StatusChangeReason,
+}
+
+/// Work done by one scheduler pass
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SchedulerReport {
+    pub published: usize,
+    pub closed: usize,
+    pub expired: usize,
+    pub warned: usize,
+}
+
+/// Decide which transition, if any, a job is due for at `now`
+pub fn due_transition(job: &job::Model, now: DateTime<Utc>) -> Option<DueTransition> {
+    if job.deleted_at.is_some() {
+        return None;
+    }
+
+    let ended = job.end_date <= now;
+    let deadline_passed = job.application_deadline.is_some_and(|deadline| deadline <= now);
+
+    match job.status {
+        JobStatusDb::Draft if job.publish_at.is_some_and(|at| at <= now) => Some(if ended {
+            // The whole listing window went by before it was due to publish
+            DueTransition { to: JobStatusDb::Expired, reason: StatusChangeReason::EndDatePassed }
+        } else if deadline_passed {
+            DueTransition { to: JobStatusDb::Closed, reason: StatusChangeReason::ApplicationDeadlinePassed }
+        } else {
+            DueTransition { to: JobStatusDb::Active, reason: StatusChangeReason::ScheduledPublish }
+        }),
+        JobStatusDb::Active | JobStatusDb::Closed if ended => Some(DueTransition {
+            to: JobStatusDb::Expired,
+            reason: StatusChangeReason::EndDatePassed,
+        }),
+        JobStatusDb::Active if deadline_passed => Some(DueTransition {
+            to: JobStatusDb::Closed,
+            reason: StatusChangeReason::ApplicationDeadlinePassed,
+        }),
+        _ => None,
+    }
+}
+
+/// Whether the job's owner should now be warned that it is about to expire
+pub fn needs_expiry_warning(job: &job::Model, now: DateTime<Utc>) -> bool {
+    job.is_active()
+        && job.expiry_warning_sent_at.is_none()
+        && job.end_date > now
+        && job.end_date <= now + Duration::hours(EXPIRY_WARNING_WINDOW_HOURS)
+}
+
+/// Moves jobs through their lifecycle as time passes: publishes scheduled
+/// drafts, closes jobs past their application deadline, expires jobs past
+/// their end date and warns owners shortly before expiry
+#[derive(Clone)]
+pub struct JobScheduler {
+    db: DatabaseConnection,
+    clock: Arc<dyn Clock>,
+    notifications: NotificationService,
+}
+
+impl JobScheduler {
+    pub fn new(db: DatabaseConnection, clock: Arc<dyn Clock>, notifications: NotificationService) -> Self {
+        Self { db, clock, notifications }
+    }
+
+    /// Run every due transition and expiry warning once
+    pub async fn run_once(&self) -> Result<SchedulerReport, AppError> {
+        let now = self.clock.now();
+        let mut report = SchedulerReport::default();
+
+        for job in self.find_due_jobs(now).await? {
+            let Some(transition) = due_transition(&job, now) else { continue };
+            if !self.apply(&job, &transition).await? {
+                continue;
+            }
+
+            match transition.to {
+                JobStatusDb::Active => report.published += 1,
+                JobStatusDb::Closed => report.closed += 1,
+                JobStatusDb::Expired => report.expired += 1,
+                _ => {}
+            }
+        }
+
+        report.warned = self.send_expiry_warnings(now).await?;
+        Ok(report)
+    }
+
+    /// Set or clear when a draft job should go live
+    pub async fn schedule_publish(
+        &self,
+        job_id: Uuid,
+        user_id: Uuid,
+        is_admin: bool,
+        publish_at: Option<DateTime<Utc>>,
+    ) -> Result<job::Model, AppError> {
+        let job = job::Entity::find_by_id(job_id)
+            .filter(job::Column::DeletedAt.is_null())
+            .one(&self.db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+
+        if job.created_by != user_id && !is_admin {
+            return Err(AppError::Forbidden);
+        }
+        if job.status != JobStatusDb::Draft {
+            return Err(AppError::Validation("Only draft jobs can be scheduled for publishing".to_string()));
+        }
+        if publish_at.is_some_and(|at| at >= job.end_date) {
+            return Err(AppError::Validation("Publish time must be before the job's end date".to_string()));
+        }
+
+        let mut active: job::ActiveModel = job.into();
+        active.publish_at = Set(publish_at.map(Into::into));
+        active.updated_at = Set(self.clock.now().into());
+        Ok(active.update(&self.db).await?)
+    }
+
+    async fn find_due_jobs(&self, now: DateTime<Utc>) -> Result<Vec<job::Model>, AppError> {
+        let due = Condition::any()
+            .add(
+                Condition::all()
+                    .add(job::Column::Status.eq(JobStatusDb::Draft))
+                    .add(job::Column::PublishAt.lte(now)),
+            )
+            .add(
+                Condition::all()
+                    .add(job::Column::Status.is_in([JobStatusDb::Active, JobStatusDb::Closed]))
+                    .add(job::Column::EndDate.lte(now)),
+            )
+            .add(
+                Condition::all()
+                    .add(job::Column::Status.eq(JobStatusDb::Active))
+                    .add(job::Column::ApplicationDeadline.lte(now)),
+            );
+
+        Ok(job::Entity::find()
+            .filter(job::Column::DeletedAt.is_null())
+            .filter(due)
+            .order_by_asc(job::Column::EndDate)
+            .all(&self.db)
+            .await?)
+    }
+
+    /// Apply a transition and record it; returns false if another instance got there first
+    async fn apply(&self, job: &job::Model, transition: &DueTransition) -> Result<bool, AppError> {
+        let now = self.clock.now();
+        let txn = self.db.begin().await?;
+
+        // Only move the job if it is still in the status we decided from
+        let updated = job::Entity::update_many()
+            .col_expr(job::Column::Status, Expr::value(transition.to.clone()))
+            .col_expr(job::Column::UpdatedAt, Expr::value(sea_orm::Value::from(now)))
+            .filter(job::Column::Id.eq(job.id))
+            .filter(job::Column::Status.eq(job.status.clone()))
+            .exec(&txn)
+            .await?;
+
+        if updated.rows_affected == 0 {
+            txn.rollback().await?;
+            return Ok(false);
+        }
+
+        job_status_change::ActiveModel {
+            job_id: Set(job.id),
+            from_status: Set(job.status.clone()),
+            to_status: Set(transition.to.clone()),
+            reason: Set(transition.reason),
+            changed_by: Set(None),
+            ..job_status_change::ActiveModel::new()
+        }
+        .insert(&txn)
+        .await?;
+
+        AuditService::new(self.db.clone())
+            .record_in(
+                &txn,
+                AuditEntry::new(AuditAction::JobStatusChanged, "job", job.id).change(
+                    &json!({ "status": job.status }),
+                    &json!({ "status": transition.to, "reason": transition.reason }),
+                ),
+            )
+            .await?;
+
+        // Nothing after this point may fail: the transition has happened
+        txn.commit().await?;
+
+        tracing::info!(
+            "⏰ Job {} moved from {:?} to {:?} ({:?})",
+            job.id, job.status, transition.to, transition.reason
+        );
+
+        if transition.to == JobStatusDb::Active {
+            websocket::broadcast_job_posted(JobNotification::from_job(job)).await;
+            SavedSearchService::new(self.db.clone()).job_published(job).await;
+            WebhookService::new(self.db.clone()).job_published(job).await;
+        }
+        websocket::broadcast_job_status_changed(
+            job.id,
+            format!("{:?}", job.status),
+            format!("{:?}", transition.to),
+            format!("{:?}", transition.reason),
+        )
+        .await;
+
+        Ok(true)
+    }
+
+    async fn send_expiry_warnings(&self, now: DateTime<Utc>) -> Result<usize, AppError> {
+        let window_end = now + Duration::hours(EXPIRY_WARNING_WINDOW_HOURS);
+        let expiring = job::Entity::find()
+            .filter(job::Column::Status.eq(JobStatusDb::Active))
+            .filter(job::Column::DeletedAt.is_null())
+            .filter(job::Column::ExpiryWarningSentAt.is_null())
+            .filter(job::Column::EndDate.gt(now))
+            .filter(job::Column::EndDate.lte(window_end))
+            .all(&self.db)
+            .await?;
+
+        let mut warned = 0;
+        for job in expiring.into_iter().filter(|job| needs_expiry_warning(job, now)) {
+            // Claim the warning so only one instance sends it
+            let claimed = job::Entity::update_many()
+                .col_expr(job::Column::ExpiryWarningSentAt, Expr::value(sea_orm::Value::from(now)))
+                .filter(job::Column::Id.eq(job.id))
+                .filter(job::Column::ExpiryWarningSentAt.is_null())
+                .exec(&self.db)
+                .await?;
+
+            if claimed.rows_affected == 1 {
+                self.notifications.dispatch_all(vec![NotificationEvent::job_expiring(&job)]).await;
+                warned += 1;
+            }
+        }
+
+        Ok(warned)
+    }
+}
+
+/// Run the scheduler in the background for the life of the process
+pub fn spawn_scheduler(scheduler: JobScheduler) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SCHEDULER_INTERVAL);
+
+        loop {
+            interval.tick().await;
+            match scheduler.run_once().await {
+                Ok(report) if report != SchedulerReport::default() => {
+                    tracing::info!("⏰ Job scheduler: {:?}", report)
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Job scheduler pass failed: {}", e),
+            }
+        }
+    })
+}
+
+impl From<&AppState> for JobScheduler {
+    fn from(state: &AppState) -> Self {
+        Self::new(
+            state.db.clone(),
+            Arc::new(SystemClock),
+            NotificationService::from(state),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use std::sync::Mutex;
+
+    /// A clock tests can move by hand
+    struct ManualClock(Mutex<DateTime<Utc>>);
+
+    impl ManualClock {
+        fn advance(&self, by: Duration) {
+            *self.0.lock().unwrap() += by;
+        }
+    }
+
+    impl Clock for ManualClock {
+        fn now(&self) -> DateTime<Utc> {
+            *self.0.lock().unwrap()
+        }
+    }
+
+    fn job_at(clock: &ManualClock, status: JobStatusDb) -> job::Model {
+        let now = clock.now();
+        job::Model {
+            id: Uuid::new_v4(),
+            title: "Locum Pharmacist".to_string(),
+            description: "Weekend cover".to_string(),
+            pharmacy_name: "Glenelg Beach Pharmacy".to_string(),
+            hourly_rate: Decimal::new(6500, 2),
+            address: "15 Jetty Road".to_string(),
+            suburb: "Glenelg".to_string(),
+            postcode: "5045".to_string(),
+            state: "SA".to_string(),
+            latitude: None,
+            longitude: None,
+            start_date: (now + Duration::days(5)).into(),
+            end_date: (now + Duration::days(10)).into(),
+            start_time: "09:00".to_string(),
+            end_time: "17:00".to_string(),
+            job_type: job::JobTypeDb::Pharmacist,
+            status,
+            is_urgent: false,
+            requirements_text: None,
+            benefits_text: None,
+            contact_email: None,
+            contact_phone: None,
+            application_deadline: Some((now + Duration::days(3)).into()),
+            publish_at: Some((now + Duration::days(1)).into()),
+            expiry_warning_sent_at: None,
+            view_count: 0,
+            application_count: 0,
+            created_by: Uuid::new_v4(),
+            created_at: now.into(),
+            updated_at: now.into(),
+            deleted_at: None,
+        }
+    }
+
+    fn clock() -> ManualClock {
+        ManualClock(Mutex::new(Utc::now()))
+    }
+
+    #[test]
+    fn drafts_publish_when_due() {
+        let clock = clock();
+        let draft = job_at(&clock, JobStatusDb::Draft);
+        assert_eq!(due_transition(&draft, clock.now()), None);
+
+        clock.advance(Duration::days(1));
+        assert_eq!(
+            due_transition(&draft, clock.now()),
+            Some(DueTransition { to: JobStatusDb::Active, reason: StatusChangeReason::ScheduledPublish })
+        );
+
+        let unscheduled = job::Model { publish_at: None, ..draft };
+        assert_eq!(due_transition(&unscheduled, clock.now()), None);
+    }
+
+    #[test]
+    fn active_jobs_close_then_expire() {
+        let clock = clock();
+        let job = job_at(&clock, JobStatusDb::Active);
+        assert_eq!(due_transition(&job, clock.now()), None);
+
+        clock.advance(Duration::days(3));
+        assert_eq!(
+            due_transition(&job, clock.now()).map(|t| t.to),
+            Some(JobStatusDb::Closed)
+        );
+
+        let closed = job::Model { status: JobStatusDb::Closed, ..job };
+        assert_eq!(due_transition(&closed, clock.now()), None);
+
+        clock.advance(Duration::days(7));
+        assert_eq!(
+            due_transition(&closed, clock.now()),
+            Some(DueTransition { to: JobStatusDb::Expired, reason: StatusChangeReason::EndDatePassed })
+        );
+    }
+
+    #[test]
+    fn stale_drafts_skip_straight_to_expired() {
+        let clock = clock();
+        let draft = job_at(&clock, JobStatusDb::Draft);
+        clock.advance(Duration::days(11));
+        assert_eq!(due_transition(&draft, clock.now()).map(|t| t.to), Some(JobStatusDb::Expired));
+    }
+
+    #[test]
+    fn filled_and_deleted_jobs_are_left_alone() {
+        let clock = clock();
+        let filled = job_at(&clock, JobStatusDb::Filled);
+        let deleted = job::Model {
+            deleted_at: Some(clock.now().into()),
+            ..job_at(&clock, JobStatusDb::Active)
+        };
+        clock.advance(Duration::days(30));
+        assert_eq!(due_transition(&filled, clock.now()), None);
+        assert_eq!(due_transition(&deleted, clock.now()), None);
+    }
+
+    #[test]
+    fn expiry_warning_is_sent_once_inside_the_window() {
+        let clock = clock();
+        let job = job_at(&clock, JobStatusDb::Active);
+        assert!(!needs_expiry_warning(&job, clock.now()));
+
+        clock.advance(Duration::days(8) + Duration::minutes(1));
+        assert!(needs_expiry_warning(&job, clock.now()));
+
+        let warned = job::Model { expiry_warning_sent_at: Some(clock.now().into()), ..job.clone() };
+        assert!(!needs_expiry_warning(&warned, clock.now()));
+
+        clock.advance(Duration::days(2));
+        assert!(!needs_expiry_warning(&job, clock.now()));
+    }
+}
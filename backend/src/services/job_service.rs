@@ -1,32 +1,42 @@
+use std::sync::OnceLock;
+use chrono::{SubsecRound, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
 use uuid::Uuid;
-use shared::types::{Job as SharedJob, JobFilters, CreateJobRequest, JobType, JobStatus, JobId, UserId, Postcode, AustralianState};
+use shared::types::{Job as SharedJob, JobFilters, CreateJobRequest, JobType, JobStatus, JobId, UserId, Postcode, AustralianState, ListResponse};
 use shared::errors::AppError;
+use shared::shifts::ShiftSchedule;
+
+use crate::{
+    entities::job,
+    handlers::websocket::{self, JobNotification},
+    repository::{cursor_page_of, Cursor},
+};
 
 pub struct JobService;
 
 impl JobService {
-    /// Get all jobs with optional filtering and pagination (Demo mode)
+    /// Get jobs with optional filtering, newest first, one page after `after` (Demo mode)
     pub async fn list_jobs(
         _filters: JobFilters,
-        page: Option<u32>,
-        limit: Option<u32>,
-    ) -> Result<(Vec<SharedJob>, u64), shared::errors::AppError> {
-        let _page = page.unwrap_or(1);
-        let _limit = limit.unwrap_or(20);
-        
+        after: Option<Cursor>,
+        limit: u32,
+    ) -> Result<ListResponse<SharedJob>, shared::errors::AppError> {
         // Demo mode: Return sample data
         // TODO: Replace with actual database implementation
         let jobs = Self::get_sample_jobs();
-        let total = jobs.len() as u64;
         
-        Ok((jobs, total))
+        Ok(cursor_page_of(jobs, after, limit, |job| Cursor::new(job.created_at, job.id.0)))
     }
     
-    /// Get a specific job by ID (Demo mode)
+    /// Get a specific job by ID: the stored job, or in demo mode a sample one
     pub async fn get_job(
+        db: &DatabaseConnection,
         job_id: Uuid,
-    ) -> Result<Option<SharedJob>, shared::errors::AppError> {
-        // Demo mode: Find in sample data
+    ) -> Result<Option<SharedJob>, crate::AppError> {
+        if let Some(job) = Self::find_stored(db, job_id).await? {
+            return Ok(Some(job.to_job()?));
+        }
         let jobs = Self::get_sample_jobs();
         Ok(jobs.into_iter().find(|j| j.id == JobId(job_id)))
     }
@@ -34,11 +44,110 @@ impl JobService {
     /// Create a new job posting (Demo mode)
     pub async fn create_job(
         request: CreateJobRequest,
-        _user_id: Uuid,
+        user_id: Uuid,
     ) -> Result<SharedJob, shared::errors::AppError> {
         // Demo mode: Create a new job with provided data
+        Self::job_from_request(JobId::new(), request, UserId(user_id))
+    }
+    
+    /// Update a stored job (its poster or an admin). `precondition` sees the job as
+    /// a GET returns it and may refuse the write with 412. The write only lands if
+    /// the row is still the version that was checked, so of two writers holding the
+    /// same ETag only the first succeeds. Demo sample jobs are read-only: they are
+    /// not stored, so updating one is not found.
+    pub async fn update_job(
+        db: &DatabaseConnection,
+        job_id: Uuid,
+        user_id: Uuid,
+        is_admin: bool,
+        request: CreateJobRequest,
+        precondition: impl FnOnce(&SharedJob) -> bool,
+    ) -> Result<Option<SharedJob>, crate::AppError> {
+        let Some(current) = Self::find_stored(db, job_id).await? else {
+            return Ok(None);
+        };
+        Self::check_can_edit(&current, user_id, is_admin)?;
+        if !precondition(&current.to_job()?) {
+            return Err(crate::AppError::PreconditionFailed);
+        }
+
+        let next = Self::job_from_request(JobId(job_id), request, UserId(current.created_by))?;
+        let hourly_rate = Decimal::try_from(next.hourly_rate)
+            .map_err(|_| shared::errors::AppError::validation("hourly_rate", "Invalid hourly rate"))?
+            .round_dp(2);
+        let changes = job::ActiveModel {
+            title: Set(next.title),
+            description: Set(next.description),
+            pharmacy_name: Set(next.pharmacy_name),
+            hourly_rate: Set(hourly_rate),
+            address: Set(next.address),
+            suburb: Set(next.suburb),
+            postcode: Set(next.postcode.to_string()),
+            state: Set(next.state.to_string()),
+            latitude: Set(next.latitude),
+            longitude: Set(next.longitude),
+            start_date: Set(next.start_date.into()),
+            end_date: Set(next.end_date.into()),
+            start_time: Set(next.start_time),
+            end_time: Set(next.end_time),
+            job_type: Set(next.job_type.into()),
+            is_urgent: Set(next.is_urgent),
+            // Stored to the microsecond, so the next conditional write compares exactly
+            updated_at: Set(Utc::now().trunc_subsecs(6).into()),
+            ..Default::default()
+        };
+
+        let mut updated = job::Entity::update_many()
+            .set(changes)
+            .filter(job::Column::Id.eq(job_id))
+            .filter(job::Column::UpdatedAt.eq(current.updated_at))
+            .filter(job::Column::DeletedAt.is_null())
+            .exec_with_returning(db)
+            .await?;
+        match updated.pop() {
+            Some(job) => {
+                websocket::broadcast_job_updated(JobNotification::from_job(&job)).await;
+                Ok(Some(job.to_job()?))
+            }
+            // Changed or deleted since it was read
+            None => Err(crate::AppError::PreconditionFailed),
+        }
+    }
+    
+    /// Delete a job (Demo mode)
+    pub async fn delete_job(
+        job_id: Uuid,
+    ) -> Result<bool, shared::errors::AppError> {
+        // Demo mode: Check if job exists in sample data
+        let jobs = Self::get_sample_jobs();
+        Ok(jobs.iter().any(|j| j.id == JobId(job_id)))
+    }
+    
+    /// Only a job's poster or an admin may change it
+    pub(crate) fn check_can_edit(job: &job::Model, user_id: Uuid, is_admin: bool) -> Result<(), crate::AppError> {
+        if job.created_by != user_id && !is_admin {
+            return Err(crate::AppError::Forbidden);
+        }
+        Ok(())
+    }
+
+    /// A stored job that hasn't been deleted
+    async fn find_stored(db: &DatabaseConnection, job_id: Uuid) -> Result<Option<job::Model>, crate::AppError> {
+        Ok(job::Entity::find_by_id(job_id)
+            .filter(job::Column::DeletedAt.is_null())
+            .one(db)
+            .await?)
+    }
+
+    /// Validate a posting and lay it out as a job, with dates and hours taken from
+    /// its shift series if it has one
+    fn job_from_request(
+        id: JobId,
+        request: CreateJobRequest,
+        created_by: UserId,
+    ) -> Result<SharedJob, shared::errors::AppError> {
         let mut job = SharedJob {
-            id: JobId::new(),
+            id,
             title: request.title,
             description: request.description,
             pharmacy_name: request.pharmacy_name,
@@ -59,74 +168,45 @@ impl JobService {
             status: JobStatus::Active,
             is_urgent: request.is_urgent,
             distance_km: None,
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
-            created_by: UserId(_user_id),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            created_by,
         };
-        
+        Self::apply_shift_schedule(&mut job, request.shifts.as_ref())?;
+
         // Fix location if needed
         use crate::services::LocationService;
         LocationService::ensure_valid_location(&mut job)?;
-        
+
         Ok(job)
     }
-    
-    /// Update an existing job (Demo mode)
-    pub async fn update_job(
-        job_id: Uuid,
-        request: CreateJobRequest,
-    ) -> Result<Option<SharedJob>, shared::errors::AppError> {
-        // Demo mode: Create updated job if it exists in sample data
-        let jobs = Self::get_sample_jobs();
-        if jobs.iter().any(|j| j.id == JobId(job_id)) {
-            let mut job = SharedJob {
-                id: JobId(job_id),
-                title: request.title,
-                description: request.description,
-                pharmacy_name: request.pharmacy_name,
-                hourly_rate: request.hourly_rate,
-                address: request.address,
-                suburb: request.suburb,
-                postcode: Postcode::new(request.postcode)?,
-                state: AustralianState::from_abbrev(&request.state).ok_or_else(|| {
-                    shared::errors::AppError::validation("state", "Invalid Australian state")
-                })?,
-                latitude: request.latitude,
-                longitude: request.longitude,
-                start_date: request.start_date,
-                end_date: request.end_date,
-                start_time: request.start_time,
-                end_time: request.end_time,
-                job_type: request.job_type,
-                status: JobStatus::Active,
-                is_urgent: request.is_urgent,
-                distance_km: None,
-                created_at: chrono::Utc::now() - chrono::Duration::days(5),
-                updated_at: chrono::Utc::now(),
-                created_by: UserId(Uuid::new_v4()),
-            };
-            
-            // Fix location if needed
-            use crate::services::LocationService;
-            LocationService::ensure_valid_location(&mut job)?;
-            
-            Ok(Some(job))
-        } else {
-            Ok(None)
+
+    /// Stretch a job's dates and hours over its shift series, if it has one
+    fn apply_shift_schedule(
+        job: &mut SharedJob,
+        schedule: Option<&ShiftSchedule>,
+    ) -> Result<(), shared::errors::AppError> {
+        let Some(schedule) = schedule else {
+            return Ok(());
+        };
+        let shifts = schedule.generate()?;
+        if let Some((starts_at, ends_at)) = shared::shifts::span(&shifts) {
+            job.start_date = starts_at.and_utc();
+            job.end_date = ends_at.and_utc();
+            job.start_time = shifts[0].start_time.clone();
+            job.end_time = shifts[0].end_time.clone();
         }
+        Ok(())
     }
     
-    /// Delete a job (Demo mode)
-    pub async fn delete_job(
-        job_id: Uuid,
-    ) -> Result<bool, shared::errors::AppError> {
-        // Demo mode: Check if job exists in sample data
-        let jobs = Self::get_sample_jobs();
-        Ok(jobs.iter().any(|j| j.id == JobId(job_id)))
-    }
-    
-    /// Get sample jobs for demo mode
+    /// Get sample jobs for demo mode. Built once, so repeated reads of a job return
+    /// the same representation and its ETag and list cursors stay valid.
     fn get_sample_jobs() -> Vec<SharedJob> {
+        static SAMPLE_JOBS: OnceLock<Vec<SharedJob>> = OnceLock::new();
+        SAMPLE_JOBS.get_or_init(Self::build_sample_jobs).clone()
+    }
+
+    fn build_sample_jobs() -> Vec<SharedJob> {
         vec![
             SharedJob {
                 id: JobId(Uuid::parse_str("550e8400-e29b-41d4-a716-446655440001").unwrap()),
@@ -250,4 +330,57 @@ impl JobService {
             },
         ]
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored_job(created_by: Uuid) -> job::Model {
+        let now = Utc::now();
+        job::Model {
+            id: Uuid::new_v4(),
+            title: "Locum Pharmacist".to_string(),
+            description: "Weekend cover".to_string(),
+            pharmacy_name: "Glenelg Beach Pharmacy".to_string(),
+            hourly_rate: Decimal::new(6500, 2),
+            address: "15 Jetty Road".to_string(),
+            suburb: "Glenelg".to_string(),
+            postcode: "5045".to_string(),
+            state: "SA".to_string(),
+            latitude: None,
+            longitude: None,
+            start_date: now.into(),
+            end_date: now.into(),
+            start_time: "09:00".to_string(),
+            end_time: "17:00".to_string(),
+            job_type: job::JobTypeDb::Pharmacist,
+            status: job::JobStatusDb::Active,
+            is_urgent: false,
+            requirements_text: None,
+            benefits_text: None,
+            contact_email: None,
+            contact_phone: None,
+            application_deadline: None,
+            publish_at: None,
+            expiry_warning_sent_at: None,
+            view_count: 0,
+            application_count: 0,
+            created_by,
+            created_at: now.into(),
+            updated_at: now.into(),
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn only_the_poster_or_an_admin_can_update_a_job() {
+        let poster = Uuid::new_v4();
+        let job = stored_job(poster);
+
+        assert!(JobService::check_can_edit(&job, poster, false).is_ok());
+        assert!(JobService::check_can_edit(&job, Uuid::new_v4(), true).is_ok());
+        let refused = JobService::check_can_edit(&job, Uuid::new_v4(), false).unwrap_err();
+        assert_eq!(refused.to_problem().status, 403);
+    }
+}
@@ -0,0 +1,318 @@
+use chrono::{Duration, Utc};
+use sea_orm::{
+    sea_query::Condition, ActiveModelBehavior, ActiveModelTrait, ColumnTrait, DatabaseConnection,
+    EntityTrait, QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+use shared::job_templates::{
+    days_to_next_weekday, first_day, shift_request, template_name, CreateJobTemplateRequest,
+    RepostDates, TemplateFromJobRequest, UpdateJobTemplateRequest, DEFAULT_CLONE_OFFSET_DAYS,
+};
+use shared::types::{AustralianState, CreateJobRequest};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::{job, job_shift, job_template, tenant_users},
+    middleware::validation::field_errors,
+    AppError, AppState,
+};
+
+/// Check a posting is complete enough to post before saving it as a template
+fn validate_job(job: &CreateJobRequest) -> Result<(), AppError> {
+    job.validate()
+        .map_err(|errors| AppError::InvalidFields(field_errors(&errors)))?;
+    if AustralianState::from_abbrev(&job.state).is_none() {
+        return Err(AppError::Validation("state: Invalid Australian state".to_string()));
+    }
+    if let Some(schedule) = &job.shifts {
+        schedule.generate()?;
+    }
+    Ok(())
+}
+
+fn job_value(job: &CreateJobRequest) -> Result<serde_json::Value, AppError> {
+    serde_json::to_value(job).map_err(|e| AppError::Internal(format!("Failed to store job template: {}", e)))
+}
+
+#[derive(Clone)]
+pub struct JobTemplateService {
+    db: DatabaseConnection,
+}
+
+impl JobTemplateService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Templates the user can post from: their own and those shared with their tenants,
+    /// most used first
+    pub async fn list(&self, requester_id: Uuid) -> Result<Vec<job_template::Model>, AppError> {
+        let tenants = self.tenant_ids(requester_id).await?;
+        let mut visible = Condition::any().add(job_template::Column::OwnerId.eq(requester_id));
+        if !tenants.is_empty() {
+            visible = visible.add(job_template::Column::TenantId.is_in(tenants));
+        }
+
+        Ok(job_template::Entity::find()
+            .filter(visible)
+            .order_by_desc(job_template::Column::UseCount)
+            .order_by_asc(job_template::Column::Name)
+            .all(&self.db)
+            .await?)
+    }
+
+    /// A template visible to the requester
+    pub async fn get(
+        &self,
+        template_id: Uuid,
+        requester_id: Uuid,
+        is_admin: bool,
+    ) -> Result<job_template::Model, AppError> {
+        let template = job_template::Entity::find_by_id(template_id)
+            .one(&self.db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        if template.owner_id == requester_id || is_admin {
+            return Ok(template);
+        }
+        match template.tenant_id {
+            Some(tenant_id) if self.tenant_ids(requester_id).await?.contains(&tenant_id) => Ok(template),
+            _ => Err(AppError::NotFound),
+        }
+    }
+
+    /// Save a posting from the job wizard as a template
+    pub async fn create(
+        &self,
+        requester_id: Uuid,
+        is_admin: bool,
+        request: &CreateJobTemplateRequest,
+    ) -> Result<job_template::Model, AppError> {
+        validate_job(&request.job)?;
+        self.insert(requester_id, is_admin, &request.name, request.tenant_id, None, &request.job)
+            .await
+    }
+
+    /// Save an existing job, with its shifts, as a template (job owner or admin)
+    pub async fn create_from_job(
+        &self,
+        job_id: Uuid,
+        requester_id: Uuid,
+        is_admin: bool,
+        request: &TemplateFromJobRequest,
+    ) -> Result<job_template::Model, AppError> {
+        let (job, shifts) = self.owned_job(job_id, requester_id, is_admin).await?;
+        let posting = job.to_create_request(&shifts);
+        self.insert(requester_id, is_admin, &request.name, request.tenant_id, Some(job.id), &posting)
+            .await
+    }
+
+    /// Rename a template or replace its posting (template owner or admin)
+    pub async fn update(
+        &self,
+        template_id: Uuid,
+        requester_id: Uuid,
+        is_admin: bool,
+        request: &UpdateJobTemplateRequest,
+    ) -> Result<job_template::Model, AppError> {
+        let template = self.get(template_id, requester_id, is_admin).await?;
+        if template.owner_id != requester_id && !is_admin {
+            return Err(AppError::Forbidden);
+        }
+
+        let mut active: job_template::ActiveModel = template.clone().into();
+        if let Some(name) = &request.name {
+            let name = template_name(name)?;
+            if name != template.name {
+                self.ensure_name_free(template.owner_id, &name).await?;
+            }
+            active.name = Set(name);
+        }
+        if let Some(job) = &request.job {
+            validate_job(job)?;
+            active.job = Set(job_value(job)?);
+        }
+        active.updated_at = Set(Utc::now().into());
+        Ok(active.update(&self.db).await?)
+    }
+
+    /// Delete a template (template owner or admin)
+    pub async fn delete(&self, template_id: Uuid, requester_id: Uuid, is_admin: bool) -> Result<(), AppError> {
+        let template = self.get(template_id, requester_id, is_admin).await?;
+        if template.owner_id != requester_id && !is_admin {
+            return Err(AppError::Forbidden);
+        }
+        job_template::Entity::delete_by_id(template.id).exec(&self.db).await?;
+        Ok(())
+    }
+
+    /// A new posting from a template with its dates moved to `dates`, ready to submit.
+    /// Without a start date it lands on the template's weekday in the coming week.
+    pub async fn prefill(
+        &self,
+        template_id: Uuid,
+        requester_id: Uuid,
+        is_admin: bool,
+        dates: &RepostDates,
+    ) -> Result<(job_template::Model, CreateJobRequest), AppError> {
+        let template = self.get(template_id, requester_id, is_admin).await?;
+        let posting = template
+            .job()
+            .map_err(|e| AppError::Internal(format!("Unreadable job template {}: {}", template.id, e)))?;
+        let first = first_day(&posting);
+        let days = dates.offset(first, days_to_next_weekday(first, Utc::now().date_naive()))?;
+
+        let mut active: job_template::ActiveModel = template.clone().into();
+        active.use_count = Set(template.use_count + 1);
+        active.last_used_at = Set(Some(Utc::now().into()));
+        let template = active.update(&self.db).await?;
+
+        Ok((template, shift_request(&posting, days)))
+    }
+
+    /// Copy a job and its shifts as a new draft with the dates moved to `dates`, a
+    /// week later by default (job owner or admin). The copy starts with no applicants
+    /// and every shift open.
+    pub async fn clone_job(
+        &self,
+        job_id: Uuid,
+        requester_id: Uuid,
+        is_admin: bool,
+        dates: &RepostDates,
+    ) -> Result<(job::Model, Vec<job_shift::Model>), AppError> {
+        let (source, shifts) = self.owned_job(job_id, requester_id, is_admin).await?;
+        let days = dates.offset(source.start_date.date_naive(), DEFAULT_CLONE_OFFSET_DAYS)?;
+        let offset = Duration::days(days);
+        if source.end_date + offset <= Utc::now() {
+            return Err(AppError::Validation("The copied job would already have ended".to_string()));
+        }
+
+        let txn = self.db.begin().await?;
+        let copy = job::ActiveModel {
+            title: Set(source.title.clone()),
+            description: Set(source.description.clone()),
+            pharmacy_name: Set(source.pharmacy_name.clone()),
+            hourly_rate: Set(source.hourly_rate),
+            address: Set(source.address.clone()),
+            suburb: Set(source.suburb.clone()),
+            postcode: Set(source.postcode.clone()),
+            state: Set(source.state.clone()),
+            latitude: Set(source.latitude),
+            longitude: Set(source.longitude),
+            start_date: Set(source.start_date + offset),
+            end_date: Set(source.end_date + offset),
+            start_time: Set(source.start_time.clone()),
+            end_time: Set(source.end_time.clone()),
+            job_type: Set(source.job_type.clone()),
+            is_urgent: Set(source.is_urgent),
+            requirements_text: Set(source.requirements_text.clone()),
+            benefits_text: Set(source.benefits_text.clone()),
+            contact_email: Set(source.contact_email.clone()),
+            contact_phone: Set(source.contact_phone.clone()),
+            application_deadline: Set(source.application_deadline.map(|deadline| deadline + offset)),
+            created_by: Set(requester_id),
+            ..job::ActiveModel::new()
+        }
+        .insert(&txn)
+        .await?;
+
+        let mut copied = Vec::with_capacity(shifts.len());
+        for shift in &shifts {
+            copied.push(
+                job_shift::ActiveModel {
+                    job_id: Set(copy.id),
+                    date: Set(shift.date + offset),
+                    start_time: Set(shift.start_time),
+                    end_time: Set(shift.end_time),
+                    ..job_shift::ActiveModel::new()
+                }
+                .insert(&txn)
+                .await?,
+            );
+        }
+
+        txn.commit().await?;
+        Ok((copy, copied))
+    }
+
+    async fn insert(
+        &self,
+        owner_id: Uuid,
+        is_admin: bool,
+        name: &str,
+        tenant_id: Option<Uuid>,
+        source_job_id: Option<Uuid>,
+        posting: &CreateJobRequest,
+    ) -> Result<job_template::Model, AppError> {
+        let name = template_name(name)?;
+        if let Some(tenant_id) = tenant_id {
+            if !is_admin && !self.tenant_ids(owner_id).await?.contains(&tenant_id) {
+                return Err(AppError::Forbidden);
+            }
+        }
+        self.ensure_name_free(owner_id, &name).await?;
+
+        Ok(job_template::ActiveModel {
+            owner_id: Set(owner_id),
+            tenant_id: Set(tenant_id),
+            source_job_id: Set(source_job_id),
+            name: Set(name),
+            job: Set(job_value(posting)?),
+            ..job_template::ActiveModel::new()
+        }
+        .insert(&self.db)
+        .await?)
+    }
+
+    async fn ensure_name_free(&self, owner_id: Uuid, name: &str) -> Result<(), AppError> {
+        let existing = job_template::Entity::find()
+            .filter(job_template::Column::OwnerId.eq(owner_id))
+            .filter(job_template::Column::Name.eq(name))
+            .one(&self.db)
+            .await?;
+        if existing.is_some() {
+            return Err(AppError::Validation(format!("name: You already have a template called \"{}\"", name)));
+        }
+        Ok(())
+    }
+
+    async fn owned_job(
+        &self,
+        job_id: Uuid,
+        requester_id: Uuid,
+        is_admin: bool,
+    ) -> Result<(job::Model, Vec<job_shift::Model>), AppError> {
+        let job = job::Entity::find_by_id(job_id)
+            .filter(job::Column::DeletedAt.is_null())
+            .one(&self.db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        if job.created_by != requester_id && !is_admin {
+            return Err(AppError::Forbidden);
+        }
+
+        let shifts = job_shift::Entity::find()
+            .filter(job_shift::Column::JobId.eq(job_id))
+            .order_by_asc(job_shift::Column::Date)
+            .order_by_asc(job_shift::Column::StartTime)
+            .all(&self.db)
+            .await?;
+        Ok((job, shifts))
+    }
+
+    async fn tenant_ids(&self, user_id: Uuid) -> Result<Vec<Uuid>, AppError> {
+        Ok(tenant_users::Entity::find()
+            .filter(tenant_users::Column::UserId.eq(user_id))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|membership| membership.tenant_id)
+            .collect())
+    }
+}
+
+impl From<&AppState> for JobTemplateService {
+    fn from(state: &AppState) -> Self {
+        Self::new(state.db.clone())
+    }
+}
@@ -0,0 +1,459 @@
+use std::collections::HashMap;
+use chrono::{Duration, NaiveDate, NaiveTime, Utc};
+use num_traits::ToPrimitive;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use serde::Serialize;
+use shared::availability::{self, parse_slot_time, AvailabilitySlot, AvailabilityType};
+use shared::locations::resolve_coordinates;
+use shared::types::JobType;
+use uuid::Uuid;
+
+use crate::{
+    entities::{
+        job::{self, JobStatusDb},
+        professional_profile::{self, DEFAULT_MAX_TRAVEL_KM},
+        user::{self, UserType},
+    },
+    services::{availability_service::AvailabilityService, location_service::LocationService},
+    AppError, AppState,
+};
+
+// Score out of 100, split across the factors below
+const AVAILABILITY_POINTS: f64 = 40.0;
+const DISTANCE_POINTS: f64 = 30.0;
+const RATE_POINTS: f64 = 20.0;
+const CREDENTIAL_POINTS: f64 = 10.0;
+
+/// Share of a factor's points given when the data to judge it is missing
+const UNKNOWN_FACTOR_SHARE: f64 = 0.5;
+
+/// Longest run of shift days checked against availability
+pub const MAX_SHIFT_DAYS: i64 = 31;
+
+/// Most open jobs considered for one professional's recommendations
+const MAX_JOBS_CONSIDERED: u64 = 500;
+
+pub const DEFAULT_MATCH_LIMIT: usize = 20;
+pub const MAX_MATCH_LIMIT: usize = 100;
+
+/// What a job asks of whoever fills it
+#[derive(Debug, Clone)]
+pub struct ShiftRequirements {
+    pub job_type: JobType,
+    pub dates: Vec<NaiveDate>,
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+    pub hourly_rate: f64,
+    pub location: Option<(f64, f64)>,
+}
+
+impl ShiftRequirements {
+    pub fn from_job(job: &job::Model) -> Result<Self, AppError> {
+        let start = parse_slot_time("start_time", &job.start_time)?;
+        let mut end = parse_slot_time("end_time", &job.end_time)?;
+        if end <= start {
+            // Overnight shifts are matched on the part before midnight
+            end = NaiveTime::from_hms_opt(23, 59, 59).unwrap();
+        }
+
+        let first = job.start_date.date_naive();
+        let last = job
+            .end_date
+            .date_naive()
+            .clamp(first, first + Duration::days(MAX_SHIFT_DAYS - 1));
+
+        Ok(Self {
+            job_type: job.job_type.clone().into(),
+            dates: first.iter_days().take_while(|date| *date <= last).collect(),
+            start,
+            end,
+            hourly_rate: job.hourly_rate.to_f64().unwrap_or_default(),
+            location: job
+                .latitude
+                .zip(job.longitude)
+                .or_else(|| resolve_coordinates(Some(&job.suburb), Some(&job.postcode))),
+        })
+    }
+
+    fn first_date(&self) -> Option<NaiveDate> {
+        self.dates.first().copied()
+    }
+}
+
+/// What is known about a professional when matching them to a shift
+#[derive(Debug, Clone, Copy)]
+pub struct CandidateProfile<'a> {
+    pub profile: Option<&'a professional_profile::Model>,
+    /// Stored availability slots; recurring ones are expanded as needed
+    pub slots: &'a [AvailabilitySlot],
+    pub home: Option<(f64, f64)>,
+}
+
+/// How well a professional fits a shift, with the reasons behind the score
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchScore {
+    pub score: f64,
+    pub match_reasons: Vec<String>,
+    pub available_days: usize,
+    pub shift_days: usize,
+    pub distance_km: Option<f64>,
+}
+
+/// Score a professional against a shift out of 100, or `None` when they cannot
+/// take it: wrong role, missing registration, no availability or too far away
+pub fn score_match(shift: &ShiftRequirements, candidate: CandidateProfile<'_>) -> Option<MatchScore> {
+    let first_date = shift.first_date()?;
+    let mut reasons = Vec::new();
+    let mut score = 0.0;
+
+    // Role eligibility; no roles listed means open to any
+    let job_types = candidate.profile.map(|p| p.job_types()).unwrap_or_default();
+    if !job_types.is_empty() {
+        if !job_types.contains(&shift.job_type) {
+            return None;
+        }
+        reasons.push(format!("Works as {}", shift.job_type));
+    }
+
+    // Credentials
+    let registered = candidate.profile.is_some_and(|p| p.is_registered_on(first_date));
+    if registered {
+        score += CREDENTIAL_POINTS;
+        reasons.push("AHPRA registration verified".to_string());
+    } else if shift.job_type.requires_registration() {
+        return None;
+    } else {
+        score += CREDENTIAL_POINTS * UNKNOWN_FACTOR_SHARE;
+    }
+
+    // Availability across the shift's days
+    let mut available_days = 0;
+    let mut preferred = false;
+    let mut minimum_rate: Option<f64> = None;
+    for date in &shift.dates {
+        let covering = availability::covering_slots(candidate.slots, *date, shift.start, shift.end);
+        if covering.is_empty() {
+            continue;
+        }
+        available_days += 1;
+        preferred |= covering.iter().any(|slot| slot.availability_type == AvailabilityType::Preferred);
+        // The cheapest covering slot is the least they have said they would accept
+        for rate in covering.iter().filter_map(|slot| slot.hourly_rate) {
+            minimum_rate = Some(minimum_rate.map_or(rate, |current| current.min(rate)));
+        }
+    }
+    if available_days == 0 {
+        return None;
+    }
+    let shift_days = shift.dates.len();
+    score += AVAILABILITY_POINTS * available_days as f64 / shift_days as f64;
+    reasons.push(if available_days == shift_days && shift_days == 1 {
+        format!("Available {}–{}", shift.start.format("%H:%M"), shift.end.format("%H:%M"))
+    } else if available_days == shift_days {
+        format!("Available for all {} shift days", shift_days)
+    } else {
+        format!("Available for {} of {} shift days", available_days, shift_days)
+    });
+    if preferred {
+        reasons.push("Marked this time as preferred".to_string());
+    }
+
+    // Distance from home
+    let max_travel_km = candidate
+        .profile
+        .map_or(DEFAULT_MAX_TRAVEL_KM, |p| p.max_travel_km) as f64;
+    let distance_km = match (candidate.home, shift.location) {
+        (Some((home_lat, home_lng)), Some((job_lat, job_lng))) => {
+            let distance = LocationService::calculate_distance(home_lat, home_lng, job_lat, job_lng);
+            if distance > max_travel_km {
+                return None;
+            }
+            score += DISTANCE_POINTS * (1.0 - distance / max_travel_km);
+            reasons.push(format!("{:.1} km from home (travels up to {:.0} km)", distance, max_travel_km));
+            Some(distance)
+        }
+        _ => {
+            score += DISTANCE_POINTS * UNKNOWN_FACTOR_SHARE;
+            reasons.push("Distance unknown".to_string());
+            None
+        }
+    };
+
+    // Pay against their minimum rate
+    match minimum_rate {
+        Some(minimum) if shift.hourly_rate >= minimum => {
+            score += RATE_POINTS;
+            reasons.push(format!("Pays ${:.2}/hr, meets ${:.2}/hr minimum", shift.hourly_rate, minimum));
+        }
+        Some(minimum) => {
+            score += RATE_POINTS * shift.hourly_rate / minimum;
+            reasons.push(format!("Pays ${:.2}/hr, below ${:.2}/hr minimum", shift.hourly_rate, minimum));
+        }
+        None => score += RATE_POINTS * UNKNOWN_FACTOR_SHARE,
+    }
+
+    Some(MatchScore {
+        score: (score * 10.0).round() / 10.0,
+        match_reasons: reasons,
+        available_days,
+        shift_days,
+        distance_km: distance_km.map(|d| (d * 10.0).round() / 10.0),
+    })
+}
+
+/// Clamp a requested number of matches to the allowed range
+pub fn match_limit(requested: Option<usize>) -> usize {
+    requested.unwrap_or(DEFAULT_MATCH_LIMIT).clamp(1, MAX_MATCH_LIMIT)
+}
+
+fn home_of(user: &user::Model) -> Option<(f64, f64)> {
+    resolve_coordinates(user.suburb.as_deref(), user.postcode.as_deref())
+}
+
+#[derive(Clone)]
+pub struct MatchingService {
+    db: DatabaseConnection,
+    availability: AvailabilityService,
+}
+
+impl MatchingService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self {
+            availability: AvailabilityService::new(db.clone()),
+            db,
+        }
+    }
+
+    /// Open jobs the professional can take, best match first
+    pub async fn recommendations(&self, user_id: Uuid, limit: usize) -> Result<Vec<(job::Model, MatchScore)>, AppError> {
+        let user = user::Entity::find_by_id(user_id)
+            .one(&self.db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        let profile = professional_profile::Entity::find_by_id(user_id)
+            .one(&self.db)
+            .await?;
+
+        let now = Utc::now();
+        let jobs = job::Entity::find()
+            .filter(job::Column::Status.eq(JobStatusDb::Active))
+            .filter(job::Column::DeletedAt.is_null())
+            .filter(job::Column::EndDate.gte(now))
+            .order_by_asc(job::Column::StartDate)
+            .limit(MAX_JOBS_CONSIDERED)
+            .all(&self.db)
+            .await?;
+        let Some(last_date) = jobs.iter().map(|job| job.end_date.date_naive()).max() else {
+            return Ok(Vec::new());
+        };
+
+        let slots = self
+            .availability
+            .slots_by_user(Some(&[user_id]), now.date_naive(), last_date)
+            .await?
+            .remove(&user_id)
+            .unwrap_or_default();
+        let candidate = CandidateProfile {
+            profile: profile.as_ref(),
+            slots: &slots,
+            home: home_of(&user),
+        };
+
+        let mut matches = Vec::new();
+        for job in jobs {
+            // Skip jobs with unreadable times rather than failing the whole list
+            let Ok(shift) = ShiftRequirements::from_job(&job) else {
+                continue;
+            };
+            if let Some(score) = score_match(&shift, candidate) {
+                matches.push((job, score));
+            }
+        }
+        Ok(Self::best(matches, limit))
+    }
+
+    /// Professionals who could fill a job, best match first; only the job's poster
+    /// or an admin may ask
+    pub async fn candidates(
+        &self,
+        job_id: Uuid,
+        requester_id: Uuid,
+        is_admin: bool,
+        limit: usize,
+    ) -> Result<Vec<(user::Model, MatchScore)>, AppError> {
+        let job = job::Entity::find_by_id(job_id)
+            .filter(job::Column::DeletedAt.is_null())
+            .one(&self.db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        if job.created_by != requester_id && !is_admin {
+            return Err(AppError::Forbidden);
+        }
+
+        let shift = ShiftRequirements::from_job(&job)?;
+        let (Some(&first), Some(&last)) = (shift.dates.first(), shift.dates.last()) else {
+            return Ok(Vec::new());
+        };
+
+        // Only people with some availability in the window can match
+        let mut slots = self.availability.slots_by_user(None, first, last).await?;
+        let user_ids: Vec<Uuid> = slots.keys().copied().collect();
+        if user_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let users = user::Entity::find()
+            .filter(user::Column::Id.is_in(user_ids.clone()))
+            .filter(user::Column::UserType.eq(UserType::Professional))
+            .filter(user::Column::IsActive.eq(true))
+            .all(&self.db)
+            .await?;
+        let profiles: HashMap<Uuid, professional_profile::Model> = professional_profile::Entity::find()
+            .filter(professional_profile::Column::UserId.is_in(user_ids))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|profile| (profile.user_id, profile))
+            .collect();
+
+        let mut matches = Vec::new();
+        for user in users {
+            let user_slots = slots.remove(&user.id).unwrap_or_default();
+            let candidate = CandidateProfile {
+                profile: profiles.get(&user.id),
+                slots: &user_slots,
+                home: home_of(&user),
+            };
+            if let Some(score) = score_match(&shift, candidate) {
+                matches.push((user, score));
+            }
+        }
+        Ok(Self::best(matches, limit))
+    }
+
+    fn best<T>(mut matches: Vec<(T, MatchScore)>, limit: usize) -> Vec<(T, MatchScore)> {
+        matches.sort_by(|(_, a), (_, b)| b.score.total_cmp(&a.score));
+        matches.truncate(limit);
+        matches
+    }
+}
+
+impl From<&AppState> for MatchingService {
+    fn from(state: &AppState) -> Self {
+        Self::new(state.db.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::professional_profile::RegistrationStatus;
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, d).unwrap()
+    }
+
+    fn time(h: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, 0, 0).unwrap()
+    }
+
+    fn shift(job_type: JobType) -> ShiftRequirements {
+        ShiftRequirements {
+            job_type,
+            dates: vec![date(10), date(11)],
+            start: time(9),
+            end: time(17),
+            hourly_rate: 60.0,
+            // Norwood
+            location: Some((-34.9206, 138.6326)),
+        }
+    }
+
+    fn slot(day: NaiveDate, rate: Option<f64>) -> AvailabilitySlot {
+        AvailabilitySlot {
+            id: Uuid::new_v4(),
+            date: day,
+            start_time: "08:00".to_string(),
+            end_time: "18:00".to_string(),
+            availability_type: AvailabilityType::Available,
+            notes: None,
+            job_type_preference: None,
+            hourly_rate: rate,
+            is_recurring: false,
+            recurring_pattern: None,
+        }
+    }
+
+    fn profile(job_types: &[JobType], status: RegistrationStatus) -> professional_profile::Model {
+        let now = Utc::now();
+        professional_profile::Model {
+            user_id: Uuid::new_v4(),
+            job_types: serde_json::to_value(job_types).unwrap(),
+            registration_number: Some("PHA0001234".to_string()),
+            registration_status: status,
+            registration_expires_on: None,
+            verified_at: None,
+            verified_by: None,
+            max_travel_km: 20,
+            abn: None,
+            gst_registered: false,
+            created_at: now.into(),
+            updated_at: now.into(),
+        }
+    }
+
+    #[test]
+    fn test_pharmacist_shift_needs_verified_registration() {
+        let slots = vec![slot(date(10), None)];
+        let pending = profile(&[JobType::Pharmacist], RegistrationStatus::Pending);
+        let verified = profile(&[JobType::Pharmacist], RegistrationStatus::Verified);
+
+        let candidate = |profile| CandidateProfile { profile, slots: &slots, home: None };
+        assert!(score_match(&shift(JobType::Pharmacist), candidate(None)).is_none());
+        assert!(score_match(&shift(JobType::Pharmacist), candidate(Some(&pending))).is_none());
+        assert!(score_match(&shift(JobType::Pharmacist), candidate(Some(&verified))).is_some());
+    }
+
+    #[test]
+    fn test_job_type_must_be_listed() {
+        let slots = vec![slot(date(10), None)];
+        let assistant = profile(&[JobType::PharmacyAssistant], RegistrationStatus::Unregistered);
+        let candidate = CandidateProfile { profile: Some(&assistant), slots: &slots, home: None };
+
+        assert!(score_match(&shift(JobType::PharmacyTechnician), candidate).is_none());
+        let matched = score_match(&shift(JobType::PharmacyAssistant), candidate).unwrap();
+        assert!(matched.match_reasons.contains(&"Works as Pharmacy Assistant".to_string()));
+    }
+
+    #[test]
+    fn test_availability_scales_score() {
+        let one_day = vec![slot(date(10), None)];
+        let both_days = vec![slot(date(10), None), slot(date(11), None)];
+        let job = shift(JobType::Student);
+
+        let partial = score_match(&job, CandidateProfile { profile: None, slots: &one_day, home: None }).unwrap();
+        let full = score_match(&job, CandidateProfile { profile: None, slots: &both_days, home: None }).unwrap();
+
+        assert_eq!((partial.available_days, partial.shift_days), (1, 2));
+        assert!(full.score > partial.score);
+        assert!(full.match_reasons.contains(&"Available for all 2 shift days".to_string()));
+        assert!(score_match(&job, CandidateProfile { profile: None, slots: &[], home: None }).is_none());
+    }
+
+    #[test]
+    fn test_distance_and_rate_reasons() {
+        let slots = vec![slot(date(10), Some(70.0)), slot(date(11), Some(55.0))];
+        let student = profile(&[], RegistrationStatus::Unregistered);
+        let job = shift(JobType::Student);
+
+        // Adelaide CBD is a few kilometres from Norwood
+        let nearby = CandidateProfile { profile: Some(&student), slots: &slots, home: Some((-34.9285, 138.6007)) };
+        let matched = score_match(&job, nearby).unwrap();
+        assert!(matched.distance_km.unwrap() < 5.0);
+        assert!(matched.match_reasons.iter().any(|r| r.contains("meets $55.00/hr minimum")));
+
+        // Beyond the 20 km travel range
+        let far = CandidateProfile { profile: Some(&student), slots: &slots, home: Some((-34.5, 138.6)) };
+        assert!(score_match(&job, far).is_none());
+    }
+}
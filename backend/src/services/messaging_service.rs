@@ -0,0 +1,574 @@
+use std::collections::{HashMap, HashSet};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use sea_orm::{
+    sea_query::Expr, ActiveModelBehavior, ActiveModelTrait, ColumnTrait, DatabaseConnection,
+    EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, Set,
+    TransactionTrait,
+};
+
+use crate::{
+    entities::{
+        application, conversation, conversation_participant, job, message, message_attachment,
+        upload,
+    },
+    services::upload_service::UploadService,
+    AppError, AppState,
+};
+
+/// Longest message body accepted
+pub const MAX_MESSAGE_LENGTH: usize = 5000;
+
+/// Most attachments a single message may carry
+pub const MAX_ATTACHMENTS_PER_MESSAGE: usize = 5;
+
+/// Page size used for message history when the client does not ask for one
+pub const DEFAULT_HISTORY_LIMIT: u64 = 50;
+
+/// Largest page of message history returned in one request
+pub const MAX_HISTORY_LIMIT: u64 = 100;
+
+/// A conversation as seen by one of its participants
+#[derive(Debug, Clone)]
+pub struct ConversationSummary {
+    pub conversation: conversation::Model,
+    pub participants: Vec<conversation_participant::Model>,
+    pub unread_count: u64,
+}
+
+/// A message together with the uploads attached to it
+#[derive(Debug, Clone)]
+pub struct MessageWithAttachments {
+    pub message: message::Model,
+    pub attachments: Vec<upload::Model>,
+}
+
+/// A page of message history, oldest first
+#[derive(Debug, Clone)]
+pub struct MessagePage {
+    pub messages: Vec<MessageWithAttachments>,
+    pub participants: Vec<conversation_participant::Model>,
+    pub has_more: bool,
+}
+
+/// Clamp a requested history page size to the allowed range
+pub fn history_limit(requested: Option<u64>) -> u64 {
+    requested
+        .unwrap_or(DEFAULT_HISTORY_LIMIT)
+        .clamp(1, MAX_HISTORY_LIMIT)
+}
+
+/// Validate a message body and attachment count, returning the trimmed body
+pub fn validate_message(body: &str, attachment_count: usize) -> Result<String, String> {
+    let body = body.trim();
+
+    if body.is_empty() && attachment_count == 0 {
+        return Err("Message must have a body or at least one attachment".to_string());
+    }
+    if body.chars().count() > MAX_MESSAGE_LENGTH {
+        return Err(format!("Message cannot exceed {} characters", MAX_MESSAGE_LENGTH));
+    }
+    if attachment_count > MAX_ATTACHMENTS_PER_MESSAGE {
+        return Err(format!(
+            "A message can have at most {} attachments",
+            MAX_ATTACHMENTS_PER_MESSAGE
+        ));
+    }
+
+    Ok(body.to_string())
+}
+
+/// Participants (other than the sender) who have read a message
+pub fn read_by(message: &message::Model, participants: &[conversation_participant::Model]) -> Vec<Uuid> {
+    participants
+        .iter()
+        .filter(|p| p.user_id != message.sender_id && p.has_read(message.created_at))
+        .map(|p| p.user_id)
+        .collect()
+}
+
+#[derive(Clone)]
+pub struct MessagingService {
+    db: DatabaseConnection,
+    uploads: UploadService,
+}
+
+impl MessagingService {
+    pub fn new(db: DatabaseConnection, uploads: UploadService) -> Self {
+        Self { db, uploads }
+    }
+
+    /// Start (or reopen) a conversation about an application or a job.
+    ///
+    /// Application conversations are between the applicant and the job's poster and
+    /// there is only ever one per application. Job conversations are between the
+    /// poster and one other user; the poster must name the recipient.
+    pub async fn start_conversation(
+        &self,
+        creator_id: Uuid,
+        is_admin: bool,
+        job_id: Option<Uuid>,
+        application_id: Option<Uuid>,
+        recipient_id: Option<Uuid>,
+        subject: Option<String>,
+    ) -> Result<ConversationSummary, AppError> {
+        let (job, application) = match (job_id, application_id) {
+            (_, Some(application_id)) => {
+                let (application, job) = application::Entity::find_by_id(application_id)
+                    .find_also_related(job::Entity)
+                    .one(&self.db)
+                    .await
+                    .map_err(|e| AppError::Database(format!("Database error: {}", e)))?
+                    .ok_or(AppError::NotFound)?;
+                let job = job.ok_or(AppError::NotFound)?;
+
+                if job_id.is_some_and(|id| id != job.id) {
+                    return Err(AppError::Validation(
+                        "Application does not belong to the given job".to_string(),
+                    ));
+                }
+                (job, Some(application))
+            }
+            (Some(job_id), None) => {
+                let job = job::Entity::find_by_id(job_id)
+                    .one(&self.db)
+                    .await
+                    .map_err(|e| AppError::Database(format!("Database error: {}", e)))?
+                    .ok_or(AppError::NotFound)?;
+                (job, None)
+            }
+            (None, None) => {
+                return Err(AppError::Validation(
+                    "A conversation must be about a job or an application".to_string(),
+                ));
+            }
+        };
+
+        let mut participant_ids = match &application {
+            Some(application) => vec![application.user_id, job.created_by],
+            None if creator_id == job.created_by => {
+                let recipient_id = recipient_id.ok_or_else(|| {
+                    AppError::Validation("recipient_id is required to message about your own job".to_string())
+                })?;
+                vec![job.created_by, recipient_id]
+            }
+            None => vec![creator_id, job.created_by],
+        };
+        participant_ids.dedup();
+
+        if participant_ids.len() < 2 {
+            return Err(AppError::Validation("You cannot start a conversation with yourself".to_string()));
+        }
+        if !participant_ids.contains(&creator_id) {
+            if !is_admin {
+                return Err(AppError::Forbidden);
+            }
+            participant_ids.push(creator_id);
+        }
+
+        if let Some(existing) = self
+            .find_existing_conversation(job.id, application.as_ref().map(|a| a.id), &participant_ids)
+            .await?
+        {
+            return self.summary_for(existing, creator_id).await;
+        }
+
+        let txn = self.db.begin().await?;
+
+        let conversation = conversation::ActiveModel {
+            job_id: Set(Some(job.id)),
+            application_id: Set(application.as_ref().map(|a| a.id)),
+            subject: Set(subject.or_else(|| Some(job.title.clone()))),
+            created_by: Set(creator_id),
+            ..conversation::ActiveModel::new()
+        }
+        .insert(&txn)
+        .await?;
+
+        for user_id in &participant_ids {
+            conversation_participant::ActiveModel {
+                conversation_id: Set(conversation.id),
+                user_id: Set(*user_id),
+                ..conversation_participant::ActiveModel::new()
+            }
+            .insert(&txn)
+            .await?;
+        }
+
+        txn.commit().await?;
+
+        self.summary_for(conversation, creator_id).await
+    }
+
+    /// List the user's conversations, most recently active first
+    pub async fn list_conversations(&self, user_id: Uuid) -> Result<Vec<ConversationSummary>, AppError> {
+        let conversation_ids: Vec<Uuid> = conversation_participant::Entity::find()
+            .filter(conversation_participant::Column::UserId.eq(user_id))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|p| p.conversation_id)
+            .collect();
+
+        if conversation_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let conversations = conversation::Entity::find()
+            .filter(conversation::Column::Id.is_in(conversation_ids))
+            .order_by_desc(Expr::cust("COALESCE(last_message_at, created_at)"))
+            .all(&self.db)
+            .await?;
+
+        let mut summaries = Vec::with_capacity(conversations.len());
+        for conversation in conversations {
+            summaries.push(self.summary_for(conversation, user_id).await?);
+        }
+
+        Ok(summaries)
+    }
+
+    /// Load a conversation the user takes part in (or may view through `ViewAllMessages`)
+    pub async fn get_conversation(
+        &self,
+        conversation_id: Uuid,
+        user_id: Uuid,
+        can_view_all: bool,
+    ) -> Result<ConversationSummary, AppError> {
+        let conversation = conversation::Entity::find_by_id(conversation_id)
+            .one(&self.db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+
+        let summary = self.summary_for(conversation, user_id).await?;
+        if !can_view_all && !summary.participants.iter().any(|p| p.user_id == user_id) {
+            return Err(AppError::NotFound);
+        }
+
+        Ok(summary)
+    }
+
+    /// Send a message; returns it with the conversation's participants for delivery
+    pub async fn send_message(
+        &self,
+        conversation_id: Uuid,
+        sender_id: Uuid,
+        body: &str,
+        attachment_ids: &[Uuid],
+    ) -> Result<(MessageWithAttachments, Vec<conversation_participant::Model>), AppError> {
+        let attachment_ids: Vec<Uuid> = attachment_ids
+            .iter()
+            .copied()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let body = validate_message(body, attachment_ids.len()).map_err(AppError::Validation)?;
+
+        let summary = self.get_conversation(conversation_id, sender_id, false).await?;
+        let attachments = self.uploads.find_owned(&attachment_ids, sender_id).await?;
+
+        let txn = self.db.begin().await?;
+
+        let message = message::ActiveModel {
+            conversation_id: Set(conversation_id),
+            sender_id: Set(sender_id),
+            body: Set(body),
+            ..message::ActiveModel::new()
+        }
+        .insert(&txn)
+        .await?;
+
+        for upload in &attachments {
+            message_attachment::ActiveModel {
+                message_id: Set(message.id),
+                upload_id: Set(upload.id),
+                ..message_attachment::ActiveModel::new()
+            }
+            .insert(&txn)
+            .await?;
+        }
+
+        let mut conversation: conversation::ActiveModel = summary.conversation.into();
+        conversation.last_message_at = Set(Some(message.created_at));
+        conversation.update(&txn).await?;
+
+        // Senders have always read their own messages
+        conversation_participant::Entity::update_many()
+            .col_expr(conversation_participant::Column::LastReadAt, Expr::value(message.created_at))
+            .filter(conversation_participant::Column::ConversationId.eq(conversation_id))
+            .filter(conversation_participant::Column::UserId.eq(sender_id))
+            .exec(&txn)
+            .await?;
+
+        txn.commit().await?;
+
+        let participants = self.participants(conversation_id).await?;
+        Ok((MessageWithAttachments { message, attachments }, participants))
+    }
+
+    /// Page backwards through a conversation's history from `before` (or the latest message)
+    pub async fn message_history(
+        &self,
+        conversation_id: Uuid,
+        user_id: Uuid,
+        can_view_all: bool,
+        before: Option<DateTime<Utc>>,
+        limit: Option<u64>,
+    ) -> Result<MessagePage, AppError> {
+        let summary = self.get_conversation(conversation_id, user_id, can_view_all).await?;
+
+        let limit = history_limit(limit);
+        let mut messages = message::Entity::find()
+            .filter(message::Column::ConversationId.eq(conversation_id))
+            .apply_if(before, |query, before| {
+                query.filter(message::Column::CreatedAt.lt(before))
+            })
+            .order_by_desc(message::Column::CreatedAt)
+            .limit(limit + 1)
+            .all(&self.db)
+            .await?;
+
+        let has_more = messages.len() as u64 > limit;
+        messages.truncate(limit as usize);
+        messages.reverse();
+
+        let messages = self.with_attachments(messages).await?;
+        Ok(MessagePage {
+            messages,
+            participants: summary.participants,
+            has_more,
+        })
+    }
+
+    /// Mark the conversation read up to `up_to` (defaults to now); read positions never move backwards
+    pub async fn mark_read(
+        &self,
+        conversation_id: Uuid,
+        user_id: Uuid,
+        up_to: Option<DateTime<Utc>>,
+    ) -> Result<conversation_participant::Model, AppError> {
+        let participant = conversation_participant::Entity::find()
+            .filter(conversation_participant::Column::ConversationId.eq(conversation_id))
+            .filter(conversation_participant::Column::UserId.eq(user_id))
+            .one(&self.db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+
+        let read_at = up_to.unwrap_or_else(Utc::now).min(Utc::now());
+        if participant.has_read(read_at.into()) {
+            return Ok(participant);
+        }
+
+        let mut active: conversation_participant::ActiveModel = participant.into();
+        active.last_read_at = Set(Some(read_at.into()));
+        Ok(active.update(&self.db).await?)
+    }
+
+    /// Total unread messages across all of the user's conversations
+    pub async fn total_unread(&self, user_id: Uuid) -> Result<u64, AppError> {
+        let participations = conversation_participant::Entity::find()
+            .filter(conversation_participant::Column::UserId.eq(user_id))
+            .all(&self.db)
+            .await?;
+
+        let mut total = 0;
+        for participant in &participations {
+            total += self.unread_count(participant).await?;
+        }
+
+        Ok(total)
+    }
+
+    /// Check whether the user can see an upload through a conversation they take part in
+    pub async fn can_access_upload(&self, upload_id: Uuid, user_id: Uuid) -> Result<bool, AppError> {
+        let message_ids: Vec<Uuid> = message_attachment::Entity::find()
+            .filter(message_attachment::Column::UploadId.eq(upload_id))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|a| a.message_id)
+            .collect();
+
+        if message_ids.is_empty() {
+            return Ok(false);
+        }
+
+        let conversation_ids: Vec<Uuid> = message::Entity::find()
+            .filter(message::Column::Id.is_in(message_ids))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|m| m.conversation_id)
+            .collect();
+
+        let count = conversation_participant::Entity::find()
+            .filter(conversation_participant::Column::ConversationId.is_in(conversation_ids))
+            .filter(conversation_participant::Column::UserId.eq(user_id))
+            .count(&self.db)
+            .await?;
+
+        Ok(count > 0)
+    }
+
+    async fn participants(&self, conversation_id: Uuid) -> Result<Vec<conversation_participant::Model>, AppError> {
+        Ok(conversation_participant::Entity::find()
+            .filter(conversation_participant::Column::ConversationId.eq(conversation_id))
+            .order_by_asc(conversation_participant::Column::JoinedAt)
+            .all(&self.db)
+            .await?)
+    }
+
+    async fn unread_count(&self, participant: &conversation_participant::Model) -> Result<u64, AppError> {
+        Ok(message::Entity::find()
+            .filter(message::Column::ConversationId.eq(participant.conversation_id))
+            .filter(message::Column::SenderId.ne(participant.user_id))
+            .apply_if(participant.last_read_at, |query, read_at| {
+                query.filter(message::Column::CreatedAt.gt(read_at))
+            })
+            .count(&self.db)
+            .await?)
+    }
+
+    async fn summary_for(
+        &self,
+        conversation: conversation::Model,
+        user_id: Uuid,
+    ) -> Result<ConversationSummary, AppError> {
+        let participants = self.participants(conversation.id).await?;
+        let unread_count = match participants.iter().find(|p| p.user_id == user_id) {
+            Some(participant) => self.unread_count(participant).await?,
+            None => 0,
+        };
+
+        Ok(ConversationSummary {
+            conversation,
+            participants,
+            unread_count,
+        })
+    }
+
+    async fn find_existing_conversation(
+        &self,
+        job_id: Uuid,
+        application_id: Option<Uuid>,
+        participant_ids: &[Uuid],
+    ) -> Result<Option<conversation::Model>, AppError> {
+        let candidates = conversation::Entity::find()
+            .filter(conversation::Column::JobId.eq(job_id))
+            .apply_if(application_id, |query, id| {
+                query.filter(conversation::Column::ApplicationId.eq(id))
+            })
+            .apply_if(application_id.is_none().then_some(()), |query, _| {
+                query.filter(conversation::Column::ApplicationId.is_null())
+            })
+            .all(&self.db)
+            .await?;
+
+        for candidate in candidates {
+            // One conversation per application, whoever is in it
+            if application_id.is_some() {
+                return Ok(Some(candidate));
+            }
+
+            let members: HashSet<Uuid> = self
+                .participants(candidate.id)
+                .await?
+                .into_iter()
+                .map(|p| p.user_id)
+                .collect();
+            if participant_ids.iter().all(|id| members.contains(id)) {
+                return Ok(Some(candidate));
+            }
+        }
+
+        Ok(None)
+    }
+
+    async fn with_attachments(&self, messages: Vec<message::Model>) -> Result<Vec<MessageWithAttachments>, AppError> {
+        let message_ids: Vec<Uuid> = messages.iter().map(|m| m.id).collect();
+        let mut attachments: HashMap<Uuid, Vec<upload::Model>> = HashMap::new();
+
+        if !message_ids.is_empty() {
+            let rows = message_attachment::Entity::find()
+                .filter(message_attachment::Column::MessageId.is_in(message_ids))
+                .find_also_related(upload::Entity)
+                .all(&self.db)
+                .await?;
+
+            for (attachment, upload) in rows {
+                if let Some(upload) = upload {
+                    attachments.entry(attachment.message_id).or_default().push(upload);
+                }
+            }
+        }
+
+        Ok(messages
+            .into_iter()
+            .map(|message| MessageWithAttachments {
+                attachments: attachments.remove(&message.id).unwrap_or_default(),
+                message,
+            })
+            .collect())
+    }
+}
+
+impl From<&AppState> for MessagingService {
+    fn from(state: &AppState) -> Self {
+        Self::new(state.db.clone(), UploadService::from(state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn participant(user_id: Uuid, last_read_at: Option<DateTime<Utc>>) -> conversation_participant::Model {
+        conversation_participant::Model {
+            id: Uuid::new_v4(),
+            conversation_id: Uuid::new_v4(),
+            user_id,
+            last_read_at: last_read_at.map(Into::into),
+            joined_at: Utc::now().into(),
+        }
+    }
+
+    #[test]
+    fn test_validate_message() {
+        assert_eq!(validate_message("  Hello  ", 0), Ok("Hello".to_string()));
+        assert_eq!(validate_message("", 1), Ok(String::new()));
+        assert!(validate_message("   ", 0).is_err());
+        assert!(validate_message(&"a".repeat(MAX_MESSAGE_LENGTH + 1), 0).is_err());
+        assert!(validate_message("Hi", MAX_ATTACHMENTS_PER_MESSAGE + 1).is_err());
+    }
+
+    #[test]
+    fn test_history_limit() {
+        assert_eq!(history_limit(None), DEFAULT_HISTORY_LIMIT);
+        assert_eq!(history_limit(Some(0)), 1);
+        assert_eq!(history_limit(Some(20)), 20);
+        assert_eq!(history_limit(Some(10_000)), MAX_HISTORY_LIMIT);
+    }
+
+    #[test]
+    fn test_read_by() {
+        let sender = Uuid::new_v4();
+        let reader = Uuid::new_v4();
+        let behind = Uuid::new_v4();
+        let sent_at = Utc::now();
+
+        let message = message::Model {
+            id: Uuid::new_v4(),
+            conversation_id: Uuid::new_v4(),
+            sender_id: sender,
+            body: "Are you available Saturday?".to_string(),
+            created_at: sent_at.into(),
+        };
+        let participants = vec![
+            participant(sender, Some(sent_at)),
+            participant(reader, Some(sent_at + chrono::Duration::seconds(5))),
+            participant(behind, Some(sent_at - chrono::Duration::minutes(1))),
+        ];
+
+        assert_eq!(read_by(&message, &participants), vec![reader]);
+    }
+}
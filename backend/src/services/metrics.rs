@@ -0,0 +1,320 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, OnceLock,
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use sea_orm::DatabaseConnection;
+
+/// Upper bounds, in seconds, of the HTTP request duration buckets
+const LATENCY_BUCKETS: [f64; 11] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// `/proc/<pid>/stat` reports CPU time in clock ticks, 100 a second on Linux
+const CLOCK_TICKS_PER_SECOND: f64 = 100.0;
+
+/// Business events counted for dashboards and alerts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DomainEvent {
+    JobPosted,
+    ApplicationSubmitted,
+    OfferAccepted,
+    LoginFailed,
+}
+
+impl DomainEvent {
+    const ALL: [DomainEvent; 4] = [
+        DomainEvent::JobPosted,
+        DomainEvent::ApplicationSubmitted,
+        DomainEvent::OfferAccepted,
+        DomainEvent::LoginFailed,
+    ];
+
+    fn metric(&self) -> (&'static str, &'static str) {
+        match self {
+            DomainEvent::JobPosted => ("loco_jobs_posted_total", "Jobs posted"),
+            DomainEvent::ApplicationSubmitted => ("loco_applications_submitted_total", "Applications submitted"),
+            DomainEvent::OfferAccepted => ("loco_offers_accepted_total", "Applications accepted, filling their shifts"),
+            DomainEvent::LoginFailed => ("loco_logins_failed_total", "Sign-in attempts that were refused"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, seconds: f64) {
+        for (bucket, bound) in self.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if seconds <= bound {
+                *bucket += 1;
+            }
+        }
+        self.count += 1;
+        self.sum += seconds;
+    }
+}
+
+/// Requests are keyed by method, matched route and status
+type RequestKey = (String, String, u16);
+
+/// Metrics collected since the process started
+pub struct Metrics {
+    started: Instant,
+    started_at: SystemTime,
+    requests: Mutex<BTreeMap<RequestKey, Histogram>>,
+    events: [AtomicU64; DomainEvent::ALL.len()],
+}
+
+/// The process-wide metrics
+pub fn global() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(|| Metrics {
+        started: Instant::now(),
+        started_at: SystemTime::now(),
+        requests: Mutex::new(BTreeMap::new()),
+        events: Default::default(),
+    })
+}
+
+/// Count a business event
+pub fn record(event: DomainEvent) {
+    global().events[event as usize].fetch_add(1, Ordering::Relaxed);
+}
+
+impl Metrics {
+    pub fn uptime(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Record a handled request; `route` is the matched route pattern, not the raw
+    /// path, so IDs don't create a series each
+    pub fn observe_request(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        self.requests
+            .lock()
+            .unwrap()
+            .entry((method.to_string(), route.to_string(), status))
+            .or_default()
+            .observe(elapsed.as_secs_f64());
+    }
+
+    fn event_count(&self, event: DomainEvent) -> u64 {
+        self.events[event as usize].load(Ordering::Relaxed)
+    }
+
+    /// Everything in the Prometheus text exposition format
+    pub fn render(&self, process: &ProcessStats, pool: Option<&PoolStats>, sockets: &SocketStats) -> String {
+        let mut out = String::new();
+
+        let start = self.started_at.duration_since(UNIX_EPOCH).unwrap_or_default();
+        gauge(&mut out, "process_start_time_seconds", "Start time of the process since the Unix epoch", start.as_secs_f64());
+        gauge(&mut out, "process_uptime_seconds", "Seconds since the process started", self.uptime().as_secs_f64());
+        if let Some(rss) = process.resident_memory_bytes {
+            gauge(&mut out, "process_resident_memory_bytes", "Resident memory size", rss as f64);
+        }
+        if let Some(virtual_memory) = process.virtual_memory_bytes {
+            gauge(&mut out, "process_virtual_memory_bytes", "Virtual memory size", virtual_memory as f64);
+        }
+        if let Some(cpu) = process.cpu_seconds {
+            counter(&mut out, "process_cpu_seconds_total", "User and system CPU time", cpu);
+        }
+        if let Some(fds) = process.open_fds {
+            gauge(&mut out, "process_open_fds", "Open file descriptors", fds as f64);
+        }
+        if let Some(threads) = process.threads {
+            gauge(&mut out, "process_threads", "OS threads", threads as f64);
+        }
+
+        if let Some(pool) = pool {
+            gauge(&mut out, "loco_db_pool_connections", "Connections open in the database pool", pool.size as f64);
+            gauge(&mut out, "loco_db_pool_idle_connections", "Idle connections in the database pool", pool.idle as f64);
+            gauge(&mut out, "loco_db_pool_max_connections", "Most connections the database pool will open", pool.max as f64);
+            gauge(&mut out, "loco_db_pool_utilisation_ratio", "Share of the pool's maximum connections in use", pool.utilisation());
+        }
+
+        gauge(&mut out, "loco_websocket_connections", "Open WebSocket connections on this instance", sockets.connections as f64);
+        gauge(
+            &mut out,
+            "loco_websocket_authenticated_connections",
+            "Open WebSocket connections with a signed-in user",
+            sockets.authenticated as f64,
+        );
+
+        for event in DomainEvent::ALL {
+            let (name, help) = event.metric();
+            counter(&mut out, name, help, self.event_count(event) as f64);
+        }
+
+        let requests = self.requests.lock().unwrap();
+        let _ = writeln!(out, "# HELP http_request_duration_seconds HTTP request duration by route and status");
+        let _ = writeln!(out, "# TYPE http_request_duration_seconds histogram");
+        for ((method, route, status), histogram) in requests.iter() {
+            let labels = format!(
+                "method=\"{}\",route=\"{}\",status=\"{}\"",
+                method,
+                escape_label(route),
+                status
+            );
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(histogram.buckets) {
+                let _ = writeln!(out, "http_request_duration_seconds_bucket{{{},le=\"{}\"}} {}", labels, bound, count);
+            }
+            let _ = writeln!(out, "http_request_duration_seconds_bucket{{{},le=\"+Inf\"}} {}", labels, histogram.count);
+            let _ = writeln!(out, "http_request_duration_seconds_sum{{{}}} {}", labels, histogram.sum);
+            let _ = writeln!(out, "http_request_duration_seconds_count{{{}}} {}", labels, histogram.count);
+        }
+
+        out
+    }
+}
+
+fn gauge(out: &mut String, name: &str, help: &str, value: f64) {
+    sample(out, name, help, "gauge", value);
+}
+
+fn counter(out: &mut String, name: &str, help: &str, value: f64) {
+    sample(out, name, help, "counter", value);
+}
+
+fn sample(out: &mut String, name: &str, help: &str, kind: &str, value: f64) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    let _ = writeln!(out, "{} {}", name, value);
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Resource use of this process, where the platform reports it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProcessStats {
+    pub resident_memory_bytes: Option<u64>,
+    pub virtual_memory_bytes: Option<u64>,
+    pub cpu_seconds: Option<f64>,
+    pub open_fds: Option<u64>,
+    pub threads: Option<u64>,
+}
+
+impl ProcessStats {
+    /// Read from `/proc/self`; everything is `None` where that doesn't exist
+    pub fn current() -> Self {
+        let mut stats = std::fs::read_to_string("/proc/self/status")
+            .map(|status| Self::from_status(&status))
+            .unwrap_or_default();
+        stats.cpu_seconds = std::fs::read_to_string("/proc/self/stat")
+            .ok()
+            .and_then(|stat| cpu_seconds(&stat));
+        stats.open_fds = std::fs::read_dir("/proc/self/fd")
+            .ok()
+            .map(|entries| entries.count() as u64);
+        stats
+    }
+
+    fn from_status(status: &str) -> Self {
+        let field = |name: &str| {
+            status
+                .lines()
+                .find_map(|line| line.strip_prefix(name)?.strip_prefix(':'))
+                .and_then(|value| value.split_whitespace().next()?.parse::<u64>().ok())
+        };
+        Self {
+            resident_memory_bytes: field("VmRSS").map(|kb| kb * 1024),
+            virtual_memory_bytes: field("VmSize").map(|kb| kb * 1024),
+            threads: field("Threads"),
+            ..Self::default()
+        }
+    }
+}
+
+/// User plus system time from `/proc/<pid>/stat`, whose second field (the command)
+/// is parenthesised and may contain spaces
+fn cpu_seconds(stat: &str) -> Option<f64> {
+    let after_command = &stat[stat.rfind(')')? + 1..];
+    let fields: Vec<&str> = after_command.split_whitespace().collect();
+    // utime and stime are fields 14 and 15 of the whole line
+    let user: f64 = fields.get(11)?.parse().ok()?;
+    let system: f64 = fields.get(12)?.parse().ok()?;
+    Some((user + system) / CLOCK_TICKS_PER_SECOND)
+}
+
+/// Use of the sea-orm connection pool
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoolStats {
+    pub size: u32,
+    pub idle: u32,
+    pub max: u32,
+}
+
+impl PoolStats {
+    /// `None` unless connected to Postgres through a pool
+    pub fn of(db: &DatabaseConnection) -> Option<Self> {
+        match db {
+            DatabaseConnection::SqlxPostgresPoolConnection(_) => {
+                let pool = db.get_postgres_connection_pool();
+                Some(Self {
+                    size: pool.size(),
+                    idle: pool.num_idle() as u32,
+                    max: pool.options().get_max_connections(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    pub fn utilisation(&self) -> f64 {
+        if self.max == 0 {
+            return 0.0;
+        }
+        self.size.saturating_sub(self.idle) as f64 / self.max as f64
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SocketStats {
+    pub connections: usize,
+    pub authenticated: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_proc_status_and_stat() {
+        let status = "Name:\tbackend\nVmSize:\t  204800 kB\nVmRSS:\t   51200 kB\nThreads:\t12\n";
+        let stats = ProcessStats::from_status(status);
+        assert_eq!(stats.resident_memory_bytes, Some(51200 * 1024));
+        assert_eq!(stats.virtual_memory_bytes, Some(204800 * 1024));
+        assert_eq!(stats.threads, Some(12));
+
+        let stat = "4242 (back end) S 1 4242 4242 0 -1 4194560 900 0 0 0 250 130 0 0 20 0 12 0 1000 0 0";
+        assert_eq!(cpu_seconds(stat), Some(3.8));
+    }
+
+    #[test]
+    fn test_renders_request_histograms_and_events() {
+        let metrics = global();
+        metrics.observe_request("GET", "/api/v1/jobs/:id", 200, Duration::from_millis(30));
+        metrics.observe_request("GET", "/api/v1/jobs/:id", 200, Duration::from_millis(700));
+        record(DomainEvent::LoginFailed);
+
+        let pool = PoolStats { size: 6, idle: 2, max: 10 };
+        let text = metrics.render(&ProcessStats::default(), Some(&pool), &SocketStats { connections: 3, authenticated: 2 });
+
+        let labels = "method=\"GET\",route=\"/api/v1/jobs/:id\",status=\"200\"";
+        assert!(text.contains(&format!("http_request_duration_seconds_bucket{{{},le=\"0.05\"}} 1", labels)));
+        assert!(text.contains(&format!("http_request_duration_seconds_bucket{{{},le=\"1\"}} 2", labels)));
+        assert!(text.contains(&format!("http_request_duration_seconds_count{{{}}} 2", labels)));
+        assert!(text.contains("loco_db_pool_utilisation_ratio 0.4\n"));
+        assert!(text.contains("loco_websocket_connections 3\n"));
+        assert!(text.contains("# TYPE loco_logins_failed_total counter"));
+        assert!(metrics.event_count(DomainEvent::LoginFailed) >= 1);
+    }
+}
@@ -6,6 +6,27 @@ pub mod location_service;
 pub mod supabase_auth;
 pub mod search_service;
 pub mod application_service;
+pub mod upload_service;
+pub mod messaging_service;
+pub mod email_service;
+pub mod notification_service;
+pub mod realtime_backplane;
+pub mod job_scheduler;
+pub mod task_queue;
+pub mod availability_service;
+pub mod professional_profile_service;
+pub mod matching_service;
+pub mod shift_service;
+pub mod pay_service;
+pub mod timesheet_service;
+pub mod invoice_service;
+pub mod job_template_service;
+pub mod metrics;
+pub mod health_service;
+pub mod audit_service;
+pub mod idempotency_service;
+pub mod webhook_service;
+pub mod saved_search_service;
 
 // Re-export services
 pub use job_service::JobService;
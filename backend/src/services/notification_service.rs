@@ -0,0 +1,553 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use chrono::Utc;
+use num_traits::ToPrimitive;
+use uuid::Uuid;
+use shared::pay::PayEstimate;
+use shared::types::{CursorParams, ListResponse};
+use sea_orm::{
+    sea_query::{Expr, OnConflict}, ActiveModelBehavior, ActiveModelTrait, ColumnTrait,
+    DatabaseConnection, EntityTrait, Iterable, PaginatorTrait, QueryFilter, QueryOrder, Set,
+};
+
+use crate::{
+    entities::{
+        application::{self, ApplicationStatus},
+        job,
+        notification::{self, NotificationChannels, NotificationEventType},
+        notification_preference, user,
+    },
+    handlers::websocket::{self, NotificationPayload},
+    repository::{cursor_page, Cursor},
+    services::{
+        email_service::{EmailSender, SendEmail},
+        pay_service::PayService,
+        task_queue::TaskQueue,
+    },
+    AppError, AppState,
+};
+
+/// How often pending digest notifications are emailed
+pub const DIGEST_INTERVAL: std::time::Duration = std::time::Duration::from_secs(24 * 60 * 60);
+
+/// Longest message preview included in a new message notification
+const MESSAGE_PREVIEW_LENGTH: usize = 140;
+
+/// Something that happened which a user may want to hear about
+#[derive(Debug, Clone)]
+pub struct NotificationEvent {
+    pub user_id: Uuid,
+    pub event_type: NotificationEventType,
+    pub title: String,
+    pub body: String,
+    pub link: Option<String>,
+    pub data: Option<serde_json::Value>,
+}
+
+impl NotificationEvent {
+    pub fn new(
+        user_id: Uuid,
+        event_type: NotificationEventType,
+        title: impl Into<String>,
+        body: impl Into<String>,
+    ) -> Self {
+        Self {
+            user_id,
+            event_type,
+            title: title.into(),
+            body: body.into(),
+            link: None,
+            data: None,
+        }
+    }
+
+    pub fn with_link(mut self, link: impl Into<String>) -> Self {
+        self.link = Some(link.into());
+        self
+    }
+
+    pub fn with_data(mut self, data: serde_json::Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Tell a job's poster that someone applied
+    pub fn application_received(job: &job::Model, application: &application::Model) -> Self {
+        Self::new(
+            job.created_by,
+            NotificationEventType::ApplicationReceived,
+            format!("New application for {}", job.title),
+            format!("A new application has been submitted for {} at {}.", job.title, job.pharmacy_name),
+        )
+        .with_link(format!("/jobs/{}/applications", job.id))
+        .with_data(serde_json::json!({ "job_id": job.id, "application_id": application.id }))
+    }
+
+    /// Tell an applicant their application moved to a new status
+    pub fn application_status_changed(job: &job::Model, application: &application::Model) -> Self {
+        Self::new(
+            application.user_id,
+            NotificationEventType::ApplicationStatusChanged,
+            format!("Application update: {}", job.title),
+            format!(
+                "Your application for {} at {} is now: {}.",
+                job.title,
+                job.pharmacy_name,
+                application.status_display()
+            ),
+        )
+        .with_link(format!("/applications/{}", application.id))
+        .with_data(serde_json::json!({
+            "job_id": job.id,
+            "application_id": application.id,
+            "status": application.status,
+        }))
+    }
+
+    /// Add an offer's estimated pay to the message and its data
+    pub fn with_pay_estimate(mut self, estimate: &PayEstimate) -> Self {
+        self.body = format!(
+            "{} Estimated pay: ${:.2} for {} shift{} ({:.1} hours).",
+            self.body,
+            estimate.total,
+            estimate.shifts.len(),
+            if estimate.shifts.len() == 1 { "" } else { "s" },
+            estimate.hours
+        );
+        if let Some(serde_json::Value::Object(data)) = self.data.as_mut() {
+            data.insert("estimated_pay".to_string(), serde_json::json!(estimate.total.to_f64()));
+        }
+        self
+    }
+
+    /// Warn a job's poster that the listing ends soon
+    pub fn job_expiring(job: &job::Model) -> Self {
+        Self::new(
+            job.created_by,
+            NotificationEventType::JobExpiring,
+            format!("{} expires soon", job.title),
+            format!(
+                "Your listing {} at {} expires on {}. Extend it or post a new one to keep receiving applications.",
+                job.title,
+                job.pharmacy_name,
+                job.end_date.format("%-d %b %Y")
+            ),
+        )
+        .with_link(format!("/jobs/{}", job.id))
+        .with_data(serde_json::json!({ "job_id": job.id, "end_date": job.end_date }))
+    }
+
+    /// Tell a conversation participant a message arrived
+    pub fn new_message(recipient_id: Uuid, conversation_id: Uuid, body: &str) -> Self {
+        let mut preview: String = body.chars().take(MESSAGE_PREVIEW_LENGTH).collect();
+        if body.chars().count() > MESSAGE_PREVIEW_LENGTH {
+            preview.push('…');
+        }
+
+        Self::new(recipient_id, NotificationEventType::NewMessage, "New message", preview)
+            .with_link(format!("/messages/{}", conversation_id))
+            .with_data(serde_json::json!({ "conversation_id": conversation_id }))
+    }
+}
+
+/// Render the plain-text body of a digest email
+pub fn render_digest(notifications: &[notification::Model]) -> String {
+    let mut body = format!(
+        "Here's what happened on Loco Platform since your last update ({} item{}):\n\n",
+        notifications.len(),
+        if notifications.len() == 1 { "" } else { "s" }
+    );
+
+    for item in notifications {
+        body.push_str(&format!("• {}\n  {}\n", item.title, item.body));
+    }
+
+    body
+}
+
+fn to_payload(model: &notification::Model) -> NotificationPayload {
+    NotificationPayload {
+        id: model.id.to_string(),
+        event_type: format!("{:?}", model.event_type),
+        title: model.title.clone(),
+        body: model.body.clone(),
+        link: model.link.clone(),
+        created_at: model.created_at.into(),
+    }
+}
+
+/// Persists notifications and fans events out to each user's chosen channels
+#[derive(Clone)]
+pub struct NotificationService {
+    db: DatabaseConnection,
+    mailer: Arc<dyn EmailSender>,
+    tasks: TaskQueue,
+}
+
+impl NotificationService {
+    pub fn new(db: DatabaseConnection, mailer: Arc<dyn EmailSender>) -> Self {
+        let tasks = TaskQueue::new(db.clone());
+        Self { db, mailer, tasks }
+    }
+
+    /// Effective channel preferences for every event type
+    pub async fn preferences(&self, user_id: Uuid) -> Result<Vec<(NotificationEventType, NotificationChannels)>, AppError> {
+        let stored: HashMap<NotificationEventType, NotificationChannels> = notification_preference::Entity::find()
+            .filter(notification_preference::Column::UserId.eq(user_id))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|p| (p.event_type, p.channels()))
+            .collect();
+
+        Ok(NotificationEventType::iter()
+            .map(|event_type| {
+                let channels = stored
+                    .get(&event_type)
+                    .copied()
+                    .unwrap_or_else(|| event_type.default_channels());
+                (event_type, channels)
+            })
+            .collect())
+    }
+
+    /// Save channel preferences, returning the full effective set
+    pub async fn update_preferences(
+        &self,
+        user_id: Uuid,
+        updates: Vec<(NotificationEventType, NotificationChannels)>,
+    ) -> Result<Vec<(NotificationEventType, NotificationChannels)>, AppError> {
+        for (event_type, channels) in updates {
+            let preference = notification_preference::ActiveModel {
+                user_id: Set(user_id),
+                event_type: Set(event_type),
+                in_app: Set(channels.in_app),
+                email: Set(channels.email),
+                digest: Set(channels.digest),
+                ..notification_preference::ActiveModel::new()
+            };
+
+            notification_preference::Entity::insert(preference)
+                .on_conflict(
+                    OnConflict::columns([
+                        notification_preference::Column::UserId,
+                        notification_preference::Column::EventType,
+                    ])
+                    .update_columns([
+                        notification_preference::Column::InApp,
+                        notification_preference::Column::Email,
+                        notification_preference::Column::Digest,
+                        notification_preference::Column::UpdatedAt,
+                    ])
+                    .to_owned(),
+                )
+                .exec(&self.db)
+                .await?;
+        }
+
+        self.preferences(user_id).await
+    }
+
+    async fn channels_for(&self, user_id: Uuid, event_type: NotificationEventType) -> Result<NotificationChannels, AppError> {
+        let preference = notification_preference::Entity::find()
+            .filter(notification_preference::Column::UserId.eq(user_id))
+            .filter(notification_preference::Column::EventType.eq(event_type))
+            .one(&self.db)
+            .await?;
+
+        Ok(preference
+            .map(|p| p.channels())
+            .unwrap_or_else(|| event_type.default_channels()))
+    }
+
+    /// Deliver an event through the user's channels; returns the stored notification if one was kept
+    pub async fn dispatch(&self, event: NotificationEvent) -> Result<Option<notification::Model>, AppError> {
+        let channels = self.channels_for(event.user_id, event.event_type).await?;
+        if !channels.any() {
+            return Ok(None);
+        }
+
+        let mut stored = None;
+        if channels.in_app || channels.digest {
+            let model = notification::ActiveModel {
+                user_id: Set(event.user_id),
+                event_type: Set(event.event_type),
+                title: Set(event.title.clone()),
+                body: Set(event.body.clone()),
+                link: Set(event.link.clone()),
+                data: Set(event.data.clone()),
+                in_app: Set(channels.in_app),
+                digest_pending: Set(channels.digest),
+                ..notification::ActiveModel::new()
+            }
+            .insert(&self.db)
+            .await?;
+
+            if channels.in_app {
+                websocket::send_notification(&[event.user_id], to_payload(&model)).await;
+            }
+            stored = Some(model);
+        }
+
+        if channels.email {
+            let recipient = user::Entity::find_by_id(event.user_id)
+                .one(&self.db)
+                .await?
+                .ok_or(AppError::NotFound)?;
+
+            let mut body = event.body.clone();
+            if let Some(link) = &event.link {
+                body.push_str(&format!("\n\nView it on Loco Platform: {}", link));
+            }
+            // Delivered by a task worker, outside the caller's request
+            self.tasks
+                .enqueue(&SendEmail {
+                    to: recipient.email,
+                    subject: event.title.clone(),
+                    body,
+                })
+                .await?;
+
+            if let Some(model) = stored.take() {
+                let mut active: notification::ActiveModel = model.into();
+                active.emailed_at = Set(Some(Utc::now().into()));
+                stored = Some(active.update(&self.db).await?);
+            }
+        }
+
+        Ok(stored)
+    }
+
+    /// Dispatch several events; failures are logged and never fail the caller
+    pub async fn dispatch_all(&self, events: Vec<NotificationEvent>) {
+        for event in events {
+            let (user_id, event_type) = (event.user_id, event.event_type);
+            if let Err(e) = self.dispatch(event).await {
+                tracing::warn!("Failed to dispatch {:?} notification to {}: {}", event_type, user_id, e);
+            }
+        }
+    }
+
+    /// Notify a job's poster about a new application
+    pub async fn application_received(&self, application: &application::Model) {
+        match job::Entity::find_by_id(application.job_id).one(&self.db).await {
+            Ok(Some(job)) => {
+                self.dispatch_all(vec![NotificationEvent::application_received(&job, application)])
+                    .await
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Failed to load job {} for notification: {}", application.job_id, e),
+        }
+    }
+
+    /// Notify applicants of status changes, optionally with a personal message from the employer
+    pub async fn application_status_changed(&self, changes: Vec<(application::Model, Option<String>)>) {
+        let job_ids: Vec<Uuid> = changes.iter().map(|(app, _)| app.job_id).collect();
+        let jobs: HashMap<Uuid, job::Model> = match job::Entity::find()
+            .filter(job::Column::Id.is_in(job_ids))
+            .all(&self.db)
+            .await
+        {
+            Ok(jobs) => jobs.into_iter().map(|j| (j.id, j)).collect(),
+            Err(e) => {
+                tracing::warn!("Failed to load jobs for status notifications: {}", e);
+                return;
+            }
+        };
+
+        let pay = PayService::new(self.db.clone());
+        let mut events = Vec::with_capacity(changes.len());
+        for (application, message) in changes {
+            let Some(job) = jobs.get(&application.job_id) else {
+                continue;
+            };
+            let mut event = NotificationEvent::application_status_changed(job, &application);
+            if let Some(message) = message {
+                event.body = message;
+            }
+            // Offers say what the shifts pay
+            if application.status == ApplicationStatus::Offered {
+                match pay.estimate(job, Some(&application)).await {
+                    Ok(estimate) => event = event.with_pay_estimate(&estimate),
+                    Err(e) => tracing::warn!("Failed to estimate pay for offer {}: {}", application.id, e),
+                }
+            }
+            events.push(event);
+        }
+
+        self.dispatch_all(events).await;
+    }
+
+    /// Notifications shown in the user's notification centre, newest first
+    pub async fn list(
+        &self,
+        user_id: Uuid,
+        unread_only: bool,
+        page: CursorParams,
+    ) -> Result<ListResponse<notification::Model>, AppError> {
+        let after = Cursor::from_params(&page)?;
+        let mut select = notification::Entity::find()
+            .filter(notification::Column::UserId.eq(user_id))
+            .filter(notification::Column::InApp.eq(true));
+        if unread_only {
+            select = select.filter(notification::Column::ReadAt.is_null());
+        }
+
+        Ok(cursor_page(&self.db, select, after, page.limit()).await?)
+    }
+
+    pub async fn unread_count(&self, user_id: Uuid) -> Result<u64, AppError> {
+        Ok(notification::Entity::find()
+            .filter(notification::Column::UserId.eq(user_id))
+            .filter(notification::Column::InApp.eq(true))
+            .filter(notification::Column::ReadAt.is_null())
+            .count(&self.db)
+            .await?)
+    }
+
+    pub async fn mark_read(&self, user_id: Uuid, notification_id: Uuid) -> Result<notification::Model, AppError> {
+        let existing = notification::Entity::find_by_id(notification_id)
+            .filter(notification::Column::UserId.eq(user_id))
+            .one(&self.db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+
+        if existing.is_read() {
+            return Ok(existing);
+        }
+
+        let mut active: notification::ActiveModel = existing.into();
+        active.read_at = Set(Some(Utc::now().into()));
+        Ok(active.update(&self.db).await?)
+    }
+
+    /// Mark every unread notification read, returning how many changed
+    pub async fn mark_all_read(&self, user_id: Uuid) -> Result<u64, AppError> {
+        let result = notification::Entity::update_many()
+            .col_expr(notification::Column::ReadAt, Expr::value(Utc::now()))
+            .filter(notification::Column::UserId.eq(user_id))
+            .filter(notification::Column::ReadAt.is_null())
+            .exec(&self.db)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+
+    /// Email each user a single digest of their pending notifications; returns digests sent
+    pub async fn send_digests(&self) -> Result<usize, AppError> {
+        let pending = notification::Entity::find()
+            .filter(notification::Column::DigestPending.eq(true))
+            .order_by_asc(notification::Column::CreatedAt)
+            .all(&self.db)
+            .await?;
+
+        let mut by_user: HashMap<Uuid, Vec<notification::Model>> = HashMap::new();
+        for item in pending {
+            by_user.entry(item.user_id).or_default().push(item);
+        }
+
+        let mut sent = 0;
+        for (user_id, items) in by_user {
+            let Some(recipient) = user::Entity::find_by_id(user_id).one(&self.db).await? else {
+                continue;
+            };
+
+            if let Err(e) = self
+                .mailer
+                .send(&recipient.email, "Your Loco Platform digest", &render_digest(&items))
+                .await
+            {
+                tracing::warn!("Failed to send digest to {}: {}", user_id, e);
+                continue;
+            }
+
+            notification::Entity::update_many()
+                .col_expr(notification::Column::DigestPending, Expr::value(false))
+                .filter(notification::Column::Id.is_in(items.iter().map(|n| n.id)))
+                .exec(&self.db)
+                .await?;
+            sent += 1;
+        }
+
+        Ok(sent)
+    }
+}
+
+/// Periodically email notification digests in the background
+pub fn spawn_digest_task(service: NotificationService) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(DIGEST_INTERVAL);
+        // The first tick fires immediately; wait a full interval before the first digest
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            match service.send_digests().await {
+                Ok(sent) => tracing::info!("📬 Sent {} notification digests", sent),
+                Err(e) => tracing::error!("Failed to send notification digests: {}", e),
+            }
+        }
+    })
+}
+
+impl From<&AppState> for NotificationService {
+    fn from(state: &AppState) -> Self {
+        Self::new(state.db.clone(), state.mailer.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_notification(title: &str, body: &str) -> notification::Model {
+        notification::Model {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            event_type: NotificationEventType::NewMessage,
+            title: title.to_string(),
+            body: body.to_string(),
+            link: None,
+            data: None,
+            in_app: true,
+            digest_pending: true,
+            read_at: None,
+            emailed_at: None,
+            created_at: Utc::now().into(),
+        }
+    }
+
+    #[test]
+    fn test_default_channels_deliver_in_app() {
+        for event_type in NotificationEventType::iter() {
+            assert!(event_type.default_channels().in_app, "{:?} should default to in-app", event_type);
+        }
+    }
+
+    #[test]
+    fn test_new_message_preview_is_truncated() {
+        let recipient = Uuid::new_v4();
+        let event = NotificationEvent::new_message(recipient, Uuid::new_v4(), &"x".repeat(500));
+
+        assert_eq!(event.user_id, recipient);
+        assert_eq!(event.event_type, NotificationEventType::NewMessage);
+        assert_eq!(event.body.chars().count(), MESSAGE_PREVIEW_LENGTH + 1);
+        assert!(event.body.ends_with('…'));
+
+        let short = NotificationEvent::new_message(recipient, Uuid::new_v4(), "See you Monday");
+        assert_eq!(short.body, "See you Monday");
+    }
+
+    #[test]
+    fn test_render_digest() {
+        let digest = render_digest(&[
+            test_notification("New message", "Are you free Saturday?"),
+            test_notification("New application for Pharmacist", "A new application has been submitted."),
+        ]);
+
+        assert!(digest.contains("2 items"));
+        assert!(digest.contains("• New message\n  Are you free Saturday?"));
+        assert!(digest.contains("• New application for Pharmacist"));
+        assert!(render_digest(&[test_notification("One", "Only")]).contains("1 item)"));
+    }
+}
@@ -0,0 +1,111 @@
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use shared::calendar;
+use shared::pay::{PayCalculator, PayEstimate};
+use shared::shifts::{self, ShiftSchedule, ShiftSpec};
+use shared::types::JobType;
+use uuid::Uuid;
+
+use crate::{
+    entities::{application, application_shift, job, job_shift},
+    AppError, AppState,
+};
+
+/// Shifts a job without a shift series stands for: its daily hours on every day it runs
+pub fn implied_shifts(job: &job::Model) -> Result<Vec<ShiftSpec>, AppError> {
+    let schedule = ShiftSchedule::weekly(
+        job.start_date.date_naive(),
+        job.end_date.date_naive(),
+        Vec::new(),
+        job.start_time.clone(),
+        job.end_time.clone(),
+    );
+    Ok(schedule.generate()?)
+}
+
+#[derive(Clone)]
+pub struct PayService {
+    db: DatabaseConnection,
+}
+
+impl PayService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Estimated pay for every shift of a job
+    pub async fn for_job(&self, job_id: Uuid) -> Result<(job::Model, PayEstimate), AppError> {
+        let job = job::Entity::find_by_id(job_id)
+            .filter(job::Column::DeletedAt.is_null())
+            .one(&self.db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        let estimate = self.estimate(&job, None).await?;
+        Ok((job, estimate))
+    }
+
+    /// Estimated pay for the shifts an application is for (the applicant, the job's poster or an admin)
+    pub async fn for_application(
+        &self,
+        application_id: Uuid,
+        requester_id: Uuid,
+        is_admin: bool,
+    ) -> Result<(application::Model, PayEstimate), AppError> {
+        let (application, job) = application::Entity::find_by_id(application_id)
+            .find_also_related(job::Entity)
+            .one(&self.db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        let job = job.ok_or(AppError::NotFound)?;
+        if application.user_id != requester_id && job.created_by != requester_id && !is_admin {
+            return Err(AppError::NotFound);
+        }
+
+        let estimate = self.estimate(&job, Some(&application)).await?;
+        Ok((application, estimate))
+    }
+
+    /// Pay for a job's shifts at its advertised rate, or just the shifts an
+    /// application picked
+    pub async fn estimate(
+        &self,
+        job: &job::Model,
+        application: Option<&application::Model>,
+    ) -> Result<PayEstimate, AppError> {
+        let mut query = job_shift::Entity::find()
+            .filter(job_shift::Column::JobId.eq(job.id))
+            .order_by_asc(job_shift::Column::Date)
+            .order_by_asc(job_shift::Column::StartTime);
+        if let Some(application) = application {
+            let picked: Vec<Uuid> = application_shift::Entity::find()
+                .filter(application_shift::Column::ApplicationId.eq(application.id))
+                .all(&self.db)
+                .await?
+                .into_iter()
+                .map(|row| row.shift_id)
+                .collect();
+            if !picked.is_empty() {
+                query = query.filter(job_shift::Column::Id.is_in(picked));
+            }
+        }
+
+        let stored: Vec<ShiftSpec> = query.all(&self.db).await?.iter().map(job_shift::Model::to_spec).collect();
+        let shifts = if stored.is_empty() { implied_shifts(job)? } else { stored };
+
+        // Holidays in the job's state; an unknown state is costed at the day's usual rates
+        let holidays = match (job.australian_state(), shifts::span(&shifts)) {
+            (Some(state), Some((starts_at, ends_at))) => {
+                calendar::holiday_dates(&state, starts_at.date(), ends_at.date())
+            }
+            _ => Vec::new(),
+        };
+
+        let calculator = PayCalculator::new(&holidays);
+        Ok(calculator.series(&shifts, &JobType::from(job.job_type.clone()), job.hourly_rate)?)
+    }
+}
+
+impl From<&AppState> for PayService {
+    fn from(state: &AppState) -> Self {
+        Self::new(state.db.clone())
+    }
+}
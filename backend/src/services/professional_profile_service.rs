@@ -0,0 +1,148 @@
+use chrono::{NaiveDate, Utc};
+use sea_orm::{
+    ActiveModelBehavior, ActiveModelTrait, DatabaseConnection, EntityTrait, Set, TransactionTrait,
+};
+use shared::types::JobType;
+use uuid::Uuid;
+
+use crate::{
+    entities::{
+        audit_event::AuditAction,
+        professional_profile::{self, RegistrationStatus},
+    },
+    middleware::validation::validators,
+    services::audit_service::{AuditEntry, AuditService},
+    AppError, AppState,
+};
+
+/// Longest travel range a professional can set
+pub const MAX_TRAVEL_KM: i32 = 500;
+
+#[derive(Clone)]
+pub struct ProfessionalProfileService {
+    db: DatabaseConnection,
+}
+
+impl ProfessionalProfileService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    pub async fn find(&self, user_id: Uuid) -> Result<Option<professional_profile::Model>, AppError> {
+        Ok(professional_profile::Entity::find_by_id(user_id).one(&self.db).await?)
+    }
+
+    /// Create or update the professional's own profile. A new registration number
+    /// needs verifying again, so it resets the registration status. The ABN is
+    /// stored as its 11 digits.
+    pub async fn update(
+        &self,
+        user_id: Uuid,
+        job_types: Vec<JobType>,
+        registration_number: Option<String>,
+        max_travel_km: i32,
+        abn: Option<String>,
+        gst_registered: bool,
+    ) -> Result<professional_profile::Model, AppError> {
+        if !(1..=MAX_TRAVEL_KM).contains(&max_travel_km) {
+            return Err(AppError::Validation(format!(
+                "max_travel_km must be between 1 and {}",
+                MAX_TRAVEL_KM
+            )));
+        }
+        let registration_number = registration_number
+            .map(|number| number.trim().to_uppercase())
+            .filter(|number| !number.is_empty());
+        if let Some(number) = &registration_number {
+            validators::validate_ahpra(number)
+                .map_err(|_| AppError::Validation("Invalid AHPRA registration number".to_string()))?;
+        }
+
+        let abn = abn
+            .map(|abn| abn.chars().filter(|c| !c.is_whitespace()).collect::<String>())
+            .filter(|abn| !abn.is_empty());
+        if let Some(abn) = &abn {
+            validators::validate_abn(abn).map_err(|_| AppError::Validation("Invalid ABN".to_string()))?;
+        }
+        if gst_registered && abn.is_none() {
+            return Err(AppError::Validation("GST registration needs an ABN".to_string()));
+        }
+
+        let existing = self.find(user_id).await?;
+        let is_new = existing.is_none();
+        let registration_changed =
+            !matches!(&existing, Some(profile) if profile.registration_number == registration_number);
+
+        let mut profile = match existing {
+            Some(profile) => profile.into(),
+            None => professional_profile::ActiveModel {
+                user_id: Set(user_id),
+                ..professional_profile::ActiveModel::new()
+            },
+        };
+
+        profile.job_types = Set(serde_json::to_value(&job_types)
+            .map_err(|e| AppError::Internal(format!("Failed to encode job types: {}", e)))?);
+        profile.max_travel_km = Set(max_travel_km);
+        profile.abn = Set(abn);
+        profile.gst_registered = Set(gst_registered);
+        if registration_changed {
+            profile.registration_status = Set(if registration_number.is_some() {
+                RegistrationStatus::Pending
+            } else {
+                RegistrationStatus::Unregistered
+            });
+            profile.registration_number = Set(registration_number);
+            profile.registration_expires_on = Set(None);
+            profile.verified_at = Set(None);
+            profile.verified_by = Set(None);
+        }
+        profile.updated_at = Set(Utc::now().into());
+
+        if is_new {
+            Ok(profile.insert(&self.db).await?)
+        } else {
+            Ok(profile.update(&self.db).await?)
+        }
+    }
+
+    /// Record the outcome of checking a registration against the AHPRA register (admin only)
+    pub async fn set_registration_status(
+        &self,
+        user_id: Uuid,
+        admin_id: Uuid,
+        status: RegistrationStatus,
+        expires_on: Option<NaiveDate>,
+    ) -> Result<professional_profile::Model, AppError> {
+        let profile = self.find(user_id).await?.ok_or(AppError::NotFound)?;
+        if profile.registration_number.is_none() && status != RegistrationStatus::Unregistered {
+            return Err(AppError::BadRequest("No registration number to verify".to_string()));
+        }
+
+        let mut active: professional_profile::ActiveModel = profile.clone().into();
+        active.registration_status = Set(status);
+        active.registration_expires_on = Set(expires_on);
+        active.verified_at = Set(Some(Utc::now().into()));
+        active.verified_by = Set(Some(admin_id));
+        active.updated_at = Set(Utc::now().into());
+
+        let txn = self.db.begin().await?;
+        let reviewed = active.update(&txn).await?;
+        AuditService::new(self.db.clone())
+            .record_in(
+                &txn,
+                AuditEntry::new(AuditAction::AdminAction, "professional_profile", user_id)
+                    .actor(admin_id)
+                    .change(&profile, &reviewed),
+            )
+            .await?;
+        txn.commit().await?;
+        Ok(reviewed)
+    }
+}
+
+impl From<&AppState> for ProfessionalProfileService {
+    fn from(state: &AppState) -> Self {
+        Self::new(state.db.clone())
+    }
+}
@@ -0,0 +1,409 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use async_trait::async_trait;
+use sea_orm::{ConnectionTrait, DatabaseBackend, DatabaseConnection, Statement};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgListener;
+use uuid::Uuid;
+
+use crate::{
+    config::Config,
+    handlers::websocket::{self, Topic, WsMessage},
+    AppError,
+};
+
+/// Postgres channel that carries real-time events between instances
+pub const NOTIFY_CHANNEL: &str = "realtime_events";
+
+/// Postgres rejects NOTIFY payloads of 8000 bytes or more; leave headroom
+const MAX_NOTIFY_PAYLOAD: usize = 7900;
+
+/// How long oversized events are kept for listeners to load
+const STORED_EVENT_TTL_MINUTES: i32 = 10;
+
+/// Topic counters idle this long are deleted. A pruned topic counts from 1 again,
+/// which resuming clients see as a position past the current one and resync.
+const IDLE_SEQUENCE_TTL_DAYS: i32 = 30;
+
+/// How often idle topic counters are pruned
+const SEQUENCE_PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+lazy_static::lazy_static! {
+    /// Identifies this process so it can skip its own notifications
+    pub static ref INSTANCE_ID: Uuid = Uuid::new_v4();
+}
+
+static BACKPLANE: OnceLock<Arc<dyn Backplane>> = OnceLock::new();
+
+/// A real-time event and the sequence number it was given on each of its topics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Envelope {
+    pub id: Uuid,
+    pub origin: Uuid,
+    pub sequences: BTreeMap<String, u64>,
+    pub message: WsMessage,
+}
+
+impl Envelope {
+    pub fn new(sequences: BTreeMap<String, u64>, message: WsMessage) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            origin: *INSTANCE_ID,
+            sequences,
+            message,
+        }
+    }
+
+    pub fn topics(&self) -> Vec<Topic> {
+        self.sequences.keys().filter_map(|t| Topic::parse(t)).collect()
+    }
+}
+
+/// What goes over the NOTIFY channel
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Notice {
+    Event { envelope: Envelope },
+    /// The envelope was too large and is stored in `realtime_event`
+    Stored { event_id: Uuid },
+}
+
+fn encode(notice: &Notice) -> Result<String, AppError> {
+    serde_json::to_string(notice)
+        .map_err(|e| AppError::Internal(format!("Failed to encode realtime event: {}", e)))
+}
+
+/// Fans real-time events out to the WebSocket connections of every backend instance
+#[async_trait]
+pub trait Backplane: Send + Sync {
+    /// Assign the next sequence number on each topic and deliver the event everywhere
+    async fn publish(&self, topics: &[Topic], message: WsMessage) -> Result<(), AppError>;
+
+    /// The last sequence number given out on each topic; topics with none are left out
+    async fn current_sequences(&self, topics: &[String]) -> Result<HashMap<String, u64>, AppError>;
+}
+
+/// Delivers events within this process only; for single-instance deployments
+#[derive(Default)]
+pub struct InProcessBackplane {
+    sequences: Mutex<HashMap<String, u64>>,
+}
+
+impl InProcessBackplane {
+    fn next_sequences(&self, topics: &[Topic]) -> BTreeMap<String, u64> {
+        let mut sequences = self.sequences.lock().unwrap();
+        topics
+            .iter()
+            .map(|topic| {
+                let seq = sequences.entry(topic.to_string()).or_insert(0);
+                *seq += 1;
+                (topic.to_string(), *seq)
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Backplane for InProcessBackplane {
+    async fn publish(&self, topics: &[Topic], message: WsMessage) -> Result<(), AppError> {
+        websocket::deliver(Envelope::new(self.next_sequences(topics), message));
+        Ok(())
+    }
+
+    async fn current_sequences(&self, topics: &[String]) -> Result<HashMap<String, u64>, AppError> {
+        let sequences = self.sequences.lock().unwrap();
+        Ok(topics
+            .iter()
+            .filter_map(|topic| sequences.get(topic).map(|seq| (topic.clone(), *seq)))
+            .collect())
+    }
+}
+
+/// Shares events between instances with Postgres `LISTEN/NOTIFY`; sequence
+/// numbers come from `realtime_topic_sequence` so every instance agrees on them
+#[derive(Clone)]
+pub struct PostgresBackplane {
+    db: DatabaseConnection,
+}
+
+impl PostgresBackplane {
+    /// Start listening for other instances' events on a dedicated connection
+    pub async fn connect(db: DatabaseConnection, database_url: &str) -> Result<Self, AppError> {
+        let mut listener = PgListener::connect(database_url)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to open realtime listener: {}", e)))?;
+        listener
+            .listen(NOTIFY_CHANNEL)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to LISTEN on {}: {}", NOTIFY_CHANNEL, e)))?;
+
+        let backplane = Self { db };
+        tokio::spawn(backplane.clone().listen(listener));
+        tokio::spawn(backplane.clone().prune_idle_sequences());
+        Ok(backplane)
+    }
+
+    /// Job, user, tenant and saved-search topics come and go, so their counters
+    /// are dropped once nothing has been published on them for a while
+    async fn prune_idle_sequences(self) {
+        let mut interval = tokio::time::interval(SEQUENCE_PRUNE_INTERVAL);
+        loop {
+            interval.tick().await;
+            let pruned = self
+                .db
+                .execute(Statement::from_sql_and_values(
+                    DatabaseBackend::Postgres,
+                    "DELETE FROM realtime_topic_sequence WHERE updated_at < now() - make_interval(days => $1)",
+                    [IDLE_SEQUENCE_TTL_DAYS.into()],
+                ))
+                .await;
+            match pruned {
+                Ok(result) if result.rows_affected() > 0 => {
+                    tracing::debug!("Pruned {} idle realtime topic counters", result.rows_affected())
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Failed to prune realtime topic counters: {}", e),
+            }
+        }
+    }
+
+    async fn next_sequence(&self, topic: &Topic) -> Result<u64, AppError> {
+        let row = self
+            .db
+            .query_one(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                r#"INSERT INTO realtime_topic_sequence (topic, seq, updated_at)
+                   VALUES ($1, 1, now())
+                   ON CONFLICT (topic) DO UPDATE
+                   SET seq = realtime_topic_sequence.seq + 1, updated_at = now()
+                   RETURNING seq"#,
+                [topic.to_string().into()],
+            ))
+            .await?
+            .ok_or_else(|| AppError::Internal(format!("No sequence returned for {}", topic)))?;
+
+        let seq: i64 = row.try_get("", "seq")?;
+        Ok(seq as u64)
+    }
+
+    async fn notify(&self, payload: String) -> Result<(), AppError> {
+        self.db
+            .execute(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                "SELECT pg_notify($1, $2)",
+                [NOTIFY_CHANNEL.into(), payload.into()],
+            ))
+            .await?;
+        Ok(())
+    }
+
+    async fn store(&self, envelope: &Envelope) -> Result<(), AppError> {
+        let payload = serde_json::to_value(envelope)
+            .map_err(|e| AppError::Internal(format!("Failed to encode realtime event: {}", e)))?;
+
+        self.db
+            .execute(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                "INSERT INTO realtime_event (id, payload) VALUES ($1, $2)",
+                [envelope.id.into(), payload.into()],
+            ))
+            .await?;
+
+        // Every listener has had ample time to load older events
+        self.db
+            .execute(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                "DELETE FROM realtime_event WHERE created_at < now() - make_interval(mins => $1)",
+                [STORED_EVENT_TTL_MINUTES.into()],
+            ))
+            .await?;
+        Ok(())
+    }
+
+    async fn load(&self, event_id: Uuid) -> Result<Option<Envelope>, AppError> {
+        let row = self
+            .db
+            .query_one(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                "SELECT payload FROM realtime_event WHERE id = $1",
+                [event_id.into()],
+            ))
+            .await?;
+
+        match row {
+            Some(row) => {
+                let payload: serde_json::Value = row.try_get("", "payload")?;
+                serde_json::from_value(payload)
+                    .map(Some)
+                    .map_err(|e| AppError::Internal(format!("Invalid stored realtime event: {}", e)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn receive(&self, payload: &str) -> Result<(), AppError> {
+        let notice: Notice = serde_json::from_str(payload)
+            .map_err(|e| AppError::Internal(format!("Invalid realtime notification: {}", e)))?;
+
+        let envelope = match notice {
+            Notice::Event { envelope } => Some(envelope),
+            Notice::Stored { event_id } => self.load(event_id).await?,
+        };
+
+        match envelope {
+            // Our own events were delivered locally when they were published
+            Some(envelope) if envelope.origin != *INSTANCE_ID => websocket::deliver(envelope),
+            Some(_) => {}
+            None => tracing::warn!("Stored realtime event expired before it was loaded"),
+        }
+        Ok(())
+    }
+
+    async fn listen(self, mut listener: PgListener) {
+        loop {
+            // recv reconnects on its own after a dropped connection
+            match listener.recv().await {
+                Ok(notification) => {
+                    if let Err(e) = self.receive(notification.payload()).await {
+                        tracing::error!("Failed to handle realtime notification: {}", e);
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Realtime listener error: {}", e);
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Backplane for PostgresBackplane {
+    async fn publish(&self, topics: &[Topic], message: WsMessage) -> Result<(), AppError> {
+        let mut sequences = BTreeMap::new();
+        for topic in topics {
+            sequences.insert(topic.to_string(), self.next_sequence(topic).await?);
+        }
+        let envelope = Envelope::new(sequences, message);
+
+        let payload = encode(&Notice::Event { envelope: envelope.clone() })?;
+        if payload.len() > MAX_NOTIFY_PAYLOAD {
+            self.store(&envelope).await?;
+            self.notify(encode(&Notice::Stored { event_id: envelope.id })?).await?;
+        } else {
+            self.notify(payload).await?;
+        }
+
+        websocket::deliver(envelope);
+        Ok(())
+    }
+
+    async fn current_sequences(&self, topics: &[String]) -> Result<HashMap<String, u64>, AppError> {
+        if topics.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let placeholders: Vec<String> = (1..=topics.len()).map(|i| format!("${}", i)).collect();
+        let sql = format!(
+            "SELECT topic, seq FROM realtime_topic_sequence WHERE topic IN ({})",
+            placeholders.join(", ")
+        );
+        let rows = self
+            .db
+            .query_all(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                &sql,
+                topics.iter().map(|topic| topic.clone().into()),
+            ))
+            .await?;
+
+        let mut sequences = HashMap::new();
+        for row in rows {
+            let topic: String = row.try_get("", "topic")?;
+            let seq: i64 = row.try_get("", "seq")?;
+            sequences.insert(topic, seq as u64);
+        }
+        Ok(sequences)
+    }
+}
+
+/// Build the backplane selected by `REALTIME_BACKPLANE`
+pub async fn backplane_from_config(
+    config: &Config,
+    db: &DatabaseConnection,
+) -> Result<Arc<dyn Backplane>, AppError> {
+    match config.realtime_backplane.as_str() {
+        "postgres" => {
+            // Same URL the main pool was opened with
+            Ok(Arc::new(PostgresBackplane::connect(db.clone(), config.database.url.expose()).await?))
+        }
+        "memory" => Ok(Arc::new(InProcessBackplane::default())),
+        other => Err(AppError::Configuration(format!(
+            "Unknown REALTIME_BACKPLANE {:?}, expected \"postgres\" or \"memory\"",
+            other
+        ))),
+    }
+}
+
+/// Install the process-wide backplane; must be called before serving requests
+pub fn install(backplane: Arc<dyn Backplane>) {
+    if BACKPLANE.set(backplane).is_err() {
+        tracing::warn!("Realtime backplane already installed, ignoring");
+    }
+}
+
+/// The installed backplane, falling back to in-process delivery
+pub fn current() -> Arc<dyn Backplane> {
+    BACKPLANE
+        .get_or_init(|| Arc::new(InProcessBackplane::default()))
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_process_sequences_are_per_topic() {
+        let backplane = InProcessBackplane::default();
+        let job = Topic::Job(Uuid::new_v4());
+
+        let first = backplane.next_sequences(&[Topic::Jobs]);
+        let second = backplane.next_sequences(&[Topic::Jobs, job.clone()]);
+
+        assert_eq!(first.get("jobs"), Some(&1));
+        assert_eq!(second.get("jobs"), Some(&2));
+        assert_eq!(second.get(&job.to_string()), Some(&1));
+    }
+
+    #[tokio::test]
+    async fn in_process_current_sequences_leave_out_unused_topics() {
+        let backplane = InProcessBackplane::default();
+        backplane.next_sequences(&[Topic::Jobs]);
+        backplane.next_sequences(&[Topic::Jobs]);
+
+        let current = backplane
+            .current_sequences(&["jobs".to_string(), "system".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(current, HashMap::from([("jobs".to_string(), 2)]));
+    }
+
+    #[test]
+    fn notices_round_trip() {
+        let mut sequences = BTreeMap::new();
+        sequences.insert("jobs".to_string(), 7);
+        let notice = Notice::Event {
+            envelope: Envelope::new(sequences, WsMessage::JobDeleted { job_id: "abc".to_string() }),
+        };
+
+        let decoded: Notice = serde_json::from_str(&serde_json::to_string(&notice).unwrap()).unwrap();
+        match decoded {
+            Notice::Event { envelope } => {
+                assert_eq!(envelope.origin, *INSTANCE_ID);
+                assert_eq!(envelope.topics(), vec![Topic::Jobs]);
+            }
+            Notice::Stored { .. } => panic!("expected an inline event"),
+        }
+    }
+}
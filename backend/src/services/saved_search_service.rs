@@ -0,0 +1,177 @@
+use sea_orm::{
+    ActiveModelBehavior, ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait,
+    QueryFilter, QueryOrder, Set,
+};
+use shared::types::JobFilters;
+use uuid::Uuid;
+
+use crate::{
+    entities::{job, saved_search},
+    handlers::websocket::{self, JobNotification},
+    AppError, AppState,
+};
+
+const MAX_NAME_LENGTH: usize = 100;
+const MAX_QUERY_LENGTH: usize = 255;
+
+#[derive(Clone)]
+pub struct SavedSearchService {
+    db: DatabaseConnection,
+}
+
+impl SavedSearchService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// The user's saved searches, newest first
+    pub async fn list(&self, user_id: Uuid) -> Result<Vec<saved_search::Model>, AppError> {
+        Ok(saved_search::Entity::find()
+            .filter(saved_search::Column::UserId.eq(user_id))
+            .order_by_desc(saved_search::Column::CreatedAt)
+            .all(&self.db)
+            .await?)
+    }
+
+    /// Keep a search so the user hears about new jobs matching it
+    pub async fn save(
+        &self,
+        user_id: Uuid,
+        name: &str,
+        query: &str,
+        filters: serde_json::Value,
+    ) -> Result<saved_search::Model, AppError> {
+        let name = name.trim();
+        if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+            return Err(AppError::Validation(format!(
+                "name: Must be between 1 and {} characters",
+                MAX_NAME_LENGTH
+            )));
+        }
+        let query = query.trim();
+        if query.chars().count() > MAX_QUERY_LENGTH {
+            return Err(AppError::Validation(format!(
+                "query: Must be at most {} characters",
+                MAX_QUERY_LENGTH
+            )));
+        }
+        let filters = if filters.is_null() { serde_json::json!({}) } else { filters };
+        serde_json::from_value::<JobFilters>(filters.clone())
+            .map_err(|e| AppError::Validation(format!("filters: {}", e)))?;
+
+        let search = saved_search::ActiveModel {
+            user_id: Set(user_id),
+            name: Set(name.to_string()),
+            query: Set(query.to_string()),
+            filters: Set(filters),
+            ..saved_search::ActiveModel::new()
+        };
+        Ok(search.insert(&self.db).await?)
+    }
+
+    /// Publish a newly live job on the topic of every saved search it matches;
+    /// failures are logged and never fail the caller
+    pub async fn job_published(&self, job: &job::Model) {
+        let searches = match saved_search::Entity::find().all(&self.db).await {
+            Ok(searches) => searches,
+            Err(e) => {
+                tracing::warn!("Failed to load saved searches for job {}: {}", job.id, e);
+                return;
+            }
+        };
+
+        let matched: Vec<Uuid> = searches
+            .iter()
+            .filter(|search| search.matches(job))
+            .map(|search| search.id)
+            .collect();
+        if !matched.is_empty() {
+            websocket::broadcast_saved_search_matches(&matched, JobNotification::from_job(job)).await;
+        }
+    }
+}
+
+impl From<&AppState> for SavedSearchService {
+    fn from(state: &AppState) -> Self {
+        Self::new(state.db.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+    use serde_json::json;
+
+    fn posted_job() -> job::Model {
+        let now = Utc::now();
+        job::Model {
+            id: Uuid::new_v4(),
+            title: "Locum Pharmacist".to_string(),
+            description: "Weekend cover".to_string(),
+            pharmacy_name: "Glenelg Beach Pharmacy".to_string(),
+            hourly_rate: Decimal::new(6500, 2),
+            address: "15 Jetty Road".to_string(),
+            suburb: "Glenelg".to_string(),
+            postcode: "5045".to_string(),
+            state: "SA".to_string(),
+            latitude: None,
+            longitude: None,
+            start_date: now.into(),
+            end_date: now.into(),
+            start_time: "09:00".to_string(),
+            end_time: "17:00".to_string(),
+            job_type: job::JobTypeDb::Pharmacist,
+            status: job::JobStatusDb::Active,
+            is_urgent: true,
+            requirements_text: None,
+            benefits_text: None,
+            contact_email: None,
+            contact_phone: None,
+            application_deadline: None,
+            publish_at: None,
+            expiry_warning_sent_at: None,
+            view_count: 0,
+            application_count: 0,
+            created_by: Uuid::new_v4(),
+            created_at: now.into(),
+            updated_at: now.into(),
+            deleted_at: None,
+        }
+    }
+
+    fn search(query: &str, filters: serde_json::Value) -> saved_search::Model {
+        saved_search::Model {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            name: "Weekend work".to_string(),
+            query: query.to_string(),
+            filters,
+            created_at: Utc::now().into(),
+        }
+    }
+
+    #[test]
+    fn saved_searches_match_jobs_like_the_search_endpoint() {
+        let job = posted_job();
+        let cases = [
+            ("", json!({}), true),
+            ("Pharmacist", json!({}), true),
+            ("Glenelg", json!({}), true),
+            ("Hobart", json!({}), false),
+            ("", json!({ "job_type": "Pharmacist", "state": "SA" }), true),
+            ("", json!({ "job_type": "Intern" }), false),
+            ("", json!({ "state": "VIC" }), false),
+            ("", json!({ "min_rate": 60.0, "max_rate": 70.0 }), true),
+            ("", json!({ "min_rate": 70.0 }), false),
+            ("", json!({ "max_rate": 60.0 }), false),
+            ("", json!({ "suburb": "Glen" }), true),
+            ("", json!({ "is_urgent": false }), false),
+            ("Weekend", json!({ "is_urgent": true }), true),
+        ];
+        for (query, filters, expected) in cases {
+            assert_eq!(search(query, filters.clone()).matches(&job), expected, "{:?} {}", query, filters);
+        }
+    }
+}
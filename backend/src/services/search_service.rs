@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
-use shared::types::{Job, JobFilters, SearchResponse};
+use shared::types::Job;
 use shared::validation::ValidatedJobSearchRequest;
 use crate::AppError;
 
@@ -427,13 +427,13 @@ impl SearchService {
     /// Get sample jobs for demo mode
     async fn get_sample_jobs(&self) -> Result<Vec<Job>, AppError> {
         // Return sample jobs from the job service
-        let (jobs, _) = crate::services::JobService::list_jobs(
+        let jobs = crate::services::JobService::list_jobs(
             shared::types::JobFilters::default(),
-            Some(1),
-            Some(50),
+            None,
+            shared::types::MAX_PAGE_LIMIT,
         ).await?;
         
-        Ok(jobs)
+        Ok(jobs.items)
     }
 
     /// Auto-complete search suggestions
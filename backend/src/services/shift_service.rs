@@ -0,0 +1,223 @@
+use std::collections::HashSet;
+use chrono::Utc;
+use sea_orm::{
+    sea_query::Expr, ActiveModelBehavior, ActiveModelTrait, ColumnTrait, ConnectionTrait,
+    DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+use shared::shifts::{self, ShiftSchedule, ShiftSpec};
+use uuid::Uuid;
+
+use crate::{
+    entities::{application, application_shift, job, job_shift},
+    AppError, AppState,
+};
+
+/// Record which of a job's shifts an application is for.
+///
+/// An empty selection means the whole job and records nothing. Every selected
+/// shift must belong to the job and still be open.
+pub async fn record_selection<C: ConnectionTrait>(
+    conn: &C,
+    application_id: Uuid,
+    job_id: Uuid,
+    shift_ids: &[Uuid],
+) -> Result<Vec<job_shift::Model>, AppError> {
+    let mut seen = HashSet::new();
+    let ids: Vec<Uuid> = shift_ids.iter().copied().filter(|id| seen.insert(*id)).collect();
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let selected = job_shift::Entity::find()
+        .filter(job_shift::Column::JobId.eq(job_id))
+        .filter(job_shift::Column::Id.is_in(ids.clone()))
+        .order_by_asc(job_shift::Column::Date)
+        .order_by_asc(job_shift::Column::StartTime)
+        .all(conn)
+        .await?;
+    if selected.len() != ids.len() {
+        return Err(AppError::Validation("shift_ids: Unknown shift for this job".to_string()));
+    }
+    if selected.iter().any(job_shift::Model::is_filled) {
+        return Err(AppError::Validation("shift_ids: Shift has already been filled".to_string()));
+    }
+
+    application_shift::Entity::insert_many(selected.iter().map(|shift| application_shift::ActiveModel {
+        application_id: Set(application_id),
+        shift_id: Set(shift.id),
+        ..application_shift::ActiveModel::new()
+    }))
+    .exec(conn)
+    .await?;
+
+    Ok(selected)
+}
+
+/// Fill the shifts an accepted application is for with its applicant.
+///
+/// Shifts the applicant picked must all still be open, otherwise nothing is
+/// filled and an error is returned so the acceptance can be rolled back. An
+/// application for the whole job takes whichever shifts are still open.
+pub async fn fill_for_application<C: ConnectionTrait>(
+    conn: &C,
+    application: &application::Model,
+) -> Result<u64, AppError> {
+    let selected: Vec<Uuid> = application_shift::Entity::find()
+        .filter(application_shift::Column::ApplicationId.eq(application.id))
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|row| row.shift_id)
+        .collect();
+
+    let mut update = job_shift::Entity::update_many()
+        .col_expr(job_shift::Column::FilledBy, Expr::value(application.user_id))
+        .col_expr(job_shift::Column::FilledAt, Expr::value(sea_orm::Value::from(Utc::now())))
+        .filter(job_shift::Column::JobId.eq(application.job_id))
+        .filter(job_shift::Column::FilledBy.is_null());
+    if !selected.is_empty() {
+        update = update.filter(job_shift::Column::Id.is_in(selected.clone()));
+    }
+
+    let filled = update.exec(conn).await?.rows_affected;
+    if !selected.is_empty() && filled < selected.len() as u64 {
+        return Err(AppError::Validation(
+            "Some of the applicant's shifts have already been filled".to_string(),
+        ));
+    }
+    Ok(filled)
+}
+
+#[derive(Clone)]
+pub struct ShiftService {
+    db: DatabaseConnection,
+}
+
+impl ShiftService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// A job's shifts by date then start time
+    pub async fn list(&self, job_id: Uuid) -> Result<Vec<job_shift::Model>, AppError> {
+        Ok(job_shift::Entity::find()
+            .filter(job_shift::Column::JobId.eq(job_id))
+            .order_by_asc(job_shift::Column::Date)
+            .order_by_asc(job_shift::Column::StartTime)
+            .all(&self.db)
+            .await?)
+    }
+
+    /// An application and the shifts it is for (empty when it is for the whole job).
+    /// Visible to the applicant, the job's poster and admins.
+    pub async fn for_application(
+        &self,
+        application_id: Uuid,
+        requester_id: Uuid,
+        is_admin: bool,
+    ) -> Result<(application::Model, Vec<job_shift::Model>), AppError> {
+        let (application, job) = application::Entity::find_by_id(application_id)
+            .find_also_related(job::Entity)
+            .one(&self.db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        let is_poster = job.is_some_and(|job| job.created_by == requester_id);
+        if application.user_id != requester_id && !is_poster && !is_admin {
+            return Err(AppError::NotFound);
+        }
+
+        let shifts = job_shift::Entity::find()
+            .inner_join(application_shift::Entity)
+            .filter(application_shift::Column::ApplicationId.eq(application_id))
+            .order_by_asc(job_shift::Column::Date)
+            .order_by_asc(job_shift::Column::StartTime)
+            .all(&self.db)
+            .await?;
+        Ok((application, shifts))
+    }
+
+    /// Replace a job's shifts with those generated from `schedule` and stretch the
+    /// job's dates and hours to cover them. Refused once any shift has been filled or
+    /// picked by an applicant.
+    pub async fn replace(
+        &self,
+        job_id: Uuid,
+        requester_id: Uuid,
+        is_admin: bool,
+        schedule: &ShiftSchedule,
+    ) -> Result<Vec<job_shift::Model>, AppError> {
+        let job = job::Entity::find_by_id(job_id)
+            .filter(job::Column::DeletedAt.is_null())
+            .one(&self.db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        if job.created_by != requester_id && !is_admin {
+            return Err(AppError::Forbidden);
+        }
+
+        let specs = schedule.generate()?;
+        let (starts_at, ends_at) = shifts::span(&specs)
+            .ok_or_else(|| AppError::Validation("The schedule does not produce any shifts".to_string()))?;
+
+        let txn = self.db.begin().await?;
+        let filled = job_shift::Entity::find()
+            .filter(job_shift::Column::JobId.eq(job_id))
+            .filter(job_shift::Column::FilledBy.is_not_null())
+            .one(&txn)
+            .await?;
+        if filled.is_some() {
+            return Err(AppError::Validation(
+                "Shifts cannot be changed once one has been filled".to_string(),
+            ));
+        }
+        // Replacing would silently turn those applications into ones for the whole job
+        let picked = application_shift::Entity::find()
+            .inner_join(job_shift::Entity)
+            .filter(job_shift::Column::JobId.eq(job_id))
+            .one(&txn)
+            .await?;
+        if picked.is_some() {
+            return Err(AppError::Validation(
+                "Shifts cannot be changed once applicants have picked some".to_string(),
+            ));
+        }
+
+        job_shift::Entity::delete_many()
+            .filter(job_shift::Column::JobId.eq(job_id))
+            .exec(&txn)
+            .await?;
+
+        let mut created = Vec::with_capacity(specs.len());
+        for spec in &specs {
+            created.push(Self::shift_model(job_id, spec)?.insert(&txn).await?);
+        }
+
+        let first = &specs[0];
+        let mut job: job::ActiveModel = job.into();
+        job.start_date = Set(starts_at.and_utc().into());
+        job.end_date = Set(ends_at.and_utc().into());
+        job.start_time = Set(first.start_time.clone());
+        job.end_time = Set(first.end_time.clone());
+        job.update(&txn).await?;
+
+        txn.commit().await?;
+        Ok(created)
+    }
+
+    fn shift_model(job_id: Uuid, spec: &ShiftSpec) -> Result<job_shift::ActiveModel, AppError> {
+        let (start, end) = spec.time_range()?;
+        Ok(job_shift::ActiveModel {
+            job_id: Set(job_id),
+            date: Set(spec.date),
+            start_time: Set(start),
+            end_time: Set(end),
+            ..job_shift::ActiveModel::new()
+        })
+    }
+}
+
+impl From<&AppState> for ShiftService {
+    fn from(state: &AppState) -> Self {
+        Self::new(state.db.clone())
+    }
+}
@@ -0,0 +1,403 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::Utc;
+use futures_util::future::BoxFuture;
+use serde::{de::DeserializeOwned, Serialize};
+use uuid::Uuid;
+use sea_orm::{
+    ActiveModelBehavior, ActiveModelTrait, ColumnTrait, DatabaseBackend, DatabaseConnection,
+    EntityTrait, QueryFilter, QueryOrder, Set, Statement, TransactionTrait, Value,
+};
+use shared::types::{CursorParams, ListResponse};
+use shared::utils::exponential_backoff;
+
+use crate::{
+    entities::{
+        audit_event::AuditAction,
+        background_task::{self, TaskStatus},
+    },
+    repository::{cursor_page, Cursor},
+    services::audit_service::{AuditEntry, AuditService},
+    AppError, AppState,
+};
+
+/// Delay before the first retry; doubles with each further attempt
+pub const TASK_RETRY_BASE: Duration = Duration::from_secs(30);
+
+/// Longest delay between retries
+pub const TASK_RETRY_MAX: Duration = Duration::from_secs(60 * 60);
+
+/// How long a single task may run before it counts as failed
+pub const TASK_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// Running tasks locked for longer than this belonged to a worker that died
+const STALE_LOCK_MINUTES: i64 = 15;
+
+/// How long an idle worker waits before polling again
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A typed unit of background work, stored as JSON
+pub trait Task: Serialize + DeserializeOwned + Send + Sync + 'static {
+    /// Stable name stored with each queued task; do not rename once in use
+    const KIND: &'static str;
+    /// Attempts before the task is dead-lettered
+    const MAX_ATTEMPTS: u32 = 5;
+}
+
+/// Delay before retrying a task that has failed `attempt` times
+pub fn retry_delay(attempt: u32) -> Duration {
+    exponential_backoff(TASK_RETRY_BASE, attempt).min(TASK_RETRY_MAX)
+}
+
+type TaskFuture = BoxFuture<'static, Result<(), AppError>>;
+type HandlerFn = Arc<dyn Fn(serde_json::Value) -> Result<TaskFuture, serde_json::Error> + Send + Sync>;
+
+/// Handlers for each kind of task this process can run
+#[derive(Clone, Default)]
+pub struct TaskRegistry {
+    handlers: HashMap<&'static str, HandlerFn>,
+}
+
+impl TaskRegistry {
+    pub fn register<T, F, Fut>(&mut self, handler: F) -> &mut Self
+    where
+        T: Task,
+        F: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), AppError>> + Send + 'static,
+    {
+        let handler = Arc::new(handler);
+        let run: HandlerFn = Arc::new(move |payload| {
+            let task: T = serde_json::from_value(payload)?;
+            let handler = handler.clone();
+            Ok(Box::pin(async move { handler(task).await }))
+        });
+        self.handlers.insert(T::KIND, run);
+        self
+    }
+
+    fn kinds(&self) -> Vec<&'static str> {
+        let mut kinds: Vec<_> = self.handlers.keys().copied().collect();
+        kinds.sort_unstable();
+        kinds
+    }
+}
+
+/// Claim the oldest due task of one of `kinds`, skipping rows other workers hold
+fn claim_statement(worker_id: &str, kinds: &[&str]) -> Statement {
+    let placeholders: Vec<String> = (0..kinds.len()).map(|i| format!("${}", i + 2)).collect();
+    let sql = format!(
+        r#"UPDATE background_task
+           SET status = 'Running', attempts = attempts + 1, locked_at = now(), locked_by = $1, updated_at = now()
+           WHERE id = (
+               SELECT id FROM background_task
+               WHERE status = 'Pending' AND run_at <= now() AND kind IN ({})
+               ORDER BY run_at
+               LIMIT 1
+               FOR UPDATE SKIP LOCKED
+           )
+           RETURNING *"#,
+        placeholders.join(", ")
+    );
+
+    let mut values: Vec<Value> = vec![worker_id.into()];
+    values.extend(kinds.iter().map(|kind| Value::from(*kind)));
+    Statement::from_sql_and_values(DatabaseBackend::Postgres, sql, values)
+}
+
+/// Enqueues tasks and lets admins inspect and retry them
+#[derive(Clone)]
+pub struct TaskQueue {
+    db: DatabaseConnection,
+}
+
+impl TaskQueue {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Queue a task to run as soon as a worker is free
+    pub async fn enqueue<T: Task>(&self, task: &T) -> Result<Uuid, AppError> {
+        let payload = serde_json::to_value(task)
+            .map_err(|e| AppError::Internal(format!("Failed to encode {} task: {}", T::KIND, e)))?;
+
+        let model = background_task::ActiveModel {
+            kind: Set(T::KIND.to_string()),
+            payload: Set(payload),
+            max_attempts: Set(T::MAX_ATTEMPTS as i32),
+            ..background_task::ActiveModel::new()
+        }
+        .insert(&self.db)
+        .await?;
+
+        Ok(model.id)
+    }
+
+    /// List tasks, newest first, a cursor page at a time
+    pub async fn list(
+        &self,
+        status: Option<TaskStatus>,
+        kind: Option<String>,
+        page: CursorParams,
+    ) -> Result<ListResponse<background_task::Model>, AppError> {
+        let after = Cursor::from_params(&page)?;
+        let mut query = background_task::Entity::find();
+        if let Some(status) = status {
+            query = query.filter(background_task::Column::Status.eq(status));
+        }
+        if let Some(kind) = kind {
+            query = query.filter(background_task::Column::Kind.eq(kind));
+        }
+
+        Ok(cursor_page(&self.db, query, after, page.limit()).await?)
+    }
+
+    pub async fn get(&self, task_id: Uuid) -> Result<background_task::Model, AppError> {
+        background_task::Entity::find_by_id(task_id)
+            .one(&self.db)
+            .await?
+            .ok_or(AppError::NotFound)
+    }
+
+    /// Give a dead-lettered task a fresh set of attempts
+    pub async fn retry(&self, task_id: Uuid) -> Result<background_task::Model, AppError> {
+        let task = self.get(task_id).await?;
+        if task.status != TaskStatus::DeadLettered {
+            return Err(AppError::Validation("Only dead-lettered tasks can be retried".to_string()));
+        }
+
+        let now = Utc::now();
+        let mut active: background_task::ActiveModel = task.clone().into();
+        active.status = Set(TaskStatus::Pending);
+        active.attempts = Set(0);
+        active.run_at = Set(now.into());
+        active.locked_at = Set(None);
+        active.locked_by = Set(None);
+        active.updated_at = Set(now.into());
+
+        let txn = self.db.begin().await?;
+        let retried = active.update(&txn).await?;
+        AuditService::new(self.db.clone())
+            .record_in(&txn, AuditEntry::new(AuditAction::AdminAction, "background_task", retried.id).change(&task, &retried))
+            .await?;
+        txn.commit().await?;
+        Ok(retried)
+    }
+
+    /// How long the longest-waiting due task has waited for a worker; zero when
+    /// nothing is due
+    pub async fn lag(&self) -> Result<Duration, AppError> {
+        let now = Utc::now();
+        let oldest = background_task::Entity::find()
+            .filter(background_task::Column::Status.eq(TaskStatus::Pending))
+            .filter(background_task::Column::RunAt.lte(now))
+            .order_by_asc(background_task::Column::RunAt)
+            .one(&self.db)
+            .await?;
+        Ok(oldest
+            .and_then(|task| (now - task.run_at.with_timezone(&Utc)).to_std().ok())
+            .unwrap_or_default())
+    }
+}
+
+impl From<&AppState> for TaskQueue {
+    fn from(state: &AppState) -> Self {
+        Self::new(state.db.clone())
+    }
+}
+
+/// Claims and runs queued tasks
+pub struct TaskWorker {
+    db: DatabaseConnection,
+    registry: TaskRegistry,
+    worker_id: String,
+}
+
+impl TaskWorker {
+    pub fn new(db: DatabaseConnection, registry: TaskRegistry) -> Self {
+        Self {
+            db,
+            registry,
+            worker_id: format!("worker-{}", Uuid::new_v4()),
+        }
+    }
+
+    /// Run the next due task, if any; returns whether one was run
+    pub async fn run_next(&self) -> Result<bool, AppError> {
+        let kinds = self.registry.kinds();
+        if kinds.is_empty() {
+            return Ok(false);
+        }
+
+        let Some(task) = background_task::Entity::find()
+            .from_raw_sql(claim_statement(&self.worker_id, &kinds))
+            .one(&self.db)
+            .await?
+        else {
+            return Ok(false);
+        };
+
+        let Some(handler) = self.registry.handlers.get(task.kind.as_str()) else {
+            // Kinds are filtered in the claim, so this only happens if the registry changed
+            self.fail(task, "No handler registered".to_string(), true).await?;
+            return Ok(true);
+        };
+
+        let future = match handler(task.payload.clone()) {
+            Ok(future) => future,
+            Err(e) => {
+                self.fail(task, format!("Invalid payload: {}", e), true).await?;
+                return Ok(true);
+            }
+        };
+
+        // Run on its own task so a panicking handler fails the task, not the worker
+        let outcome = tokio::time::timeout(TASK_TIMEOUT, tokio::spawn(future)).await;
+        match outcome {
+            Ok(Ok(Ok(()))) => self.complete(task).await?,
+            Ok(Ok(Err(e))) => self.fail(task, e.to_string(), false).await?,
+            Ok(Err(join_error)) => self.fail(task, format!("Task panicked: {}", join_error), false).await?,
+            Err(_) => self.fail(task, format!("Timed out after {:?}", TASK_TIMEOUT), false).await?,
+        }
+
+        Ok(true)
+    }
+
+    async fn complete(&self, task: background_task::Model) -> Result<(), AppError> {
+        let now = Utc::now();
+        let mut active: background_task::ActiveModel = task.into();
+        active.status = Set(TaskStatus::Completed);
+        active.locked_at = Set(None);
+        active.locked_by = Set(None);
+        active.last_error = Set(None);
+        active.completed_at = Set(Some(now.into()));
+        active.updated_at = Set(now.into());
+        active.update(&self.db).await?;
+        Ok(())
+    }
+
+    async fn fail(&self, task: background_task::Model, error: String, permanent: bool) -> Result<(), AppError> {
+        let now = Utc::now();
+        let dead = permanent || task.is_last_attempt();
+        let attempts = task.attempts.max(1) as u32;
+
+        if dead {
+            tracing::error!("☠️ Task {} ({}) dead-lettered after {} attempts: {}", task.id, task.kind, task.attempts, error);
+        } else {
+            tracing::warn!("Task {} ({}) failed on attempt {}: {}", task.id, task.kind, task.attempts, error);
+        }
+
+        let mut active: background_task::ActiveModel = task.into();
+        if dead {
+            active.status = Set(TaskStatus::DeadLettered);
+        } else {
+            let delay = chrono::Duration::from_std(retry_delay(attempts)).unwrap_or(chrono::Duration::hours(1));
+            active.status = Set(TaskStatus::Pending);
+            active.run_at = Set((now + delay).into());
+        }
+        active.locked_at = Set(None);
+        active.locked_by = Set(None);
+        active.last_error = Set(Some(error));
+        active.updated_at = Set(now.into());
+        active.update(&self.db).await?;
+        Ok(())
+    }
+
+    /// Return tasks held by workers that died mid-run to the queue
+    pub async fn release_stale(&self) -> Result<u64, AppError> {
+        let cutoff = Utc::now() - chrono::Duration::minutes(STALE_LOCK_MINUTES);
+        let result = background_task::Entity::update_many()
+            .col_expr(background_task::Column::Status, TaskStatus::Pending.into())
+            .col_expr(background_task::Column::LockedAt, Option::<chrono::DateTime<Utc>>::None.into())
+            .col_expr(background_task::Column::LockedBy, Option::<String>::None.into())
+            .filter(background_task::Column::Status.eq(TaskStatus::Running))
+            .filter(background_task::Column::LockedAt.lt(cutoff))
+            .exec(&self.db)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+}
+
+/// Start `count` worker loops plus a sweeper for stale locks
+pub fn spawn_workers(worker: TaskWorker, count: usize) {
+    let worker = Arc::new(worker);
+
+    for _ in 0..count {
+        let worker = worker.clone();
+        tokio::spawn(async move {
+            loop {
+                match worker.run_next().await {
+                    Ok(true) => continue,
+                    Ok(false) => tokio::time::sleep(POLL_INTERVAL).await,
+                    Err(e) => {
+                        tracing::error!("Task worker error: {}", e);
+                        tokio::time::sleep(POLL_INTERVAL).await;
+                    }
+                }
+            }
+        });
+    }
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            match worker.release_stale().await {
+                Ok(0) => {}
+                Ok(released) => tracing::warn!("Released {} stale background tasks", released),
+                Err(e) => tracing::error!("Failed to release stale background tasks: {}", e),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Echo {
+        value: u32,
+    }
+
+    impl Task for Echo {
+        const KIND: &'static str = "echo";
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_the_cap() {
+        assert_eq!(retry_delay(1), Duration::from_secs(30));
+        assert_eq!(retry_delay(2), Duration::from_secs(60));
+        assert_eq!(retry_delay(4), Duration::from_secs(240));
+        assert_eq!(retry_delay(20), TASK_RETRY_MAX);
+    }
+
+    #[tokio::test]
+    async fn registry_decodes_typed_payloads() {
+        let mut registry = TaskRegistry::default();
+        registry.register(|task: Echo| async move {
+            if task.value == 7 {
+                Ok(())
+            } else {
+                Err(AppError::Internal("unexpected value".to_string()))
+            }
+        });
+
+        let handler = registry.handlers.get("echo").unwrap();
+        assert!(handler(serde_json::json!({ "value": 7 })).unwrap().await.is_ok());
+        assert!(handler(serde_json::json!({ "value": 8 })).unwrap().await.is_err());
+        assert!(handler(serde_json::json!({ "other": true })).is_err());
+        assert_eq!(registry.kinds(), vec!["echo"]);
+    }
+
+    #[test]
+    fn claim_only_targets_registered_kinds() {
+        let statement = claim_statement("worker-1", &["echo", "send_email"]);
+        assert!(statement.sql.contains("kind IN ($2, $3)"));
+        assert!(statement.sql.contains("FOR UPDATE SKIP LOCKED"));
+        assert_eq!(statement.values.map(|v| v.0.len()), Some(3));
+    }
+}
@@ -0,0 +1,411 @@
+use chrono::{Datelike, Duration, Utc};
+use sea_orm::{
+    ActiveModelBehavior, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    EntityTrait, QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+use shared::calendar;
+use shared::pay::{PayCalculator, ShiftPay, WorkedShift};
+use shared::timesheets::TimesheetEntry;
+use shared::types::JobType;
+use uuid::Uuid;
+
+use crate::{
+    entities::{
+        application::{self, ApplicationStatus},
+        job, job_shift,
+        timesheet::{self, TimesheetStatus},
+        timesheet_comment,
+        timesheet_revision::{self, TimesheetAction},
+    },
+    services::webhook_service::WebhookService,
+    AppError, AppState,
+};
+
+/// Longest comment on a timesheet
+pub const MAX_COMMENT_LENGTH: usize = 2000;
+
+/// Which side of the booking someone is on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Party {
+    Locum,
+    /// The job's poster, or an admin acting for them
+    Pharmacy,
+}
+
+fn party(application: &application::Model, job: &job::Model, requester_id: Uuid, is_admin: bool) -> Result<Party, AppError> {
+    if application.user_id == requester_id {
+        Ok(Party::Locum)
+    } else if job.created_by == requester_id || is_admin {
+        Ok(Party::Pharmacy)
+    } else {
+        Err(AppError::NotFound)
+    }
+}
+
+/// A timesheet with its edit history and discussion, oldest first
+#[derive(Debug, Clone)]
+pub struct TimesheetDetail {
+    pub timesheet: timesheet::Model,
+    pub revisions: Vec<timesheet_revision::Model>,
+    pub comments: Vec<timesheet_comment::Model>,
+}
+
+#[derive(Clone)]
+pub struct TimesheetService {
+    db: DatabaseConnection,
+}
+
+impl TimesheetService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// An application's timesheets by date (the locum, the job's poster or an admin)
+    pub async fn list_for_application(
+        &self,
+        application_id: Uuid,
+        requester_id: Uuid,
+        is_admin: bool,
+    ) -> Result<Vec<timesheet::Model>, AppError> {
+        let (application, job) = self.application(application_id).await?;
+        party(&application, &job, requester_id, is_admin)?;
+
+        Ok(timesheet::Entity::find()
+            .filter(timesheet::Column::ApplicationId.eq(application_id))
+            .order_by_asc(timesheet::Column::Date)
+            .order_by_asc(timesheet::Column::StartTime)
+            .all(&self.db)
+            .await?)
+    }
+
+    pub async fn get(&self, timesheet_id: Uuid, requester_id: Uuid, is_admin: bool) -> Result<TimesheetDetail, AppError> {
+        let (timesheet, application, job) = self.load(timesheet_id).await?;
+        party(&application, &job, requester_id, is_admin)?;
+        self.detail(timesheet).await
+    }
+
+    /// Record the hours the locum worked on a shift of their accepted application
+    pub async fn submit(
+        &self,
+        application_id: Uuid,
+        requester_id: Uuid,
+        entry: &TimesheetEntry,
+    ) -> Result<timesheet::Model, AppError> {
+        let (application, job) = self.application(application_id).await?;
+        if party(&application, &job, requester_id, false)? != Party::Locum {
+            return Err(AppError::Forbidden);
+        }
+        if application.status != ApplicationStatus::Accepted {
+            return Err(AppError::Validation(
+                "Timesheets can only be submitted for accepted applications".to_string(),
+            ));
+        }
+        self.check_entry(&application, entry).await?;
+
+        let (start, end) = entry.spec().time_range()?;
+        let duplicate = timesheet::Entity::find()
+            .filter(timesheet::Column::ApplicationId.eq(application_id))
+            .filter(timesheet::Column::Date.eq(entry.date))
+            .filter(timesheet::Column::StartTime.eq(start))
+            .one(&self.db)
+            .await?;
+        if duplicate.is_some() {
+            return Err(AppError::Validation(
+                "A timesheet for this shift has already been submitted".to_string(),
+            ));
+        }
+
+        let txn = self.db.begin().await?;
+        let timesheet = timesheet::ActiveModel {
+            application_id: Set(application_id),
+            shift_id: Set(entry.shift_id),
+            date: Set(entry.date),
+            start_time: Set(start),
+            end_time: Set(end),
+            break_minutes: Set(entry.break_minutes as i32),
+            ..timesheet::ActiveModel::new()
+        }
+        .insert(&txn)
+        .await?;
+        Self::record_revision(&txn, &timesheet, requester_id, TimesheetAction::Submitted).await?;
+        txn.commit().await?;
+
+        Ok(timesheet)
+    }
+
+    /// Correct a timesheet's times (the locum only). A disputed timesheet goes back
+    /// for review; approved ones are locked.
+    pub async fn edit(
+        &self,
+        timesheet_id: Uuid,
+        requester_id: Uuid,
+        entry: &TimesheetEntry,
+    ) -> Result<timesheet::Model, AppError> {
+        let (timesheet, application, job) = self.load(timesheet_id).await?;
+        if party(&application, &job, requester_id, false)? != Party::Locum {
+            return Err(AppError::Forbidden);
+        }
+        Self::check_unlocked(&timesheet)?;
+        self.check_entry(&application, entry).await?;
+
+        let (start, end) = entry.spec().time_range()?;
+        let txn = self.db.begin().await?;
+        let mut active: timesheet::ActiveModel = timesheet.into();
+        active.shift_id = Set(entry.shift_id);
+        active.date = Set(entry.date);
+        active.start_time = Set(start);
+        active.end_time = Set(end);
+        active.break_minutes = Set(entry.break_minutes as i32);
+        active.status = Set(TimesheetStatus::Submitted);
+        active.updated_at = Set(Utc::now().into());
+        let timesheet = active.update(&txn).await?;
+        Self::record_revision(&txn, &timesheet, requester_id, TimesheetAction::Edited).await?;
+        txn.commit().await?;
+
+        Ok(timesheet)
+    }
+
+    /// Approve a timesheet (the job's poster or an admin), pricing it under the award
+    /// and locking it against further edits
+    pub async fn approve(&self, timesheet_id: Uuid, requester_id: Uuid, is_admin: bool) -> Result<timesheet::Model, AppError> {
+        let (timesheet, application, job) = self.load(timesheet_id).await?;
+        if party(&application, &job, requester_id, is_admin)? != Party::Pharmacy {
+            return Err(AppError::Forbidden);
+        }
+        Self::check_unlocked(&timesheet)?;
+
+        let pay = self.price(&timesheet, &job).await?;
+        let breakdown = serde_json::to_value(&pay)
+            .map_err(|e| AppError::Internal(format!("Failed to encode timesheet pay: {}", e)))?;
+
+        let txn = self.db.begin().await?;
+        let now = Utc::now();
+        let changes = timesheet::ActiveModel {
+            status: Set(TimesheetStatus::Approved),
+            pay_total: Set(Some(pay.total)),
+            pay_breakdown: Set(Some(breakdown)),
+            reviewed_by: Set(Some(requester_id)),
+            reviewed_at: Set(Some(now.into())),
+            updated_at: Set(now.into()),
+            ..Default::default()
+        };
+        // Only as it was priced: not approved by someone else or edited in the meantime
+        let timesheet = timesheet::Entity::update_many()
+            .set(changes)
+            .filter(timesheet::Column::Id.eq(timesheet.id))
+            .filter(timesheet::Column::Status.eq(timesheet.status))
+            .filter(timesheet::Column::UpdatedAt.eq(timesheet.updated_at))
+            .exec_with_returning(&txn)
+            .await?
+            .pop()
+            .ok_or_else(|| {
+                AppError::Conflict("The timesheet changed while it was being approved; reload it and try again".to_string())
+            })?;
+        Self::record_revision(&txn, &timesheet, requester_id, TimesheetAction::Approved).await?;
+        txn.commit().await?;
+        WebhookService::new(self.db.clone()).timesheet_approved(&job, &timesheet).await;
+
+        Ok(timesheet)
+    }
+
+    /// Dispute a submitted timesheet (the job's poster or an admin), starting the
+    /// discussion with the reason
+    pub async fn dispute(
+        &self,
+        timesheet_id: Uuid,
+        requester_id: Uuid,
+        is_admin: bool,
+        reason: &str,
+    ) -> Result<TimesheetDetail, AppError> {
+        let (timesheet, application, job) = self.load(timesheet_id).await?;
+        if party(&application, &job, requester_id, is_admin)? != Party::Pharmacy {
+            return Err(AppError::Forbidden);
+        }
+        Self::check_unlocked(&timesheet)?;
+        if timesheet.status == TimesheetStatus::Disputed {
+            return Err(AppError::Validation(
+                "The timesheet is already disputed; add a comment instead".to_string(),
+            ));
+        }
+        let reason = Self::comment_body(reason)?;
+
+        let txn = self.db.begin().await?;
+        let now = Utc::now();
+        let mut active: timesheet::ActiveModel = timesheet.into();
+        active.status = Set(TimesheetStatus::Disputed);
+        active.reviewed_by = Set(Some(requester_id));
+        active.reviewed_at = Set(Some(now.into()));
+        active.updated_at = Set(now.into());
+        let timesheet = active.update(&txn).await?;
+        Self::record_revision(&txn, &timesheet, requester_id, TimesheetAction::Disputed).await?;
+        Self::insert_comment(&txn, timesheet.id, requester_id, reason).await?;
+        txn.commit().await?;
+
+        self.detail(timesheet).await
+    }
+
+    /// Add to a timesheet's discussion (the locum, the job's poster or an admin)
+    pub async fn comment(
+        &self,
+        timesheet_id: Uuid,
+        requester_id: Uuid,
+        is_admin: bool,
+        body: &str,
+    ) -> Result<timesheet_comment::Model, AppError> {
+        let (timesheet, application, job) = self.load(timesheet_id).await?;
+        party(&application, &job, requester_id, is_admin)?;
+        let body = Self::comment_body(body)?;
+
+        Self::insert_comment(&self.db, timesheet.id, requester_id, body).await
+    }
+
+    /// Award pay for a timesheet, with weekly overtime counted after the locum's other
+    /// approved timesheets for the job that week
+    async fn price(&self, timesheet: &timesheet::Model, job: &job::Model) -> Result<ShiftPay, AppError> {
+        let entry = timesheet.to_entry();
+        let week_start = entry.date - Duration::days(i64::from(entry.date.weekday().num_days_from_monday()));
+
+        let mut worked: Vec<WorkedShift> = timesheet::Entity::find()
+            .filter(timesheet::Column::ApplicationId.eq(timesheet.application_id))
+            .filter(timesheet::Column::Status.eq(TimesheetStatus::Approved))
+            .filter(timesheet::Column::Date.between(week_start, entry.date))
+            .filter(timesheet::Column::Id.ne(timesheet.id))
+            .all(&self.db)
+            .await?
+            .iter()
+            .map(|other| other.to_entry().worked())
+            .collect::<Result<_, _>>()?;
+        worked.push(entry.worked()?);
+
+        let holidays = match job.australian_state() {
+            Some(state) => calendar::holiday_dates(&state, week_start, entry.date + Duration::days(1)),
+            None => Vec::new(),
+        };
+        let estimate =
+            PayCalculator::new(&holidays).worked(&worked, &JobType::from(job.job_type.clone()), job.hourly_rate)?;
+
+        estimate
+            .shifts
+            .into_iter()
+            .find(|shift| shift.date == entry.date && shift.start_time == entry.start_time)
+            .ok_or_else(|| AppError::Internal("Timesheet missing from its pay calculation".to_string()))
+    }
+
+    /// The times must be valid, already worked, and on a shift the locum was given
+    async fn check_entry(&self, application: &application::Model, entry: &TimesheetEntry) -> Result<(), AppError> {
+        entry.worked()?;
+        // Shifts are in Australian time, up to a day ahead of the server's UTC clock
+        if entry.ends_at()? > Utc::now().naive_utc() + Duration::days(1) {
+            return Err(AppError::Validation("Timesheets can't be submitted before the shift ends".to_string()));
+        }
+
+        if let Some(shift_id) = entry.shift_id {
+            let shift = job_shift::Entity::find_by_id(shift_id)
+                .filter(job_shift::Column::JobId.eq(application.job_id))
+                .one(&self.db)
+                .await?
+                .ok_or_else(|| AppError::Validation("shift_id: Unknown shift for this job".to_string()))?;
+            if shift.filled_by != Some(application.user_id) {
+                return Err(AppError::Validation("shift_id: The shift wasn't filled by this locum".to_string()));
+            }
+            if shift.date != entry.date {
+                return Err(AppError::Validation("date: The date doesn't match the shift".to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_unlocked(timesheet: &timesheet::Model) -> Result<(), AppError> {
+        if timesheet.status.is_locked() {
+            return Err(AppError::Validation("Approved timesheets are locked".to_string()));
+        }
+        Ok(())
+    }
+
+    fn comment_body(body: &str) -> Result<&str, AppError> {
+        let body = body.trim();
+        if body.is_empty() {
+            return Err(AppError::Validation("Comment cannot be empty".to_string()));
+        }
+        if body.chars().count() > MAX_COMMENT_LENGTH {
+            return Err(AppError::Validation(format!(
+                "Comment cannot be longer than {} characters",
+                MAX_COMMENT_LENGTH
+            )));
+        }
+        Ok(body)
+    }
+
+    async fn record_revision<C: ConnectionTrait>(
+        conn: &C,
+        timesheet: &timesheet::Model,
+        revised_by: Uuid,
+        action: TimesheetAction,
+    ) -> Result<timesheet_revision::Model, AppError> {
+        Ok(timesheet_revision::ActiveModel {
+            timesheet_id: Set(timesheet.id),
+            revised_by: Set(Some(revised_by)),
+            action: Set(action),
+            start_time: Set(timesheet.start_time),
+            end_time: Set(timesheet.end_time),
+            break_minutes: Set(timesheet.break_minutes),
+            ..timesheet_revision::ActiveModel::new()
+        }
+        .insert(conn)
+        .await?)
+    }
+
+    async fn insert_comment<C: ConnectionTrait>(
+        conn: &C,
+        timesheet_id: Uuid,
+        author_id: Uuid,
+        body: &str,
+    ) -> Result<timesheet_comment::Model, AppError> {
+        Ok(timesheet_comment::ActiveModel {
+            timesheet_id: Set(timesheet_id),
+            author_id: Set(Some(author_id)),
+            body: Set(body.to_string()),
+            ..timesheet_comment::ActiveModel::new()
+        }
+        .insert(conn)
+        .await?)
+    }
+
+    async fn detail(&self, timesheet: timesheet::Model) -> Result<TimesheetDetail, AppError> {
+        let revisions = timesheet_revision::Entity::find()
+            .filter(timesheet_revision::Column::TimesheetId.eq(timesheet.id))
+            .order_by_asc(timesheet_revision::Column::CreatedAt)
+            .all(&self.db)
+            .await?;
+        let comments = timesheet_comment::Entity::find()
+            .filter(timesheet_comment::Column::TimesheetId.eq(timesheet.id))
+            .order_by_asc(timesheet_comment::Column::CreatedAt)
+            .all(&self.db)
+            .await?;
+        Ok(TimesheetDetail { timesheet, revisions, comments })
+    }
+
+    async fn application(&self, application_id: Uuid) -> Result<(application::Model, job::Model), AppError> {
+        let (application, job) = application::Entity::find_by_id(application_id)
+            .find_also_related(job::Entity)
+            .one(&self.db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        Ok((application, job.ok_or(AppError::NotFound)?))
+    }
+
+    async fn load(&self, timesheet_id: Uuid) -> Result<(timesheet::Model, application::Model, job::Model), AppError> {
+        let timesheet = timesheet::Entity::find_by_id(timesheet_id)
+            .one(&self.db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        let (application, job) = self.application(timesheet.application_id).await?;
+        Ok((timesheet, application, job))
+    }
+}
+
+impl From<&AppState> for TimesheetService {
+    fn from(state: &AppState) -> Self {
+        Self::new(state.db.clone())
+    }
+}
@@ -0,0 +1,165 @@
+use std::path::PathBuf;
+use uuid::Uuid;
+use sea_orm::{
+    ActiveModelBehavior, ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set,
+};
+
+use crate::{
+    entities::upload::{self, Entity as Upload},
+    AppError, AppState,
+};
+
+/// Largest file accepted by the upload endpoint
+pub const MAX_UPLOAD_BYTES: usize = 10 * 1024 * 1024;
+
+/// Content types users may upload (documents and images)
+pub const ALLOWED_CONTENT_TYPES: &[&str] = &[
+    "application/pdf",
+    "application/msword",
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    "image/jpeg",
+    "image/png",
+    "image/webp",
+    "text/plain",
+];
+
+/// Strip directory components and control characters from a client-supplied file name
+pub fn sanitize_file_name(name: &str) -> String {
+    let base = name.rsplit(['/', '\\']).next().unwrap_or_default();
+    let cleaned: String = base
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect::<String>()
+        .trim()
+        .trim_start_matches('.')
+        .chars()
+        .take(255)
+        .collect();
+
+    if cleaned.is_empty() {
+        "file".to_string()
+    } else {
+        cleaned
+    }
+}
+
+/// Stores uploaded files on local disk with their metadata in the `upload` table
+#[derive(Clone)]
+pub struct UploadService {
+    db: DatabaseConnection,
+    upload_dir: PathBuf,
+}
+
+impl UploadService {
+    pub fn new(db: DatabaseConnection, upload_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            db,
+            upload_dir: upload_dir.into(),
+        }
+    }
+
+    /// Validate and persist a file for the given owner
+    pub async fn store(
+        &self,
+        owner_id: Uuid,
+        file_name: &str,
+        content_type: &str,
+        bytes: &[u8],
+    ) -> Result<upload::Model, AppError> {
+        if bytes.is_empty() {
+            return Err(AppError::Validation("Uploaded file is empty".to_string()));
+        }
+        if bytes.len() > MAX_UPLOAD_BYTES {
+            return Err(AppError::Validation(format!(
+                "Uploaded file exceeds the {} MB limit",
+                MAX_UPLOAD_BYTES / (1024 * 1024)
+            )));
+        }
+        if !ALLOWED_CONTENT_TYPES.contains(&content_type) {
+            return Err(AppError::Validation(format!(
+                "Files of type {} are not accepted",
+                content_type
+            )));
+        }
+
+        let storage_key = Uuid::new_v4().simple().to_string();
+
+        tokio::fs::create_dir_all(&self.upload_dir)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to prepare upload storage: {}", e)))?;
+        tokio::fs::write(self.upload_dir.join(&storage_key), bytes)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to store upload: {}", e)))?;
+
+        let model = upload::ActiveModel {
+            owner_id: Set(owner_id),
+            file_name: Set(sanitize_file_name(file_name)),
+            content_type: Set(content_type.to_string()),
+            size_bytes: Set(bytes.len() as i64),
+            storage_key: Set(storage_key),
+            ..upload::ActiveModel::new()
+        };
+
+        model
+            .insert(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to record upload: {}", e)))
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> Result<upload::Model, AppError> {
+        Upload::find_by_id(id)
+            .one(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Database error: {}", e)))?
+            .ok_or(AppError::NotFound)
+    }
+
+    /// Load uploads by ID, failing unless every one exists and belongs to the owner
+    pub async fn find_owned(&self, ids: &[Uuid], owner_id: Uuid) -> Result<Vec<upload::Model>, AppError> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let uploads = Upload::find()
+            .filter(upload::Column::Id.is_in(ids.to_vec()))
+            .filter(upload::Column::OwnerId.eq(owner_id))
+            .all(&self.db)
+            .await
+            .map_err(|e| AppError::Database(format!("Database error: {}", e)))?;
+
+        if let Some(missing) = ids.iter().find(|id| !uploads.iter().any(|u| u.id == **id)) {
+            return Err(AppError::Validation(format!("Unknown attachment {}", missing)));
+        }
+
+        Ok(uploads)
+    }
+
+    /// Read the stored bytes for an upload
+    pub async fn read(&self, upload: &upload::Model) -> Result<Vec<u8>, AppError> {
+        tokio::fs::read(self.upload_dir.join(&upload.storage_key))
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to read upload {}: {}", upload.id, e)))
+    }
+}
+
+impl From<&AppState> for UploadService {
+    fn from(state: &AppState) -> Self {
+        Self::new(state.db.clone(), &state.config.upload_dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_file_name() {
+        assert_eq!(sanitize_file_name("resume.pdf"), "resume.pdf");
+        assert_eq!(sanitize_file_name("../../etc/passwd"), "passwd");
+        assert_eq!(sanitize_file_name("C:\\Users\\me\\cv.docx"), "cv.docx");
+        assert_eq!(sanitize_file_name(".hidden"), "hidden");
+        assert_eq!(sanitize_file_name("bad\nname.txt"), "badname.txt");
+        assert_eq!(sanitize_file_name(""), "file");
+        assert_eq!(sanitize_file_name("../"), "file");
+    }
+}
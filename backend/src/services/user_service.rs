@@ -1,6 +1,12 @@
 use uuid::Uuid;
-use shared::types::{User, UserType};
-use crate::AppError;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, Set, TransactionTrait};
+use shared::types::{CursorParams, ListResponse, User, UserType};
+use crate::{
+    entities::{audit_event::AuditAction, user},
+    repository::{Cursor, UserRepository},
+    services::audit_service::{AuditEntry, AuditService},
+    AppError,
+};
 
 pub struct UserService;
 
@@ -86,6 +92,54 @@ impl UserService {
         Ok(users.iter().any(|u| u.id == user_id))
     }
     
+    /// Users matching `search` on email or name, newest first (admin only)
+    pub async fn list_users(
+        db: &DatabaseConnection,
+        search: Option<&str>,
+        page: CursorParams,
+    ) -> Result<ListResponse<User>, AppError> {
+        let after = Cursor::from_params(&page)?;
+        let users = UserRepository::new()
+            .find_users(db, search, after, page.limit())
+            .await?;
+        Ok(users.map(|user| user.to_user()))
+    }
+
+    /// Change a user's role (admin only). Admins cannot change their own role, so
+    /// there is always another admin to undo a mistake.
+    pub async fn change_role(
+        db: &DatabaseConnection,
+        user_id: Uuid,
+        admin_id: Uuid,
+        user_type: UserType,
+    ) -> Result<User, AppError> {
+        if user_id == admin_id {
+            return Err(AppError::Validation("user_type: You cannot change your own role".to_string()));
+        }
+        let existing = user::Entity::find_by_id(user_id)
+            .one(db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+
+        // The new role and its audit event are saved together
+        let txn = db.begin().await?;
+        let mut active: user::ActiveModel = existing.clone().into();
+        active.user_type = Set(user_type.into());
+        let updated = active.update(&txn).await?;
+
+        AuditService::new(db.clone())
+            .record_in(
+                &txn,
+                AuditEntry::new(AuditAction::RoleChanged, "user", user_id)
+                    .actor(admin_id)
+                    .change(&existing, &updated),
+            )
+            .await?;
+        txn.commit().await?;
+        tracing::info!("👤 User {} is now {:?} (changed by {})", user_id, updated.user_type, admin_id);
+        Ok(updated.to_user())
+    }
+
     /// Update last login (Demo mode)
     pub async fn update_last_login(
         user_id: Uuid,
@@ -7,6 +7,25 @@ mod m20220101_000004_create_tenants_table;
 mod m20220101_000005_create_tenant_users_table;
 mod m20220101_000006_add_tenant_to_tables;
 mod m20220101_000007_create_rls_policies;
+mod m20220101_000008_create_uploads_table;
+mod m20220101_000009_create_messaging_tables;
+mod m20220101_000010_create_notifications_tables;
+mod m20220101_000011_create_realtime_tables;
+mod m20220101_000012_add_job_lifecycle;
+mod m20220101_000013_create_background_tasks_table;
+mod m20220101_000014_create_availability_table;
+mod m20220101_000015_create_professional_profiles_table;
+mod m20220101_000016_create_job_shifts_table;
+mod m20220101_000017_create_timesheets_table;
+mod m20220101_000018_create_invoices_table;
+mod m20220101_000019_create_job_templates_table;
+mod m20220101_000020_create_audit_events_table;
+mod m20220101_000021_create_idempotency_keys_table;
+mod m20220101_000022_add_keyset_indexes;
+mod m20220101_000023_create_webhook_tables;
+mod m20220101_000024_add_realtime_sequence_index;
+mod m20220101_000025_create_saved_searches_table;
+mod m20220101_000026_create_audit_outbox_table;
 
 pub struct Migrator;
 
@@ -21,6 +40,25 @@ impl MigratorTrait for Migrator {
             Box::new(m20220101_000005_create_tenant_users_table::Migration),
             Box::new(m20220101_000006_add_tenant_to_tables::Migration),
             Box::new(m20220101_000007_create_rls_policies::Migration),
+            Box::new(m20220101_000008_create_uploads_table::Migration),
+            Box::new(m20220101_000009_create_messaging_tables::Migration),
+            Box::new(m20220101_000010_create_notifications_tables::Migration),
+            Box::new(m20220101_000011_create_realtime_tables::Migration),
+            Box::new(m20220101_000012_add_job_lifecycle::Migration),
+            Box::new(m20220101_000013_create_background_tasks_table::Migration),
+            Box::new(m20220101_000014_create_availability_table::Migration),
+            Box::new(m20220101_000015_create_professional_profiles_table::Migration),
+            Box::new(m20220101_000016_create_job_shifts_table::Migration),
+            Box::new(m20220101_000017_create_timesheets_table::Migration),
+            Box::new(m20220101_000018_create_invoices_table::Migration),
+            Box::new(m20220101_000019_create_job_templates_table::Migration),
+            Box::new(m20220101_000020_create_audit_events_table::Migration),
+            Box::new(m20220101_000021_create_idempotency_keys_table::Migration),
+            Box::new(m20220101_000022_add_keyset_indexes::Migration),
+            Box::new(m20220101_000023_create_webhook_tables::Migration),
+            Box::new(m20220101_000024_add_realtime_sequence_index::Migration),
+            Box::new(m20220101_000025_create_saved_searches_table::Migration),
+            Box::new(m20220101_000026_create_audit_outbox_table::Migration),
         ]
     }
 }
\ No newline at end of file
@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create uploads table for user-supplied files (file bytes live in upload storage)
+        manager
+            .create_table(
+                Table::create()
+                    .table(Upload::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Upload::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Upload::OwnerId).uuid().not_null())
+                    .col(ColumnDef::new(Upload::FileName).string().not_null())
+                    .col(ColumnDef::new(Upload::ContentType).string().not_null())
+                    .col(ColumnDef::new(Upload::SizeBytes).big_integer().not_null())
+                    .col(ColumnDef::new(Upload::StorageKey).string().not_null().unique_key())
+                    .col(
+                        ColumnDef::new(Upload::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_upload_owner")
+                            .from(Upload::Table, Upload::OwnerId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_upload_owner")
+                    .table(Upload::Table)
+                    .col(Upload::OwnerId)
+                    .col(Upload::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Upload::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Upload {
+    Table,
+    Id,
+    OwnerId,
+    FileName,
+    ContentType,
+    SizeBytes,
+    StorageKey,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,355 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create conversations table; every conversation hangs off a job or an application
+        manager
+            .create_table(
+                Table::create()
+                    .table(Conversation::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Conversation::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Conversation::JobId).uuid())
+                    .col(ColumnDef::new(Conversation::ApplicationId).uuid())
+                    .col(ColumnDef::new(Conversation::Subject).string())
+                    .col(ColumnDef::new(Conversation::CreatedBy).uuid().not_null())
+                    .col(ColumnDef::new(Conversation::LastMessageAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(Conversation::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Conversation::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .check(
+                        Expr::col(Conversation::JobId)
+                            .is_not_null()
+                            .or(Expr::col(Conversation::ApplicationId).is_not_null()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_conversation_job")
+                            .from(Conversation::Table, Conversation::JobId)
+                            .to(Job::Table, Job::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_conversation_application")
+                            .from(Conversation::Table, Conversation::ApplicationId)
+                            .to(Application::Table, Application::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_conversation_created_by")
+                            .from(Conversation::Table, Conversation::CreatedBy)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_conversation_job")
+                    .table(Conversation::Table)
+                    .col(Conversation::JobId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_conversation_application")
+                    .table(Conversation::Table)
+                    .col(Conversation::ApplicationId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Participants carry the read position used for read receipts and unread counts
+        manager
+            .create_table(
+                Table::create()
+                    .table(ConversationParticipant::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ConversationParticipant::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ConversationParticipant::ConversationId).uuid().not_null())
+                    .col(ColumnDef::new(ConversationParticipant::UserId).uuid().not_null())
+                    .col(ColumnDef::new(ConversationParticipant::LastReadAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(ConversationParticipant::JoinedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_conversation_participant_conversation")
+                            .from(ConversationParticipant::Table, ConversationParticipant::ConversationId)
+                            .to(Conversation::Table, Conversation::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_conversation_participant_user")
+                            .from(ConversationParticipant::Table, ConversationParticipant::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_conversation_participant_unique")
+                    .table(ConversationParticipant::Table)
+                    .col(ConversationParticipant::ConversationId)
+                    .col(ConversationParticipant::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_conversation_participant_user")
+                    .table(ConversationParticipant::Table)
+                    .col(ConversationParticipant::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create messages table
+        manager
+            .create_table(
+                Table::create()
+                    .table(Message::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Message::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Message::ConversationId).uuid().not_null())
+                    .col(ColumnDef::new(Message::SenderId).uuid().not_null())
+                    .col(ColumnDef::new(Message::Body).text().not_null())
+                    .col(
+                        ColumnDef::new(Message::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_message_conversation")
+                            .from(Message::Table, Message::ConversationId)
+                            .to(Conversation::Table, Conversation::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_message_sender")
+                            .from(Message::Table, Message::SenderId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // History is always read newest-first within a conversation
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_message_conversation_created")
+                    .table(Message::Table)
+                    .col(Message::ConversationId)
+                    .col(Message::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Link messages to uploaded files
+        manager
+            .create_table(
+                Table::create()
+                    .table(MessageAttachment::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MessageAttachment::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(MessageAttachment::MessageId).uuid().not_null())
+                    .col(ColumnDef::new(MessageAttachment::UploadId).uuid().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_message_attachment_message")
+                            .from(MessageAttachment::Table, MessageAttachment::MessageId)
+                            .to(Message::Table, Message::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_message_attachment_upload")
+                            .from(MessageAttachment::Table, MessageAttachment::UploadId)
+                            .to(Upload::Table, Upload::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_message_attachment_unique")
+                    .table(MessageAttachment::Table)
+                    .col(MessageAttachment::MessageId)
+                    .col(MessageAttachment::UploadId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_message_attachment_upload")
+                    .table(MessageAttachment::Table)
+                    .col(MessageAttachment::UploadId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MessageAttachment::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Message::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ConversationParticipant::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Conversation::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Conversation {
+    Table,
+    Id,
+    JobId,
+    ApplicationId,
+    Subject,
+    CreatedBy,
+    LastMessageAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ConversationParticipant {
+    Table,
+    Id,
+    ConversationId,
+    UserId,
+    LastReadAt,
+    JoinedAt,
+}
+
+#[derive(DeriveIden)]
+enum Message {
+    Table,
+    Id,
+    ConversationId,
+    SenderId,
+    Body,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum MessageAttachment {
+    Table,
+    Id,
+    MessageId,
+    UploadId,
+}
+
+#[derive(DeriveIden)]
+enum Job {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Application {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Upload {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
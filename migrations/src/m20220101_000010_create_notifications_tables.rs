@@ -0,0 +1,197 @@
+use sea_orm_migration::prelude::*;
+
+const EVENT_TYPES: [&str; 5] = [
+    "ApplicationReceived",
+    "ApplicationStatusChanged",
+    "JobExpiring",
+    "SavedSearchMatch",
+    "NewMessage",
+];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create notifications table for the per-user notification centre
+        manager
+            .create_table(
+                Table::create()
+                    .table(Notification::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Notification::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Notification::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(Notification::EventType)
+                            .string_len(40)
+                            .not_null()
+                            .check(Expr::col(Notification::EventType).is_in(EVENT_TYPES)),
+                    )
+                    .col(ColumnDef::new(Notification::Title).string().not_null())
+                    .col(ColumnDef::new(Notification::Body).text().not_null())
+                    .col(ColumnDef::new(Notification::Link).string())
+                    .col(ColumnDef::new(Notification::Data).json_binary())
+                    .col(ColumnDef::new(Notification::InApp).boolean().not_null().default(true))
+                    .col(ColumnDef::new(Notification::DigestPending).boolean().not_null().default(false))
+                    .col(ColumnDef::new(Notification::ReadAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(Notification::EmailedAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(Notification::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_notification_user")
+                            .from(Notification::Table, Notification::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_notification_user_created")
+                    .table(Notification::Table)
+                    .col(Notification::UserId)
+                    .col(Notification::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_notification_user_read")
+                    .table(Notification::Table)
+                    .col(Notification::UserId)
+                    .col(Notification::ReadAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_notification_digest_pending")
+                    .table(Notification::Table)
+                    .col(Notification::DigestPending)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Channel preferences; a missing row means the event type's defaults apply
+        manager
+            .create_table(
+                Table::create()
+                    .table(NotificationPreference::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(NotificationPreference::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(NotificationPreference::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(NotificationPreference::EventType)
+                            .string_len(40)
+                            .not_null()
+                            .check(Expr::col(NotificationPreference::EventType).is_in(EVENT_TYPES)),
+                    )
+                    .col(ColumnDef::new(NotificationPreference::InApp).boolean().not_null().default(true))
+                    .col(ColumnDef::new(NotificationPreference::Email).boolean().not_null().default(false))
+                    .col(ColumnDef::new(NotificationPreference::Digest).boolean().not_null().default(false))
+                    .col(
+                        ColumnDef::new(NotificationPreference::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_notification_preference_user")
+                            .from(NotificationPreference::Table, NotificationPreference::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_notification_preference_unique")
+                    .table(NotificationPreference::Table)
+                    .col(NotificationPreference::UserId)
+                    .col(NotificationPreference::EventType)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(NotificationPreference::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Notification::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Notification {
+    Table,
+    Id,
+    UserId,
+    EventType,
+    Title,
+    Body,
+    Link,
+    Data,
+    InApp,
+    DigestPending,
+    ReadAt,
+    EmailedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum NotificationPreference {
+    Table,
+    Id,
+    UserId,
+    EventType,
+    InApp,
+    Email,
+    Digest,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
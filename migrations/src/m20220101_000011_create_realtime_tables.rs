@@ -0,0 +1,94 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Per-topic sequence counters shared by every backend instance
+        manager
+            .create_table(
+                Table::create()
+                    .table(RealtimeTopicSequence::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(RealtimeTopicSequence::Topic)
+                            .string_len(255)
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(RealtimeTopicSequence::Seq).big_integer().not_null())
+                    .col(
+                        ColumnDef::new(RealtimeTopicSequence::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Events too large for a NOTIFY payload; listeners load them by id
+        manager
+            .create_table(
+                Table::create()
+                    .table(RealtimeEvent::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(RealtimeEvent::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(RealtimeEvent::Payload).json_binary().not_null())
+                    .col(
+                        ColumnDef::new(RealtimeEvent::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_realtime_event_created")
+                    .table(RealtimeEvent::Table)
+                    .col(RealtimeEvent::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RealtimeEvent::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(RealtimeTopicSequence::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum RealtimeTopicSequence {
+    Table,
+    Topic,
+    Seq,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum RealtimeEvent {
+    Table,
+    Id,
+    Payload,
+    CreatedAt,
+}
@@ -0,0 +1,137 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Scheduling fields used by the job lifecycle scheduler
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Job::Table)
+                    .add_column(ColumnDef::new(Job::PublishAt).timestamp_with_time_zone())
+                    .add_column(ColumnDef::new(Job::ExpiryWarningSentAt).timestamp_with_time_zone())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_job_status_publish_at")
+                    .table(Job::Table)
+                    .col(Job::Status)
+                    .col(Job::PublishAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Audit trail of job status changes, manual or scheduled
+        manager
+            .create_table(
+                Table::create()
+                    .table(JobStatusChange::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(JobStatusChange::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(JobStatusChange::JobId).uuid().not_null())
+                    .col(ColumnDef::new(JobStatusChange::FromStatus).string_len(20).not_null())
+                    .col(ColumnDef::new(JobStatusChange::ToStatus).string_len(20).not_null())
+                    .col(ColumnDef::new(JobStatusChange::Reason).string_len(40).not_null())
+                    // Null when the scheduler made the change
+                    .col(ColumnDef::new(JobStatusChange::ChangedBy).uuid())
+                    .col(
+                        ColumnDef::new(JobStatusChange::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_job_status_change_job")
+                            .from(JobStatusChange::Table, JobStatusChange::JobId)
+                            .to(Job::Table, Job::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_job_status_change_user")
+                            .from(JobStatusChange::Table, JobStatusChange::ChangedBy)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_job_status_change_job")
+                    .table(JobStatusChange::Table)
+                    .col(JobStatusChange::JobId)
+                    .col(JobStatusChange::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(JobStatusChange::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_index(Index::drop().name("idx_job_status_publish_at").table(Job::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Job::Table)
+                    .drop_column(Job::PublishAt)
+                    .drop_column(Job::ExpiryWarningSentAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Job {
+    Table,
+    Id,
+    Status,
+    PublishAt,
+    ExpiryWarningSentAt,
+}
+
+#[derive(DeriveIden)]
+enum JobStatusChange {
+    Table,
+    Id,
+    JobId,
+    FromStatus,
+    ToStatus,
+    Reason,
+    ChangedBy,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,113 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Postgres-backed queue for work that runs outside the request path
+        manager
+            .create_table(
+                Table::create()
+                    .table(BackgroundTask::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(BackgroundTask::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(BackgroundTask::Kind).string_len(60).not_null())
+                    .col(ColumnDef::new(BackgroundTask::Payload).json_binary().not_null())
+                    .col(
+                        ColumnDef::new(BackgroundTask::Status)
+                            .string_len(20)
+                            .not_null()
+                            .default("Pending")
+                            .check(Expr::col(BackgroundTask::Status).is_in([
+                                "Pending",
+                                "Running",
+                                "Completed",
+                                "DeadLettered",
+                            ])),
+                    )
+                    .col(ColumnDef::new(BackgroundTask::Attempts).integer().not_null().default(0))
+                    .col(ColumnDef::new(BackgroundTask::MaxAttempts).integer().not_null())
+                    .col(
+                        ColumnDef::new(BackgroundTask::RunAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(BackgroundTask::LockedAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(BackgroundTask::LockedBy).string())
+                    .col(ColumnDef::new(BackgroundTask::LastError).text())
+                    .col(
+                        ColumnDef::new(BackgroundTask::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(BackgroundTask::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(BackgroundTask::CompletedAt).timestamp_with_time_zone())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Workers claim the oldest due pending task
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_background_task_status_run_at")
+                    .table(BackgroundTask::Table)
+                    .col(BackgroundTask::Status)
+                    .col(BackgroundTask::RunAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_background_task_kind")
+                    .table(BackgroundTask::Table)
+                    .col(BackgroundTask::Kind)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(BackgroundTask::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum BackgroundTask {
+    Table,
+    Id,
+    Kind,
+    Payload,
+    Status,
+    Attempts,
+    MaxAttempts,
+    RunAt,
+    LockedAt,
+    LockedBy,
+    LastError,
+    CreatedAt,
+    UpdatedAt,
+    CompletedAt,
+}
@@ -0,0 +1,143 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Availability slots from the calendar; recurring slots are stored once and expanded on read
+        manager
+            .create_table(
+                Table::create()
+                    .table(Availability::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Availability::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Availability::UserId).uuid().not_null())
+                    .col(ColumnDef::new(Availability::Date).date().not_null())
+                    .col(ColumnDef::new(Availability::StartTime).time().not_null())
+                    .col(ColumnDef::new(Availability::EndTime).time().not_null())
+                    .col(
+                        ColumnDef::new(Availability::Kind)
+                            .string_len(20)
+                            .not_null()
+                            .check(Expr::col(Availability::Kind).is_in([
+                                "Available",
+                                "Busy",
+                                "Preferred",
+                                "Unavailable",
+                            ])),
+                    )
+                    .col(ColumnDef::new(Availability::Notes).text())
+                    .col(ColumnDef::new(Availability::JobTypePreference).string())
+                    .col(ColumnDef::new(Availability::HourlyRate).decimal_len(10, 2))
+                    .col(
+                        ColumnDef::new(Availability::RecurrenceType)
+                            .string_len(20)
+                            .check(Expr::col(Availability::RecurrenceType).is_in([
+                                "Daily",
+                                "Weekly",
+                                "Monthly",
+                                "Custom",
+                            ])),
+                    )
+                    .col(
+                        ColumnDef::new(Availability::RecurrenceInterval)
+                            .integer()
+                            .not_null()
+                            .default(1)
+                            .check(Expr::col(Availability::RecurrenceInterval).gte(1)),
+                    )
+                    // Bit 0 is Monday through bit 6 for Sunday
+                    .col(ColumnDef::new(Availability::RecurrenceDays).small_integer().not_null().default(0))
+                    .col(ColumnDef::new(Availability::RecurrenceEndDate).date())
+                    .col(
+                        ColumnDef::new(Availability::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Availability::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .check(Expr::col(Availability::EndTime).gt(Expr::col(Availability::StartTime)))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_availability_user")
+                            .from(Availability::Table, Availability::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_availability_user_date")
+                    .table(Availability::Table)
+                    .col(Availability::UserId)
+                    .col(Availability::Date)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Shift-window lookups scan by first date across all users
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_availability_date")
+                    .table(Availability::Table)
+                    .col(Availability::Date)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Availability::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Availability {
+    Table,
+    Id,
+    UserId,
+    Date,
+    StartTime,
+    EndTime,
+    #[sea_orm(iden = "availability_type")]
+    Kind,
+    Notes,
+    JobTypePreference,
+    HourlyRate,
+    RecurrenceType,
+    RecurrenceInterval,
+    RecurrenceDays,
+    RecurrenceEndDate,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
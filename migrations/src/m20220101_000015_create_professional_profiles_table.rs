@@ -0,0 +1,109 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // What a professional can be matched to: eligible job types, registration and travel range
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProfessionalProfile::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ProfessionalProfile::UserId)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ProfessionalProfile::JobTypes)
+                            .json_binary()
+                            .not_null()
+                            .default(Expr::cust("'[]'::jsonb")),
+                    )
+                    .col(ColumnDef::new(ProfessionalProfile::RegistrationNumber).string_len(20))
+                    .col(
+                        ColumnDef::new(ProfessionalProfile::RegistrationStatus)
+                            .string_len(20)
+                            .not_null()
+                            .default("Unregistered")
+                            .check(Expr::col(ProfessionalProfile::RegistrationStatus).is_in([
+                                "Unregistered",
+                                "Pending",
+                                "Verified",
+                                "Suspended",
+                            ])),
+                    )
+                    .col(ColumnDef::new(ProfessionalProfile::RegistrationExpiresOn).date())
+                    .col(ColumnDef::new(ProfessionalProfile::VerifiedAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(ProfessionalProfile::VerifiedBy).uuid())
+                    .col(
+                        ColumnDef::new(ProfessionalProfile::MaxTravelKm)
+                            .integer()
+                            .not_null()
+                            .default(50)
+                            .check(Expr::col(ProfessionalProfile::MaxTravelKm).gt(0)),
+                    )
+                    .col(
+                        ColumnDef::new(ProfessionalProfile::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(ProfessionalProfile::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_professional_profile_user")
+                            .from(ProfessionalProfile::Table, ProfessionalProfile::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_professional_profile_verified_by")
+                            .from(ProfessionalProfile::Table, ProfessionalProfile::VerifiedBy)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ProfessionalProfile::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ProfessionalProfile {
+    Table,
+    UserId,
+    JobTypes,
+    RegistrationNumber,
+    RegistrationStatus,
+    RegistrationExpiresOn,
+    VerifiedAt,
+    VerifiedBy,
+    MaxTravelKm,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
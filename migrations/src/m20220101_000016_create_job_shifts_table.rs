@@ -0,0 +1,176 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Individual shifts under a job; a shift ending before it starts runs past midnight
+        manager
+            .create_table(
+                Table::create()
+                    .table(JobShift::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(JobShift::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(JobShift::JobId).uuid().not_null())
+                    .col(ColumnDef::new(JobShift::Date).date().not_null())
+                    .col(ColumnDef::new(JobShift::StartTime).time().not_null())
+                    .col(ColumnDef::new(JobShift::EndTime).time().not_null())
+                    .col(ColumnDef::new(JobShift::FilledBy).uuid())
+                    .col(ColumnDef::new(JobShift::FilledAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(JobShift::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .check(Expr::col(JobShift::EndTime).ne(Expr::col(JobShift::StartTime)))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_job_shift_job")
+                            .from(JobShift::Table, JobShift::JobId)
+                            .to(Job::Table, Job::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_job_shift_filled_by")
+                            .from(JobShift::Table, JobShift::FilledBy)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_job_shift_job_date_start")
+                    .table(JobShift::Table)
+                    .col(JobShift::JobId)
+                    .col(JobShift::Date)
+                    .col(JobShift::StartTime)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Shifts an application is for; none recorded means the whole job
+        manager
+            .create_table(
+                Table::create()
+                    .table(ApplicationShift::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ApplicationShift::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ApplicationShift::ApplicationId).uuid().not_null())
+                    .col(ColumnDef::new(ApplicationShift::ShiftId).uuid().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_application_shift_application")
+                            .from(ApplicationShift::Table, ApplicationShift::ApplicationId)
+                            .to(Application::Table, Application::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_application_shift_shift")
+                            .from(ApplicationShift::Table, ApplicationShift::ShiftId)
+                            .to(JobShift::Table, JobShift::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_application_shift_unique")
+                    .table(ApplicationShift::Table)
+                    .col(ApplicationShift::ApplicationId)
+                    .col(ApplicationShift::ShiftId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_application_shift_shift")
+                    .table(ApplicationShift::Table)
+                    .col(ApplicationShift::ShiftId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ApplicationShift::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(JobShift::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum JobShift {
+    Table,
+    Id,
+    JobId,
+    Date,
+    StartTime,
+    EndTime,
+    FilledBy,
+    FilledAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ApplicationShift {
+    Table,
+    Id,
+    ApplicationId,
+    ShiftId,
+}
+
+#[derive(DeriveIden)]
+enum Job {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Application {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
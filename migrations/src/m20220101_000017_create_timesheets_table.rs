@@ -0,0 +1,287 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Hours actually worked under an accepted application, one row per shift
+        manager
+            .create_table(
+                Table::create()
+                    .table(Timesheet::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Timesheet::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Timesheet::ApplicationId).uuid().not_null())
+                    .col(ColumnDef::new(Timesheet::ShiftId).uuid())
+                    .col(ColumnDef::new(Timesheet::Date).date().not_null())
+                    .col(ColumnDef::new(Timesheet::StartTime).time().not_null())
+                    .col(ColumnDef::new(Timesheet::EndTime).time().not_null())
+                    .col(
+                        ColumnDef::new(Timesheet::BreakMinutes)
+                            .integer()
+                            .not_null()
+                            .default(0)
+                            .check(Expr::col(Timesheet::BreakMinutes).gte(0)),
+                    )
+                    .col(
+                        ColumnDef::new(Timesheet::Status)
+                            .string_len(20)
+                            .not_null()
+                            .default("Submitted")
+                            .check(Expr::col(Timesheet::Status).is_in([
+                                "Submitted",
+                                "Approved",
+                                "Disputed",
+                            ])),
+                    )
+                    // Set on approval, after which the timesheet is locked
+                    .col(ColumnDef::new(Timesheet::PayTotal).decimal())
+                    .col(ColumnDef::new(Timesheet::PayBreakdown).json_binary())
+                    .col(ColumnDef::new(Timesheet::ReviewedBy).uuid())
+                    .col(ColumnDef::new(Timesheet::ReviewedAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(Timesheet::SubmittedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Timesheet::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .check(Expr::col(Timesheet::EndTime).ne(Expr::col(Timesheet::StartTime)))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_timesheet_application")
+                            .from(Timesheet::Table, Timesheet::ApplicationId)
+                            .to(Application::Table, Application::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_timesheet_shift")
+                            .from(Timesheet::Table, Timesheet::ShiftId)
+                            .to(JobShift::Table, JobShift::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_timesheet_reviewed_by")
+                            .from(Timesheet::Table, Timesheet::ReviewedBy)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_timesheet_application_date_start")
+                    .table(Timesheet::Table)
+                    .col(Timesheet::ApplicationId)
+                    .col(Timesheet::Date)
+                    .col(Timesheet::StartTime)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Every submission, edit and review with the times as they stood afterwards
+        manager
+            .create_table(
+                Table::create()
+                    .table(TimesheetRevision::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TimesheetRevision::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(TimesheetRevision::TimesheetId).uuid().not_null())
+                    .col(ColumnDef::new(TimesheetRevision::RevisedBy).uuid())
+                    .col(ColumnDef::new(TimesheetRevision::Action).string_len(20).not_null())
+                    .col(ColumnDef::new(TimesheetRevision::StartTime).time().not_null())
+                    .col(ColumnDef::new(TimesheetRevision::EndTime).time().not_null())
+                    .col(ColumnDef::new(TimesheetRevision::BreakMinutes).integer().not_null())
+                    .col(
+                        ColumnDef::new(TimesheetRevision::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_timesheet_revision_timesheet")
+                            .from(TimesheetRevision::Table, TimesheetRevision::TimesheetId)
+                            .to(Timesheet::Table, Timesheet::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_timesheet_revision_revised_by")
+                            .from(TimesheetRevision::Table, TimesheetRevision::RevisedBy)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_timesheet_revision_timesheet")
+                    .table(TimesheetRevision::Table)
+                    .col(TimesheetRevision::TimesheetId)
+                    .col(TimesheetRevision::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Discussion between the locum and the pharmacy, mostly about disputes
+        manager
+            .create_table(
+                Table::create()
+                    .table(TimesheetComment::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TimesheetComment::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(TimesheetComment::TimesheetId).uuid().not_null())
+                    .col(ColumnDef::new(TimesheetComment::AuthorId).uuid())
+                    .col(ColumnDef::new(TimesheetComment::Body).text().not_null())
+                    .col(
+                        ColumnDef::new(TimesheetComment::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_timesheet_comment_timesheet")
+                            .from(TimesheetComment::Table, TimesheetComment::TimesheetId)
+                            .to(Timesheet::Table, Timesheet::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_timesheet_comment_author")
+                            .from(TimesheetComment::Table, TimesheetComment::AuthorId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_timesheet_comment_timesheet")
+                    .table(TimesheetComment::Table)
+                    .col(TimesheetComment::TimesheetId)
+                    .col(TimesheetComment::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TimesheetComment::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(TimesheetRevision::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Timesheet::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Timesheet {
+    Table,
+    Id,
+    ApplicationId,
+    ShiftId,
+    Date,
+    StartTime,
+    EndTime,
+    BreakMinutes,
+    Status,
+    PayTotal,
+    PayBreakdown,
+    ReviewedBy,
+    ReviewedAt,
+    SubmittedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum TimesheetRevision {
+    Table,
+    Id,
+    TimesheetId,
+    RevisedBy,
+    Action,
+    StartTime,
+    EndTime,
+    BreakMinutes,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum TimesheetComment {
+    Table,
+    Id,
+    TimesheetId,
+    AuthorId,
+    Body,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Application {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum JobShift {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
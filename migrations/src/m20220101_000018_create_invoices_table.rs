@@ -0,0 +1,251 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Locums contracting through their own business invoice under its ABN
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ProfessionalProfile::Table)
+                    .add_column(ColumnDef::new(ProfessionalProfile::Abn).string_len(11))
+                    .add_column(
+                        ColumnDef::new(ProfessionalProfile::GstRegistered)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Tax invoices from a locum to the pharmacy, with the supplier details and
+        // line items as they stood when issued
+        manager
+            .create_table(
+                Table::create()
+                    .table(Invoice::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Invoice::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Invoice::Number).string_len(20).not_null())
+                    .col(ColumnDef::new(Invoice::Sequence).integer().not_null())
+                    .col(ColumnDef::new(Invoice::SupplierId).uuid().not_null())
+                    .col(ColumnDef::new(Invoice::CustomerId).uuid().not_null())
+                    .col(ColumnDef::new(Invoice::ApplicationId).uuid().not_null())
+                    .col(ColumnDef::new(Invoice::JobId).uuid().not_null())
+                    .col(ColumnDef::new(Invoice::SupplierName).string().not_null())
+                    .col(ColumnDef::new(Invoice::SupplierAbn).string_len(11).not_null())
+                    .col(ColumnDef::new(Invoice::GstRegistered).boolean().not_null())
+                    .col(ColumnDef::new(Invoice::CustomerName).string().not_null())
+                    .col(ColumnDef::new(Invoice::CustomerAddress).text().not_null())
+                    .col(
+                        ColumnDef::new(Invoice::Lines)
+                            .json_binary()
+                            .not_null()
+                            .default(Expr::cust("'[]'::jsonb")),
+                    )
+                    .col(ColumnDef::new(Invoice::Subtotal).decimal().not_null())
+                    .col(ColumnDef::new(Invoice::Gst).decimal().not_null())
+                    .col(ColumnDef::new(Invoice::Total).decimal().not_null())
+                    .col(ColumnDef::new(Invoice::IssuedOn).date().not_null())
+                    .col(ColumnDef::new(Invoice::DueOn).date().not_null())
+                    .col(ColumnDef::new(Invoice::PaymentTermsDays).integer().not_null())
+                    .col(
+                        ColumnDef::new(Invoice::Status)
+                            .string_len(20)
+                            .not_null()
+                            .default("Issued")
+                            .check(Expr::col(Invoice::Status).is_in(["Issued", "Paid", "Overdue"])),
+                    )
+                    .col(ColumnDef::new(Invoice::PaidAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(Invoice::Notes).text())
+                    .col(
+                        ColumnDef::new(Invoice::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Invoice::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_invoice_supplier")
+                            .from(Invoice::Table, Invoice::SupplierId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_invoice_customer")
+                            .from(Invoice::Table, Invoice::CustomerId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_invoice_application")
+                            .from(Invoice::Table, Invoice::ApplicationId)
+                            .to(Application::Table, Application::Id)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_invoice_job")
+                            .from(Invoice::Table, Invoice::JobId)
+                            .to(Job::Table, Job::Id)
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_invoice_supplier_sequence")
+                    .table(Invoice::Table)
+                    .col(Invoice::SupplierId)
+                    .col(Invoice::Sequence)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_invoice_status_due_on")
+                    .table(Invoice::Table)
+                    .col(Invoice::Status)
+                    .col(Invoice::DueOn)
+                    .to_owned(),
+            )
+            .await?;
+
+        // A timesheet is invoiced at most once
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Timesheet::Table)
+                    .add_column(ColumnDef::new(Timesheet::InvoiceId).uuid())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_timesheet_invoice")
+                            .from_tbl(Timesheet::Table)
+                            .from_col(Timesheet::InvoiceId)
+                            .to_tbl(Invoice::Table)
+                            .to_col(Invoice::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Timesheet::Table)
+                    .drop_foreign_key(Alias::new("fk_timesheet_invoice"))
+                    .drop_column(Timesheet::InvoiceId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Invoice::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ProfessionalProfile::Table)
+                    .drop_column(ProfessionalProfile::Abn)
+                    .drop_column(ProfessionalProfile::GstRegistered)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Invoice {
+    Table,
+    Id,
+    Number,
+    Sequence,
+    SupplierId,
+    CustomerId,
+    ApplicationId,
+    JobId,
+    SupplierName,
+    SupplierAbn,
+    GstRegistered,
+    CustomerName,
+    CustomerAddress,
+    Lines,
+    Subtotal,
+    Gst,
+    Total,
+    IssuedOn,
+    DueOn,
+    PaymentTermsDays,
+    Status,
+    PaidAt,
+    Notes,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ProfessionalProfile {
+    Table,
+    Abn,
+    GstRegistered,
+}
+
+#[derive(DeriveIden)]
+enum Timesheet {
+    Table,
+    InvoiceId,
+}
+
+#[derive(DeriveIden)]
+enum Application {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Job {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,139 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Postings saved for reuse, private to their owner or shared across a tenant.
+        // The posting is kept as a CreateJobRequest with the dates it was saved with.
+        manager
+            .create_table(
+                Table::create()
+                    .table(JobTemplate::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(JobTemplate::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(JobTemplate::OwnerId).uuid().not_null())
+                    .col(ColumnDef::new(JobTemplate::TenantId).uuid())
+                    .col(ColumnDef::new(JobTemplate::SourceJobId).uuid())
+                    .col(ColumnDef::new(JobTemplate::Name).string_len(100).not_null())
+                    .col(ColumnDef::new(JobTemplate::Job).json_binary().not_null())
+                    .col(
+                        ColumnDef::new(JobTemplate::UseCount)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(JobTemplate::LastUsedAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(JobTemplate::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(JobTemplate::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_job_template_owner")
+                            .from(JobTemplate::Table, JobTemplate::OwnerId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_job_template_tenant")
+                            .from(JobTemplate::Table, JobTemplate::TenantId)
+                            .to(Tenants::Table, Tenants::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_job_template_source_job")
+                            .from(JobTemplate::Table, JobTemplate::SourceJobId)
+                            .to(Job::Table, Job::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_job_template_owner_name")
+                    .table(JobTemplate::Table)
+                    .col(JobTemplate::OwnerId)
+                    .col(JobTemplate::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_job_template_tenant")
+                    .table(JobTemplate::Table)
+                    .col(JobTemplate::TenantId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(JobTemplate::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum JobTemplate {
+    Table,
+    Id,
+    OwnerId,
+    TenantId,
+    SourceJobId,
+    Name,
+    Job,
+    UseCount,
+    LastUsedAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Tenants {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Job {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,146 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Append-only audit trail. Each row carries the hash of the one before it, so
+        // editing or removing a row breaks the chain from that point on. No foreign
+        // keys: events must outlive the users, tenants and records they mention.
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuditEvent::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AuditEvent::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AuditEvent::Seq).big_integer().not_null().unique_key())
+                    .col(ColumnDef::new(AuditEvent::ActorId).uuid())
+                    .col(ColumnDef::new(AuditEvent::TenantId).uuid())
+                    .col(ColumnDef::new(AuditEvent::Action).string_len(50).not_null())
+                    .col(ColumnDef::new(AuditEvent::EntityType).string_len(50).not_null())
+                    .col(ColumnDef::new(AuditEvent::EntityId).string_len(100))
+                    .col(ColumnDef::new(AuditEvent::Ip).string_len(64))
+                    .col(ColumnDef::new(AuditEvent::RequestId).string_len(128))
+                    .col(ColumnDef::new(AuditEvent::Before).json_binary())
+                    .col(ColumnDef::new(AuditEvent::After).json_binary())
+                    .col(ColumnDef::new(AuditEvent::PrevHash).string_len(64).not_null())
+                    .col(ColumnDef::new(AuditEvent::Hash).string_len(64).not_null())
+                    .col(
+                        ColumnDef::new(AuditEvent::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        for (name, column) in [
+            ("idx_audit_event_actor", AuditEvent::ActorId),
+            ("idx_audit_event_tenant", AuditEvent::TenantId),
+            ("idx_audit_event_action", AuditEvent::Action),
+            ("idx_audit_event_created_at", AuditEvent::CreatedAt),
+        ] {
+            manager
+                .create_index(
+                    Index::create()
+                        .if_not_exists()
+                        .name(name)
+                        .table(AuditEvent::Table)
+                        .col(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_audit_event_entity")
+                    .table(AuditEvent::Table)
+                    .col(AuditEvent::EntityType)
+                    .col(AuditEvent::EntityId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Refuse changes to recorded events, even from the application's own role
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE OR REPLACE FUNCTION audit_event_append_only()
+                RETURNS TRIGGER AS $$
+                BEGIN
+                    RAISE EXCEPTION 'audit_event is append-only';
+                END;
+                $$ LANGUAGE plpgsql
+                "#,
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TRIGGER audit_event_no_update_or_delete
+                BEFORE UPDATE OR DELETE ON audit_event
+                FOR EACH ROW EXECUTE FUNCTION audit_event_append_only()
+                "#,
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE TRIGGER audit_event_no_truncate
+                BEFORE TRUNCATE ON audit_event
+                FOR EACH STATEMENT EXECUTE FUNCTION audit_event_append_only()
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AuditEvent::Table).to_owned())
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP FUNCTION IF EXISTS audit_event_append_only()")
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum AuditEvent {
+    Table,
+    Id,
+    Seq,
+    ActorId,
+    TenantId,
+    Action,
+    EntityType,
+    EntityId,
+    Ip,
+    RequestId,
+    Before,
+    After,
+    PrevHash,
+    Hash,
+    CreatedAt,
+}
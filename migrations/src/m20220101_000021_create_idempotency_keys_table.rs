@@ -0,0 +1,96 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // First response to each Idempotency-Key, per user and route, so retried
+        // POSTs replay it instead of running twice. A row without a response status
+        // is a request still in flight. Rows expire, so they need no foreign keys.
+        manager
+            .create_table(
+                Table::create()
+                    .table(IdempotencyKey::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(IdempotencyKey::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(IdempotencyKey::UserId).uuid().not_null())
+                    .col(ColumnDef::new(IdempotencyKey::Method).string_len(10).not_null())
+                    .col(ColumnDef::new(IdempotencyKey::Route).string_len(255).not_null())
+                    .col(ColumnDef::new(IdempotencyKey::Key).string_len(255).not_null())
+                    .col(ColumnDef::new(IdempotencyKey::RequestHash).string_len(64).not_null())
+                    .col(ColumnDef::new(IdempotencyKey::ResponseStatus).small_integer())
+                    .col(ColumnDef::new(IdempotencyKey::ResponseHeaders).json_binary())
+                    .col(ColumnDef::new(IdempotencyKey::ResponseBody).binary())
+                    .col(
+                        ColumnDef::new(IdempotencyKey::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(IdempotencyKey::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_idempotency_key_scope")
+                    .table(IdempotencyKey::Table)
+                    .col(IdempotencyKey::UserId)
+                    .col(IdempotencyKey::Method)
+                    .col(IdempotencyKey::Route)
+                    .col(IdempotencyKey::Key)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_idempotency_key_expires_at")
+                    .table(IdempotencyKey::Table)
+                    .col(IdempotencyKey::ExpiresAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(IdempotencyKey::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum IdempotencyKey {
+    Table,
+    Id,
+    UserId,
+    Method,
+    Route,
+    Key,
+    RequestHash,
+    ResponseStatus,
+    ResponseHeaders,
+    ResponseBody,
+    CreatedAt,
+    ExpiresAt,
+}
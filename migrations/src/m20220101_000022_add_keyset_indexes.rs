@@ -0,0 +1,130 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Indexes matching the newest-first (created_at, id) order the cursor-paged lists
+/// use, so each page is an index range scan from the previous page's last row
+fn keyset_indexes() -> [(&'static str, IndexCreateStatement); 7] {
+    [
+        (
+            "idx_job_keyset",
+            Index::create().table(Job::Table).col(Job::CreatedAt).col(Job::Id).to_owned(),
+        ),
+        (
+            "idx_application_job_keyset",
+            Index::create()
+                .table(Application::Table)
+                .col(Application::JobId)
+                .col(Application::AppliedAt)
+                .col(Application::Id)
+                .to_owned(),
+        ),
+        (
+            "idx_application_user_keyset",
+            Index::create()
+                .table(Application::Table)
+                .col(Application::UserId)
+                .col(Application::AppliedAt)
+                .col(Application::Id)
+                .to_owned(),
+        ),
+        (
+            "idx_user_keyset",
+            Index::create().table(User::Table).col(User::CreatedAt).col(User::Id).to_owned(),
+        ),
+        (
+            "idx_notification_user_keyset",
+            Index::create()
+                .table(Notification::Table)
+                .col(Notification::UserId)
+                .col(Notification::CreatedAt)
+                .col(Notification::Id)
+                .to_owned(),
+        ),
+        (
+            "idx_audit_event_keyset",
+            Index::create()
+                .table(AuditEvent::Table)
+                .col(AuditEvent::CreatedAt)
+                .col(AuditEvent::Id)
+                .to_owned(),
+        ),
+        (
+            "idx_background_task_status_keyset",
+            Index::create()
+                .table(BackgroundTask::Table)
+                .col(BackgroundTask::Status)
+                .col(BackgroundTask::CreatedAt)
+                .col(BackgroundTask::Id)
+                .to_owned(),
+        ),
+    ]
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (name, mut index) in keyset_indexes() {
+            manager
+                .create_index(index.if_not_exists().name(name).to_owned())
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (name, _) in keyset_indexes() {
+            manager
+                .drop_index(Index::drop().if_exists().name(name).to_owned())
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Job {
+    Table,
+    Id,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Application {
+    Table,
+    Id,
+    JobId,
+    UserId,
+    AppliedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Notification {
+    Table,
+    Id,
+    UserId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum AuditEvent {
+    Table,
+    Id,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum BackgroundTask {
+    Table,
+    Id,
+    Status,
+    CreatedAt,
+}
@@ -0,0 +1,213 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // URLs a tenant's own systems listen on, with the event types each wants and
+        // the secret its deliveries are signed with
+        manager
+            .create_table(
+                Table::create()
+                    .table(WebhookEndpoint::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WebhookEndpoint::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(WebhookEndpoint::TenantId).uuid().not_null())
+                    .col(ColumnDef::new(WebhookEndpoint::Url).string_len(2048).not_null())
+                    .col(ColumnDef::new(WebhookEndpoint::Secret).string_len(100).not_null())
+                    .col(ColumnDef::new(WebhookEndpoint::EventTypes).json_binary().not_null())
+                    .col(ColumnDef::new(WebhookEndpoint::Description).string_len(255))
+                    .col(
+                        ColumnDef::new(WebhookEndpoint::IsActive)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(ColumnDef::new(WebhookEndpoint::CreatedBy).uuid().not_null())
+                    .col(
+                        ColumnDef::new(WebhookEndpoint::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookEndpoint::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_webhook_endpoint_tenant")
+                            .from(WebhookEndpoint::Table, WebhookEndpoint::TenantId)
+                            .to(Tenants::Table, Tenants::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_webhook_endpoint_created_by")
+                            .from(WebhookEndpoint::Table, WebhookEndpoint::CreatedBy)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_webhook_endpoint_tenant")
+                    .table(WebhookEndpoint::Table)
+                    .col(WebhookEndpoint::TenantId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // One row per event sent to an endpoint, updated on every attempt. Replays are
+        // new rows pointing back at the delivery they repeat.
+        manager
+            .create_table(
+                Table::create()
+                    .table(WebhookDelivery::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WebhookDelivery::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(WebhookDelivery::EndpointId).uuid().not_null())
+                    .col(ColumnDef::new(WebhookDelivery::EventId).uuid().not_null())
+                    .col(ColumnDef::new(WebhookDelivery::EventType).string_len(50).not_null())
+                    .col(ColumnDef::new(WebhookDelivery::Payload).json_binary().not_null())
+                    .col(
+                        ColumnDef::new(WebhookDelivery::Status)
+                            .string_len(20)
+                            .not_null()
+                            .default("Pending"),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookDelivery::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(WebhookDelivery::ResponseStatus).small_integer())
+                    .col(ColumnDef::new(WebhookDelivery::ResponseBody).text())
+                    .col(ColumnDef::new(WebhookDelivery::DurationMs).integer())
+                    .col(ColumnDef::new(WebhookDelivery::LastError).text())
+                    .col(ColumnDef::new(WebhookDelivery::ReplayOf).uuid())
+                    .col(
+                        ColumnDef::new(WebhookDelivery::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(WebhookDelivery::LastAttemptAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(WebhookDelivery::NextAttemptAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(WebhookDelivery::DeliveredAt).timestamp_with_time_zone())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_webhook_delivery_endpoint")
+                            .from(WebhookDelivery::Table, WebhookDelivery::EndpointId)
+                            .to(WebhookEndpoint::Table, WebhookEndpoint::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_webhook_delivery_replay_of")
+                            .from(WebhookDelivery::Table, WebhookDelivery::ReplayOf)
+                            .to(WebhookDelivery::Table, WebhookDelivery::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // The delivery log is read newest first, a cursor page at a time
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_webhook_delivery_endpoint_keyset")
+                    .table(WebhookDelivery::Table)
+                    .col(WebhookDelivery::EndpointId)
+                    .col(WebhookDelivery::CreatedAt)
+                    .col(WebhookDelivery::Id)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(WebhookDelivery::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(WebhookEndpoint::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum WebhookEndpoint {
+    Table,
+    Id,
+    TenantId,
+    Url,
+    Secret,
+    EventTypes,
+    Description,
+    IsActive,
+    CreatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum WebhookDelivery {
+    Table,
+    Id,
+    EndpointId,
+    EventId,
+    EventType,
+    Payload,
+    Status,
+    Attempts,
+    ResponseStatus,
+    ResponseBody,
+    DurationMs,
+    LastError,
+    ReplayOf,
+    CreatedAt,
+    LastAttemptAt,
+    NextAttemptAt,
+    DeliveredAt,
+}
+
+#[derive(DeriveIden)]
+enum Tenants {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,38 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Idle topic counters are pruned by age
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_realtime_topic_sequence_updated")
+                    .table(RealtimeTopicSequence::Table)
+                    .col(RealtimeTopicSequence::UpdatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_realtime_topic_sequence_updated")
+                    .table(RealtimeTopicSequence::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum RealtimeTopicSequence {
+    Table,
+    UpdatedAt,
+}
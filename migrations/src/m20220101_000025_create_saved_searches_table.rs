@@ -0,0 +1,80 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Searches a user keeps so newly published jobs matching them reach their
+        // `search:{id}` realtime topic
+        manager
+            .create_table(
+                Table::create()
+                    .table(SavedSearch::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SavedSearch::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SavedSearch::UserId).uuid().not_null())
+                    .col(ColumnDef::new(SavedSearch::Name).string_len(100).not_null())
+                    .col(ColumnDef::new(SavedSearch::Query).string_len(255).not_null())
+                    .col(ColumnDef::new(SavedSearch::Filters).json_binary().not_null())
+                    .col(
+                        ColumnDef::new(SavedSearch::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_saved_search_user")
+                            .from(SavedSearch::Table, SavedSearch::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_saved_search_user")
+                    .table(SavedSearch::Table)
+                    .col(SavedSearch::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SavedSearch::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SavedSearch {
+    Table,
+    Id,
+    UserId,
+    Name,
+    Query,
+    Filters,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
@@ -0,0 +1,79 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Audit events written in the same transaction as the change they describe,
+        // waiting to be appended to the hash chain. Appending takes a global lock,
+        // so it happens afterwards in a short transaction of its own rather than
+        // for the length of every audited write.
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuditOutbox::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AuditOutbox::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AuditOutbox::ActorId).uuid())
+                    .col(ColumnDef::new(AuditOutbox::TenantId).uuid())
+                    .col(ColumnDef::new(AuditOutbox::Action).string_len(50).not_null())
+                    .col(ColumnDef::new(AuditOutbox::EntityType).string_len(50).not_null())
+                    .col(ColumnDef::new(AuditOutbox::EntityId).string_len(100))
+                    .col(ColumnDef::new(AuditOutbox::Ip).string_len(64))
+                    .col(ColumnDef::new(AuditOutbox::RequestId).string_len(128))
+                    .col(ColumnDef::new(AuditOutbox::Before).json_binary())
+                    .col(ColumnDef::new(AuditOutbox::After).json_binary())
+                    .col(
+                        ColumnDef::new(AuditOutbox::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("idx_audit_outbox_created_at")
+                    .table(AuditOutbox::Table)
+                    .col(AuditOutbox::CreatedAt)
+                    .col(AuditOutbox::Id)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AuditOutbox::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AuditOutbox {
+    Table,
+    Id,
+    ActorId,
+    TenantId,
+    Action,
+    EntityType,
+    EntityId,
+    Ip,
+    RequestId,
+    Before,
+    After,
+    CreatedAt,
+}
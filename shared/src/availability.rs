@@ -0,0 +1,411 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+
+/// Longest date range recurring slots are expanded over in one request
+pub const MAX_EXPANSION_DAYS: i64 = 366;
+
+/// Slot times are exchanged as "HH:MM"
+pub const SLOT_TIME_FORMAT: &str = "%H:%M";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub enum AvailabilityType {
+    Available,
+    Busy,
+    Preferred,
+    Unavailable,
+}
+
+impl AvailabilityType {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            AvailabilityType::Available => "Available",
+            AvailabilityType::Busy => "Busy",
+            AvailabilityType::Preferred => "Preferred",
+            AvailabilityType::Unavailable => "Unavailable",
+        }
+    }
+
+    /// Whether the slot offers time for shifts rather than blocking it out
+    pub fn is_bookable(&self) -> bool {
+        matches!(self, AvailabilityType::Available | AvailabilityType::Preferred)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct AvailabilitySlot {
+    pub id: Uuid,
+    pub date: NaiveDate,
+    pub start_time: String, // "09:00"
+    pub end_time: String,   // "17:00"
+    pub availability_type: AvailabilityType,
+    pub notes: Option<String>,
+    pub job_type_preference: Option<String>,
+    pub hourly_rate: Option<f64>,
+    pub is_recurring: bool,
+    pub recurring_pattern: Option<RecurringPattern>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct RecurringPattern {
+    pub pattern_type: RecurringType,
+    pub end_date: Option<NaiveDate>,
+    pub days_of_week: Vec<Weekday>,
+    pub interval: u32, // Every N days/weeks/months
+}
+
+/// How a recurring slot repeats from its first date
+///
+/// - `Daily`: every `interval` days
+/// - `Weekly`: every `interval` weeks on `days_of_week`, or the first date's weekday if none are given
+/// - `Monthly`: the first date's day of the month every `interval` months; months without that day are skipped
+/// - `Custom`: every `interval` days, but only on `days_of_week` (e.g. weekdays only)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub enum RecurringType {
+    Daily,
+    Weekly,
+    Monthly,
+    Custom,
+}
+
+/// Body for creating or replacing a slot; the slot recurs when `recurring_pattern` is set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct AvailabilityRequest {
+    pub date: NaiveDate,
+    pub start_time: String,
+    pub end_time: String,
+    pub availability_type: AvailabilityType,
+    pub notes: Option<String>,
+    pub job_type_preference: Option<String>,
+    pub hourly_rate: Option<f64>,
+    pub recurring_pattern: Option<RecurringPattern>,
+}
+
+impl AvailabilityRequest {
+    pub fn into_slot(self, id: Uuid) -> AvailabilitySlot {
+        AvailabilitySlot {
+            id,
+            date: self.date,
+            start_time: self.start_time,
+            end_time: self.end_time,
+            availability_type: self.availability_type,
+            notes: self.notes,
+            job_type_preference: self.job_type_preference,
+            hourly_rate: self.hourly_rate,
+            is_recurring: self.recurring_pattern.is_some(),
+            recurring_pattern: self.recurring_pattern,
+        }
+    }
+}
+
+impl From<&AvailabilitySlot> for AvailabilityRequest {
+    fn from(slot: &AvailabilitySlot) -> Self {
+        Self {
+            date: slot.date,
+            start_time: slot.start_time.clone(),
+            end_time: slot.end_time.clone(),
+            availability_type: slot.availability_type,
+            notes: slot.notes.clone(),
+            job_type_preference: slot.job_type_preference.clone(),
+            hourly_rate: slot.hourly_rate,
+            recurring_pattern: slot.recurring_pattern.clone().filter(|_| slot.is_recurring),
+        }
+    }
+}
+
+/// Parse an "HH:MM" slot time
+pub fn parse_slot_time(field: &str, value: &str) -> Result<NaiveTime, AppError> {
+    NaiveTime::parse_from_str(value, SLOT_TIME_FORMAT)
+        .map_err(|_| AppError::validation(field, "Time must be in HH:MM format"))
+}
+
+impl RecurringPattern {
+    /// Dates in `from..=to` on which a slot first held on `anchor` recurs
+    pub fn occurrences(&self, anchor: NaiveDate, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        let interval = self.interval.max(1);
+        let first = from.max(anchor);
+        let last = self.end_date.map_or(to, |end| end.min(to));
+        if first > last {
+            return Vec::new();
+        }
+
+        match self.pattern_type {
+            RecurringType::Daily => days_between(first, last)
+                .filter(|date| (*date - anchor).num_days() % interval as i64 == 0)
+                .collect(),
+            RecurringType::Weekly => {
+                let weekdays = if self.days_of_week.is_empty() {
+                    vec![anchor.weekday()]
+                } else {
+                    self.days_of_week.clone()
+                };
+                let anchor_week = week_start(anchor);
+                days_between(first, last)
+                    .filter(|date| weekdays.contains(&date.weekday()))
+                    .filter(|date| {
+                        ((week_start(*date) - anchor_week).num_days() / 7) % interval as i64 == 0
+                    })
+                    .collect()
+            }
+            RecurringType::Monthly => {
+                let anchor_month = month_index(anchor);
+                let mut dates = Vec::new();
+                let mut month = anchor_month;
+                // Step to the first recurring month that could fall in range
+                while month + interval as i32 <= month_index(first) {
+                    month += interval as i32;
+                }
+                while month <= month_index(last) {
+                    if let Some(date) = NaiveDate::from_ymd_opt(month.div_euclid(12), month.rem_euclid(12) as u32 + 1, anchor.day()) {
+                        if date >= first && date <= last {
+                            dates.push(date);
+                        }
+                    }
+                    month += interval as i32;
+                }
+                dates
+            }
+            RecurringType::Custom => days_between(first, last)
+                .filter(|date| (*date - anchor).num_days() % interval as i64 == 0)
+                .filter(|date| self.days_of_week.is_empty() || self.days_of_week.contains(&date.weekday()))
+                .collect(),
+        }
+    }
+}
+
+impl AvailabilitySlot {
+    /// Check the slot is well formed before it is stored
+    pub fn validate(&self) -> Result<(), AppError> {
+        let (start, end) = self.time_range()?;
+        if end <= start {
+            return Err(AppError::validation("end_time", "End time must be after start time"));
+        }
+        if matches!(self.hourly_rate, Some(rate) if rate < 0.0) {
+            return Err(AppError::validation("hourly_rate", "Hourly rate cannot be negative"));
+        }
+        if let Some(pattern) = &self.recurring_pattern {
+            if pattern.interval == 0 {
+                return Err(AppError::validation("recurring_pattern.interval", "Interval must be at least 1"));
+            }
+            if matches!(pattern.end_date, Some(end_date) if end_date < self.date) {
+                return Err(AppError::validation(
+                    "recurring_pattern.end_date",
+                    "Recurrence cannot end before the first date",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn time_range(&self) -> Result<(NaiveTime, NaiveTime), AppError> {
+        Ok((
+            parse_slot_time("start_time", &self.start_time)?,
+            parse_slot_time("end_time", &self.end_time)?,
+        ))
+    }
+
+    /// Dates in `from..=to` the slot applies to
+    pub fn occurrences(&self, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        match &self.recurring_pattern {
+            Some(pattern) if self.is_recurring => pattern.occurrences(self.date, from, to),
+            _ if self.date >= from && self.date <= to => vec![self.date],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Copies of the slot for each date it applies to in `from..=to`, keeping the slot's id
+    pub fn expand(&self, from: NaiveDate, to: NaiveDate) -> Vec<AvailabilitySlot> {
+        self.occurrences(from, to)
+            .into_iter()
+            .map(|date| AvailabilitySlot { date, ..self.clone() })
+            .collect()
+    }
+
+    fn occurs_on(&self, date: NaiveDate) -> bool {
+        !self.occurrences(date, date).is_empty()
+    }
+
+    fn overlaps(&self, date: NaiveDate, start: NaiveTime, end: NaiveTime) -> bool {
+        match self.time_range() {
+            Ok((slot_start, slot_end)) => self.occurs_on(date) && slot_start < end && start < slot_end,
+            Err(_) => false,
+        }
+    }
+
+    fn covers(&self, date: NaiveDate, start: NaiveTime, end: NaiveTime) -> bool {
+        match self.time_range() {
+            Ok((slot_start, slot_end)) => self.occurs_on(date) && slot_start <= start && end <= slot_end,
+            Err(_) => false,
+        }
+    }
+}
+
+/// Whether one person's slots make them available for a shift on `date` from `start` to `end`:
+/// a bookable slot must cover the whole shift and no busy or unavailable slot may overlap it
+pub fn is_available_for(slots: &[AvailabilitySlot], date: NaiveDate, start: NaiveTime, end: NaiveTime) -> bool {
+    !covering_slots(slots, date, start, end).is_empty()
+}
+
+/// The bookable slots that cover a shift, or none if something blocks it
+pub fn covering_slots(slots: &[AvailabilitySlot], date: NaiveDate, start: NaiveTime, end: NaiveTime) -> Vec<&AvailabilitySlot> {
+    let blocked = slots
+        .iter()
+        .filter(|slot| !slot.availability_type.is_bookable())
+        .any(|slot| slot.overlaps(date, start, end));
+    if blocked {
+        return Vec::new();
+    }
+
+    slots
+        .iter()
+        .filter(|slot| slot.availability_type.is_bookable())
+        .filter(|slot| slot.covers(date, start, end))
+        .collect()
+}
+
+fn days_between(first: NaiveDate, last: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+    first.iter_days().take_while(move |date| *date <= last)
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+fn month_index(date: NaiveDate) -> i32 {
+    date.year() * 12 + date.month0() as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn pattern(pattern_type: RecurringType, interval: u32, days_of_week: Vec<Weekday>) -> RecurringPattern {
+        RecurringPattern {
+            pattern_type,
+            end_date: None,
+            days_of_week,
+            interval,
+        }
+    }
+
+    fn slot(day: NaiveDate, start: &str, end: &str, availability_type: AvailabilityType) -> AvailabilitySlot {
+        AvailabilitySlot {
+            id: Uuid::new_v4(),
+            date: day,
+            start_time: start.to_string(),
+            end_time: end.to_string(),
+            availability_type,
+            notes: None,
+            job_type_preference: None,
+            hourly_rate: None,
+            is_recurring: false,
+            recurring_pattern: None,
+        }
+    }
+
+    #[test]
+    fn test_daily_recurrence_with_interval() {
+        let dates = pattern(RecurringType::Daily, 2, vec![]).occurrences(date(2025, 3, 3), date(2025, 3, 4), date(2025, 3, 10));
+        assert_eq!(dates, vec![date(2025, 3, 5), date(2025, 3, 7), date(2025, 3, 9)]);
+    }
+
+    #[test]
+    fn test_weekly_recurrence_on_selected_days() {
+        // Fortnightly Monday and Wednesday, starting Monday 3 March 2025
+        let dates = pattern(RecurringType::Weekly, 2, vec![Weekday::Mon, Weekday::Wed])
+            .occurrences(date(2025, 3, 3), date(2025, 3, 1), date(2025, 3, 31));
+        assert_eq!(
+            dates,
+            vec![date(2025, 3, 3), date(2025, 3, 5), date(2025, 3, 17), date(2025, 3, 19), date(2025, 3, 31)]
+        );
+    }
+
+    #[test]
+    fn test_weekly_recurrence_defaults_to_anchor_weekday() {
+        let dates = pattern(RecurringType::Weekly, 1, vec![]).occurrences(date(2025, 3, 6), date(2025, 3, 1), date(2025, 3, 20));
+        assert_eq!(dates, vec![date(2025, 3, 6), date(2025, 3, 13), date(2025, 3, 20)]);
+    }
+
+    #[test]
+    fn test_monthly_recurrence_skips_short_months() {
+        let dates = pattern(RecurringType::Monthly, 1, vec![]).occurrences(date(2025, 1, 31), date(2025, 1, 1), date(2025, 5, 31));
+        assert_eq!(dates, vec![date(2025, 1, 31), date(2025, 3, 31), date(2025, 5, 31)]);
+    }
+
+    #[test]
+    fn test_monthly_recurrence_with_interval_across_years() {
+        let dates = pattern(RecurringType::Monthly, 3, vec![]).occurrences(date(2024, 11, 15), date(2025, 1, 1), date(2025, 12, 31));
+        assert_eq!(dates, vec![date(2025, 2, 15), date(2025, 5, 15), date(2025, 8, 15), date(2025, 11, 15)]);
+    }
+
+    #[test]
+    fn test_custom_recurrence_filters_weekdays() {
+        let weekdays = vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri];
+        let dates = pattern(RecurringType::Custom, 1, weekdays).occurrences(date(2025, 3, 7), date(2025, 3, 7), date(2025, 3, 11));
+        assert_eq!(dates, vec![date(2025, 3, 7), date(2025, 3, 10), date(2025, 3, 11)]);
+    }
+
+    #[test]
+    fn test_recurrence_stops_at_end_date() {
+        let mut daily = pattern(RecurringType::Daily, 1, vec![]);
+        daily.end_date = Some(date(2025, 3, 4));
+        let dates = daily.occurrences(date(2025, 3, 1), date(2025, 3, 1), date(2025, 3, 31));
+        assert_eq!(dates.last(), Some(&date(2025, 3, 4)));
+        assert_eq!(dates.len(), 4);
+    }
+
+    #[test]
+    fn test_one_off_slot_occurrences() {
+        let single = slot(date(2025, 3, 5), "09:00", "17:00", AvailabilityType::Available);
+        assert_eq!(single.occurrences(date(2025, 3, 1), date(2025, 3, 31)), vec![date(2025, 3, 5)]);
+        assert!(single.occurrences(date(2025, 4, 1), date(2025, 4, 30)).is_empty());
+    }
+
+    #[test]
+    fn test_slot_validation() {
+        let mut invalid = slot(date(2025, 3, 5), "17:00", "09:00", AvailabilityType::Available);
+        assert!(invalid.validate().is_err());
+
+        invalid.end_time = "9am".to_string();
+        assert!(invalid.validate().is_err());
+
+        let mut recurring = slot(date(2025, 3, 5), "09:00", "17:00", AvailabilityType::Available);
+        recurring.is_recurring = true;
+        recurring.recurring_pattern = Some(RecurringPattern {
+            end_date: Some(date(2025, 3, 1)),
+            ..pattern(RecurringType::Daily, 1, vec![])
+        });
+        assert!(recurring.validate().is_err());
+    }
+
+    #[test]
+    fn test_availability_for_shift_window() {
+        let day = date(2025, 3, 10);
+        let nine = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+        let five = NaiveTime::from_hms_opt(17, 0, 0).unwrap();
+
+        let mut weekly = slot(date(2025, 3, 3), "08:00", "18:00", AvailabilityType::Available);
+        weekly.is_recurring = true;
+        weekly.recurring_pattern = Some(pattern(RecurringType::Weekly, 1, vec![]));
+        assert!(is_available_for(&[weekly.clone()], day, nine, five));
+
+        // Partial cover is not enough
+        let morning = slot(day, "08:00", "12:00", AvailabilityType::Preferred);
+        assert!(!is_available_for(&[morning], day, nine, five));
+
+        // A busy block during the shift wins over availability
+        let busy = slot(day, "12:00", "13:00", AvailabilityType::Busy);
+        assert!(!is_available_for(&[weekly, busy], day, nine, five));
+    }
+}
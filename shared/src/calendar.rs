@@ -0,0 +1,381 @@
+//! Public holidays for each Australian state and territory.
+//!
+//! Holidays are generated from rules rather than stored lists: fixed dates, dates
+//! relative to Easter Sunday, and the nth (or last) weekday of a month. A fixed-date
+//! holiday that falls on a weekend may also be observed on the next free weekday,
+//! which is how Christmas on a Saturday gives a Monday off and Boxing Day on the
+//! Sunday a Tuesday.
+//!
+//! The rules follow current legislation and are applied to every year, so dates
+//! before a holiday was introduced or moved are not historically accurate. Holidays
+//! proclaimed each year (Victoria's AFL Grand Final Friday), regional show days and
+//! part-day holidays (Christmas Eve and New Year's Eve evenings in SA and the NT)
+//! are not included.
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+use crate::types::AustralianState;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicHoliday {
+    pub date: NaiveDate,
+    pub name: String,
+    /// Stands in for a holiday that fell on a weekend
+    pub observed: bool,
+}
+
+/// What happens when a fixed-date holiday falls on a weekend
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Weekend {
+    /// Only the date itself is a holiday
+    Keep,
+    /// The date stays a holiday and the next free weekday is one too
+    AddWeekday,
+    /// Only the next free weekday is a holiday
+    MoveToWeekday,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Rule {
+    Fixed { month: u32, day: u32, weekend: Weekend },
+    /// Days after (or before, if negative) Easter Sunday
+    Easter(i64),
+    /// The nth given weekday of the month, counting from 1
+    NthWeekday { month: u32, weekday: Weekday, n: u32 },
+    LastWeekday { month: u32, weekday: Weekday },
+    /// The first given weekday on or after a date
+    OnOrAfter { month: u32, day: u32, weekday: Weekday },
+}
+
+const NEW_YEARS_DAY: (&str, Rule) = ("New Year's Day", Rule::Fixed { month: 1, day: 1, weekend: Weekend::AddWeekday });
+const AUSTRALIA_DAY: (&str, Rule) = ("Australia Day", Rule::Fixed { month: 1, day: 26, weekend: Weekend::MoveToWeekday });
+const GOOD_FRIDAY: (&str, Rule) = ("Good Friday", Rule::Easter(-2));
+const EASTER_SATURDAY: (&str, Rule) = ("Easter Saturday", Rule::Easter(-1));
+const EASTER_SUNDAY: (&str, Rule) = ("Easter Sunday", Rule::Easter(0));
+const EASTER_MONDAY: (&str, Rule) = ("Easter Monday", Rule::Easter(1));
+const ANZAC_DAY: (&str, Rule) = ("Anzac Day", Rule::Fixed { month: 4, day: 25, weekend: Weekend::Keep });
+const KINGS_BIRTHDAY: (&str, Rule) = ("King's Birthday", Rule::NthWeekday { month: 6, weekday: Weekday::Mon, n: 2 });
+const CHRISTMAS_DAY: (&str, Rule) = ("Christmas Day", Rule::Fixed { month: 12, day: 25, weekend: Weekend::AddWeekday });
+const BOXING_DAY: (&str, Rule) = ("Boxing Day", Rule::Fixed { month: 12, day: 26, weekend: Weekend::AddWeekday });
+
+fn rules(state: &AustralianState) -> Vec<(&'static str, Rule)> {
+    use AustralianState::*;
+
+    let mut rules = vec![NEW_YEARS_DAY, AUSTRALIA_DAY, GOOD_FRIDAY, EASTER_MONDAY];
+    rules.extend(match state {
+        NewSouthWales => vec![
+            EASTER_SATURDAY,
+            EASTER_SUNDAY,
+            ANZAC_DAY,
+            KINGS_BIRTHDAY,
+            ("Labour Day", Rule::NthWeekday { month: 10, weekday: Weekday::Mon, n: 1 }),
+            CHRISTMAS_DAY,
+            BOXING_DAY,
+        ],
+        Victoria => vec![
+            ("Labour Day", Rule::NthWeekday { month: 3, weekday: Weekday::Mon, n: 2 }),
+            EASTER_SATURDAY,
+            EASTER_SUNDAY,
+            ANZAC_DAY,
+            KINGS_BIRTHDAY,
+            ("Melbourne Cup", Rule::NthWeekday { month: 11, weekday: Weekday::Tue, n: 1 }),
+            CHRISTMAS_DAY,
+            BOXING_DAY,
+        ],
+        Queensland => vec![
+            EASTER_SATURDAY,
+            EASTER_SUNDAY,
+            ANZAC_DAY,
+            ("Labour Day", Rule::NthWeekday { month: 5, weekday: Weekday::Mon, n: 1 }),
+            ("King's Birthday", Rule::NthWeekday { month: 10, weekday: Weekday::Mon, n: 1 }),
+            CHRISTMAS_DAY,
+            BOXING_DAY,
+        ],
+        WesternAustralia => vec![
+            ("Labour Day", Rule::NthWeekday { month: 3, weekday: Weekday::Mon, n: 1 }),
+            EASTER_SUNDAY,
+            ("Anzac Day", Rule::Fixed { month: 4, day: 25, weekend: Weekend::AddWeekday }),
+            ("Western Australia Day", Rule::NthWeekday { month: 6, weekday: Weekday::Mon, n: 1 }),
+            // Proclaimed each year, usually for the last Monday in September
+            ("King's Birthday", Rule::LastWeekday { month: 9, weekday: Weekday::Mon }),
+            CHRISTMAS_DAY,
+            BOXING_DAY,
+        ],
+        SouthAustralia => vec![
+            ("Adelaide Cup Day", Rule::NthWeekday { month: 3, weekday: Weekday::Mon, n: 2 }),
+            EASTER_SATURDAY,
+            EASTER_SUNDAY,
+            ANZAC_DAY,
+            KINGS_BIRTHDAY,
+            ("Labour Day", Rule::NthWeekday { month: 10, weekday: Weekday::Mon, n: 1 }),
+            CHRISTMAS_DAY,
+            ("Proclamation Day", Rule::Fixed { month: 12, day: 26, weekend: Weekend::AddWeekday }),
+        ],
+        Tasmania => vec![
+            ("Eight Hours Day", Rule::NthWeekday { month: 3, weekday: Weekday::Mon, n: 2 }),
+            ANZAC_DAY,
+            KINGS_BIRTHDAY,
+            CHRISTMAS_DAY,
+            BOXING_DAY,
+        ],
+        AustralianCapitalTerritory => vec![
+            ("Canberra Day", Rule::NthWeekday { month: 3, weekday: Weekday::Mon, n: 2 }),
+            EASTER_SATURDAY,
+            EASTER_SUNDAY,
+            ("Anzac Day", Rule::Fixed { month: 4, day: 25, weekend: Weekend::AddWeekday }),
+            ("Reconciliation Day", Rule::OnOrAfter { month: 5, day: 27, weekday: Weekday::Mon }),
+            KINGS_BIRTHDAY,
+            ("Labour Day", Rule::NthWeekday { month: 10, weekday: Weekday::Mon, n: 1 }),
+            CHRISTMAS_DAY,
+            BOXING_DAY,
+        ],
+        NorthernTerritory => vec![
+            EASTER_SATURDAY,
+            EASTER_SUNDAY,
+            ANZAC_DAY,
+            ("May Day", Rule::NthWeekday { month: 5, weekday: Weekday::Mon, n: 1 }),
+            KINGS_BIRTHDAY,
+            ("Picnic Day", Rule::NthWeekday { month: 8, weekday: Weekday::Mon, n: 1 }),
+            CHRISTMAS_DAY,
+            BOXING_DAY,
+        ],
+    });
+    rules
+}
+
+/// Easter Sunday in the Gregorian calendar (the anonymous Gregorian algorithm)
+pub fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year.rem_euclid(19);
+    let b = year.div_euclid(100);
+    let c = year.rem_euclid(100);
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+/// The nth (from 1) given weekday of a month, if the month has that many
+pub fn nth_weekday(year: i32, month: u32, weekday: Weekday, n: u32) -> Option<NaiveDate> {
+    NaiveDate::from_weekday_of_month_opt(year, month, weekday, u8::try_from(n).ok()?)
+}
+
+/// The last given weekday of a month
+pub fn last_weekday(year: i32, month: u32, weekday: Weekday) -> Option<NaiveDate> {
+    let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    let last_day = NaiveDate::from_ymd_opt(next_year, next_month, 1)?.pred_opt()?;
+    let back = (7 + last_day.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+    Some(last_day - Duration::days(back as i64))
+}
+
+fn is_weekend(date: NaiveDate) -> bool {
+    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+/// Every public holiday in the state for a year, in date order
+pub fn holidays(state: &AustralianState, year: i32) -> Vec<PublicHoliday> {
+    let mut holidays: Vec<PublicHoliday> = Vec::new();
+    let mut substitutes = Vec::new();
+
+    for (name, rule) in rules(state) {
+        let date = match rule {
+            Rule::Fixed { month, day, weekend } => {
+                let Some(date) = NaiveDate::from_ymd_opt(year, month, day) else { continue };
+                if is_weekend(date) && weekend != Weekend::Keep {
+                    substitutes.push((name, date));
+                    if weekend == Weekend::MoveToWeekday {
+                        continue;
+                    }
+                }
+                Some(date)
+            }
+            Rule::Easter(offset) => easter_sunday(year).map(|easter| easter + Duration::days(offset)),
+            Rule::NthWeekday { month, weekday, n } => nth_weekday(year, month, weekday, n),
+            Rule::LastWeekday { month, weekday } => last_weekday(year, month, weekday),
+            Rule::OnOrAfter { month, day, weekday } => NaiveDate::from_ymd_opt(year, month, day).map(|date| {
+                let ahead = (7 + weekday.num_days_from_monday() - date.weekday().num_days_from_monday()) % 7;
+                date + Duration::days(ahead as i64)
+            }),
+        };
+        if let Some(date) = date {
+            holidays.push(PublicHoliday { date, name: name.to_string(), observed: false });
+        }
+    }
+
+    // Substitutes take the next weekday that isn't already a holiday, in date order,
+    // so back-to-back weekend holidays land on consecutive weekdays
+    substitutes.sort_by_key(|(_, date)| *date);
+    for (name, date) in substitutes {
+        let mut observed = date + Duration::days(1);
+        while is_weekend(observed) || holidays.iter().any(|holiday| holiday.date == observed) {
+            observed += Duration::days(1);
+        }
+        holidays.push(PublicHoliday { date: observed, name: format!("{} (observed)", name), observed: true });
+    }
+
+    holidays.sort_by_key(|holiday| holiday.date);
+    holidays
+}
+
+/// Public holidays in the state from `from` to `to` inclusive
+pub fn holidays_between(state: &AustralianState, from: NaiveDate, to: NaiveDate) -> Vec<PublicHoliday> {
+    (from.year()..=to.year())
+        .flat_map(|year| holidays(state, year))
+        .filter(|holiday| holiday.date >= from && holiday.date <= to)
+        .collect()
+}
+
+/// Dates of the state's public holidays from `from` to `to` inclusive
+pub fn holiday_dates(state: &AustralianState, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+    holidays_between(state, from, to).into_iter().map(|holiday| holiday.date).collect()
+}
+
+/// The public holiday on a date, if there is one
+pub fn holiday_on(state: &AustralianState, date: NaiveDate) -> Option<PublicHoliday> {
+    holidays(state, date.year()).into_iter().find(|holiday| holiday.date == date)
+}
+
+pub fn is_public_holiday(state: &AustralianState, date: NaiveDate) -> bool {
+    holiday_on(state, date).is_some()
+}
+
+/// The first public holiday on or after a date
+pub fn next_holiday(state: &AustralianState, from: NaiveDate) -> Option<PublicHoliday> {
+    (from.year()..=from.year() + 1)
+        .flat_map(|year| holidays(state, year))
+        .find(|holiday| holiday.date >= from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use AustralianState::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn names_on(state: &AustralianState, day: NaiveDate) -> Option<String> {
+        holiday_on(state, day).map(|holiday| holiday.name)
+    }
+
+    #[test]
+    fn test_easter_sunday() {
+        assert_eq!(easter_sunday(2019), Some(date(2019, 4, 21)));
+        assert_eq!(easter_sunday(2024), Some(date(2024, 3, 31)));
+        assert_eq!(easter_sunday(2025), Some(date(2025, 4, 20)));
+        assert_eq!(easter_sunday(2026), Some(date(2026, 4, 5)));
+        assert_eq!(easter_sunday(2038), Some(date(2038, 4, 25)));
+    }
+
+    #[test]
+    fn test_weekday_rules() {
+        assert_eq!(nth_weekday(2026, 6, Weekday::Mon, 2), Some(date(2026, 6, 8)));
+        assert_eq!(nth_weekday(2025, 11, Weekday::Tue, 1), Some(date(2025, 11, 4)));
+        assert_eq!(nth_weekday(2026, 2, Weekday::Mon, 5), None);
+        assert_eq!(last_weekday(2025, 9, Weekday::Mon), Some(date(2025, 9, 29)));
+        assert_eq!(last_weekday(2026, 12, Weekday::Thu), Some(date(2026, 12, 31)));
+    }
+
+    #[test]
+    fn test_nsw_2026() {
+        let dates: Vec<NaiveDate> = holidays(&NewSouthWales, 2026).iter().map(|holiday| holiday.date).collect();
+        assert_eq!(
+            dates,
+            vec![
+                date(2026, 1, 1),
+                date(2026, 1, 26),
+                date(2026, 4, 3),
+                date(2026, 4, 4),
+                date(2026, 4, 5),
+                date(2026, 4, 6),
+                date(2026, 4, 25),
+                date(2026, 6, 8),
+                date(2026, 10, 5),
+                date(2026, 12, 25),
+                date(2026, 12, 26),
+                date(2026, 12, 28),
+            ]
+        );
+        assert_eq!(names_on(&NewSouthWales, date(2026, 12, 28)).as_deref(), Some("Boxing Day (observed)"));
+    }
+
+    #[test]
+    fn test_christmas_substitutes() {
+        // Saturday and Sunday: Monday and Tuesday off
+        assert!(holidays(&Victoria, 2021).iter().any(|h| h.date == date(2021, 12, 27) && h.observed));
+        assert!(is_public_holiday(&Victoria, date(2021, 12, 28)));
+        // Sunday and Monday: Christmas moves past Boxing Day to the Tuesday
+        assert_eq!(names_on(&Queensland, date(2022, 12, 26)).as_deref(), Some("Boxing Day"));
+        assert_eq!(names_on(&Queensland, date(2022, 12, 27)).as_deref(), Some("Christmas Day (observed)"));
+        assert_eq!(names_on(&SouthAustralia, date(2021, 12, 28)).as_deref(), Some("Proclamation Day (observed)"));
+    }
+
+    #[test]
+    fn test_weekend_rules_per_holiday() {
+        // Australia Day on a Sunday moves to the Monday
+        assert!(!is_public_holiday(&Tasmania, date(2025, 1, 26)));
+        assert!(is_public_holiday(&Tasmania, date(2025, 1, 27)));
+        // Anzac Day on a Saturday: a Monday in WA and the ACT only
+        assert!(is_public_holiday(&WesternAustralia, date(2026, 4, 27)));
+        assert!(is_public_holiday(&AustralianCapitalTerritory, date(2026, 4, 27)));
+        assert!(!is_public_holiday(&NewSouthWales, date(2026, 4, 27)));
+        assert!(is_public_holiday(&NewSouthWales, date(2026, 4, 25)));
+    }
+
+    #[test]
+    fn test_state_specific_holidays() {
+        assert_eq!(names_on(&Victoria, date(2025, 11, 4)).as_deref(), Some("Melbourne Cup"));
+        assert_eq!(names_on(&Queensland, date(2025, 10, 6)).as_deref(), Some("King's Birthday"));
+        assert_eq!(names_on(&WesternAustralia, date(2025, 6, 2)).as_deref(), Some("Western Australia Day"));
+        assert_eq!(names_on(&AustralianCapitalTerritory, date(2024, 5, 27)).as_deref(), Some("Reconciliation Day"));
+        assert_eq!(names_on(&AustralianCapitalTerritory, date(2025, 6, 2)).as_deref(), Some("Reconciliation Day"));
+        assert_eq!(names_on(&NorthernTerritory, date(2025, 8, 4)).as_deref(), Some("Picnic Day"));
+        assert!(!is_public_holiday(&Tasmania, date(2025, 4, 19)));
+        assert!(!is_public_holiday(&WesternAustralia, date(2025, 4, 19)));
+        assert!(is_public_holiday(&SouthAustralia, date(2025, 4, 19)));
+    }
+
+    #[test]
+    fn test_range_queries() {
+        let summer = holidays_between(&NewSouthWales, date(2025, 12, 20), date(2026, 1, 31));
+        let dates: Vec<NaiveDate> = summer.iter().map(|holiday| holiday.date).collect();
+        assert_eq!(dates, vec![date(2025, 12, 25), date(2025, 12, 26), date(2026, 1, 1), date(2026, 1, 26)]);
+        assert_eq!(holiday_dates(&NewSouthWales, date(2026, 2, 1), date(2026, 3, 31)), Vec::<NaiveDate>::new());
+
+        let next = next_holiday(&Victoria, date(2025, 12, 29)).unwrap();
+        assert_eq!((next.date, next.name.as_str()), (date(2026, 1, 1), "New Year's Day"));
+    }
+
+    #[test]
+    fn test_every_state_has_national_holidays() {
+        for state in [
+            NewSouthWales,
+            Victoria,
+            Queensland,
+            WesternAustralia,
+            SouthAustralia,
+            Tasmania,
+            AustralianCapitalTerritory,
+            NorthernTerritory,
+        ] {
+            for year in 2020..=2035 {
+                let list = holidays(&state, year);
+                assert!(list.windows(2).all(|pair| pair[0].date < pair[1].date), "{} {}", state, year);
+                let easter = easter_sunday(year).unwrap();
+                assert!(list.iter().any(|h| h.date == easter - Duration::days(2)));
+                assert!(list.iter().any(|h| h.date == easter + Duration::days(1)));
+                assert!(list.iter().any(|h| h.date.month() == 12 && h.date.day() == 25));
+                assert!(list.iter().all(|h| !h.observed || !is_weekend(h.date)));
+            }
+        }
+    }
+}
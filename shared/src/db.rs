@@ -63,7 +63,10 @@ impl TransactionHelper for DatabaseConnection {
                         return Err(AppError::Database(e.to_string()));
                     }
                     // Exponential backoff
-                    let delay = std::time::Duration::from_millis(100 * 2u64.pow(attempts - 1));
+                    let delay = crate::utils::exponential_backoff(
+                        std::time::Duration::from_millis(100),
+                        attempts,
+                    );
                     tokio::time::sleep(delay).await;
                 }
                 Err(TransactionError::Transaction(e)) => {
@@ -109,6 +109,82 @@ pub enum AppError {
     },
 }
 
+/// Media type of error responses (RFC 7807)
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// Base of the `type` URI that identifies each kind of problem
+pub const PROBLEM_TYPE_BASE: &str = "https://locoplatform.com.au/problems";
+
+/// Why one field of a request was rejected
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// An RFC 7807 problem details body, the shape of every API error response.
+///
+/// Besides the standard members it carries a stable machine-readable `code`, text
+/// that can be shown to the user as is, per-field validation errors and the
+/// correlation ID of the request for support.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    pub code: String,
+    pub user_message: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+    /// Seconds to wait before retrying, also sent as the `Retry-After` header
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<String>,
+}
+
+impl ProblemDetails {
+    pub fn new(status: u16, code: &str, title: impl Into<String>, user_message: impl Into<String>) -> Self {
+        Self {
+            problem_type: format!("{}/{}", PROBLEM_TYPE_BASE, code.replace('_', "-")),
+            title: title.into(),
+            status,
+            detail: None,
+            instance: None,
+            code: code.to_string(),
+            user_message: user_message.into(),
+            errors: Vec::new(),
+            correlation_id: None,
+            retry_after: None,
+            suggestions: Vec::new(),
+        }
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    pub fn with_errors(mut self, errors: Vec<FieldError>) -> Self {
+        self.errors = errors;
+        self
+    }
+
+    /// Whether the server, rather than the request, was at fault
+    pub fn is_server_error(&self) -> bool {
+        self.status >= 500
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ErrorSeverity {
     Info,
@@ -172,6 +248,94 @@ impl AppError {
         )
     }
     
+    /// Stable machine-readable identifier for the kind of error
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Database(_) | AppError::DatabaseWithContext { .. } => "database_error",
+            AppError::Validation { .. } => "validation_failed",
+            AppError::Authentication { .. } => "authentication_failed",
+            AppError::Authorisation { .. } => "forbidden",
+            AppError::NotFound | AppError::NotFoundDetailed { .. } => "not_found",
+            AppError::Internal { .. } => "internal_error",
+            AppError::ExternalService { .. } => "external_service_error",
+            AppError::RateLimit { .. } => "rate_limited",
+            AppError::InvalidInput { .. } => "invalid_input",
+            AppError::Network { timeout: true, .. } => "timeout",
+            AppError::Network { .. } => "network_error",
+            AppError::BusinessRule { .. } => "business_rule_violation",
+            AppError::FileOperation { .. } => "file_operation_failed",
+        }
+    }
+
+    /// HTTP status the error is reported with
+    pub fn status_code(&self) -> u16 {
+        match self {
+            AppError::Validation { .. } | AppError::InvalidInput { .. } => 400,
+            AppError::Authentication { .. } => 401,
+            AppError::Authorisation { .. } => 403,
+            AppError::NotFound | AppError::NotFoundDetailed { .. } => 404,
+            AppError::BusinessRule { .. } => 422,
+            AppError::RateLimit { .. } => 429,
+            AppError::ExternalService { .. } => 502,
+            AppError::Network { timeout: true, .. } => 504,
+            AppError::Network { .. } => 503,
+            AppError::Database(_)
+            | AppError::DatabaseWithContext { .. }
+            | AppError::Internal { .. }
+            | AppError::FileOperation { .. } => 500,
+        }
+    }
+
+    /// Seconds the client should wait before trying again, when known
+    pub fn retry_after(&self) -> Option<u64> {
+        match self {
+            AppError::RateLimit { retry_after_seconds, .. } => Some(*retry_after_seconds),
+            AppError::ExternalService { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// The fields at fault, for validation errors
+    pub fn field_errors(&self) -> Vec<FieldError> {
+        match self {
+            AppError::Validation { field, message, .. }
+            | AppError::InvalidInput { field: Some(field), message, .. } => vec![FieldError {
+                field: field.clone(),
+                message: message.clone(),
+            }],
+            _ => Vec::new(),
+        }
+    }
+
+    /// The error as a problem details body. Server errors carry no detail so
+    /// internals don't leak; they are logged against the correlation ID instead.
+    pub fn to_problem(&self) -> ProblemDetails {
+        let status = self.status_code();
+        let title = match status {
+            400 => "Invalid request",
+            401 => "Authentication required",
+            403 => "Forbidden",
+            404 => "Not found",
+            422 => "Request cannot be completed",
+            429 => "Too many requests",
+            502..=504 => "Service unavailable",
+            _ => "Internal server error",
+        };
+
+        let mut problem = ProblemDetails::new(status, self.code(), title, self.user_message())
+            .with_errors(self.field_errors());
+        if status < 500 {
+            problem.detail = Some(self.to_string());
+        }
+        problem.retry_after = self.retry_after();
+        match self {
+            AppError::NotFoundDetailed { suggestions, .. } => problem.suggestions = suggestions.clone(),
+            AppError::Internal { error_id, .. } => problem.correlation_id = Some(error_id.clone()),
+            _ => {}
+        }
+        problem
+    }
+
     /// Get user-friendly error message (Australian English)
     pub fn user_message(&self) -> String {
         match self {
@@ -184,6 +348,11 @@ impl AppError {
             AppError::RateLimit { retry_after_seconds, .. } => {
                 format!("You're doing that too quickly. Please wait {} seconds.", retry_after_seconds)
             },
+            AppError::InvalidInput { message, .. } => format!("Please check your input: {}", message),
+            AppError::BusinessRule { message, .. } => message.clone(),
+            AppError::ExternalService { .. } | AppError::Network { .. } => {
+                "A service we rely on isn't responding. Please try again shortly.".to_string()
+            },
             _ => "Something went wrong. Our team has been notified.".to_string(),
         }
     }
@@ -204,4 +373,47 @@ impl From<uuid::Error> for AppError {
     fn from(err: uuid::Error) -> Self {
         AppError::validation("id", format!("Invalid UUID format: {}", err))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation_problem_keeps_field_details() {
+        let problem = AppError::validation("postcode", "Postcode must be 4 digits").to_problem();
+        assert_eq!(problem.status, 400);
+        assert_eq!(problem.code, "validation_failed");
+        assert_eq!(problem.problem_type, "https://locoplatform.com.au/problems/validation-failed");
+        assert_eq!(
+            problem.errors,
+            vec![FieldError { field: "postcode".to_string(), message: "Postcode must be 4 digits".to_string() }]
+        );
+        assert_eq!(problem.user_message, "Please check your postcode: Postcode must be 4 digits");
+    }
+
+    #[test]
+    fn test_rate_limit_and_server_errors() {
+        let limited = AppError::RateLimit { resource: "search".to_string(), retry_after_seconds: 30, limit: 60 }.to_problem();
+        assert_eq!(limited.status, 429);
+        assert_eq!(limited.retry_after, Some(30));
+
+        let internal = AppError::internal("connection pool exhausted", "db");
+        let problem = internal.to_problem();
+        assert_eq!(problem.status, 500);
+        assert_eq!(problem.detail, None);
+        assert!(problem.correlation_id.is_some());
+        assert!(!problem.user_message.contains("pool"));
+    }
+
+    #[test]
+    fn test_problem_round_trip() {
+        let problem = AppError::not_found("job", "42").to_problem();
+        let json = serde_json::to_value(&problem).unwrap();
+        assert_eq!(json["type"], "https://locoplatform.com.au/problems/not-found");
+        assert!(json.get("errors").is_none());
+
+        let decoded: ProblemDetails = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, problem);
+    }
+}
@@ -0,0 +1,222 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+
+/// GST charged by registered suppliers
+pub const GST_RATE: Decimal = Decimal::from_parts(10, 0, 0, false, 2);
+
+/// Days to pay when the locum doesn't set their own terms
+pub const DEFAULT_PAYMENT_TERMS_DAYS: u32 = 14;
+
+/// Longest payment terms an invoice can offer
+pub const MAX_PAYMENT_TERMS_DAYS: u32 = 90;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub enum InvoiceLineKind {
+    /// An approved timesheet, at its award pay
+    Shift,
+    /// Travel, accommodation and other costs the pharmacy agreed to cover
+    Allowance,
+}
+
+/// One line of an invoice. Money and quantities are written as decimal strings
+/// so no client rounds them through a float; numbers are still read, as lines
+/// stored before were written that way.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct InvoiceLine {
+    pub kind: InvoiceLineKind,
+    pub description: String,
+    #[serde(serialize_with = "rust_decimal::serde::str::serialize")]
+    #[cfg_attr(feature = "openapi", schemars(with = "String"))]
+    pub quantity: Decimal,
+    #[serde(serialize_with = "rust_decimal::serde::str::serialize")]
+    #[cfg_attr(feature = "openapi", schemars(with = "String"))]
+    pub unit_price: Decimal,
+    #[serde(serialize_with = "rust_decimal::serde::str::serialize")]
+    #[cfg_attr(feature = "openapi", schemars(with = "String"))]
+    pub amount: Decimal,
+    #[serde(default)]
+    pub timesheet_id: Option<Uuid>,
+}
+
+/// An allowance claimed on an invoice, e.g. 120 km of travel at $0.88
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct Allowance {
+    pub description: String,
+    #[serde(default = "one", serialize_with = "rust_decimal::serde::str::serialize")]
+    #[cfg_attr(feature = "openapi", schemars(with = "String"))]
+    pub quantity: Decimal,
+    #[serde(serialize_with = "rust_decimal::serde::str::serialize")]
+    #[cfg_attr(feature = "openapi", schemars(with = "String"))]
+    pub unit_price: Decimal,
+}
+
+fn one() -> Decimal {
+    Decimal::ONE
+}
+
+impl Allowance {
+    pub fn to_line(&self) -> Result<InvoiceLine, AppError> {
+        let description = self.description.trim();
+        if description.is_empty() {
+            return Err(AppError::validation("allowances", "Each allowance needs a description"));
+        }
+        if self.quantity <= Decimal::ZERO {
+            return Err(AppError::validation("allowances", "Allowance quantities must be positive"));
+        }
+        if self.unit_price < Decimal::ZERO {
+            return Err(AppError::validation("allowances", "Allowance amounts cannot be negative"));
+        }
+
+        Ok(InvoiceLine {
+            kind: InvoiceLineKind::Allowance,
+            description: description.to_string(),
+            quantity: self.quantity,
+            unit_price: round_cents(self.unit_price),
+            amount: round_cents(self.quantity * self.unit_price),
+            timesheet_id: None,
+        })
+    }
+}
+
+/// What a locum asks to bill for an accepted application
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct CreateInvoiceRequest {
+    /// Approved timesheets to bill; every one not yet invoiced when empty
+    #[serde(default)]
+    pub timesheet_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub allowances: Vec<Allowance>,
+    pub payment_terms_days: Option<u32>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct InvoiceTotals {
+    #[serde(serialize_with = "rust_decimal::serde::str::serialize")]
+    pub subtotal: Decimal,
+    #[serde(serialize_with = "rust_decimal::serde::str::serialize")]
+    pub gst: Decimal,
+    #[serde(serialize_with = "rust_decimal::serde::str::serialize")]
+    pub total: Decimal,
+}
+
+impl InvoiceTotals {
+    /// Totals for the lines, with GST on top when the supplier is registered for it
+    pub fn from_lines(lines: &[InvoiceLine], gst_registered: bool) -> Self {
+        let subtotal = round_cents(lines.iter().map(|line| line.amount).sum());
+        let gst = if gst_registered { round_cents(subtotal * GST_RATE) } else { Decimal::ZERO };
+        Self {
+            subtotal,
+            gst,
+            total: round_cents(subtotal + gst),
+        }
+    }
+}
+
+/// Invoice numbers run per supplier: INV-00001, INV-00002, ...
+pub fn invoice_number(sequence: i32) -> String {
+    format!("INV-{:05}", sequence)
+}
+
+/// An ABN's digits grouped the way the register shows them ("51 824 753 556")
+pub fn format_abn(abn: &str) -> String {
+    let digits: String = abn.chars().filter(|c| c.is_ascii_digit()).collect();
+    if digits.len() != 11 {
+        return abn.to_string();
+    }
+    format!("{} {} {} {}", &digits[..2], &digits[2..5], &digits[5..8], &digits[8..])
+}
+
+/// To the nearest cent, halves away from zero
+fn round_cents(amount: Decimal) -> Decimal {
+    amount.round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dollars(amount: &str) -> Decimal {
+        amount.parse().unwrap()
+    }
+
+    fn shift_line(amount: &str) -> InvoiceLine {
+        InvoiceLine {
+            kind: InvoiceLineKind::Shift,
+            description: "Shift".to_string(),
+            quantity: Decimal::ONE,
+            unit_price: dollars(amount),
+            amount: dollars(amount),
+            timesheet_id: None,
+        }
+    }
+
+    #[test]
+    fn test_totals_with_and_without_gst() {
+        let travel = Allowance { description: " Travel ".to_string(), quantity: dollars("120"), unit_price: dollars("0.88") }
+            .to_line()
+            .unwrap();
+        assert_eq!(travel.description, "Travel");
+        assert_eq!(travel.amount, dollars("105.6"));
+
+        let lines = vec![shift_line("412.35"), shift_line("380"), travel];
+        assert_eq!(
+            InvoiceTotals::from_lines(&lines, true),
+            InvoiceTotals { subtotal: dollars("897.95"), gst: dollars("89.80"), total: dollars("987.75") }
+        );
+        assert_eq!(
+            InvoiceTotals::from_lines(&lines, false),
+            InvoiceTotals { subtotal: dollars("897.95"), gst: Decimal::ZERO, total: dollars("897.95") }
+        );
+    }
+
+    #[test]
+    fn test_gst_rounds_half_cents_up() {
+        // 11.5 cents of GST, which binary floating point sees as 11.4999...
+        let totals = InvoiceTotals::from_lines(&[shift_line("1.15")], true);
+        assert_eq!(totals.gst, dollars("0.12"));
+        assert_eq!(totals.total, dollars("1.27"));
+    }
+
+    #[test]
+    fn test_allowance_validation() {
+        let allowance = |description: &str, quantity: &str, unit_price: &str| Allowance {
+            description: description.to_string(),
+            quantity: dollars(quantity),
+            unit_price: dollars(unit_price),
+        };
+        assert!(allowance("", "1", "50").to_line().is_err());
+        assert!(allowance("Parking", "0", "50").to_line().is_err());
+        assert!(allowance("Parking", "1", "-5").to_line().is_err());
+        assert!(serde_json::from_str::<Allowance>(r#"{"description":"Parking","unit_price":"NaN"}"#).is_err());
+
+        let parking: Allowance = serde_json::from_str(r#"{"description":"Parking","unit_price":18.5}"#).unwrap();
+        let line = parking.to_line().unwrap();
+        assert_eq!(line.amount, dollars("18.5"));
+        let written = serde_json::to_value(&line).unwrap();
+        assert_eq!(written["amount"], serde_json::json!("18.5"));
+        assert_eq!(serde_json::from_value::<InvoiceLine>(written).unwrap(), line);
+
+        // Lines stored before amounts were written as strings
+        let stored: InvoiceLine = serde_json::from_str(
+            r#"{"kind":"Allowance","description":"Parking","quantity":1,"unit_price":18.5,"amount":18.5}"#,
+        )
+        .unwrap();
+        assert_eq!(stored.amount, dollars("18.5"));
+    }
+
+    #[test]
+    fn test_numbering_and_abn_format() {
+        assert_eq!(invoice_number(7), "INV-00007");
+        assert_eq!(format_abn("51824753556"), "51 824 753 556");
+        assert_eq!(format_abn("51 824 753 556"), "51 824 753 556");
+        assert_eq!(format_abn("123"), "123");
+    }
+}
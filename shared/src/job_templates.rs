@@ -0,0 +1,181 @@
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::types::CreateJobRequest;
+
+/// Longest a template name can be
+pub const MAX_TEMPLATE_NAME_LEN: usize = 100;
+
+/// How far a cloned job moves when no new start date is given
+pub const DEFAULT_CLONE_OFFSET_DAYS: i64 = 7;
+
+/// A posting saved for reuse, e.g. "Saturday locum - Bondi"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct CreateJobTemplateRequest {
+    pub name: String,
+    /// Share the template with everyone in this tenant rather than keep it private
+    #[serde(default)]
+    pub tenant_id: Option<Uuid>,
+    pub job: CreateJobRequest,
+}
+
+/// Save an existing job as a template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct TemplateFromJobRequest {
+    pub name: String,
+    #[serde(default)]
+    pub tenant_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct UpdateJobTemplateRequest {
+    pub name: Option<String>,
+    pub job: Option<CreateJobRequest>,
+}
+
+/// When a posting made from a template, or a cloned job, should start.
+///
+/// `start_date` moves the first day there and everything else with it; `days` moves
+/// everything by that many days instead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct RepostDates {
+    #[serde(default)]
+    pub start_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub days: Option<i64>,
+}
+
+impl RepostDates {
+    /// Days to move a posting whose first day is `first_day`, or `default_days` when
+    /// neither a start date nor an offset was given
+    pub fn offset(&self, first_day: NaiveDate, default_days: i64) -> Result<i64, AppError> {
+        match (self.start_date, self.days) {
+            (Some(_), Some(_)) => Err(AppError::validation(
+                "start_date",
+                "Give either a start date or a number of days, not both",
+            )),
+            (Some(start_date), None) => Ok((start_date - first_day).num_days()),
+            (None, Some(days)) => Ok(days),
+            (None, None) => Ok(default_days),
+        }
+    }
+}
+
+/// The template name as stored, trimmed
+pub fn template_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::validation("name", "Template name is required"));
+    }
+    if name.chars().count() > MAX_TEMPLATE_NAME_LEN {
+        return Err(AppError::validation(
+            "name",
+            format!("Template name can be at most {} characters", MAX_TEMPLATE_NAME_LEN),
+        ));
+    }
+    Ok(name.to_string())
+}
+
+/// First day of the posting
+pub fn first_day(request: &CreateJobRequest) -> NaiveDate {
+    request.start_date.date_naive()
+}
+
+/// Days from `first_day` to the next date after `today` on the same weekday, so a
+/// weekly posting lands on its usual day
+pub fn days_to_next_weekday(first_day: NaiveDate, today: NaiveDate) -> i64 {
+    let ahead = (first_day.weekday().num_days_from_monday() as i64
+        - today.weekday().num_days_from_monday() as i64)
+        .rem_euclid(7);
+    let next = today + Duration::days(if ahead == 0 { 7 } else { ahead });
+    (next - first_day).num_days()
+}
+
+/// The same posting moved `days` later, shifts and all
+pub fn shift_request(request: &CreateJobRequest, days: i64) -> CreateJobRequest {
+    let offset = Duration::days(days);
+    CreateJobRequest {
+        start_date: request.start_date + offset,
+        end_date: request.end_date + offset,
+        shifts: request.shifts.as_ref().map(|schedule| schedule.shifted(days)),
+        ..request.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shifts::ShiftSchedule;
+    use crate::types::JobType;
+    use chrono::{TimeZone, Utc, Weekday};
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn saturday_locum() -> CreateJobRequest {
+        CreateJobRequest {
+            title: "Saturday locum pharmacist".to_string(),
+            description: "Weekend dispensary cover for a busy community pharmacy".to_string(),
+            pharmacy_name: "Bondi Junction Pharmacy".to_string(),
+            hourly_rate: 68.0,
+            address: "500 Oxford Street".to_string(),
+            suburb: "Bondi Junction".to_string(),
+            postcode: "2022".to_string(),
+            state: "NSW".to_string(),
+            latitude: None,
+            longitude: None,
+            start_date: Utc.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap(),
+            end_date: Utc.with_ymd_and_hms(2025, 3, 15, 13, 0, 0).unwrap(),
+            start_time: "09:00".to_string(),
+            end_time: "13:00".to_string(),
+            job_type: JobType::Pharmacist,
+            is_urgent: false,
+            shifts: Some(ShiftSchedule::weekly(date(2025, 3, 1), date(2025, 3, 15), vec![Weekday::Sat], "09:00", "13:00")),
+        }
+    }
+
+    #[test]
+    fn test_shift_request_moves_dates_and_shifts() {
+        let template = saturday_locum();
+        let days = RepostDates { start_date: Some(date(2025, 4, 5)), days: None }
+            .offset(first_day(&template), DEFAULT_CLONE_OFFSET_DAYS)
+            .unwrap();
+        assert_eq!(days, 35);
+
+        let posting = shift_request(&template, days);
+        assert_eq!(posting.start_date, Utc.with_ymd_and_hms(2025, 4, 5, 9, 0, 0).unwrap());
+        assert_eq!(posting.end_date, Utc.with_ymd_and_hms(2025, 4, 19, 13, 0, 0).unwrap());
+        assert_eq!(posting.title, template.title);
+
+        let shifts = posting.shifts.unwrap().generate().unwrap();
+        assert_eq!(shifts.len(), 3);
+        assert_eq!(shifts[0].date, date(2025, 4, 5));
+        assert_eq!(shifts[2].date, date(2025, 4, 19));
+    }
+
+    #[test]
+    fn test_repost_offsets() {
+        let first = date(2025, 3, 1); // Saturday
+        assert_eq!(RepostDates::default().offset(first, DEFAULT_CLONE_OFFSET_DAYS).unwrap(), 7);
+        assert_eq!(RepostDates { start_date: None, days: Some(14) }.offset(first, 7).unwrap(), 14);
+        assert!(RepostDates { start_date: Some(first), days: Some(14) }.offset(first, 7).is_err());
+
+        // From a Wednesday the next Saturday is three days away; from a Saturday, a week
+        assert_eq!(first + Duration::days(days_to_next_weekday(first, date(2025, 5, 7))), date(2025, 5, 10));
+        assert_eq!(days_to_next_weekday(first, date(2025, 5, 10)), 77);
+    }
+
+    #[test]
+    fn test_template_name() {
+        assert_eq!(template_name("  Saturday locum ").unwrap(), "Saturday locum");
+        assert!(template_name("   ").is_err());
+        assert!(template_name(&"x".repeat(MAX_TEMPLATE_NAME_LEN + 1)).is_err());
+    }
+}
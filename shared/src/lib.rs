@@ -4,6 +4,14 @@ pub mod utils;
 pub mod supabase;
 pub mod locations;
 pub mod validation;
+pub mod availability;
+pub mod shifts;
+pub mod pay;
+pub mod timesheets;
+pub mod invoices;
+pub mod calendar;
+pub mod job_templates;
+pub mod webhooks;
 
 #[cfg(feature = "db")]
 pub mod db;
@@ -6,6 +6,7 @@ pub use suburb_coordinates::{
     is_valid_adelaide_location,
     fix_swapped_coordinates,
     get_location_with_fallback,
+    resolve_coordinates,
     ADELAIDE_SUBURBS,
 };
 
@@ -341,6 +341,13 @@ pub fn get_location_with_fallback(
     (-34.9285, 138.6007)
 }
 
+/// Best-known location for a suburb and/or postcode, without falling back to the CBD
+pub fn resolve_coordinates(suburb: Option<&str>, postcode: Option<&str>) -> Option<(f64, f64)> {
+    suburb
+        .and_then(get_suburb_coordinates)
+        .or_else(|| postcode.and_then(postcode_to_suburb).and_then(get_suburb_coordinates))
+}
+
 /// Map postcodes to primary suburbs
 fn postcode_to_suburb(postcode: &str) -> Option<&'static str> {
     match postcode {
@@ -407,6 +414,13 @@ mod tests {
         assert_eq!(get_suburb_coordinates("unknown_suburb"), None);
     }
 
+    #[test]
+    fn test_resolve_coordinates() {
+        assert_eq!(resolve_coordinates(Some("Norwood"), None), Some((-34.9206, 138.6326)));
+        assert_eq!(resolve_coordinates(Some("unknown_suburb"), Some("5000")), get_suburb_coordinates("adelaide"));
+        assert_eq!(resolve_coordinates(None, Some("9999")), None);
+    }
+
     #[test]
     fn test_coordinate_validation() {
         assert!(is_valid_adelaide_location(-34.9285, 138.6007)); // Adelaide CBD
@@ -0,0 +1,604 @@
+//! Shift pay under the Pharmacy Industry Award 2020 (MA000012).
+//!
+//! The award's loadings are simplified to whole-day and time-of-day bands: weekday
+//! early mornings and evenings, Saturdays, Sundays and public holidays each pay a
+//! multiple of the base rate. Hours past the daily or weekly overtime threshold are
+//! paid at overtime rates instead, or at the day's penalty rate if that is higher.
+//! Casual loading is paid on every hour that is not overtime. An unpaid break is
+//! taken from the middle of the shift. Rates and amounts are exact decimals,
+//! rounded to the cent half away from zero.
+
+use std::collections::HashMap;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::AppError;
+use crate::shifts::ShiftSpec;
+use crate::types::JobType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub enum EmploymentType {
+    Casual,
+    PartTime,
+    FullTime,
+}
+
+impl EmploymentType {
+    pub fn is_casual(&self) -> bool {
+        matches!(self, EmploymentType::Casual)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub enum PayCategory {
+    Ordinary,
+    EarlyMorning,
+    Evening,
+    Saturday,
+    Sunday,
+    PublicHoliday,
+    Overtime,
+    DoubleTime,
+    CasualLoading,
+}
+
+impl PayCategory {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            PayCategory::Ordinary => "Ordinary hours",
+            PayCategory::EarlyMorning => "Early morning",
+            PayCategory::Evening => "Evening",
+            PayCategory::Saturday => "Saturday",
+            PayCategory::Sunday => "Sunday",
+            PayCategory::PublicHoliday => "Public holiday",
+            PayCategory::Overtime => "Overtime",
+            PayCategory::DoubleTime => "Overtime (double time)",
+            PayCategory::CasualLoading => "Casual loading",
+        }
+    }
+}
+
+/// Loadings as multiples of the base rate, and when they apply
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AwardRules {
+    /// When false the base rate is all-inclusive: no penalties, casual loading or overtime
+    pub apply_penalties: bool,
+    /// Weekday hours before this are early-morning hours
+    pub ordinary_starts: NaiveTime,
+    /// Weekday hours from this until midnight are evening hours
+    pub evening_starts: NaiveTime,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub early_morning_multiplier: Decimal,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub evening_multiplier: Decimal,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub saturday_multiplier: Decimal,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub sunday_multiplier: Decimal,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub public_holiday_multiplier: Decimal,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub casual_loading: Decimal,
+    /// Hours in one shift before overtime starts
+    pub daily_overtime_after: f64,
+    /// Hours in one Monday-to-Sunday week before overtime starts
+    pub weekly_overtime_after: f64,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub overtime_multiplier: Decimal,
+    /// Overtime hours paid at `overtime_multiplier` before double time starts
+    pub double_time_after: f64,
+    #[serde(with = "rust_decimal::serde::float")]
+    pub double_time_multiplier: Decimal,
+}
+
+impl Default for AwardRules {
+    fn default() -> Self {
+        Self {
+            apply_penalties: true,
+            ordinary_starts: NaiveTime::from_hms_opt(7, 0, 0).unwrap(),
+            evening_starts: NaiveTime::from_hms_opt(19, 0, 0).unwrap(),
+            early_morning_multiplier: Decimal::new(15, 1),
+            evening_multiplier: Decimal::new(125, 2),
+            saturday_multiplier: Decimal::new(125, 2),
+            sunday_multiplier: Decimal::TWO,
+            public_holiday_multiplier: Decimal::new(25, 1),
+            casual_loading: Decimal::new(25, 2),
+            daily_overtime_after: 10.0,
+            weekly_overtime_after: 38.0,
+            overtime_multiplier: Decimal::new(15, 1),
+            double_time_after: 2.0,
+            double_time_multiplier: Decimal::TWO,
+        }
+    }
+}
+
+impl AwardRules {
+    /// Every hour at the base rate, for rates quoted as all-inclusive
+    pub fn flat() -> Self {
+        Self {
+            apply_penalties: false,
+            ..Self::default()
+        }
+    }
+
+    fn penalty(&self, category: PayCategory) -> Decimal {
+        match category {
+            PayCategory::EarlyMorning => self.early_morning_multiplier,
+            PayCategory::Evening => self.evening_multiplier,
+            PayCategory::Saturday => self.saturday_multiplier,
+            PayCategory::Sunday => self.sunday_multiplier,
+            PayCategory::PublicHoliday => self.public_holiday_multiplier,
+            PayCategory::Overtime => self.overtime_multiplier,
+            PayCategory::DoubleTime => self.double_time_multiplier,
+            PayCategory::CasualLoading => self.casual_loading,
+            PayCategory::Ordinary => Decimal::ONE,
+        }
+    }
+}
+
+/// Indicative adult minimum hourly rate for a classification (award rates from 1 July 2024)
+pub fn award_minimum_rate(job_type: &JobType) -> Decimal {
+    let cents = match job_type {
+        JobType::Pharmacist => 3490,
+        JobType::Intern => 2852,
+        JobType::PharmacyTechnician => 2662,
+        JobType::PharmacyAssistant => 2495,
+        JobType::Student => 2323,
+    };
+    Decimal::new(cents, 2)
+}
+
+/// Hours of one kind and what they pay, with the rate and amount as decimal
+/// strings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct PayLine {
+    pub category: PayCategory,
+    pub hours: f64,
+    #[serde(with = "rust_decimal::serde::float")]
+    #[cfg_attr(feature = "openapi", schemars(with = "f64"))]
+    pub multiplier: Decimal,
+    #[serde(serialize_with = "rust_decimal::serde::str::serialize")]
+    #[cfg_attr(feature = "openapi", schemars(with = "String"))]
+    pub rate: Decimal,
+    #[serde(serialize_with = "rust_decimal::serde::str::serialize")]
+    #[cfg_attr(feature = "openapi", schemars(with = "String"))]
+    pub amount: Decimal,
+}
+
+/// A shift as worked, with its unpaid break
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkedShift {
+    #[serde(flatten)]
+    pub shift: ShiftSpec,
+    #[serde(default)]
+    pub break_minutes: u32,
+}
+
+impl From<ShiftSpec> for WorkedShift {
+    fn from(shift: ShiftSpec) -> Self {
+        Self { shift, break_minutes: 0 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct ShiftPay {
+    pub date: NaiveDate,
+    pub start_time: String,
+    pub end_time: String,
+    #[serde(default)]
+    pub break_minutes: u32,
+    /// Paid hours, not counting the break
+    pub hours: f64,
+    pub lines: Vec<PayLine>,
+    #[serde(serialize_with = "rust_decimal::serde::str::serialize")]
+    #[cfg_attr(feature = "openapi", schemars(with = "String"))]
+    pub total: Decimal,
+}
+
+/// Itemised pay for one or more shifts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct PayEstimate {
+    #[serde(serialize_with = "rust_decimal::serde::str::serialize")]
+    #[cfg_attr(feature = "openapi", schemars(with = "String"))]
+    pub base_rate: Decimal,
+    pub employment: EmploymentType,
+    pub shifts: Vec<ShiftPay>,
+    /// Lines across every shift, one per category and multiplier
+    pub lines: Vec<PayLine>,
+    pub hours: f64,
+    #[serde(serialize_with = "rust_decimal::serde::str::serialize")]
+    #[cfg_attr(feature = "openapi", schemars(with = "String"))]
+    pub total: Decimal,
+    pub below_award_minimum: bool,
+}
+
+/// Works out shift pay for a classification and base rate
+#[derive(Debug, Clone)]
+pub struct PayCalculator<'a> {
+    pub rules: AwardRules,
+    pub employment: EmploymentType,
+    /// Public holidays in the state the shifts are worked in
+    pub public_holidays: &'a [NaiveDate],
+}
+
+/// Minutes of one kind within a shift
+#[derive(Debug, Clone, Copy)]
+struct Worked {
+    category: PayCategory,
+    multiplier: Decimal,
+    minutes: i64,
+    loaded: bool,
+}
+
+impl<'a> PayCalculator<'a> {
+    /// Casual employment under the default award rules
+    pub fn new(public_holidays: &'a [NaiveDate]) -> Self {
+        Self {
+            rules: AwardRules::default(),
+            employment: EmploymentType::Casual,
+            public_holidays,
+        }
+    }
+
+    pub fn with_rules(mut self, rules: AwardRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    pub fn with_employment(mut self, employment: EmploymentType) -> Self {
+        self.employment = employment;
+        self
+    }
+
+    /// Pay for a single shift
+    pub fn shift(&self, shift: &ShiftSpec, job_type: &JobType, base_rate: Decimal) -> Result<PayEstimate, AppError> {
+        self.series(std::slice::from_ref(shift), job_type, base_rate)
+    }
+
+    /// Pay for a set of shifts, with weekly overtime counted across them
+    pub fn series(&self, shifts: &[ShiftSpec], job_type: &JobType, base_rate: Decimal) -> Result<PayEstimate, AppError> {
+        let worked: Vec<WorkedShift> = shifts.iter().cloned().map(WorkedShift::from).collect();
+        self.worked(&worked, job_type, base_rate)
+    }
+
+    /// Pay for shifts as worked, less their unpaid breaks
+    pub fn worked(&self, shifts: &[WorkedShift], job_type: &JobType, base_rate: Decimal) -> Result<PayEstimate, AppError> {
+        if base_rate <= Decimal::ZERO {
+            return Err(AppError::validation("hourly_rate", "Hourly rate must be positive"));
+        }
+
+        let mut timed = Vec::with_capacity(shifts.len());
+        for worked in shifts {
+            let (start, end) = (worked.shift.starts_at()?, worked.shift.ends_at()?);
+            if i64::from(worked.break_minutes) >= (end - start).num_minutes() {
+                return Err(AppError::validation("break_minutes", "Break must be shorter than the shift"));
+            }
+            timed.push((start, end, worked));
+        }
+        timed.sort_by_key(|(start, _, _)| *start);
+
+        let mut week_minutes: HashMap<NaiveDate, i64> = HashMap::new();
+        let mut shift_pays = Vec::with_capacity(timed.len());
+        for (start, end, worked) in timed {
+            let break_minutes = i64::from(worked.break_minutes);
+            let paid_minutes = (end - start).num_minutes() - break_minutes;
+            let break_start = start + Duration::minutes(paid_minutes / 2);
+            let periods = [(start, break_start), (break_start + Duration::minutes(break_minutes), end)];
+
+            let week = week_start(start.date());
+            let before = week_minutes.get(&week).copied().unwrap_or(0);
+            let pieces = self.classify(&periods, before);
+            *week_minutes.entry(week).or_default() += paid_minutes;
+
+            let lines = self.price(&pieces, base_rate);
+            shift_pays.push(ShiftPay {
+                date: worked.shift.date,
+                start_time: worked.shift.start_time.clone(),
+                end_time: worked.shift.end_time.clone(),
+                break_minutes: worked.break_minutes,
+                hours: minutes_to_hours(paid_minutes),
+                total: lines.iter().map(|line| line.amount).sum(),
+                lines,
+            });
+        }
+
+        let mut lines: Vec<PayLine> = Vec::new();
+        for line in shift_pays.iter().flat_map(|shift| shift.lines.iter()) {
+            match lines
+                .iter_mut()
+                .find(|merged| merged.category == line.category && merged.multiplier == line.multiplier)
+            {
+                Some(merged) => {
+                    merged.hours += line.hours;
+                    merged.amount += line.amount;
+                }
+                None => lines.push(line.clone()),
+            }
+        }
+
+        Ok(PayEstimate {
+            base_rate,
+            employment: self.employment,
+            hours: shift_pays.iter().map(|shift| shift.hours).sum(),
+            total: shift_pays.iter().map(|shift| shift.total).sum(),
+            shifts: shift_pays,
+            lines,
+            below_award_minimum: base_rate < award_minimum_rate(job_type),
+        })
+    }
+
+    fn is_public_holiday(&self, date: NaiveDate) -> bool {
+        self.public_holidays.contains(&date)
+    }
+
+    /// The penalty band a moment falls in
+    fn band(&self, at: NaiveDateTime) -> PayCategory {
+        if !self.rules.apply_penalties {
+            return PayCategory::Ordinary;
+        }
+        if self.is_public_holiday(at.date()) {
+            return PayCategory::PublicHoliday;
+        }
+        match at.date().weekday() {
+            Weekday::Sat => PayCategory::Saturday,
+            Weekday::Sun => PayCategory::Sunday,
+            _ if at.time() < self.rules.ordinary_starts => PayCategory::EarlyMorning,
+            _ if at.time() >= self.rules.evening_starts => PayCategory::Evening,
+            _ => PayCategory::Ordinary,
+        }
+    }
+
+    /// Next moment after `at` where the penalty band can change
+    fn next_boundary(&self, at: NaiveDateTime) -> NaiveDateTime {
+        let date = at.date();
+        [self.rules.ordinary_starts, self.rules.evening_starts]
+            .into_iter()
+            .map(|time| date.and_time(time))
+            .filter(|boundary| *boundary > at)
+            .min()
+            .unwrap_or_else(|| (date + Duration::days(1)).and_time(NaiveTime::MIN))
+    }
+
+    /// Split a shift's working periods into bands, moving hours past the overtime
+    /// thresholds into overtime
+    fn classify(&self, periods: &[(NaiveDateTime, NaiveDateTime)], week_minutes_before: i64) -> Vec<Worked> {
+        let overtime_applies = self.rules.apply_penalties;
+        let daily_limit = hours_to_minutes(self.rules.daily_overtime_after);
+        let weekly_limit = hours_to_minutes(self.rules.weekly_overtime_after);
+        let double_after = hours_to_minutes(self.rules.double_time_after);
+
+        let mut worked = Vec::new();
+        let mut shift_minutes = 0;
+        let mut overtime_minutes = 0;
+        for &(start, end) in periods {
+            let mut at = start;
+            while at < end {
+                let segment_end = self.next_boundary(at).min(end);
+                let band = self.band(at);
+                let mut remaining = (segment_end - at).num_minutes();
+
+                while remaining > 0 {
+                    let ordinary_left = if overtime_applies {
+                        (daily_limit - shift_minutes).min(weekly_limit - week_minutes_before - shift_minutes)
+                    } else {
+                        remaining
+                    };
+
+                    let (taken, category, multiplier, loaded) = if ordinary_left > 0 {
+                        let taken = remaining.min(ordinary_left);
+                        (taken, band, self.rules.penalty(band), true)
+                    } else {
+                        let at_overtime_rate = double_after - overtime_minutes;
+                        let (taken, overtime) = if at_overtime_rate > 0 {
+                            (remaining.min(at_overtime_rate), PayCategory::Overtime)
+                        } else {
+                            (remaining, PayCategory::DoubleTime)
+                        };
+                        overtime_minutes += taken;
+                        // Overtime never pays less than the day's penalty rate
+                        if self.rules.penalty(band) > self.rules.penalty(overtime) {
+                            (taken, band, self.rules.penalty(band), false)
+                        } else {
+                            (taken, overtime, self.rules.penalty(overtime), false)
+                        }
+                    };
+
+                    worked.push(Worked { category, multiplier, minutes: taken, loaded });
+                    shift_minutes += taken;
+                    remaining -= taken;
+                }
+                at = segment_end;
+            }
+        }
+        worked
+    }
+
+    fn price(&self, worked: &[Worked], base_rate: Decimal) -> Vec<PayLine> {
+        let mut merged: Vec<Worked> = Vec::new();
+        for piece in worked {
+            match merged
+                .iter_mut()
+                .find(|m| m.category == piece.category && m.multiplier == piece.multiplier)
+            {
+                Some(m) => m.minutes += piece.minutes,
+                None => merged.push(*piece),
+            }
+        }
+
+        let mut lines: Vec<PayLine> = merged
+            .iter()
+            .map(|m| line(m.category, m.minutes, m.multiplier, base_rate))
+            .collect();
+
+        let loaded_minutes: i64 = worked.iter().filter(|w| w.loaded).map(|w| w.minutes).sum();
+        if self.employment.is_casual() && self.rules.apply_penalties && loaded_minutes > 0 {
+            lines.push(line(PayCategory::CasualLoading, loaded_minutes, self.rules.casual_loading, base_rate));
+        }
+        lines
+    }
+}
+
+fn line(category: PayCategory, minutes: i64, multiplier: Decimal, base_rate: Decimal) -> PayLine {
+    let rate = base_rate * multiplier;
+    PayLine {
+        category,
+        hours: minutes_to_hours(minutes),
+        multiplier,
+        rate: round_cents(rate),
+        amount: round_cents(rate * Decimal::from(minutes) / Decimal::from(60)),
+    }
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+fn hours_to_minutes(hours: f64) -> i64 {
+    (hours * 60.0).round() as i64
+}
+
+fn minutes_to_hours(minutes: i64) -> f64 {
+    minutes as f64 / 60.0
+}
+
+fn round_cents(amount: Decimal) -> Decimal {
+    amount.round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shift(y: i32, m: u32, d: u32, start: &str, end: &str) -> ShiftSpec {
+        ShiftSpec {
+            date: NaiveDate::from_ymd_opt(y, m, d).unwrap(),
+            start_time: start.to_string(),
+            end_time: end.to_string(),
+        }
+    }
+
+    fn dollars(amount: &str) -> Decimal {
+        amount.parse().unwrap()
+    }
+
+    fn hours_in(estimate: &PayEstimate, category: PayCategory) -> f64 {
+        estimate
+            .lines
+            .iter()
+            .filter(|line| line.category == category)
+            .map(|line| line.hours)
+            .sum()
+    }
+
+    #[test]
+    fn test_weekday_ordinary_and_evening() {
+        // Wednesday 2pm to 9pm: five ordinary hours and two evening hours
+        let calculator = PayCalculator::new(&[]).with_employment(EmploymentType::PartTime);
+        let pay = calculator.shift(&shift(2025, 3, 5, "14:00", "21:00"), &JobType::Pharmacist, dollars("50")).unwrap();
+
+        assert_eq!(hours_in(&pay, PayCategory::Ordinary), 5.0);
+        assert_eq!(hours_in(&pay, PayCategory::Evening), 2.0);
+        assert_eq!(pay.total, dollars("375")); // 5h at $50 and 2h at $62.50
+        assert!(!pay.below_award_minimum);
+    }
+
+    #[test]
+    fn test_casual_loading_and_weekend_rates() {
+        let calculator = PayCalculator::new(&[]);
+        let saturday = calculator.shift(&shift(2025, 3, 8, "09:00", "13:00"), &JobType::Pharmacist, dollars("40")).unwrap();
+        // 4h at 125% plus 25% casual loading
+        assert_eq!(hours_in(&saturday, PayCategory::Saturday), 4.0);
+        assert_eq!(hours_in(&saturday, PayCategory::CasualLoading), 4.0);
+        assert_eq!(saturday.total, dollars("240"));
+
+        let sunday = calculator.shift(&shift(2025, 3, 9, "09:00", "13:00"), &JobType::Pharmacist, dollars("40")).unwrap();
+        assert_eq!(sunday.total, dollars("360"));
+    }
+
+    #[test]
+    fn test_public_holiday_and_overnight() {
+        let holidays = [NaiveDate::from_ymd_opt(2025, 1, 27).unwrap()];
+        let calculator = PayCalculator::new(&holidays).with_employment(EmploymentType::FullTime);
+
+        // Sunday 10pm into the Monday public holiday
+        let pay = calculator.shift(&shift(2025, 1, 26, "22:00", "02:00"), &JobType::Pharmacist, dollars("40")).unwrap();
+        assert_eq!(hours_in(&pay, PayCategory::Sunday), 2.0);
+        assert_eq!(hours_in(&pay, PayCategory::PublicHoliday), 2.0);
+        assert_eq!(pay.total, dollars("360"));
+    }
+
+    #[test]
+    fn test_daily_overtime() {
+        // Tuesday 7am to 8pm: 10 ordinary-threshold hours, then 2h at 150% and 1h at 200%
+        let calculator = PayCalculator::new(&[]).with_employment(EmploymentType::FullTime);
+        let pay = calculator.shift(&shift(2025, 3, 4, "07:00", "20:00"), &JobType::Pharmacist, dollars("40")).unwrap();
+
+        assert_eq!(hours_in(&pay, PayCategory::Ordinary), 10.0);
+        assert_eq!(hours_in(&pay, PayCategory::Overtime), 2.0);
+        assert_eq!(hours_in(&pay, PayCategory::DoubleTime), 1.0);
+        assert_eq!(hours_in(&pay, PayCategory::Evening), 0.0);
+        assert_eq!(pay.total, dollars("600"));
+    }
+
+    #[test]
+    fn test_weekly_overtime_across_series() {
+        // Five 9-hour weekdays: the last 7 hours pass the 38-hour week
+        let shifts: Vec<ShiftSpec> = (3..=7).map(|day| shift(2025, 3, day, "08:00", "17:00")).collect();
+        let calculator = PayCalculator::new(&[]).with_employment(EmploymentType::FullTime);
+        let pay = calculator.series(&shifts, &JobType::Pharmacist, dollars("40")).unwrap();
+
+        assert_eq!(pay.hours, 45.0);
+        assert_eq!(hours_in(&pay, PayCategory::Ordinary), 38.0);
+        assert_eq!(hours_in(&pay, PayCategory::Overtime), 2.0);
+        assert_eq!(hours_in(&pay, PayCategory::DoubleTime), 5.0);
+        assert_eq!(pay.shifts.len(), 5);
+    }
+
+    #[test]
+    fn test_flat_rate_and_award_minimum() {
+        let calculator = PayCalculator::new(&[]).with_rules(AwardRules::flat());
+        let pay = calculator.shift(&shift(2025, 3, 9, "06:00", "20:00"), &JobType::PharmacyAssistant, dollars("20")).unwrap();
+
+        assert_eq!(pay.lines.len(), 1);
+        assert_eq!(pay.total, dollars("280"));
+        assert!(pay.below_award_minimum);
+        assert!(calculator.shift(&shift(2025, 3, 9, "06:00", "20:00"), &JobType::Pharmacist, Decimal::ZERO).is_err());
+    }
+
+    #[test]
+    fn test_unpaid_break_taken_mid_shift() {
+        // Tuesday 9am to 9pm with an hour's break from 2:30pm: 11 paid hours, the
+        // last of them past the daily overtime threshold
+        let worked = WorkedShift { shift: shift(2025, 3, 4, "09:00", "21:00"), break_minutes: 60 };
+        let calculator = PayCalculator::new(&[]).with_employment(EmploymentType::FullTime);
+        let pay = calculator.worked(&[worked.clone()], &JobType::Pharmacist, dollars("40")).unwrap();
+
+        assert_eq!(pay.hours, 11.0);
+        assert_eq!(pay.shifts[0].break_minutes, 60);
+        assert_eq!(hours_in(&pay, PayCategory::Ordinary), 9.0);
+        assert_eq!(hours_in(&pay, PayCategory::Evening), 1.0);
+        assert_eq!(hours_in(&pay, PayCategory::Overtime), 1.0);
+
+        let too_long = WorkedShift { break_minutes: 720, ..worked };
+        assert!(calculator.worked(&[too_long], &JobType::Pharmacist, dollars("40")).is_err());
+    }
+
+    #[test]
+    fn test_amounts_round_half_cents_up() {
+        // An evening hour at 125% of $30.02 is $37.525, which binary floating point
+        // sees as $37.52499...
+        let calculator = PayCalculator::new(&[]).with_employment(EmploymentType::PartTime);
+        let pay = calculator.shift(&shift(2025, 3, 5, "19:00", "20:00"), &JobType::Pharmacist, dollars("30.02")).unwrap();
+
+        assert_eq!(pay.lines[0].rate, dollars("37.53"));
+        assert_eq!(pay.total, dollars("37.53"));
+        assert_eq!(serde_json::to_value(&pay).unwrap()["total"], serde_json::json!("37.53"));
+    }
+}
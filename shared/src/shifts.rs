@@ -0,0 +1,382 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::availability::{parse_slot_time, RecurringPattern, RecurringType, MAX_EXPANSION_DAYS};
+use crate::errors::AppError;
+
+/// Most shifts a single job posting can carry
+pub const MAX_SHIFTS_PER_JOB: usize = 120;
+
+/// A single shift on a job
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct JobShift {
+    pub id: Uuid,
+    pub job_id: Uuid,
+    pub date: NaiveDate,
+    pub start_time: String, // "09:00"
+    pub end_time: String,   // "17:00"; before start_time for an overnight shift
+    pub filled_by: Option<Uuid>,
+    pub filled_at: Option<DateTime<Utc>>,
+}
+
+impl JobShift {
+    pub fn is_filled(&self) -> bool {
+        self.filled_by.is_some()
+    }
+}
+
+/// A shift as posted, before it is stored
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct ShiftSpec {
+    pub date: NaiveDate,
+    pub start_time: String,
+    pub end_time: String,
+}
+
+impl ShiftSpec {
+    /// Parsed start and end; an end at or before the start runs past midnight
+    pub fn time_range(&self) -> Result<(NaiveTime, NaiveTime), AppError> {
+        let start = parse_slot_time("start_time", &self.start_time)?;
+        let end = parse_slot_time("end_time", &self.end_time)?;
+        if start == end {
+            return Err(AppError::validation("end_time", "A shift cannot start and end at the same time"));
+        }
+        Ok((start, end))
+    }
+
+    pub fn starts_at(&self) -> Result<NaiveDateTime, AppError> {
+        let (start, _) = self.time_range()?;
+        Ok(self.date.and_time(start))
+    }
+
+    /// When the shift finishes, on the next day for an overnight shift
+    pub fn ends_at(&self) -> Result<NaiveDateTime, AppError> {
+        let (start, end) = self.time_range()?;
+        let date = if end < start { self.date + Duration::days(1) } else { self.date };
+        Ok(date.and_time(end))
+    }
+
+    /// Length of the shift in hours
+    pub fn hours(&self) -> Result<f64, AppError> {
+        Ok((self.ends_at()? - self.starts_at()?).num_minutes() as f64 / 60.0)
+    }
+}
+
+/// Hours that differ from the series default on one weekday, e.g. a shorter Saturday
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct WeekdayHours {
+    pub weekday: Weekday,
+    pub start_time: String,
+    pub end_time: String,
+}
+
+/// A rule that generates shifts, e.g. "every Saturday for 8 weeks"
+///
+/// Dates follow `pattern` from `first_date` (see [`RecurringPattern`]) and stop at the
+/// pattern's `end_date` or after `count` shifts, whichever comes first; one of them is required.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct ShiftRecurrence {
+    pub first_date: NaiveDate,
+    pub start_time: String,
+    pub end_time: String,
+    pub pattern: RecurringPattern,
+    #[serde(default)]
+    pub count: Option<u32>,
+    #[serde(default)]
+    pub weekday_hours: Vec<WeekdayHours>,
+}
+
+impl ShiftRecurrence {
+    /// The shifts this rule produces, in date order
+    pub fn generate(&self) -> Result<Vec<ShiftSpec>, AppError> {
+        if self.pattern.interval == 0 {
+            return Err(AppError::validation("recurrence.pattern.interval", "Interval must be at least 1"));
+        }
+        if self.pattern.end_date.is_none() && self.count.is_none() {
+            return Err(AppError::validation(
+                "recurrence",
+                "A shift series needs an end date or a number of shifts",
+            ));
+        }
+        if matches!(self.pattern.end_date, Some(end) if end < self.first_date) {
+            return Err(AppError::validation(
+                "recurrence.pattern.end_date",
+                "Recurrence cannot end before the first date",
+            ));
+        }
+
+        let horizon = self.first_date + Duration::days(MAX_EXPANSION_DAYS - 1);
+        let mut dates = self.pattern.occurrences(self.first_date, self.first_date, horizon);
+        if let Some(count) = self.count {
+            dates.truncate(count as usize);
+        }
+
+        Ok(dates
+            .into_iter()
+            .map(|date| {
+                let hours = self.weekday_hours.iter().find(|hours| hours.weekday == date.weekday());
+                ShiftSpec {
+                    date,
+                    start_time: hours.map_or(&self.start_time, |h| &h.start_time).clone(),
+                    end_time: hours.map_or(&self.end_time, |h| &h.end_time).clone(),
+                }
+            })
+            .collect())
+    }
+}
+
+/// How a job's shifts are described when it is posted: a recurrence rule, explicit
+/// shifts, or both. An explicit shift replaces whatever the rule generates on its date,
+/// so a block can be set up as a rule and then have individual days adjusted.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct ShiftSchedule {
+    #[serde(default)]
+    pub recurrence: Option<ShiftRecurrence>,
+    #[serde(default)]
+    pub shifts: Vec<ShiftSpec>,
+}
+
+impl ShiftSchedule {
+    /// The same hours on the given weekdays between two dates; every day when `weekdays` is empty
+    pub fn weekly(
+        from: NaiveDate,
+        to: NaiveDate,
+        weekdays: Vec<Weekday>,
+        start_time: impl Into<String>,
+        end_time: impl Into<String>,
+    ) -> Self {
+        let pattern_type = if weekdays.is_empty() {
+            RecurringType::Daily
+        } else {
+            RecurringType::Weekly
+        };
+        Self {
+            recurrence: Some(ShiftRecurrence {
+                first_date: from,
+                start_time: start_time.into(),
+                end_time: end_time.into(),
+                pattern: RecurringPattern {
+                    pattern_type,
+                    end_date: Some(to),
+                    days_of_week: weekdays,
+                    interval: 1,
+                },
+                count: None,
+                weekday_hours: Vec::new(),
+            }),
+            shifts: Vec::new(),
+        }
+    }
+
+    /// Every shift described, validated and ordered by date then start time
+    pub fn generate(&self) -> Result<Vec<ShiftSpec>, AppError> {
+        let mut shifts = match &self.recurrence {
+            Some(recurrence) => recurrence.generate()?,
+            None => Vec::new(),
+        };
+        shifts.retain(|generated| !self.shifts.iter().any(|explicit| explicit.date == generated.date));
+        shifts.extend(self.shifts.iter().cloned());
+
+        if shifts.is_empty() {
+            return Err(AppError::validation("shifts", "The schedule does not produce any shifts"));
+        }
+        if shifts.len() > MAX_SHIFTS_PER_JOB {
+            return Err(AppError::validation(
+                "shifts",
+                format!("A job can have at most {} shifts", MAX_SHIFTS_PER_JOB),
+            ));
+        }
+
+        let mut keyed = Vec::with_capacity(shifts.len());
+        for shift in shifts {
+            let (start, _) = shift.time_range()?;
+            keyed.push((shift.date, start, shift));
+        }
+        keyed.sort_by_key(|(date, start, _)| (*date, *start));
+        if keyed.windows(2).any(|pair| (pair[0].0, pair[0].1) == (pair[1].0, pair[1].1)) {
+            return Err(AppError::validation("shifts", "Two shifts start at the same time on the same day"));
+        }
+
+        Ok(keyed.into_iter().map(|(_, _, shift)| shift).collect())
+    }
+
+    /// The same schedule moved `days` later (earlier when negative). Weekdays in a
+    /// recurrence move with it, so "every Saturday" moved by two days becomes "every Monday".
+    pub fn shifted(&self, days: i64) -> Self {
+        let offset = Duration::days(days);
+        let recurrence = self.recurrence.as_ref().map(|recurrence| {
+            let mut recurrence = recurrence.clone();
+            recurrence.first_date += offset;
+            recurrence.pattern.end_date = recurrence.pattern.end_date.map(|end| end + offset);
+            for weekday in recurrence.pattern.days_of_week.iter_mut() {
+                *weekday = move_weekday(*weekday, days);
+            }
+            for hours in recurrence.weekday_hours.iter_mut() {
+                hours.weekday = move_weekday(hours.weekday, days);
+            }
+            recurrence
+        });
+
+        Self {
+            recurrence,
+            shifts: self
+                .shifts
+                .iter()
+                .map(|shift| ShiftSpec { date: shift.date + offset, ..shift.clone() })
+                .collect(),
+        }
+    }
+}
+
+fn move_weekday(weekday: Weekday, days: i64) -> Weekday {
+    (0..days.rem_euclid(7)).fold(weekday, |weekday, _| weekday.succ())
+}
+
+/// When a set of shifts begins and ends; shifts with unreadable times are ignored
+pub fn span(shifts: &[ShiftSpec]) -> Option<(NaiveDateTime, NaiveDateTime)> {
+    let bounds: Vec<(NaiveDateTime, NaiveDateTime)> = shifts
+        .iter()
+        .filter_map(|shift| Some((shift.starts_at().ok()?, shift.ends_at().ok()?)))
+        .collect();
+    let first = bounds.iter().map(|(start, _)| *start).min()?;
+    let last = bounds.iter().map(|(_, end)| *end).max()?;
+    Some((first, last))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn spec(date: NaiveDate, start: &str, end: &str) -> ShiftSpec {
+        ShiftSpec {
+            date,
+            start_time: start.to_string(),
+            end_time: end.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_every_saturday_for_eight_weeks() {
+        let schedule = ShiftSchedule {
+            recurrence: Some(ShiftRecurrence {
+                first_date: date(2025, 3, 1), // Saturday
+                start_time: "09:00".to_string(),
+                end_time: "13:00".to_string(),
+                pattern: RecurringPattern {
+                    pattern_type: RecurringType::Weekly,
+                    end_date: None,
+                    days_of_week: vec![Weekday::Sat],
+                    interval: 1,
+                },
+                count: Some(8),
+                weekday_hours: Vec::new(),
+            }),
+            shifts: Vec::new(),
+        };
+
+        let shifts = schedule.generate().unwrap();
+        assert_eq!(shifts.len(), 8);
+        assert_eq!(shifts[0].date, date(2025, 3, 1));
+        assert_eq!(shifts[7].date, date(2025, 4, 19));
+        assert!(shifts.iter().all(|shift| shift.date.weekday() == Weekday::Sat));
+    }
+
+    #[test]
+    fn test_block_with_different_hours_per_day() {
+        let mut schedule = ShiftSchedule::weekly(
+            date(2025, 3, 3),
+            date(2025, 3, 16),
+            vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri, Weekday::Sat],
+            "09:00",
+            "17:30",
+        );
+        if let Some(recurrence) = schedule.recurrence.as_mut() {
+            recurrence.weekday_hours.push(WeekdayHours {
+                weekday: Weekday::Sat,
+                start_time: "09:00".to_string(),
+                end_time: "12:00".to_string(),
+            });
+        }
+        // Late night on the second Thursday
+        schedule.shifts.push(spec(date(2025, 3, 13), "13:00", "21:00"));
+
+        let shifts = schedule.generate().unwrap();
+        assert_eq!(shifts.len(), 12);
+        assert_eq!(
+            span(&shifts),
+            Some((date(2025, 3, 3).and_hms_opt(9, 0, 0).unwrap(), date(2025, 3, 15).and_hms_opt(12, 0, 0).unwrap()))
+        );
+
+        let saturday = shifts.iter().find(|shift| shift.date == date(2025, 3, 8)).unwrap();
+        assert_eq!(saturday.end_time, "12:00");
+        let thursday: Vec<_> = shifts.iter().filter(|shift| shift.date == date(2025, 3, 13)).collect();
+        assert_eq!(thursday.len(), 1);
+        assert_eq!(thursday[0].start_time, "13:00");
+    }
+
+    #[test]
+    fn test_schedule_validation() {
+        // Unbounded series
+        let mut unbounded = ShiftSchedule::weekly(date(2025, 3, 1), date(2025, 3, 31), vec![], "09:00", "17:00");
+        if let Some(recurrence) = unbounded.recurrence.as_mut() {
+            recurrence.pattern.end_date = None;
+        }
+        assert!(unbounded.generate().is_err());
+
+        assert!(ShiftSchedule::default().generate().is_err());
+
+        let clash = ShiftSchedule {
+            recurrence: None,
+            shifts: vec![spec(date(2025, 3, 1), "09:00", "13:00"), spec(date(2025, 3, 1), "09:00", "17:00")],
+        };
+        assert!(clash.generate().is_err());
+
+        // A split shift on one day is fine
+        let split = ShiftSchedule {
+            recurrence: None,
+            shifts: vec![spec(date(2025, 3, 1), "14:00", "18:00"), spec(date(2025, 3, 1), "08:00", "12:00")],
+        };
+        let shifts = split.generate().unwrap();
+        assert_eq!(shifts[0].start_time, "08:00");
+
+        let too_many = ShiftSchedule::weekly(date(2025, 1, 1), date(2025, 12, 31), vec![], "09:00", "17:00");
+        assert!(too_many.generate().is_err());
+    }
+
+    #[test]
+    fn test_shift_hours() {
+        assert_eq!(spec(date(2025, 3, 1), "09:00", "17:30").hours().unwrap(), 8.5);
+        let overnight = spec(date(2025, 3, 1), "22:00", "06:00");
+        assert_eq!(overnight.hours().unwrap(), 8.0);
+        assert_eq!(overnight.ends_at().unwrap(), date(2025, 3, 2).and_hms_opt(6, 0, 0).unwrap());
+        assert!(spec(date(2025, 3, 1), "09:00", "09:00").hours().is_err());
+    }
+
+    #[test]
+    fn test_shifted_schedule_keeps_its_shape() {
+        let mut schedule = ShiftSchedule::weekly(date(2025, 3, 1), date(2025, 3, 22), vec![Weekday::Sat], "09:00", "13:00");
+        schedule.shifts.push(spec(date(2025, 3, 8), "10:00", "14:00"));
+
+        let next_month = schedule.shifted(28).generate().unwrap();
+        assert_eq!(next_month.len(), 4);
+        assert_eq!(next_month[0].date, date(2025, 3, 29));
+        assert_eq!(next_month[1].start_time, "10:00");
+
+        // Two days later the Saturdays become Mondays
+        let mondays = schedule.shifted(2).generate().unwrap();
+        assert_eq!(mondays.len(), 4);
+        assert!(mondays.iter().all(|shift| shift.date.weekday() == Weekday::Mon));
+        assert_eq!(mondays[3].date, date(2025, 3, 24));
+        assert_eq!(schedule.shifted(-5).shifted(5), schedule);
+    }
+}
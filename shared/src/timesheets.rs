@@ -0,0 +1,82 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::pay::WorkedShift;
+use crate::shifts::ShiftSpec;
+
+/// Longest unpaid break a timesheet can record
+pub const MAX_BREAK_MINUTES: u32 = 240;
+
+/// Hours a locum reports for one shift, as submitted or edited
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct TimesheetEntry {
+    /// The job shift worked, when the job has a shift series
+    #[serde(default)]
+    pub shift_id: Option<Uuid>,
+    pub date: NaiveDate,
+    pub start_time: String,
+    pub end_time: String,
+    #[serde(default)]
+    pub break_minutes: u32,
+}
+
+impl TimesheetEntry {
+    pub fn spec(&self) -> ShiftSpec {
+        ShiftSpec {
+            date: self.date,
+            start_time: self.start_time.clone(),
+            end_time: self.end_time.clone(),
+        }
+    }
+
+    /// Check the times make sense and return the shift as worked
+    pub fn worked(&self) -> Result<WorkedShift, AppError> {
+        let spec = self.spec();
+        let minutes = (spec.ends_at()? - spec.starts_at()?).num_minutes();
+        if self.break_minutes > MAX_BREAK_MINUTES {
+            return Err(AppError::validation(
+                "break_minutes",
+                format!("Breaks can be at most {} minutes", MAX_BREAK_MINUTES),
+            ));
+        }
+        if i64::from(self.break_minutes) >= minutes {
+            return Err(AppError::validation("break_minutes", "Break must be shorter than the shift"));
+        }
+        Ok(WorkedShift { shift: spec, break_minutes: self.break_minutes })
+    }
+
+    /// When the shift finished, so it can't be claimed before it has been worked
+    pub fn ends_at(&self) -> Result<NaiveDateTime, AppError> {
+        self.spec().ends_at()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(start: &str, end: &str, break_minutes: u32) -> TimesheetEntry {
+        TimesheetEntry {
+            shift_id: None,
+            date: NaiveDate::from_ymd_opt(2025, 3, 4).unwrap(),
+            start_time: start.to_string(),
+            end_time: end.to_string(),
+            break_minutes,
+        }
+    }
+
+    #[test]
+    fn test_worked_shift_validation() {
+        let worked = entry("22:00", "06:00", 30).worked().unwrap();
+        assert_eq!(worked.break_minutes, 30);
+        assert_eq!(entry("22:00", "06:00", 30).ends_at().unwrap().date(), NaiveDate::from_ymd_opt(2025, 3, 5).unwrap());
+
+        assert!(entry("09:00", "09:00", 0).worked().is_err());
+        assert!(entry("09:00", "10:00", 60).worked().is_err());
+        assert!(entry("08:00", "20:00", MAX_BREAK_MINUTES + 1).worked().is_err());
+        assert!(entry("9am", "17:00", 0).worked().is_err());
+    }
+}
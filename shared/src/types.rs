@@ -11,6 +11,7 @@ use crate::errors::AppError;
 
 /// Job ID with type safety
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct JobId(pub Uuid);
 
 impl JobId {
@@ -37,6 +38,7 @@ impl Default for JobId {
 
 /// User ID with type safety
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct UserId(pub Uuid);
 
 impl UserId {
@@ -63,7 +65,11 @@ impl Default for UserId {
 
 /// Australian postcode with validation
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct Postcode(pub String);
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct Postcode(
+    #[cfg_attr(feature = "openapi", schemars(regex(pattern = r"^\d{4}$")))]
+    pub String,
+);
 
 impl Postcode {
     pub fn new(postcode: impl Into<String>) -> Result<Self, AppError> {
@@ -165,6 +171,7 @@ impl Display for EmailAddress {
 
 /// Job listing with comprehensive type safety and Australian localisations
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Validate)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct Job {
     pub id: JobId,
     
@@ -331,6 +338,7 @@ impl JobBuilder {
 
 /// Australian states and territories
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub enum AustralianState {
     #[serde(rename = "NSW")]
     NewSouthWales,
@@ -397,6 +405,7 @@ impl AustralianState {
 
 /// Job types specific to Australian pharmacy sector
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub enum JobType {
     /// Pharmacy intern (pre-registration)
     Intern,
@@ -410,6 +419,13 @@ pub enum JobType {
     PharmacyTechnician,
 }
 
+impl JobType {
+    /// Whether working in this role needs AHPRA registration (provisional for interns)
+    pub fn requires_registration(&self) -> bool {
+        matches!(self, JobType::Intern | JobType::Pharmacist)
+    }
+}
+
 impl Display for JobType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let name = match self {
@@ -425,6 +441,7 @@ impl Display for JobType {
 
 /// Job status with Australian workflow considerations
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub enum JobStatus {
     /// Job is active and accepting applications
     Active,
@@ -455,6 +472,7 @@ impl Display for JobStatus {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct CreateJobRequest {
     #[validate(length(min = 3, max = 100))]
     pub title: String,
@@ -469,6 +487,7 @@ pub struct CreateJobRequest {
     #[validate(length(min = 2, max = 50))]
     pub suburb: String,
     #[validate(length(min = 4, max = 4))]
+    #[cfg_attr(feature = "openapi", schemars(regex(pattern = r"^\d{4}$")))]
     pub postcode: String,
     #[validate(length(min = 2, max = 3))]
     pub state: String,
@@ -480,9 +499,13 @@ pub struct CreateJobRequest {
     pub end_time: String,
     pub job_type: JobType,
     pub is_urgent: bool,
+    /// Individual shifts for multi-day postings; the job's dates and hours then span them
+    #[serde(default)]
+    pub shifts: Option<crate::shifts::ShiftSchedule>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct User {
     pub id: Uuid,
     pub email: String,
@@ -496,6 +519,7 @@ pub struct User {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub enum UserType {
     Professional,
     Employer,
@@ -540,6 +564,7 @@ impl From<&str> for UserRole {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct Application {
     pub id: Uuid,
     pub job_id: Uuid,
@@ -551,6 +576,7 @@ pub struct Application {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub enum ApplicationStatus {
     Pending,
     Reviewing,
@@ -564,13 +590,18 @@ pub enum ApplicationStatus {
 
 /// Request types for application management
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct CreateApplicationRequest {
     pub job_id: Uuid,
     #[validate(length(min = 10, max = 2000, message = "Cover letter must be between 10 and 2000 characters"))]
     pub cover_letter: Option<String>,
+    /// Shifts being applied for; empty means every shift of the job
+    #[serde(default)]
+    pub shift_ids: Vec<Uuid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct UpdateApplicationRequest {
     pub status: Option<ApplicationStatus>,
     pub cover_letter: Option<String>,
@@ -578,6 +609,7 @@ pub struct UpdateApplicationRequest {
 
 /// Filters for application queries
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct ApplicationFilters {
     pub status: Option<ApplicationStatus>,
     pub job_id: Option<Uuid>,
@@ -587,6 +619,7 @@ pub struct ApplicationFilters {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct JobFilters {
     pub job_type: Option<JobType>,
     pub min_rate: Option<f64>,
@@ -604,6 +637,7 @@ pub struct JobFilters {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct LocationPin {
     pub lat: f64,
     pub lng: f64,
@@ -611,23 +645,65 @@ pub struct LocationPin {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct SearchRequest {
     pub query: Option<String>,
     pub filters: JobFilters,
     pub user_location: Option<(f64, f64)>,
-    pub page: Option<u32>,
+    /// `next_cursor` from the previous page; omit for the first page
+    pub cursor: Option<String>,
     pub limit: Option<u32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SearchResponse {
-    pub jobs: Vec<Job>,
-    pub total_count: u64,
-    pub page: u32,
-    pub limit: u32,
+/// Default and largest page sizes for cursor-paged lists
+pub const DEFAULT_PAGE_LIMIT: u32 = 20;
+pub const MAX_PAGE_LIMIT: u32 = 100;
+
+/// Where to start a page of a cursor-paged list, and how many items to return
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct CursorParams {
+    /// `next_cursor` from the previous page; omit for the first page
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+
+impl CursorParams {
+    pub fn limit(&self) -> u32 {
+        self.limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT)
+    }
+}
+
+/// One page of a list, newest first. Pages follow on from the last item rather
+/// than an offset, so items added while a client scrolls never shift the pages
+/// it has still to fetch.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct ListResponse<T> {
+    pub items: Vec<T>,
+    /// Pass as `cursor` to fetch the next page; `None` on the last page
+    pub next_cursor: Option<String>,
     pub has_more: bool,
 }
 
+impl<T> ListResponse<T> {
+    pub fn new(items: Vec<T>, next_cursor: Option<String>) -> Self {
+        Self {
+            has_more: next_cursor.is_some(),
+            items,
+            next_cursor,
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> ListResponse<U> {
+        ListResponse {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+            has_more: self.has_more,
+        }
+    }
+}
+
 // ============================================================================
 // Simplified Types for Frontend Use
 // ============================================================================
@@ -698,4 +774,36 @@ pub enum ApiError {
     NotFound,
     Unauthorised,
     Internal(String),
+    /// An error response from the API in problem+json form
+    Problem(Box<crate::errors::ProblemDetails>),
+}
+
+impl ApiError {
+    /// Text to show the user for this error
+    pub fn user_message(&self) -> String {
+        match self {
+            ApiError::Problem(problem) => problem.user_message.clone(),
+            ApiError::Validation(message) => message.clone(),
+            ApiError::NotFound | ApiError::Http(404) => "Sorry, we couldn't find that.".to_string(),
+            ApiError::Unauthorised | ApiError::Http(401) => "Please sign in to continue.".to_string(),
+            ApiError::Network(_) => "We couldn't reach the server. Check your connection and try again.".to_string(),
+            _ => "Something went wrong. Please try again.".to_string(),
+        }
+    }
+
+    /// Why each field was rejected, for showing next to the form inputs
+    pub fn field_errors(&self) -> &[crate::errors::FieldError] {
+        match self {
+            ApiError::Problem(problem) => &problem.errors,
+            _ => &[],
+        }
+    }
+
+    /// Stable code of the API error, e.g. "validation_failed"
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            ApiError::Problem(problem) => Some(&problem.code),
+            _ => None,
+        }
+    }
 }
\ No newline at end of file
@@ -140,4 +140,9 @@ pub fn validate_phone_au(phone: &str) -> bool {
     } else {
         false
     }
-}
\ No newline at end of file
+}
+
+/// Delay before retry number `attempt` (1-based): `base`, then doubling each time
+pub fn exponential_backoff(base: std::time::Duration, attempt: u32) -> std::time::Duration {
+    base.saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+}
@@ -2,10 +2,12 @@ use serde::{Deserialize, Serialize};
 use validator::{Validate, ValidationError};
 use chrono::NaiveDate;
 
+use crate::shifts::ShiftSchedule;
 use crate::types::{JobType, AustralianState};
 
 /// Validated job creation request with comprehensive Australian-specific validation
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 #[validate(schema(function = "validate_create_job_request"))]
 pub struct ValidatedCreateJobRequest {
     #[validate(length(min = 5, max = 200, message = "Title must be between 5 and 200 characters"))]
@@ -25,6 +27,7 @@ pub struct ValidatedCreateJobRequest {
     pub suburb: String,
     
     #[validate(custom(function = "validate_postcode"))]
+    #[cfg_attr(feature = "openapi", schemars(regex(pattern = r"^\d{4}$")))]
     pub postcode: String,
     
     #[validate(custom(function = "validate_state"))]
@@ -71,6 +74,10 @@ pub struct ValidatedCreateJobRequest {
     
     #[validate(custom(function = "validate_phone"))]
     pub contact_phone: Option<String>,
+    
+    // Shift series, for postings that cover several days
+    #[serde(default)]
+    pub shifts: Option<ShiftSchedule>,
 }
 
 /// Validate Australian postcode
@@ -112,6 +119,12 @@ fn validate_create_job_request(job: &ValidatedCreateJobRequest) -> Result<(), Va
         }
     }
     
+    if let Some(shifts) = &job.shifts {
+        if shifts.generate().is_err() {
+            return Err(ValidationError::new("invalid_shift_schedule"));
+        }
+    }
+    
     // Validate AHPRA requirement (only for pharmacist roles)
     if job.job_type == JobType::Pharmacist && job.requires_ahpra != Some(true) {
         // Warning, not error - pharmacist jobs should require AHPRA
@@ -145,6 +158,7 @@ fn validate_phone(phone: &str) -> Result<(), ValidationError> {
 
 /// User registration validation
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct ValidatedRegisterRequest {
     #[validate(email(message = "Invalid email format"))]
     pub email: String,
@@ -227,6 +241,7 @@ fn validate_abn(abn: &str) -> Result<(), ValidationError> {
 
 /// Job search/filter validation
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct ValidatedJobSearchRequest {
     #[validate(length(max = 200, message = "Search query too long"))]
     pub query: Option<String>,
@@ -0,0 +1,178 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+
+/// `t=<unix seconds>,v1=<hex HMAC-SHA256 of "<t>.<body>">`, keyed with the endpoint's secret
+pub const SIGNATURE_HEADER: &str = "Loco-Signature";
+
+/// The event's type, e.g. `job.published`
+pub const EVENT_TYPE_HEADER: &str = "Loco-Event-Type";
+
+/// Same on every attempt and replay of an event, so receivers can drop duplicates
+pub const EVENT_ID_HEADER: &str = "Loco-Event-Id";
+
+/// Identifies one delivery in the endpoint's delivery log
+pub const DELIVERY_ID_HEADER: &str = "Loco-Delivery-Id";
+
+/// How old a signature's timestamp may be before receivers should refuse it
+pub const SIGNATURE_TOLERANCE_SECS: i64 = 5 * 60;
+
+/// Longest endpoint description
+pub const MAX_DESCRIPTION_LEN: usize = 255;
+
+/// Events an endpoint can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub enum WebhookEventType {
+    /// A job went live, immediately or at its scheduled time
+    #[serde(rename = "job.published")]
+    JobPublished,
+    #[serde(rename = "application.created")]
+    ApplicationCreated,
+    #[serde(rename = "application.status_changed")]
+    ApplicationStatusChanged,
+    /// A locum took up an offer; also sent as `application.status_changed`
+    #[serde(rename = "offer.accepted")]
+    OfferAccepted,
+    #[serde(rename = "timesheet.approved")]
+    TimesheetApproved,
+}
+
+impl WebhookEventType {
+    pub const ALL: [WebhookEventType; 5] = [
+        WebhookEventType::JobPublished,
+        WebhookEventType::ApplicationCreated,
+        WebhookEventType::ApplicationStatusChanged,
+        WebhookEventType::OfferAccepted,
+        WebhookEventType::TimesheetApproved,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WebhookEventType::JobPublished => "job.published",
+            WebhookEventType::ApplicationCreated => "application.created",
+            WebhookEventType::ApplicationStatusChanged => "application.status_changed",
+            WebhookEventType::OfferAccepted => "offer.accepted",
+            WebhookEventType::TimesheetApproved => "timesheet.approved",
+        }
+    }
+}
+
+impl fmt::Display for WebhookEventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The JSON body POSTed to an endpoint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct WebhookPayload {
+    /// Matches the `Loco-Event-Id` header
+    pub id: Uuid,
+    #[serde(rename = "type")]
+    pub event_type: WebhookEventType,
+    pub created_at: DateTime<Utc>,
+    pub tenant_id: Uuid,
+    /// Shaped like the matching WebSocket event's payload where there is one
+    pub data: serde_json::Value,
+}
+
+/// Register a URL to receive a tenant's events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct CreateWebhookEndpointRequest {
+    pub tenant_id: Uuid,
+    /// Must be https outside local development
+    pub url: String,
+    pub event_types: Vec<WebhookEventType>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct UpdateWebhookEndpointRequest {
+    pub url: Option<String>,
+    pub event_types: Option<Vec<WebhookEventType>>,
+    pub description: Option<String>,
+    /// Paused endpoints keep their settings but are sent nothing
+    pub active: Option<bool>,
+}
+
+/// The subscribed event types as stored: at least one, sorted, without repeats
+pub fn event_types(event_types: &[WebhookEventType]) -> Result<Vec<WebhookEventType>, AppError> {
+    let mut event_types = event_types.to_vec();
+    event_types.sort_unstable();
+    event_types.dedup();
+    if event_types.is_empty() {
+        return Err(AppError::validation("event_types", "Subscribe to at least one event type"));
+    }
+    Ok(event_types)
+}
+
+/// The description as stored, trimmed; blank means none
+pub fn description(description: Option<&str>) -> Result<Option<String>, AppError> {
+    let Some(description) = description.map(str::trim).filter(|d| !d.is_empty()) else {
+        return Ok(None);
+    };
+    if description.chars().count() > MAX_DESCRIPTION_LEN {
+        return Err(AppError::validation(
+            "description",
+            format!("Description can be at most {} characters", MAX_DESCRIPTION_LEN),
+        ));
+    }
+    Ok(Some(description.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_type_names() {
+        for event_type in WebhookEventType::ALL {
+            let json = serde_json::to_value(event_type).unwrap();
+            assert_eq!(json, event_type.as_str());
+            assert_eq!(serde_json::from_value::<WebhookEventType>(json).unwrap(), event_type);
+        }
+        assert!(serde_json::from_str::<WebhookEventType>("\"JobPublished\"").is_err());
+    }
+
+    #[test]
+    fn test_event_types_are_sorted_and_deduplicated() {
+        let stored = event_types(&[
+            WebhookEventType::TimesheetApproved,
+            WebhookEventType::JobPublished,
+            WebhookEventType::TimesheetApproved,
+        ])
+        .unwrap();
+        assert_eq!(stored, vec![WebhookEventType::JobPublished, WebhookEventType::TimesheetApproved]);
+        assert!(event_types(&[]).is_err());
+    }
+
+    #[test]
+    fn test_description() {
+        assert_eq!(description(None).unwrap(), None);
+        assert_eq!(description(Some("   ")).unwrap(), None);
+        assert_eq!(description(Some(" Rostering sync ")).unwrap().as_deref(), Some("Rostering sync"));
+        assert!(description(Some(&"x".repeat(MAX_DESCRIPTION_LEN + 1))).is_err());
+    }
+
+    #[test]
+    fn test_payload_uses_type_for_the_event_type() {
+        let payload = WebhookPayload {
+            id: Uuid::nil(),
+            event_type: WebhookEventType::OfferAccepted,
+            created_at: Utc::now(),
+            tenant_id: Uuid::nil(),
+            data: serde_json::json!({}),
+        };
+        let json = serde_json::to_value(&payload).unwrap();
+        assert_eq!(json["type"], "offer.accepted");
+        assert!(json.get("event_type").is_none());
+    }
+}